@@ -0,0 +1,715 @@
+use gtk4::gdk::{Key, ModifierType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ═══════════════════════════════════════════════
+//  Key Bindings
+// ═══════════════════════════════════════════════
+//
+// Every keyboard shortcut is an `Action` bound to one or more key specs in
+// the `[keybindings]` table of the config:
+//
+//   [keybindings]
+//   move_down = ["j", "Down"]
+//   last = "G"
+//   first = "gg"
+//
+// A spec is a GTK accelerator ("<Ctrl><Shift>f", "F2", "Page_Down"), or
+// several separated by spaces for a sequence ("g g"). A run of single
+// characters ("gg") is a sequence too, and an uppercase letter implies
+// Shift. Actions missing from the table keep their default keys; an empty
+// list unbinds one.
+
+/// Everything a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    Open,
+    GoParent,
+    GoBack,
+    GoForward,
+    Rename,
+    Trash,
+    Copy,
+    Cut,
+    Paste,
+    CopyToOtherPane,
+    MoveToOtherPane,
+    SelectAll,
+    Cancel,
+    SwitchPane,
+    EditPath,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+    Undo,
+    Redo,
+    QuickFinder,
+    Filter,
+    Search,
+    CommandPalette,
+    ToggleHidden,
+    ToggleDualPane,
+    CycleViewMode,
+    ToggleSortDescending,
+    ToggleFoldersFirst,
+    NewFolder,
+    NewFile,
+    Refresh,
+    OpenTerminal,
+    ClearThumbnailCache,
+    Settings,
+    About,
+}
+
+impl Action {
+    pub fn all() -> [Action; 45] {
+        use Action::*;
+        [
+            MoveLeft,
+            MoveRight,
+            MoveUp,
+            MoveDown,
+            PageUp,
+            PageDown,
+            First,
+            Last,
+            Open,
+            GoParent,
+            GoBack,
+            GoForward,
+            Rename,
+            Trash,
+            Copy,
+            Cut,
+            Paste,
+            CopyToOtherPane,
+            MoveToOtherPane,
+            SelectAll,
+            Cancel,
+            SwitchPane,
+            EditPath,
+            NewTab,
+            CloseTab,
+            NextTab,
+            PreviousTab,
+            Undo,
+            Redo,
+            QuickFinder,
+            Filter,
+            Search,
+            CommandPalette,
+            ToggleHidden,
+            ToggleDualPane,
+            CycleViewMode,
+            ToggleSortDescending,
+            ToggleFoldersFirst,
+            NewFolder,
+            NewFile,
+            Refresh,
+            OpenTerminal,
+            ClearThumbnailCache,
+            Settings,
+            About,
+        ]
+    }
+
+    /// The key in the `[keybindings]` table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::First => "first",
+            Action::Last => "last",
+            Action::Open => "open",
+            Action::GoParent => "go_parent",
+            Action::GoBack => "go_back",
+            Action::GoForward => "go_forward",
+            Action::Rename => "rename",
+            Action::Trash => "trash",
+            Action::Copy => "copy",
+            Action::Cut => "cut",
+            Action::Paste => "paste",
+            Action::CopyToOtherPane => "copy_to_other_pane",
+            Action::MoveToOtherPane => "move_to_other_pane",
+            Action::SelectAll => "select_all",
+            Action::Cancel => "cancel",
+            Action::SwitchPane => "switch_pane",
+            Action::EditPath => "edit_path",
+            Action::NewTab => "new_tab",
+            Action::CloseTab => "close_tab",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::QuickFinder => "quick_finder",
+            Action::Filter => "filter",
+            Action::Search => "search",
+            Action::CommandPalette => "command_palette",
+            Action::ToggleHidden => "toggle_hidden",
+            Action::ToggleDualPane => "toggle_dual_pane",
+            Action::CycleViewMode => "cycle_view_mode",
+            Action::ToggleSortDescending => "toggle_sort_descending",
+            Action::ToggleFoldersFirst => "toggle_folders_first",
+            Action::NewFolder => "new_folder",
+            Action::NewFile => "new_file",
+            Action::Refresh => "refresh",
+            Action::OpenTerminal => "open_terminal",
+            Action::ClearThumbnailCache => "clear_thumbnail_cache",
+            Action::Settings => "settings",
+            Action::About => "about",
+        }
+    }
+
+    /// Human-readable name (command palette).
+    pub fn title(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::MoveUp => "Move Up",
+            Action::MoveDown => "Move Down",
+            Action::PageUp => "Move Up One Page",
+            Action::PageDown => "Move Down One Page",
+            Action::First => "Go to First Item",
+            Action::Last => "Go to Last Item",
+            Action::Open => "Open",
+            Action::GoParent => "Go to Parent Folder",
+            Action::GoBack => "Go Back",
+            Action::GoForward => "Go Forward",
+            Action::Rename => "Rename",
+            Action::Trash => "Move to Trash",
+            Action::Copy => "Copy",
+            Action::Cut => "Cut",
+            Action::Paste => "Paste",
+            Action::CopyToOtherPane => "Copy to Other Pane",
+            Action::MoveToOtherPane => "Move to Other Pane",
+            Action::SelectAll => "Select All",
+            Action::Cancel => "Clear Filter or Selection",
+            Action::SwitchPane => "Switch Pane",
+            Action::EditPath => "Edit Location",
+            Action::NewTab => "New Tab",
+            Action::CloseTab => "Close Tab",
+            Action::NextTab => "Next Tab",
+            Action::PreviousTab => "Previous Tab",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::QuickFinder => "Go to File",
+            Action::Filter => "Filter This Folder",
+            Action::Search => "Search",
+            Action::CommandPalette => "Command Palette",
+            Action::ToggleHidden => "Show or Hide Hidden Files",
+            Action::ToggleDualPane => "Toggle Dual Pane",
+            Action::CycleViewMode => "Next View Mode",
+            Action::ToggleSortDescending => "Toggle Descending Sort",
+            Action::ToggleFoldersFirst => "Toggle Folders First",
+            Action::NewFolder => "New Folder",
+            Action::NewFile => "New File",
+            Action::Refresh => "Refresh",
+            Action::OpenTerminal => "Open Terminal Here",
+            Action::ClearThumbnailCache => "Clear Thumbnail Cache",
+            Action::Settings => "Settings",
+            Action::About => "About Diptych",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::all().into_iter().find(|a| a.name() == name)
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::MoveLeft => &["Left"],
+            Action::MoveRight => &["Right"],
+            Action::MoveUp => &["Up"],
+            Action::MoveDown => &["Down"],
+            Action::PageUp => &["Page_Up"],
+            Action::PageDown => &["Page_Down"],
+            Action::First => &["Home"],
+            Action::Last => &["End"],
+            Action::Open => &["Return", "KP_Enter"],
+            Action::GoParent => &["BackSpace", "<Alt>Up"],
+            Action::GoBack => &["<Alt>Left"],
+            Action::GoForward => &["<Alt>Right"],
+            Action::Rename => &["F2"],
+            Action::Trash => &["Delete"],
+            Action::Copy => &["<Ctrl>c"],
+            Action::Cut => &["<Ctrl>x"],
+            Action::Paste => &["<Ctrl>v"],
+            Action::CopyToOtherPane => &["F5"],
+            Action::MoveToOtherPane => &["F6"],
+            Action::SelectAll => &["<Ctrl>a"],
+            Action::Cancel => &["Escape"],
+            Action::SwitchPane => &["Tab"],
+            Action::EditPath => &["<Ctrl>l"],
+            Action::NewTab => &["<Ctrl>t"],
+            Action::CloseTab => &["<Ctrl>w"],
+            Action::NextTab => &["<Ctrl>Tab", "<Ctrl>Page_Down"],
+            Action::PreviousTab => &["<Ctrl><Shift>Tab", "<Ctrl>Page_Up"],
+            Action::Undo => &["<Ctrl>z"],
+            Action::Redo => &["<Ctrl><Shift>z"],
+            Action::QuickFinder => &["<Ctrl>p"],
+            Action::Filter => &["<Ctrl>f"],
+            Action::Search => &["<Ctrl><Shift>f"],
+            Action::CommandPalette => &["<Ctrl><Shift>p"],
+            Action::ToggleHidden => &["<Ctrl>h"],
+            Action::ToggleDualPane => &[],
+            Action::CycleViewMode => &[],
+            Action::ToggleSortDescending => &[],
+            Action::ToggleFoldersFirst => &[],
+            Action::NewFolder => &["<Ctrl><Shift>n"],
+            Action::NewFile => &[],
+            Action::Refresh => &["<Ctrl>r"],
+            Action::OpenTerminal => &[],
+            Action::ClearThumbnailCache => &[],
+            Action::Settings => &["<Ctrl>comma"],
+            Action::About => &[],
+        }
+    }
+}
+
+// ─── Config Table ───
+
+/// The keys of one action: a single spec or a list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    pub fn specs(&self) -> &[String] {
+        match self {
+            Keys::One(spec) => std::slice::from_ref(spec),
+            Keys::Many(specs) => specs,
+        }
+    }
+}
+
+/// The `[keybindings]` table with every action on its default keys.
+pub fn default_keybindings() -> BTreeMap<String, Keys> {
+    Action::all()
+        .into_iter()
+        .map(|a| {
+            let keys = a.default_keys().iter().map(|k| k.to_string()).collect();
+            (a.name().to_string(), Keys::Many(keys))
+        })
+        .collect()
+}
+
+// ─── Chords ───
+
+/// Modifiers that take part in bindings (Caps Lock, Num Lock etc. do not).
+pub const MODIFIERS: ModifierType = ModifierType::CONTROL_MASK
+    .union(ModifierType::SHIFT_MASK)
+    .union(ModifierType::ALT_MASK)
+    .union(ModifierType::SUPER_MASK);
+
+/// One key press with its modifiers, normalized so that bindings and
+/// key events compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chord {
+    pub key: Key,
+    pub mods: ModifierType,
+}
+
+impl Chord {
+    /// The chord of a key event; `None` for a lone modifier key.
+    pub fn from_event(key: Key, state: ModifierType) -> Option<Chord> {
+        if is_modifier(key) {
+            return None;
+        }
+        Some(Chord::normalized(key, state))
+    }
+
+    fn normalized(key: Key, mods: ModifierType) -> Chord {
+        let key = if key == Key::ISO_Left_Tab {
+            Key::Tab
+        } else {
+            key
+        };
+        let mut mods = mods & MODIFIERS;
+        // Shift is part of the symbol for punctuation ("question" is Shift+/)
+        let uncased = key.to_lower() == key.to_upper();
+        if uncased && key.to_unicode().is_some_and(|c| !c.is_control()) {
+            mods.remove(ModifierType::SHIFT_MASK);
+        }
+        Chord {
+            key: key.to_lower(),
+            mods,
+        }
+    }
+
+    /// Parses one accelerator such as "<Ctrl><Shift>f" or "G".
+    fn parse(text: &str) -> Result<Chord, String> {
+        let mut rest = text;
+        let mut mods = ModifierType::empty();
+        while let Some(tail) = rest.strip_prefix('<') {
+            let Some((name, tail)) = tail.split_once('>') else {
+                return Err(format!("unclosed modifier in “{}”", text));
+            };
+            mods |= match name.to_lowercase().as_str() {
+                "ctrl" | "control" | "primary" => ModifierType::CONTROL_MASK,
+                "shift" => ModifierType::SHIFT_MASK,
+                "alt" | "mod1" => ModifierType::ALT_MASK,
+                "super" => ModifierType::SUPER_MASK,
+                _ => return Err(format!("unknown modifier “{}”", name)),
+            };
+            rest = tail;
+        }
+        if rest.is_empty() {
+            return Err(format!("“{}” has no key", text));
+        }
+        let key = Key::from_name(rest).ok_or_else(|| format!("unknown key “{}”", rest))?;
+        if is_modifier(key) {
+            return Err(format!("“{}” is a modifier", rest));
+        }
+        if key.is_upper() {
+            mods |= ModifierType::SHIFT_MASK;
+        }
+        Ok(Chord::normalized(key, mods))
+    }
+
+    /// Display form, e.g. "Ctrl+Shift+F".
+    pub fn label(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        for (mask, name) in [
+            (ModifierType::CONTROL_MASK, "Ctrl"),
+            (ModifierType::SUPER_MASK, "Super"),
+            (ModifierType::ALT_MASK, "Alt"),
+            (ModifierType::SHIFT_MASK, "Shift"),
+        ] {
+            if self.mods.contains(mask) {
+                parts.push(name.to_string());
+            }
+        }
+        // The symbol for printable keys ("," rather than "comma")
+        let key = self.key.to_upper();
+        let name = match key
+            .to_unicode()
+            .filter(|c| c.is_alphanumeric() || c.is_ascii_punctuation())
+        {
+            Some(c) => c.to_string(),
+            None => key.name().map(|n| n.replace('_', " ")).unwrap_or_default(),
+        };
+        parts.push(name);
+        parts.join("+")
+    }
+}
+
+fn is_modifier(key: Key) -> bool {
+    matches!(
+        key,
+        Key::Shift_L
+            | Key::Shift_R
+            | Key::Control_L
+            | Key::Control_R
+            | Key::Alt_L
+            | Key::Alt_R
+            | Key::Meta_L
+            | Key::Meta_R
+            | Key::Super_L
+            | Key::Super_R
+            | Key::Hyper_L
+            | Key::Hyper_R
+            | Key::Caps_Lock
+            | Key::ISO_Level3_Shift
+    )
+}
+
+/// Parses a key spec into the chords typed one after another.
+fn parse_sequence(spec: &str) -> Result<Vec<Chord>, String> {
+    let mut chords = Vec::new();
+    for token in spec.split_whitespace() {
+        match Chord::parse(token) {
+            Ok(chord) => chords.push(chord),
+            // "gg": a run of single-character keys
+            Err(e) => {
+                let run: Option<Vec<Chord>> = if token.starts_with('<') {
+                    None
+                } else {
+                    token
+                        .chars()
+                        .map(|c| Chord::parse(&c.to_string()).ok())
+                        .collect()
+                };
+                chords.extend(run.ok_or(e)?);
+            }
+        }
+    }
+    if chords.is_empty() {
+        return Err("empty key".to_string());
+    }
+    Ok(chords)
+}
+
+fn sequence_label(chords: &[Chord]) -> String {
+    chords
+        .iter()
+        .map(Chord::label)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ─── Keymap ───
+
+/// Result of looking up the keys typed so far.
+pub enum KeyMatch {
+    Action(Action),
+    /// The start of a longer sequence.
+    Prefix,
+    None,
+}
+
+/// The compiled bindings.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    bindings: Vec<(Vec<Chord>, Action)>,
+}
+
+impl Keymap {
+    /// Compiles the `[keybindings]` table. Bindings that cannot be used
+    /// are left out and described in the returned problems. Keys set in
+    /// the table win over defaults they collide with.
+    pub fn new(table: &BTreeMap<String, Keys>) -> (Keymap, Vec<String>) {
+        let mut keymap = Keymap::default();
+        let mut problems = Vec::new();
+
+        let mut wanted: Vec<(Action, Vec<String>)> = Vec::new();
+        for (name, keys) in table {
+            match Action::from_name(name) {
+                Some(action) => wanted.push((action, keys.specs().to_vec())),
+                None => problems.push(format!("unknown action “{}”", name)),
+            }
+        }
+        for action in Action::all() {
+            if !table.contains_key(action.name()) {
+                let keys = action.default_keys().iter().map(|k| k.to_string());
+                wanted.push((action, keys.collect()));
+            }
+        }
+
+        for (action, specs) in wanted {
+            for spec in specs {
+                let chords = match parse_sequence(&spec) {
+                    Ok(chords) => chords,
+                    Err(e) => {
+                        problems.push(format!("{}: {}", action.name(), e));
+                        continue;
+                    }
+                };
+                let clash = keymap
+                    .bindings
+                    .iter()
+                    .find(|(other, _)| other.starts_with(&chords) || chords.starts_with(other));
+                match clash {
+                    Some((_, other)) if *other == action => {}
+                    Some((other_chords, other)) => problems.push(format!(
+                        "{}: “{}” collides with “{}” of {}",
+                        action.name(),
+                        sequence_label(&chords),
+                        sequence_label(other_chords),
+                        other.name()
+                    )),
+                    None => keymap.bindings.push((chords, action)),
+                }
+            }
+        }
+        (keymap, problems)
+    }
+
+    /// Looks up the chords typed so far.
+    pub fn lookup(&self, typed: &[Chord]) -> KeyMatch {
+        let mut prefix = false;
+        for (chords, action) in &self.bindings {
+            if chords == typed {
+                return KeyMatch::Action(*action);
+            }
+            prefix |= chords.starts_with(typed);
+        }
+        if prefix {
+            KeyMatch::Prefix
+        } else {
+            KeyMatch::None
+        }
+    }
+
+    /// GTK accelerator strings ("<Control>c") of the single-chord keys
+    /// bound to `action`. Sequences have no GTK form.
+    pub fn accels(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(chords, a)| *a == action && chords.len() == 1)
+            .map(|(chords, _)| gtk4::accelerator_name(chords[0].key, chords[0].mods).to_string())
+            .collect()
+    }
+
+    /// Display forms of the keys bound to `action`.
+    pub fn labels(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(chords, _)| sequence_label(chords))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(key: Key, mods: ModifierType) -> Chord {
+        Chord { key, mods }
+    }
+
+    /// A keymap from `(action, spec)` pairs on top of the defaults.
+    fn compile(bindings: &[(&str, &[&str])]) -> (Keymap, Vec<String>) {
+        let table = bindings
+            .iter()
+            .map(|(action, specs)| {
+                let specs = specs.iter().map(|s| s.to_string()).collect();
+                (action.to_string(), Keys::Many(specs))
+            })
+            .collect();
+        Keymap::new(&table)
+    }
+
+    fn action_of(keymap: &Keymap, spec: &str) -> Option<Action> {
+        match keymap.lookup(&parse_sequence(spec).unwrap()) {
+            KeyMatch::Action(action) => Some(action),
+            KeyMatch::Prefix | KeyMatch::None => None,
+        }
+    }
+
+    #[test]
+    fn parses_modified_chords() {
+        let ctrl_shift = ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK;
+        assert_eq!(
+            parse_sequence("<Ctrl><Shift>f"),
+            Ok(vec![chord(Key::f, ctrl_shift)])
+        );
+        assert_eq!(
+            parse_sequence("<control><alt>Page_Down"),
+            Ok(vec![chord(
+                Key::Page_Down,
+                ModifierType::CONTROL_MASK | ModifierType::ALT_MASK
+            )])
+        );
+        // An upper-case letter is the letter with Shift
+        assert_eq!(
+            parse_sequence("G"),
+            Ok(vec![chord(Key::g, ModifierType::SHIFT_MASK)])
+        );
+        // Shift+Tab arrives as ISO_Left_Tab
+        assert_eq!(
+            Chord::from_event(Key::ISO_Left_Tab, ModifierType::SHIFT_MASK),
+            Some(chord(Key::Tab, ModifierType::SHIFT_MASK))
+        );
+    }
+
+    #[test]
+    fn parses_sequences() {
+        let g = chord(Key::g, ModifierType::empty());
+        assert_eq!(parse_sequence("gg"), Ok(vec![g, g]));
+        assert_eq!(parse_sequence("g g"), Ok(vec![g, g]));
+        assert_eq!(
+            parse_sequence("<Ctrl>x g"),
+            Ok(vec![chord(Key::x, ModifierType::CONTROL_MASK), g])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        for spec in [
+            "",
+            "   ",
+            "<Ctrl>",
+            "<Ctrl",
+            "<Hyper>x",
+            "<Ctrl>Shift_L",
+            "<Ctrl>nosuchkey",
+        ] {
+            assert!(parse_sequence(spec).is_err(), "{:?} parsed", spec);
+        }
+    }
+
+    #[test]
+    fn lock_modifiers_do_not_change_a_chord() {
+        let locked = ModifierType::CONTROL_MASK | ModifierType::LOCK_MASK;
+        assert_eq!(
+            Chord::from_event(Key::c, locked),
+            Some(chord(Key::c, ModifierType::CONTROL_MASK))
+        );
+        assert_eq!(Chord::from_event(Key::Control_L, locked), None);
+    }
+
+    #[test]
+    fn defaults_compile_cleanly() {
+        let (keymap, problems) = compile(&[]);
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(action_of(&keymap, "<Ctrl>c"), Some(Action::Copy));
+        assert_eq!(action_of(&keymap, "<Ctrl><Shift>z"), Some(Action::Redo));
+        assert_eq!(action_of(&keymap, "<Alt>Up"), Some(Action::GoParent));
+    }
+
+    #[test]
+    fn reports_unknown_actions_and_bad_keys() {
+        let (keymap, problems) = compile(&[("fly", &["f"]), ("copy", &["<Ctrl>", "<Ctrl>k"])]);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        // The good spec of the action still binds
+        assert_eq!(action_of(&keymap, "<Ctrl>k"), Some(Action::Copy));
+        // and replaces the default
+        assert_eq!(action_of(&keymap, "<Ctrl>c"), None);
+    }
+
+    #[test]
+    fn rejects_prefix_clashes() {
+        let (keymap, problems) = compile(&[("first", &["g"]), ("last", &["g g"])]);
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("last:"), "{:?}", problems);
+        assert_eq!(action_of(&keymap, "g"), Some(Action::First));
+
+        // A sequence makes its first key a prefix
+        let (keymap, problems) = compile(&[("first", &["g g"])]);
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert!(matches!(
+            keymap.lookup(&parse_sequence("g").unwrap()),
+            KeyMatch::Prefix
+        ));
+        assert_eq!(action_of(&keymap, "gg"), Some(Action::First));
+    }
+
+    #[test]
+    fn rejects_duplicate_bindings() {
+        // Table entries are compiled in name order: "copy" claims the key
+        let (keymap, problems) = compile(&[("copy", &["<Ctrl>c"]), ("cut", &["<Ctrl>c"])]);
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("cut:"), "{:?}", problems);
+        assert_eq!(action_of(&keymap, "<Ctrl>c"), Some(Action::Copy));
+
+        // Binding the same key twice to one action is harmless
+        let (_, problems) = compile(&[("copy", &["<Ctrl>c", "<Ctrl>c"])]);
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[test]
+    fn configured_keys_win_over_defaults() {
+        let (keymap, problems) = compile(&[("undo", &["<Ctrl>c"])]);
+        // Copy's default is the one left out
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("copy:"), "{:?}", problems);
+        assert_eq!(action_of(&keymap, "<Ctrl>c"), Some(Action::Undo));
+    }
+}
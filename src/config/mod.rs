@@ -1,8 +1,13 @@
 // ─── Config Module ───
 // Manages all user-configurable settings and their disk persistence.
 
+pub mod keybindings;
 pub mod persistence;
 pub mod types;
 
 // Re-export most commonly used items for convenience.
-pub use types::{AppConfig, GroupBy, IconTheme, ViewMode};
+pub use keybindings::{Action, Keymap};
+pub use types::{
+    AppConfig, ColumnLayout, DateFilter, DetailsColumn, GroupBy, IconTheme, NameMatch, SavedSearch,
+    SavedTab, SearchQuery, Session, SortKey, SortOrder, TypeFilter, ViewMode,
+};
@@ -1,6 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
 
+use super::keybindings::Keymap;
 use super::types::AppConfig;
 
 // ─── Path Helper ───
@@ -24,6 +25,7 @@ pub fn load_config() -> AppConfig {
             Ok(content) => match toml::from_str::<AppConfig>(&content) {
                 Ok(cfg) => {
                     println!("[config] Loaded from {:?}", path);
+                    report_keybindings(&cfg);
                     return cfg;
                 }
                 Err(e) => eprintln!("[config] Parse error, using defaults: {}", e),
@@ -40,6 +42,14 @@ pub fn load_config() -> AppConfig {
     default
 }
 
+/// Reports key bindings that cannot be used (they are left out).
+fn report_keybindings(config: &AppConfig) {
+    let (_, problems) = Keymap::new(&config.keybindings);
+    for problem in problems {
+        eprintln!("[config] Ignoring key binding: {}", problem);
+    }
+}
+
 // ─── Save ───
 
 /// Persists the given config to disk as TOML.
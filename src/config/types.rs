@@ -1,4 +1,7 @@
+use super::keybindings::{self, Keys};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 // ─── Icon Theme ───
 
@@ -43,6 +46,195 @@ pub enum GroupBy {
     Name,
 }
 
+impl GroupBy {
+    pub fn all() -> [GroupBy; 4] {
+        [GroupBy::None, GroupBy::Type, GroupBy::Date, GroupBy::Name]
+    }
+
+    /// Identifier used by the `group-by` action.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GroupBy::None => "none",
+            GroupBy::Type => "type",
+            GroupBy::Date => "date",
+            GroupBy::Name => "name",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            GroupBy::None => "None",
+            GroupBy::Type => "Type",
+            GroupBy::Date => "Date",
+            GroupBy::Name => "Name",
+        }
+    }
+}
+
+// ─── Sorting ───
+
+/// What entries are sorted by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SortKey {
+    Name,
+    /// Name with digit runs compared as numbers ("file2" < "file10").
+    Natural,
+    Size,
+    Modified,
+    Created,
+    Accessed,
+    /// MIME type (folders sort first).
+    Type,
+}
+
+impl SortKey {
+    pub fn all() -> [SortKey; 7] {
+        [
+            SortKey::Name,
+            SortKey::Natural,
+            SortKey::Size,
+            SortKey::Modified,
+            SortKey::Created,
+            SortKey::Accessed,
+            SortKey::Type,
+        ]
+    }
+
+    /// Identifier used by the `sort-by` action.
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Natural => "natural",
+            SortKey::Size => "size",
+            SortKey::Modified => "modified",
+            SortKey::Created => "created",
+            SortKey::Accessed => "accessed",
+            SortKey::Type => "type",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SortKey::Name => "Name",
+            SortKey::Natural => "Name (natural)",
+            SortKey::Size => "Size",
+            SortKey::Modified => "Modified",
+            SortKey::Created => "Created",
+            SortKey::Accessed => "Accessed",
+            SortKey::Type => "Type",
+        }
+    }
+}
+
+/// The full sort order, as passed to `filesystem::sort_key`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+    pub folders_first: bool,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        Self {
+            key: SortKey::Natural,
+            descending: false,
+            folders_first: true,
+        }
+    }
+}
+
+// ─── Details View Columns ───
+
+/// A column of the details (List) view.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DetailsColumn {
+    Name,
+    Size,
+    Modified,
+    Created,
+    Accessed,
+    Type,
+    Permissions,
+    Owner,
+    Group,
+    LinkTarget,
+}
+
+impl DetailsColumn {
+    pub fn all() -> [DetailsColumn; 10] {
+        [
+            DetailsColumn::Name,
+            DetailsColumn::Size,
+            DetailsColumn::Modified,
+            DetailsColumn::Created,
+            DetailsColumn::Accessed,
+            DetailsColumn::Type,
+            DetailsColumn::Permissions,
+            DetailsColumn::Owner,
+            DetailsColumn::Group,
+            DetailsColumn::LinkTarget,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DetailsColumn::Name => "Name",
+            DetailsColumn::Size => "Size",
+            DetailsColumn::Modified => "Modified",
+            DetailsColumn::Created => "Created",
+            DetailsColumn::Accessed => "Accessed",
+            DetailsColumn::Type => "Type",
+            DetailsColumn::Permissions => "Permissions",
+            DetailsColumn::Owner => "Owner",
+            DetailsColumn::Group => "Group",
+            DetailsColumn::LinkTarget => "Link Target",
+        }
+    }
+
+    /// Stable identifier, used for action names.
+    pub fn id(&self) -> &'static str {
+        match self {
+            DetailsColumn::Name => "name",
+            DetailsColumn::Size => "size",
+            DetailsColumn::Modified => "modified",
+            DetailsColumn::Created => "created",
+            DetailsColumn::Accessed => "accessed",
+            DetailsColumn::Type => "type",
+            DetailsColumn::Permissions => "permissions",
+            DetailsColumn::Owner => "owner",
+            DetailsColumn::Group => "group",
+            DetailsColumn::LinkTarget => "link-target",
+        }
+    }
+}
+
+/// Position, visibility and width of one details column.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ColumnLayout {
+    pub column: DetailsColumn,
+    pub visible: bool,
+    /// Width in pixels, or -1 to size the column automatically.
+    pub width: i32,
+}
+
+fn default_details_columns() -> Vec<ColumnLayout> {
+    DetailsColumn::all()
+        .into_iter()
+        .map(|column| ColumnLayout {
+            column,
+            visible: matches!(
+                column,
+                DetailsColumn::Name
+                    | DetailsColumn::Size
+                    | DetailsColumn::Modified
+                    | DetailsColumn::Type
+            ),
+            width: -1,
+        })
+        .collect()
+}
+
 // ─── View Mode ───
 
 /// Switches between grid (card), list (row), graph (node), and tree (hierarchy) layouts.
@@ -54,10 +246,149 @@ pub enum ViewMode {
     Tree,
 }
 
+// ─── Session ───
+
+/// One open tab: its folder and view mode.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedTab {
+    pub path: PathBuf,
+    pub view_mode: ViewMode,
+}
+
+/// Tabs open in each pane when the window was last closed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub left_active: usize,
+    pub right_active: usize,
+    pub left: Vec<SavedTab>,
+    pub right: Vec<SavedTab>,
+}
+
+// ─── Search ───
+
+/// How the name pattern of a search is matched (always ignoring case).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum NameMatch {
+    #[default]
+    Substring,
+    Glob,
+    Regex,
+}
+
+impl NameMatch {
+    pub fn all() -> [NameMatch; 3] {
+        [NameMatch::Substring, NameMatch::Glob, NameMatch::Regex]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            NameMatch::Substring => "Contains",
+            NameMatch::Glob => "Glob",
+            NameMatch::Regex => "Regex",
+        }
+    }
+}
+
+/// The kind of item a search is limited to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum TypeFilter {
+    #[default]
+    Any,
+    Folder,
+    File,
+    Image,
+    Audio,
+    Video,
+    Document,
+    Archive,
+    Text,
+}
+
+impl TypeFilter {
+    pub fn all() -> [TypeFilter; 9] {
+        [
+            TypeFilter::Any,
+            TypeFilter::Folder,
+            TypeFilter::File,
+            TypeFilter::Image,
+            TypeFilter::Audio,
+            TypeFilter::Video,
+            TypeFilter::Document,
+            TypeFilter::Archive,
+            TypeFilter::Text,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            TypeFilter::Any => "Any Type",
+            TypeFilter::Folder => "Folders",
+            TypeFilter::File => "Files",
+            TypeFilter::Image => "Images",
+            TypeFilter::Audio => "Audio",
+            TypeFilter::Video => "Videos",
+            TypeFilter::Document => "Documents",
+            TypeFilter::Archive => "Archives",
+            TypeFilter::Text => "Text Files",
+        }
+    }
+}
+
+/// The modified-date condition of a search. `Between` takes "YYYY-MM-DD"
+/// dates, both inclusive; a missing end is open.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum DateFilter {
+    #[default]
+    Any,
+    Today,
+    PastWeek,
+    PastMonth,
+    PastYear,
+    Between {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+/// A search as entered in the search bar. Empty fields match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SearchQuery {
+    pub name: String,
+    pub name_match: NameMatch,
+    pub kind: TypeFilter,
+    /// Size bounds in bytes, inclusive (files only).
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub modified: DateFilter,
+    /// Text the file contents must include (ignoring case).
+    pub contents: String,
+}
+
+impl SearchQuery {
+    /// True if nothing at all was asked for.
+    pub fn is_empty(&self) -> bool {
+        *self == SearchQuery::default()
+    }
+}
+
+/// A search kept as a smart folder in the sidebar; opening it re-runs
+/// `query` below `root`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub root: PathBuf,
+    #[serde(default)]
+    pub query: SearchQuery,
+}
+
 // ─── Application Config ───
 
 /// All user-configurable settings, persisted to disk as TOML.
+/// Missing keys fall back to their defaults, so older config files keep loading.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     // Appearance
     pub theme: String,
@@ -70,12 +401,36 @@ pub struct AppConfig {
     pub show_file_size: bool,
     pub show_modified_date: bool,
 
+    // Sorting
+    pub sort_key: SortKey,
+    pub sort_descending: bool,
+    pub folders_first: bool,
+
     // Grouping
     pub grouping: GroupBy,
 
     // Window state
     pub window_width: i32,
     pub window_height: i32,
+    pub dual_pane: bool,
+
+    // History
+    pub persist_undo_history: bool,
+
+    // Search
+    pub respect_gitignore: bool,
+
+    // Smart folders (saved searches), in sidebar order
+    pub saved_searches: Vec<SavedSearch>,
+
+    // Keyboard shortcuts: action name → key specs (see `keybindings`)
+    pub keybindings: BTreeMap<String, Keys>,
+
+    // Details view columns, in display order
+    pub details_columns: Vec<ColumnLayout>,
+
+    // Open tabs, restored on launch
+    pub session: Session,
 }
 
 impl Default for AppConfig {
@@ -88,9 +443,19 @@ impl Default for AppConfig {
             show_hidden: false,
             show_file_size: true,
             show_modified_date: true,
+            sort_key: SortKey::Natural,
+            sort_descending: false,
+            folders_first: true,
             grouping: GroupBy::None,
             window_width: 1100,
             window_height: 700,
+            dual_pane: true,
+            persist_undo_history: false,
+            respect_gitignore: true,
+            saved_searches: Vec::new(),
+            keybindings: keybindings::default_keybindings(),
+            details_columns: default_details_columns(),
+            session: Session::default(),
         }
     }
 }
@@ -101,6 +466,42 @@ impl AppConfig {
         super::persistence::load_config()
     }
 
+    pub fn sort_order(&self) -> SortOrder {
+        SortOrder {
+            key: self.sort_key,
+            descending: self.sort_descending,
+            folders_first: self.folders_first,
+        }
+    }
+
+    /// The details view columns in display order. Columns missing from
+    /// the saved layout (e.g. added in a later version) come last, hidden.
+    pub fn details_layout(&self) -> Vec<ColumnLayout> {
+        let mut layout: Vec<ColumnLayout> = Vec::new();
+        for c in &self.details_columns {
+            if !layout.iter().any(|l| l.column == c.column) {
+                layout.push(c.clone());
+            }
+        }
+        for column in DetailsColumn::all() {
+            if !layout.iter().any(|c| c.column == column) {
+                layout.push(ColumnLayout {
+                    column,
+                    visible: column == DetailsColumn::Name,
+                    width: -1,
+                });
+            }
+        }
+        // The name column cannot be hidden
+        for c in layout
+            .iter_mut()
+            .filter(|c| c.column == DetailsColumn::Name)
+        {
+            c.visible = true;
+        }
+        layout
+    }
+
     /// Persist this config to disk (convenience wrapper).
     pub fn save(&self) {
         super::persistence::save_config(self);
@@ -0,0 +1,665 @@
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// ═══════════════════════════════════════════════
+//  Desktop Applications
+// ═══════════════════════════════════════════════
+//
+// Installed applications come from the `.desktop` files under
+// `applications/` in the XDG data dirs ($XDG_DATA_HOME first, then each
+// of $XDG_DATA_DIRS). A desktop file id ("org.gnome.TextEditor.desktop",
+// subdirectories joined with '-') is taken from the first dir that has
+// it, so user files shadow system ones.
+//
+// Default applications are read from `mimeapps.list` in the XDG config
+// and data dirs, each dir's `$XDG_CURRENT_DESKTOP-mimeapps.list` first,
+// and "Set as default" writes the user's $XDG_CONFIG_HOME/mimeapps.list.
+//
+// Both are parsed once and kept until a file they came from changes:
+// each lookup only compares the modification times of the desktop files
+// and the dirs holding them, and of the `mimeapps.list` files.
+
+/// One installed application.
+#[derive(Debug, Clone)]
+pub struct DesktopApp {
+    /// Desktop file id, e.g. "org.gnome.TextEditor.desktop".
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub mime_types: Vec<String>,
+    /// Hidden from application lists, but still a handler for its types.
+    pub no_display: bool,
+    /// The `.desktop` file itself (used to launch the app).
+    pub path: PathBuf,
+}
+
+impl DesktopApp {
+    /// True if the app declares `mime`. Apps for plain text also take
+    /// every other text type.
+    pub fn handles(&self, mime: &str) -> bool {
+        self.mime_types
+            .iter()
+            .any(|m| m == mime || (m == "text/plain" && mime.starts_with("text/")))
+    }
+}
+
+// ─── XDG Directories ───
+
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::data_dir().into_iter().collect();
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(
+        system
+            .split(':')
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs
+}
+
+fn config_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::config_dir().into_iter().collect();
+    let system = std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+    dirs.extend(
+        system
+            .split(':')
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs
+}
+
+/// `mimeapps.list` files, most important first. In each dir the lists
+/// of the current desktops ("gnome-mimeapps.list") come before the
+/// plain one.
+fn mimeapps_files() -> Vec<PathBuf> {
+    let desktops = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    let names: Vec<String> = desktops
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(|d| format!("{}-mimeapps.list", d.to_lowercase()))
+        .chain(std::iter::once("mimeapps.list".to_string()))
+        .collect();
+    let data = data_dirs().into_iter().map(|d| d.join("applications"));
+    config_dirs()
+        .into_iter()
+        .chain(data)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .collect()
+}
+
+// ─── Application Registry ───
+
+/// When a file or dir was last changed, and its size; `None` if missing.
+type Stamp = Vec<Option<(SystemTime, u64)>>;
+
+fn stamp(paths: &[PathBuf]) -> Stamp {
+    paths
+        .iter()
+        .map(|p| {
+            let meta = fs::metadata(p).ok()?;
+            Some((meta.modified().ok()?, meta.len()))
+        })
+        .collect()
+}
+
+thread_local! {
+    static APPS: RefCell<Option<(Stamp, Vec<DesktopApp>)>> = const { RefCell::new(None) };
+    static MIMEAPPS: RefCell<Option<(Stamp, MimeApps)>> = const { RefCell::new(None) };
+}
+
+/// The `applications/` dirs `roots` with every subdir and desktop file
+/// below them: what the stamp of the installed apps covers. A dir's
+/// time only changes when entries come or go, not when a file is edited.
+fn app_files(roots: &[PathBuf]) -> Vec<PathBuf> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                files.push(path.clone());
+                walk(&path, files);
+            } else if path.extension().is_some_and(|e| e == "desktop") {
+                files.push(path);
+            }
+        }
+    }
+    let mut files = roots.to_vec();
+    for root in roots {
+        walk(root, &mut files);
+    }
+    files
+}
+
+/// Every installed application, sorted by name.
+pub fn load_apps() -> Vec<DesktopApp> {
+    let dirs: Vec<PathBuf> = data_dirs()
+        .into_iter()
+        .map(|d| d.join("applications"))
+        .collect();
+    let current = stamp(&app_files(&dirs));
+    APPS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        match cache.as_ref() {
+            Some((seen, apps)) if *seen == current => apps.clone(),
+            _ => {
+                let apps = scan_apps(&dirs, &locale());
+                *cache = Some((current, apps.clone()));
+                apps
+            }
+        }
+    })
+}
+
+/// Reads the desktop files in the `applications/` dirs `roots`, most
+/// important first, with names in the locale `lang`.
+fn scan_apps(roots: &[PathBuf], lang: &str) -> Vec<DesktopApp> {
+    let mut found: HashMap<String, Option<DesktopApp>> = HashMap::new();
+    for root in roots {
+        scan_dir(root, root, lang, &mut found);
+    }
+    let mut apps: Vec<DesktopApp> = found.into_values().flatten().collect();
+    apps.sort_by_cached_key(|a| a.name.to_lowercase());
+    apps
+}
+
+/// Collects the desktop files below `dir`. Ids already seen (from a more
+/// important data dir) are kept; a `Hidden` file still claims its id.
+fn scan_dir(root: &Path, dir: &Path, lang: &str, found: &mut HashMap<String, Option<DesktopApp>>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_dir(root, &path, lang, found);
+            continue;
+        }
+        if path.extension().is_none_or(|e| e != "desktop") {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let id = relative.to_string_lossy().replace('/', "-");
+        if let Entry::Vacant(slot) = found.entry(id) {
+            let app = fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| parse_desktop_file(&text, slot.key(), &path, lang));
+            slot.insert(app);
+        }
+    }
+}
+
+/// Parses the `[Desktop Entry]` group, naming the app for the locale
+/// `lang`. Returns `None` for anything but a visible application
+/// (`Type=Application`, not `Hidden`).
+fn parse_desktop_file(text: &str, id: &str, path: &Path, lang: &str) -> Option<DesktopApp> {
+    let keys = group_keys(text, "Desktop Entry");
+    let get = |key: &str| keys.get(key).map(String::as_str);
+
+    if get("Type") != Some("Application") || get("Hidden") == Some("true") {
+        return None;
+    }
+    let name = localized(&keys, "Name", lang)?;
+    Some(DesktopApp {
+        id: id.to_string(),
+        name,
+        icon: get("Icon").map(str::to_string),
+        mime_types: split_list(get("MimeType").unwrap_or("")),
+        no_display: get("NoDisplay") == Some("true"),
+        path: path.to_path_buf(),
+    })
+}
+
+/// The user's locale for messages, e.g. "de_DE.UTF-8@euro".
+fn locale() -> String {
+    std::env::var("LC_ALL")
+        .or_else(|_| std::env::var("LC_MESSAGES"))
+        .or_else(|_| std::env::var("LANG"))
+        .unwrap_or_default()
+}
+
+/// `Key[locale]` for the locale `lang` if present, else `Key`.
+fn localized(keys: &HashMap<String, String>, key: &str, lang: &str) -> Option<String> {
+    // "de_DE.UTF-8@euro" → "de_DE", then "de"
+    let lang = lang.split(['.', '@']).next().unwrap_or("");
+    let short = lang.split('_').next().unwrap_or("");
+    [lang, short]
+        .iter()
+        .filter(|l| !l.is_empty() && **l != "C" && **l != "POSIX")
+        .find_map(|l| keys.get(&format!("{}[{}]", key, l)))
+        .or_else(|| keys.get(key))
+        .cloned()
+}
+
+// ─── MIME Associations ───
+
+/// Applications that can open files of type `mime`: the default first,
+/// then the rest by name. Associations removed in `mimeapps.list` are
+/// left out; added ones are included.
+pub fn apps_for_mime(apps: &[DesktopApp], mime: &str) -> Vec<DesktopApp> {
+    let lists = read_mimeapps();
+    let removed = lists.associations("Removed Associations", mime);
+    let added = lists.associations("Added Associations", mime);
+
+    let mut result: Vec<DesktopApp> = apps
+        .iter()
+        .filter(|a| !removed.contains(&a.id))
+        .filter(|a| a.handles(mime) || added.contains(&a.id))
+        .cloned()
+        .collect();
+    if let Some(default) = default_app_id(apps, mime) {
+        if let Some(pos) = result.iter().position(|a| a.id == default) {
+            let app = result.remove(pos);
+            result.insert(0, app);
+        }
+    }
+    result
+}
+
+/// Id of the default application for `mime`: the first installed one
+/// listed under `[Default Applications]`, else the first added association.
+pub fn default_app_id(apps: &[DesktopApp], mime: &str) -> Option<String> {
+    let lists = read_mimeapps();
+    let installed = |id: &String| apps.iter().any(|a| &a.id == id);
+    lists
+        .associations("Default Applications", mime)
+        .into_iter()
+        .find(installed)
+        .or_else(|| {
+            lists
+                .associations("Added Associations", mime)
+                .into_iter()
+                .find(installed)
+        })
+}
+
+/// Makes `app_id` the default for `mime` in the user's `mimeapps.list`
+/// (and its first added association). Other lines are kept as they are.
+pub fn set_default(mime: &str, app_id: &str) -> io::Result<()> {
+    let dir = dirs::config_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+    let path = dir.join("mimeapps.list");
+    let text = fs::read_to_string(&path).unwrap_or_default();
+
+    let mut added = vec![app_id.to_string()];
+    added.extend(
+        split_list(
+            group_keys(&text, "Added Associations")
+                .get(mime)
+                .map_or("", |v| v),
+        )
+        .into_iter()
+        .filter(|id| id != app_id),
+    );
+
+    let text = set_key(&text, "Default Applications", mime, &format!("{};", app_id));
+    let text = set_key(
+        &text,
+        "Added Associations",
+        mime,
+        &format!("{};", added.join(";")),
+    );
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, text)
+}
+
+/// The merged contents of every `mimeapps.list`, most important first.
+#[derive(Clone)]
+struct MimeApps(Vec<String>);
+
+fn read_mimeapps() -> MimeApps {
+    let files = mimeapps_files();
+    let current = stamp(&files);
+    MIMEAPPS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        match cache.as_ref() {
+            Some((seen, lists)) if *seen == current => lists.clone(),
+            _ => {
+                let lists = MimeApps(
+                    files
+                        .iter()
+                        .filter_map(|p| fs::read_to_string(p).ok())
+                        .collect(),
+                );
+                *cache = Some((current, lists.clone()));
+                lists
+            }
+        }
+    })
+}
+
+impl MimeApps {
+    /// Ids listed for `mime` in `group`, across all files in order.
+    fn associations(&self, group: &str, mime: &str) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for text in &self.0 {
+            if let Some(value) = group_keys(text, group).get(mime) {
+                for id in split_list(value) {
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+            }
+        }
+        ids
+    }
+}
+
+// ─── Terminal ───
+
+/// Terminal emulators tried in order after $TERMINAL.
+const TERMINALS: &[&str] = &[
+    "x-terminal-emulator",
+    "kgx",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "alacritty",
+    "kitty",
+    "foot",
+    "wezterm",
+    "xterm",
+];
+
+/// Starts a terminal in `dir`: $TERMINAL if set, else the first
+/// installed one of `TERMINALS`. $TERMINAL may carry arguments
+/// ("kitty --single-instance"), split with shell quoting rules.
+pub fn open_terminal(dir: &Path) -> io::Result<()> {
+    let mut candidates: Vec<Vec<OsString>> = Vec::new();
+    if let Some(preferred) = std::env::var_os("TERMINAL").filter(|t| !t.is_empty()) {
+        let argv = glib::shell_parse_argv(&preferred).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("$TERMINAL: {}", e))
+        })?;
+        candidates.push(argv);
+    }
+    candidates.extend(TERMINALS.iter().map(|t| vec![OsString::from(t)]));
+
+    for argv in candidates {
+        let Some((program, args)) = argv.split_first() else {
+            continue;
+        };
+        let spawned = std::process::Command::new(program)
+            .args(args)
+            .current_dir(dir)
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                // Reap the terminal when it exits, so it does not linger
+                // as a zombie while the file manager runs
+                std::thread::spawn(move || {
+                    let _ = child.wait();
+                });
+                return Ok(());
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no terminal emulator found (set $TERMINAL)",
+    ))
+}
+
+// ─── Key File Helpers ───
+
+/// Keys and values of `[group]` in an INI-style key file.
+fn group_keys(text: &str, group: &str) -> HashMap<String, String> {
+    let mut keys = HashMap::new();
+    let mut in_group = false;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            in_group = &line[1..line.len() - 1] == group;
+        } else if in_group && !line.starts_with('#') {
+            if let Some((key, value)) = line.split_once('=') {
+                keys.entry(key.trim().to_string())
+                    .or_insert_with(|| value.trim().to_string());
+            }
+        }
+    }
+    keys
+}
+
+/// Splits a ';'-separated list value, dropping empty items.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Returns `text` with `key=value` set in `[group]`, replacing an existing
+/// line or adding one (and the group) as needed.
+fn set_key(text: &str, group: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let header = format!("[{}]", group);
+    let entry = format!("{}={}", key, value);
+
+    let Some(start) = lines.iter().position(|l| l.trim() == header) else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        lines.push(entry);
+        return lines.join("\n") + "\n";
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim().starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    let existing = (start + 1..end).find(|&i| {
+        lines[i]
+            .split_once('=')
+            .is_some_and(|(k, _)| k.trim() == key)
+    });
+    match existing {
+        Some(i) => lines[i] = entry,
+        None => {
+            // After the group's last non-blank line
+            let at = (start + 1..end)
+                .rev()
+                .find(|&i| !lines[i].trim().is_empty())
+                .map_or(start + 1, |i| i + 1);
+            lines.insert(at, entry);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty directory under the system temp dir.
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("diptych-apps-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn desktop(name: &str, extra: &str) -> String {
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec=true %F\n{}",
+            name, extra
+        )
+    }
+
+    fn parse(text: &str, lang: &str) -> Option<DesktopApp> {
+        parse_desktop_file(text, "app.desktop", Path::new("/fixture/app.desktop"), lang)
+    }
+
+    #[test]
+    fn parses_an_application() {
+        let text = desktop("Editor", "Icon=editor\nMimeType=text/plain;text/x-csrc;\n");
+        let app = parse(&text, "").unwrap();
+        assert_eq!(app.id, "app.desktop");
+        assert_eq!(app.name, "Editor");
+        assert_eq!(app.icon.as_deref(), Some("editor"));
+        assert_eq!(app.mime_types, ["text/plain", "text/x-csrc"]);
+        assert!(!app.no_display);
+        assert!(app.handles("text/markdown"));
+        assert!(!app.handles("image/png"));
+    }
+
+    #[test]
+    fn skips_hidden_entries_and_non_applications() {
+        assert!(parse(&desktop("Gone", "Hidden=true\n"), "").is_none());
+        assert!(parse("[Desktop Entry]\nType=Link\nName=Site\n", "").is_none());
+        assert!(parse("[Desktop Entry]\nType=Application\n", "").is_none());
+    }
+
+    #[test]
+    fn keeps_no_display_apps_as_handlers() {
+        let app = parse(&desktop("Helper", "NoDisplay=true\n"), "").unwrap();
+        assert!(app.no_display);
+    }
+
+    #[test]
+    fn picks_the_localized_name() {
+        let text = desktop("Files", "Name[de]=Dateien\nName[de_AT]=Dateien (AT)\n");
+        let name = |lang| parse(&text, lang).unwrap().name;
+        assert_eq!(name("de_AT.UTF-8"), "Dateien (AT)");
+        assert_eq!(name("de_DE.UTF-8@euro"), "Dateien");
+        assert_eq!(name("fr_FR.UTF-8"), "Files");
+        assert_eq!(name("C"), "Files");
+        assert_eq!(name(""), "Files");
+    }
+
+    #[test]
+    fn earlier_data_dirs_shadow_later_ones() {
+        let root = fixture_dir("shadow");
+        let user = root.join("user/applications");
+        let system = root.join("system/applications");
+        fs::create_dir_all(user.join("kde")).unwrap();
+        fs::create_dir_all(&system).unwrap();
+
+        let write = |dir: &Path, file: &str, text: String| fs::write(dir.join(file), text).unwrap();
+        write(&user, "editor.desktop", desktop("User Editor", ""));
+        write(&system, "editor.desktop", desktop("System Editor", ""));
+        // A hidden user file still claims its id
+        write(&user, "gone.desktop", desktop("Gone", "Hidden=true\n"));
+        write(&system, "gone.desktop", desktop("Gone", ""));
+        write(&system, "viewer.desktop", desktop("Viewer", ""));
+        write(&user.join("kde"), "term.desktop", desktop("Term", ""));
+        write(&system, "notes.txt", "not a desktop file".to_string());
+
+        let apps = scan_apps(&[user.clone(), system.clone()], "");
+        let found: Vec<(&str, &str)> = apps
+            .iter()
+            .map(|a| (a.id.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("kde-term.desktop", "Term"),
+                ("editor.desktop", "User Editor"),
+                ("viewer.desktop", "Viewer"),
+            ]
+        );
+        assert_eq!(apps[1].path, user.join("editor.desktop"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn stamps_cover_nested_desktop_files() {
+        let root = fixture_dir("stamp");
+        let apps = root.join("applications");
+        fs::create_dir_all(apps.join("kde")).unwrap();
+        fs::write(apps.join("editor.desktop"), desktop("Editor", "")).unwrap();
+        fs::write(apps.join("kde/term.desktop"), desktop("Term", "")).unwrap();
+        fs::write(apps.join("notes.txt"), "").unwrap();
+
+        let mut files = app_files(std::slice::from_ref(&apps));
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                apps.clone(),
+                apps.join("editor.desktop"),
+                apps.join("kde"),
+                apps.join("kde/term.desktop"),
+            ]
+        );
+        // Editing a file in place changes the stamp
+        let before = stamp(&files);
+        fs::write(apps.join("kde/term.desktop"), desktop("Terminal", "")).unwrap();
+        assert_ne!(stamp(&files), before);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn set_key_round_trips_mimeapps_list() {
+        let text = "# edited by hand\n\
+                    [Default Applications]\n\
+                    text/plain=old.desktop;\n\
+                    \n\
+                    [Added Associations]\n\
+                    image/png=viewer.desktop;\n";
+        let text = set_key(text, "Default Applications", "text/plain", "new.desktop;");
+        let text = set_key(
+            &text,
+            "Default Applications",
+            "image/png",
+            "viewer.desktop;",
+        );
+        let text = set_key(
+            &text,
+            "Removed Associations",
+            "text/html",
+            "browser.desktop;",
+        );
+        assert_eq!(
+            text,
+            "# edited by hand\n\
+             [Default Applications]\n\
+             text/plain=new.desktop;\n\
+             image/png=viewer.desktop;\n\
+             \n\
+             [Added Associations]\n\
+             image/png=viewer.desktop;\n\
+             \n\
+             [Removed Associations]\n\
+             text/html=browser.desktop;\n"
+        );
+
+        let defaults = group_keys(&text, "Default Applications");
+        assert_eq!(defaults["text/plain"], "new.desktop;");
+        assert_eq!(split_list(&defaults["image/png"]), ["viewer.desktop"]);
+        // Setting a key to its current value changes nothing
+        assert_eq!(
+            set_key(&text, "Added Associations", "image/png", "viewer.desktop;"),
+            text
+        );
+    }
+
+    #[test]
+    fn set_key_starts_an_empty_file() {
+        assert_eq!(
+            set_key("", "Default Applications", "text/plain", "editor.desktop;"),
+            "[Default Applications]\ntext/plain=editor.desktop;\n"
+        );
+    }
+}
@@ -1,6 +1,10 @@
 // ─── Core Module ───
-// Framework-agnostic domain logic: theme definitions, color palettes.
+// Framework-agnostic domain logic: theme definitions, color palettes,
+// installed applications, their MIME associations and the terminal,
+// and the background workers that stream events to the main loop.
 
+pub mod apps;
 pub mod theme;
+pub mod worker;
 
 pub use theme::Theme;
@@ -325,6 +325,76 @@ window {{
     color: {fg_primary};
 }}
 
+/* ── Panes ── */
+.pane {{
+    border: 1px solid transparent;
+    border-radius: 10px;
+}}
+.pane-active {{
+    border-color: {border_hover};
+}}
+.pane-header {{
+    padding: 6px 14px 2px 14px;
+}}
+.pane-active .breadcrumb-label {{
+    color: {fg_primary};
+}}
+
+/* ── Pane Tabs ── */
+.pane-tabs {{
+    padding: 2px 12px 0 12px;
+}}
+.pane-tab {{
+    padding: 2px 4px 2px 10px;
+    border-radius: 8px 8px 0 0;
+    color: {fg_secondary};
+}}
+.pane-tab:hover {{
+    background-color: {bg_hover};
+}}
+.pane-tab-active {{
+    background-color: {bg_overlay};
+    color: {fg_primary};
+    box-shadow: inset 0 -2px 0 {accent};
+}}
+.pane-tab-label {{
+    font-size: 12px;
+}}
+.pane-tab-close,
+.pane-tab-new {{
+    min-width: 0;
+    min-height: 0;
+    padding: 2px;
+    color: {fg_muted};
+}}
+
+/* ── Path Bar ── */
+.path-segment {{
+    font-size: 13px;
+    font-weight: 600;
+    color: {fg_secondary};
+    padding: 2px 6px;
+    min-height: 0;
+    border-radius: 6px;
+}}
+.path-segment:hover {{
+    background-color: {bg_hover};
+}}
+.path-segment-current,
+.pane-active .path-segment-current {{
+    color: {fg_primary};
+}}
+.path-segment-arrow {{
+    padding: 2px 0;
+    min-width: 0;
+    min-height: 0;
+    color: {fg_muted};
+}}
+.path-entry {{
+    font-size: 13px;
+    min-height: 0;
+}}
+
 /* ── File Cards (Grid Mode) ── */
 .file-card {{
     background-color: {bg_overlay};
@@ -340,6 +410,11 @@ window {{
     border-color: {border_hover};
     box-shadow: 0 4px 16px {shadow_hover};
 }}
+.file-card.item-selected {{
+    background-color: {bg_hover};
+    border-color: {accent};
+    box-shadow: 0 0 0 1px {accent}, 0 4px 16px {shadow_hover};
+}}
 .file-card-name {{
     font-size: 12px;
     font-weight: 500;
@@ -364,6 +439,10 @@ window {{
 .file-row:hover {{
     background-color: {bg_overlay};
 }}
+.file-row.item-selected {{
+    background-color: {bg_hover};
+    box-shadow: inset 3px 0 0 {accent};
+}}
 .file-row-meta {{
     font-size: 10px;
     color: {fg_muted};
@@ -378,6 +457,51 @@ window {{
     letter-spacing: 0.5px;
 }}
 
+/* ── Virtualized Grid / List ── */
+.content-grid,
+.content-list {{
+    background-color: transparent;
+}}
+.content-grid > child,
+.content-list > row {{
+    padding: 0;
+    background-color: transparent;
+}}
+
+/* ── Details View ── */
+.content-details {{
+    background-color: transparent;
+}}
+.content-details row {{
+    background-color: transparent;
+}}
+.content-details row:hover {{
+    background-color: {bg_overlay};
+}}
+.content-details row.item-selected {{
+    background-color: {bg_hover};
+    box-shadow: inset 3px 0 0 {accent};
+}}
+.details-cell {{
+    margin: 0;
+    padding: 4px 6px;
+    min-height: 0;
+    border-radius: 0;
+    background: none;
+}}
+
+/* ── Drop Targets ── */
+.drop-hover {{
+    background-color: {bg_hover};
+    box-shadow: inset 0 0 0 2px {accent};
+    border-radius: 10px;
+}}
+
+/* ── Cut Items (pending paste) ── */
+.item-cut {{
+    opacity: 0.5;
+}}
+
 /* ── Inspector Panel ── */
 .inspector {{
     background-color: {bg_base};
@@ -430,6 +554,46 @@ window {{
     border-color: {accent};
     color: {fg_primary};
 }}
+.btn-danger {{
+    background-color: #f38ba8;
+    color: {bg_base};
+    border-radius: 12px;
+    padding: 8px 16px;
+    font-weight: 600;
+    border: none;
+    transition: all 150ms ease;
+}}
+.btn-danger:hover {{
+    background-color: #eba0b3;
+}}
+
+/* ── Rubber-Band Selection ── */
+.rubber-band {{
+    background-color: alpha({accent}, 0.15);
+    border: 1px solid {accent};
+    border-radius: 4px;
+}}
+
+/* ── Transfer Dialog ── */
+.transfer-dialog {{
+    background-color: {bg_surface};
+}}
+.transfer-dialog progressbar trough {{
+    background-color: {bg_overlay};
+    border-radius: 6px;
+    min-height: 8px;
+}}
+.transfer-dialog progressbar progress {{
+    background-color: {accent};
+    border-radius: 6px;
+    min-height: 8px;
+}}
+.transfer-conflict {{
+    background-color: {bg_overlay};
+    border: 1px solid {border_hover};
+    border-radius: 10px;
+    padding: 10px;
+}}
 
 /* ── Entries ── */
 entry {{
@@ -537,6 +701,59 @@ switch:checked {{
     background-color: {bg_overlay};
 }}
 
+/* ── Search ── */
+.search-bar {{
+    padding: 6px 12px 2px 12px;
+}}
+.search-message {{
+    font-size: 11px;
+    color: #f38ba8;
+    margin-top: 4px;
+}}
+.search-field-error {{
+    border-color: #f38ba8;
+}}
+.file-location {{
+    font-size: 11px;
+    color: {fg_muted};
+}}
+
+/* ── Filter Bar ── */
+.filter-bar {{
+    padding: 6px 12px 2px 12px;
+}}
+.filter-count {{
+    font-size: 11px;
+    color: {fg_muted};
+}}
+
+/* ── Quick Finder ── */
+.quick-finder-list {{
+    background: transparent;
+}}
+.quick-finder-list row {{
+    border-radius: 8px;
+}}
+.quick-finder-list row:selected {{
+    background-color: {bg_overlay};
+}}
+.quick-finder-name {{
+    font-size: 13px;
+    color: {fg_primary};
+}}
+.quick-finder-folder {{
+    font-size: 11px;
+    color: {fg_muted};
+}}
+.quick-finder-status {{
+    font-size: 11px;
+    color: {fg_muted};
+}}
+.command-palette-keys {{
+    font-size: 11px;
+    color: {fg_muted};
+}}
+
 /* ── Creation Popover Buttons ── */
 .creation-btn {{
     font-weight: 700;
@@ -656,12 +873,12 @@ switch:checked {{
     background-color: {bg_overlay};
     box-shadow: 0 1px 4px {shadow};
 }}
-.tree-row-selected {{
+.tree-row-btn.item-selected {{
     background-color: rgba(136, 192, 208, 0.14);
     border: 1px solid {border_hover};
     box-shadow: inset 3px 0 0 {accent};
 }}
-.tree-row-selected:hover {{
+.tree-row-btn.item-selected:hover {{
     background-color: rgba(136, 192, 208, 0.20);
 }}
 
@@ -0,0 +1,108 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════
+//  Background Workers
+// ═══════════════════════════════════════════════
+//
+// Listings, searches, the quick finder's index and transfers all run on
+// a std thread and stream events back to the GLib main loop with
+// `MainContext::default().invoke()`. GTK callbacks are not `Send`, so
+// only the worker id crosses threads: the listener lives in a
+// main-thread registry and is dropped after the final event or on
+// cancel. Ids are unique across event types, so the registry holds
+// every kind of listener.
+
+/// An event streamed by a worker. The final one ends the worker.
+pub trait WorkerEvent: Send + 'static {
+    fn is_final(&self) -> bool;
+}
+
+/// UI-side handle to a running worker.
+pub struct WorkerHandle {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl WorkerHandle {
+    /// Stops the worker. No further events are delivered.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        LISTENERS.with(|l| l.borrow_mut().remove(&self.id));
+    }
+}
+
+/// The worker thread's side: sends events and tells whether the
+/// worker was cancelled.
+pub struct Emitter<E> {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+    _event: PhantomData<fn(E)>,
+}
+
+impl<E: WorkerEvent> Emitter<E> {
+    /// Schedules delivery of `event` to the listener on the main loop.
+    pub fn emit(&self, event: E) {
+        let id = self.id;
+        glib::MainContext::default().invoke(move || {
+            let finished = event.is_final();
+            let listener = LISTENERS.with(|l| l.borrow().get(&id).cloned());
+            if let Some(cb) = listener
+                .as_ref()
+                .and_then(|l| l.downcast_ref::<Callback<E>>())
+            {
+                cb(event);
+            }
+            if finished {
+                LISTENERS.with(|l| l.borrow_mut().remove(&id));
+            }
+        });
+    }
+
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.cancelled
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+type Callback<E> = Box<dyn Fn(E)>;
+
+thread_local! {
+    /// Listeners of running workers, keyed by id (main thread only).
+    /// Each is a `Callback` of the worker's event type.
+    static LISTENERS: RefCell<HashMap<u64, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_WORKER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Runs `work` on a background thread.
+///
+/// Must be called from the GTK main thread; `on_event` runs there for
+/// every event `work` emits.
+pub fn spawn<E: WorkerEvent>(
+    on_event: impl Fn(E) + 'static,
+    work: impl FnOnce(Emitter<E>) + Send + 'static,
+) -> WorkerHandle {
+    let id = NEXT_WORKER_ID.fetch_add(1, Ordering::SeqCst);
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let callback: Callback<E> = Box::new(on_event);
+    LISTENERS.with(|l| l.borrow_mut().insert(id, Rc::new(callback)));
+
+    let emitter = Emitter {
+        id,
+        cancelled: cancelled.clone(),
+        _event: PhantomData,
+    };
+    std::thread::spawn(move || work(emitter));
+
+    WorkerHandle { id, cancelled }
+}
@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+use crate::filesystem::guess_mime;
+
 // ═══════════════════════════════════════════════
 //  File / Directory Entry
 // ═══════════════════════════════════════════════
@@ -13,42 +16,101 @@ pub struct Entry {
     pub is_dir: bool,
     pub size: u64,
     pub modified: Option<SystemTime>,
+    pub created: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
     pub extension: String,
+    /// MIME type guessed from the name ("inode/directory" for folders,
+    /// `UNKNOWN_MIME` when the name tells nothing).
+    pub mime: String,
 }
 
 impl Entry {
+    /// Builds an entry for a single path (follows symlinks, like `list_directory`).
+    pub fn from_path(path: &Path) -> Option<Entry> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Entry {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string()),
+            path: path.to_path_buf(),
+            is_dir: metadata.is_dir(),
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            created: metadata.created().ok(),
+            accessed: metadata.accessed().ok(),
+            extension: path
+                .extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            mime: guess_mime(path, metadata.is_dir()),
+        })
+    }
+
     /// Human-readable file size string.
     pub fn size_display(&self) -> String {
         if self.is_dir {
             return "—".to_string();
         }
-        let s = self.size as f64;
-        if s < 1024.0 {
-            format!("{} B", self.size)
-        } else if s < 1024.0 * 1024.0 {
-            format!("{:.1} KB", s / 1024.0)
-        } else if s < 1024.0 * 1024.0 * 1024.0 {
-            format!("{:.1} MB", s / (1024.0 * 1024.0))
-        } else {
-            format!("{:.2} GB", s / (1024.0 * 1024.0 * 1024.0))
-        }
+        format_size(self.size)
     }
 
     /// Human-readable modified date.
     pub fn modified_display(&self) -> String {
-        match self.modified {
-            Some(time) => {
-                let duration = time
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap_or_default();
-                let secs = duration.as_secs() as i64;
-                let dt = chrono::DateTime::from_timestamp(secs, 0);
-                match dt {
-                    Some(d) => d.format("%Y-%m-%d %H:%M").to_string(),
-                    None => "—".to_string(),
-                }
-            }
-            None => "—".to_string(),
-        }
+        format_time(self.modified)
     }
 }
+
+/// Formats a timestamp as "YYYY-MM-DD HH:MM", or "—" when unknown.
+pub fn format_time(time: Option<SystemTime>) -> String {
+    let Some(time) = time else {
+        return "—".to_string();
+    };
+    let duration = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = duration.as_secs() as i64;
+    match chrono::DateTime::from_timestamp(secs, 0) {
+        Some(d) => d.format("%Y-%m-%d %H:%M").to_string(),
+        None => "—".to_string(),
+    }
+}
+
+/// Formats a byte count as B / KB / MB / GB.
+pub fn format_size(bytes: u64) -> String {
+    let s = bytes as f64;
+    if s < 1024.0 {
+        format!("{} B", bytes)
+    } else if s < 1024.0 * 1024.0 {
+        format!("{:.1} KB", s / 1024.0)
+    } else if s < 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.1} MB", s / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} GB", s / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+/// Parses a size typed by the user: a number with an optional unit, e.g.
+/// "2048", "500 KB", "1.5M" or "2 GiB" (units are powers of 1024).
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let unit = unit.trim().to_ascii_lowercase();
+    let unit = unit
+        .strip_suffix("ib")
+        .or_else(|| unit.strip_suffix('b'))
+        .unwrap_or(&unit);
+    let factor: u64 = match unit {
+        "" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        "t" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * factor as f64).round() as u64)
+}
@@ -0,0 +1,78 @@
+use crate::filesystem::fuzzy::FuzzyMatcher;
+use crate::filesystem::gitignore::glob_match;
+
+// ═══════════════════════════════════════════════
+//  Name Filter
+// ═══════════════════════════════════════════════
+//
+// The type-to-filter text of a pane, compiled once and tested against
+// every entry name the current view shows. Fuzzy mode keeps names
+// containing the typed characters in order; glob mode matches the whole
+// name against a pattern such as "*.rs". Both ignore case, and an empty
+// filter keeps everything.
+
+/// How the filter text is matched against names.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FilterMode {
+    #[default]
+    Fuzzy,
+    Glob,
+}
+
+impl FilterMode {
+    pub fn all() -> [FilterMode; 2] {
+        [FilterMode::Fuzzy, FilterMode::Glob]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            FilterMode::Fuzzy => "Fuzzy",
+            FilterMode::Glob => "Glob",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+enum Test {
+    #[default]
+    All,
+    Fuzzy(FuzzyMatcher),
+    Glob(Vec<char>),
+}
+
+/// A compiled filter. Cheap to test, cheap enough to clone.
+#[derive(Debug, Clone, Default)]
+pub struct NameFilter {
+    test: Test,
+}
+
+impl NameFilter {
+    pub fn new(text: &str, mode: FilterMode) -> Self {
+        let text = text.trim();
+        let test = if text.is_empty() {
+            Test::All
+        } else {
+            match mode {
+                FilterMode::Fuzzy => Test::Fuzzy(FuzzyMatcher::new(text)),
+                FilterMode::Glob => Test::Glob(text.to_lowercase().chars().collect()),
+            }
+        };
+        Self { test }
+    }
+
+    /// False for an empty filter, which keeps every name.
+    pub fn is_active(&self) -> bool {
+        !matches!(self.test, Test::All)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match &self.test {
+            Test::All => true,
+            Test::Fuzzy(matcher) => matcher.matches(name),
+            Test::Glob(pattern) => {
+                let name: Vec<char> = name.to_lowercase().chars().collect();
+                glob_match(pattern, &name)
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// ═══════════════════════════════════════════════
+//  Fuzzy Path Matching
+// ═══════════════════════════════════════════════
+//
+// Scores a relative path against a query whose characters must appear in
+// order (case-insensitively), the way editor file finders do. Among all
+// ways to place the query, the best one is kept:
+//
+//   + characters at the start of a segment, after '_' '-' '.' ' ',
+//     or at a camelCase hump
+//   + runs of consecutive characters
+//   + characters inside the file name rather than the folders
+//   − every skipped character between two matched ones
+//
+// A file name (or name without extension) equal to the query ranks
+// first, and shorter paths win ties.
+
+const SEGMENT_START: i64 = 10;
+const WORD_START: i64 = 8;
+const CAMEL_HUMP: i64 = 7;
+const CONSECUTIVE: i64 = 6;
+const IN_FILE_NAME: i64 = 4;
+const MATCH: i64 = 1;
+const GAP: i64 = 1;
+const EXACT_NAME: i64 = 40;
+
+const NONE: i64 = i64::MIN / 2;
+
+/// A query, ready to score many candidates.
+#[derive(Debug, Clone)]
+pub struct FuzzyMatcher {
+    query: Vec<char>,
+    /// Per query char: best score of a match ending at the previous char.
+    last: Vec<i64>,
+    /// Per query char: best earlier match, decayed by the gap since.
+    decayed: Vec<i64>,
+}
+
+impl FuzzyMatcher {
+    pub fn new(query: &str) -> Self {
+        let query: Vec<char> = query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(fold)
+            .collect();
+        let n = query.len();
+        Self {
+            query,
+            last: vec![NONE; n],
+            decayed: vec![NONE; n],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Score of `candidate` ('/'-separated), or `None` if it does not
+    /// contain the query. Higher is better.
+    pub fn score(&mut self, candidate: &str) -> Option<i64> {
+        if self.query.is_empty() || !self.is_subsequence(candidate) {
+            return None;
+        }
+        let name_start = candidate.rfind('/').map_or(0, |i| i + 1);
+        let m = self.query.len();
+        self.last.fill(NONE);
+        self.decayed.fill(NONE);
+
+        let mut best = NONE;
+        let mut prev: Option<char> = None;
+        for (byte, c) in candidate.char_indices() {
+            // Earlier matches move one character further away
+            for i in 0..m {
+                self.decayed[i] = self.decayed[i].max(self.last[i]) - GAP;
+            }
+            let bonus =
+                MATCH + boundary_bonus(prev, c) + if byte >= name_start { IN_FILE_NAME } else { 0 };
+            let folded = fold(c);
+            // Last query char first, so row i - 1 still holds the previous column
+            for i in (0..m).rev() {
+                let score = if self.query[i] != folded {
+                    NONE
+                } else if i == 0 {
+                    bonus
+                } else {
+                    let before = (self.last[i - 1] + CONSECUTIVE).max(self.decayed[i - 1]);
+                    if before <= NONE / 2 {
+                        NONE
+                    } else {
+                        before + bonus
+                    }
+                };
+                self.last[i] = score;
+            }
+            best = best.max(self.last[m - 1]);
+            prev = Some(c);
+        }
+        if best <= NONE / 2 {
+            return None;
+        }
+
+        let name = &candidate[name_start..];
+        let stem = name
+            .split('.')
+            .next()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(name);
+        let is_query = |s: &str| s.chars().map(fold).eq(self.query.iter().copied());
+        if is_query(name) || is_query(stem) {
+            best += EXACT_NAME;
+        }
+        Some(best - candidate.len() as i64 / 8)
+    }
+
+    /// True if `candidate` contains the query, without scoring it.
+    pub fn matches(&self, candidate: &str) -> bool {
+        !self.query.is_empty() && self.is_subsequence(candidate)
+    }
+
+    fn is_subsequence(&self, candidate: &str) -> bool {
+        let mut query = self.query.iter().peekable();
+        for c in candidate.chars() {
+            if query.peek() == Some(&&fold(c)) {
+                query.next();
+            }
+        }
+        query.peek().is_none()
+    }
+}
+
+fn boundary_bonus(prev: Option<char>, c: char) -> i64 {
+    match prev {
+        None | Some('/') => SEGMENT_START,
+        Some('_' | '-' | '.' | ' ') => WORD_START,
+        Some(p) if p.is_lowercase() && c.is_uppercase() => CAMEL_HUMP,
+        _ => 0,
+    }
+}
+
+/// Case folding for matching.
+fn fold(c: char) -> char {
+    if c.is_ascii() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(query: &str, candidate: &str) -> Option<i64> {
+        FuzzyMatcher::new(query).score(candidate)
+    }
+
+    #[test]
+    fn needs_every_query_char_in_order() {
+        assert!(score("mrs", "src/main.rs").is_some());
+        assert!(score("srm", "src/main.rs").is_some());
+        assert_eq!(score("rsm", "src/main.rs"), None);
+        assert_eq!(score("xyz", "src/main.rs"), None);
+        assert!(!FuzzyMatcher::new("rsm").matches("src/main.rs"));
+        assert!(FuzzyMatcher::new("mrs").matches("src/main.rs"));
+    }
+
+    #[test]
+    fn empty_queries_match_nothing() {
+        assert!(FuzzyMatcher::new("  ").is_empty());
+        assert_eq!(score("", "src/main.rs"), None);
+        assert!(!FuzzyMatcher::new("").matches("src/main.rs"));
+    }
+
+    #[test]
+    fn ignores_case_and_spaces() {
+        assert_eq!(score("MAIN", "src/main.rs"), score("main", "src/main.rs"));
+        assert_eq!(score("ma in", "src/main.rs"), score("main", "src/main.rs"));
+        assert!(score("école", "Docs/École.txt").is_some());
+    }
+
+    #[test]
+    fn prefers_segment_starts() {
+        assert!(score("fb", "foo/bar.rs") > score("fb", "fxxb.rs"));
+        assert!(score("fb", "FooBar.rs") > score("fb", "foobar.rs"));
+        assert!(score("fb", "foo_bar.rs") > score("fb", "foobar.rs"));
+    }
+
+    #[test]
+    fn prefers_runs_and_the_file_name() {
+        assert!(score("main", "src/main.rs") > score("main", "m/a/i/n.rs"));
+        assert!(score("lib", "src/lib.rs") > score("lib", "lib/src.rs"));
+    }
+
+    #[test]
+    fn ranks_the_exact_name_first() {
+        let exact = score("main", "src/main.rs");
+        assert!(exact > score("main", "src/mainframe.rs"));
+        assert!(exact > score("main", "src/domain.rs"));
+        // with or without the extension
+        assert!(score("main.rs", "src/main.rs") > score("main.rs", "src/main.rsx"));
+    }
+
+    #[test]
+    fn shorter_paths_win_ties() {
+        assert!(score("readme", "readme.md") > score("readme", "a/b/c/d/e/f/readme.md"));
+    }
+
+    #[test]
+    fn reuses_the_matcher() {
+        let mut matcher = FuzzyMatcher::new("fb");
+        let first = matcher.score("foo/bar.rs");
+        assert_eq!(matcher.score("nothing"), None);
+        assert_eq!(matcher.score("foo/bar.rs"), first);
+    }
+}
@@ -0,0 +1,298 @@
+use std::fs;
+use std::path::Path;
+
+// ═══════════════════════════════════════════════
+//  .gitignore Rules
+// ═══════════════════════════════════════════════
+//
+// A subset of gitignore(5) that covers real-world files:
+//
+//   name        matches at any depth below the .gitignore
+//   /name       anchored to the .gitignore's directory
+//   a/b         (any inner slash) anchored as well
+//   name/       directories only
+//   !name       re-includes what an earlier rule ignored
+//   * ? [a-z]   wildcards within one path segment
+//   **          any number of segments ("**/x", "x/**", "a/**/b")
+//
+// Paths are matched relative to the directory of the .gitignore, with
+// '/' separators. The last matching rule wins.
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Vec<char>,
+    negate: bool,
+    dir_only: bool,
+    /// Matched against the whole relative path, not just the name.
+    anchored: bool,
+}
+
+/// The rules of one .gitignore file.
+#[derive(Debug, Clone, Default)]
+pub struct Gitignore {
+    rules: Vec<Rule>,
+}
+
+impl Gitignore {
+    /// Reads `dir/.gitignore`, if there is one.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let text = fs::read_to_string(dir.join(".gitignore")).ok()?;
+        let rules = Self::parse(&text);
+        (!rules.rules.is_empty()).then_some(rules)
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let rules = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negate, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line.strip_prefix('\\').unwrap_or(line)),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let line = line.strip_prefix('/').unwrap_or(line);
+                (!line.is_empty()).then(|| Rule {
+                    pattern: line.chars().collect(),
+                    negate,
+                    dir_only,
+                    anchored,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// `Some(true)` if `rel_path` is ignored, `Some(false)` if a `!` rule
+    /// re-includes it, `None` if no rule matches.
+    pub fn matched(&self, rel_path: &str, is_dir: bool) -> Option<bool> {
+        let name = rel_path.rsplit('/').next().unwrap_or(rel_path);
+        let path: Vec<char> = rel_path.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                (!rule.dir_only || is_dir)
+                    && if rule.anchored {
+                        glob_match(&rule.pattern, &path)
+                    } else {
+                        glob_match(&rule.pattern, &name)
+                    }
+            })
+            .map(|rule| !rule.negate)
+    }
+}
+
+/// Matches `text` against a glob `pattern` ('/' separates segments).
+/// Also used for the name patterns of searches and filters.
+///
+/// Iterative, with backtracking to the last `*` and the last `**/` only,
+/// so patterns like "*a*a*a*b" stay linear in the text.
+pub fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // (pattern after the wildcard, text it has consumed up to)
+    let mut star: Option<(usize, usize)> = None;
+    let mut globstar: Option<(usize, usize)> = None;
+    loop {
+        match &pattern[p..] {
+            // A trailing "**" matches everything below
+            ['*', '*'] => return true,
+            // "**/" matches zero or more whole segments
+            ['*', '*', '/', ..] => {
+                globstar = Some((p + 3, t));
+                star = None;
+                p += 3;
+                continue;
+            }
+            // Any run of non-separator characters
+            ['*', ..] => {
+                star = Some((p + 1, t));
+                p += 1;
+                continue;
+            }
+            [] if t == text.len() => return true,
+            rest => {
+                if let Some(len) = text.get(t).and_then(|&c| match_one(rest, c)) {
+                    p += len;
+                    t += 1;
+                    continue;
+                }
+            }
+        }
+
+        // Mismatch: the last `*` takes one more character, or else the
+        // last `**/` one more segment
+        if let Some((sp, st)) = star {
+            if text.get(st).is_some_and(|&c| c != '/') {
+                star = Some((sp, st + 1));
+                (p, t) = (sp, st + 1);
+                continue;
+            }
+        }
+        if let Some((gp, gt)) = globstar {
+            if let Some(slash) = text[gt..].iter().position(|&c| c == '/') {
+                let gt = gt + slash + 1;
+                globstar = Some((gp, gt));
+                star = None;
+                (p, t) = (gp, gt);
+                continue;
+            }
+        }
+        return false;
+    }
+}
+
+/// Matches `c` against the first element of `pattern` (not a `*`).
+/// Returns how many pattern characters it took.
+fn match_one(pattern: &[char], c: char) -> Option<usize> {
+    match pattern {
+        [] => None,
+        ['?', ..] => (c != '/').then_some(1),
+        ['[', rest @ ..] => match rest.iter().skip(1).position(|&r| r == ']') {
+            Some(close) => {
+                let class = &rest[..close + 1];
+                (c != '/' && class_matches(class, c)).then_some(close + 3)
+            }
+            // No closing bracket: a literal '['
+            None => (c == '[').then_some(1),
+        },
+        ['\\', escaped, ..] => (c == *escaped).then_some(2),
+        [literal, ..] => (c == *literal).then_some(1),
+    }
+}
+
+/// Matches `c` against a bracket class like "a-z0-9" or "!abc".
+fn class_matches(class: &[char], c: char) -> bool {
+    let (negate, class) = match class {
+        ['!' | '^', rest @ ..] => (true, rest),
+        _ => (false, class),
+    };
+    let mut found = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            found |= (class[i]..=class[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            found |= class[i] == c;
+            i += 1;
+        }
+    }
+    found != negate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        glob_match(&pattern, &text)
+    }
+
+    #[test]
+    fn matches_literals_and_single_wildcards() {
+        assert!(glob("main.rs", "main.rs"));
+        assert!(!glob("main.rs", "main.rsx"));
+        assert!(glob("*.rs", "main.rs"));
+        assert!(glob("*", ""));
+        assert!(glob("m*n*.rs", "main.rs"));
+        assert!(!glob("*.rs", "src/main.rs"));
+        assert!(glob("?ain.rs", "main.rs"));
+        assert!(!glob("?", "/"));
+        assert!(!glob("?", ""));
+    }
+
+    #[test]
+    fn matches_bracket_classes() {
+        assert!(glob("file[0-9].txt", "file7.txt"));
+        assert!(!glob("file[0-9].txt", "filex.txt"));
+        assert!(glob("[!a-c]x", "dx"));
+        assert!(!glob("[^a-c]x", "bx"));
+        assert!(glob("[]]", "]"));
+        assert!(!glob("a[/]b", "a/b"));
+        // An unclosed bracket is a literal
+        assert!(glob("[ab", "[ab"));
+        assert!(glob("\\*", "*"));
+        assert!(!glob("\\*", "x"));
+    }
+
+    #[test]
+    fn matches_globstars() {
+        assert!(glob("**/x", "x"));
+        assert!(glob("**/x", "a/b/x"));
+        assert!(!glob("**/x", "a/bx"));
+        assert!(glob("x/**", "x/a/b"));
+        assert!(!glob("x/**", "y/a"));
+        assert!(glob("a/**/b", "a/b"));
+        assert!(glob("a/**/b", "a/x/y/b"));
+        assert!(!glob("a/**/b", "a/x/yb"));
+        assert!(glob("a/**/*.rs", "a/src/deep/main.rs"));
+        assert!(glob("**/src/*.rs", "crates/x/src/lib.rs"));
+        assert!(!glob("**/src/*.rs", "crates/x/src/a/lib.rs"));
+    }
+
+    #[test]
+    fn stays_fast_on_many_stars() {
+        let pattern = "*a".repeat(30) + "*b";
+        let text = "a".repeat(200);
+        assert!(!glob(&pattern, &text));
+        assert!(glob(&pattern, &(text.clone() + "b")));
+        let deep = "**/a/".repeat(20) + "b";
+        assert!(!glob(&deep, &"a/".repeat(100)));
+    }
+
+    #[test]
+    fn parses_rules() {
+        let rules = Gitignore::parse("# comment\n\n*.log\n/build/\nsrc/gen\n!keep.log\n\\!bang\n");
+        assert_eq!(rules.rules.len(), 5);
+        assert!(rules.rules[1].anchored && rules.rules[1].dir_only);
+        assert!(rules.rules[2].anchored && !rules.rules[2].dir_only);
+        assert!(rules.rules[3].negate);
+        assert_eq!(rules.rules[4].pattern, "!bang".chars().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unanchored_rules_match_at_any_depth() {
+        let rules = Gitignore::parse("*.log\ntarget\n");
+        assert_eq!(rules.matched("debug.log", false), Some(true));
+        assert_eq!(rules.matched("a/b/debug.log", false), Some(true));
+        assert_eq!(rules.matched("a/target", true), Some(true));
+        assert_eq!(rules.matched("a/targets", true), None);
+    }
+
+    #[test]
+    fn anchored_rules_match_from_the_gitignore() {
+        let rules = Gitignore::parse("/build\nsrc/gen\n");
+        assert_eq!(rules.matched("build", true), Some(true));
+        assert_eq!(rules.matched("a/build", true), None);
+        assert_eq!(rules.matched("src/gen", true), Some(true));
+        assert_eq!(rules.matched("x/src/gen", true), None);
+    }
+
+    #[test]
+    fn directory_rules_skip_files() {
+        let rules = Gitignore::parse("cache/\n");
+        assert_eq!(rules.matched("cache", true), Some(true));
+        assert_eq!(rules.matched("cache", false), None);
+    }
+
+    #[test]
+    fn the_last_matching_rule_wins() {
+        let rules = Gitignore::parse("*.log\n!keep.log\n");
+        assert_eq!(rules.matched("debug.log", false), Some(true));
+        assert_eq!(rules.matched("keep.log", false), Some(false));
+
+        let rules = Gitignore::parse("!keep.log\n*.log\n");
+        assert_eq!(rules.matched("keep.log", false), Some(true));
+    }
+}
@@ -1,8 +1,7 @@
-use std::collections::BTreeMap;
 use std::time::SystemTime;
 
 use crate::config::GroupBy;
-use crate::filesystem::Entry;
+use crate::filesystem::{mime_description, Entry};
 
 // ═══════════════════════════════════════════════
 //  Grouping Logic
@@ -16,10 +15,8 @@ impl Entry {
             GroupBy::Type => {
                 if self.is_dir {
                     "📁 Folders".to_string()
-                } else if self.extension.is_empty() {
-                    "📄 Other".to_string()
                 } else {
-                    format!("📄 .{}", self.extension.to_uppercase())
+                    format!("📄 {}", mime_description(&self.mime))
                 }
             }
             GroupBy::Date => self
@@ -43,22 +40,3 @@ impl Entry {
         }
     }
 }
-
-/// Groups a slice of entries by the given grouping strategy.
-/// Returns an ordered list of (group_name, entries) pairs.
-pub fn group_entries<'a>(
-    entries: &'a [Entry],
-    group_by: &GroupBy,
-) -> Vec<(String, Vec<&'a Entry>)> {
-    if *group_by == GroupBy::None {
-        return vec![("".to_string(), entries.iter().collect())];
-    }
-
-    let mut map: BTreeMap<String, Vec<&Entry>> = BTreeMap::new();
-    for entry in entries {
-        let key = entry.group_key(group_by);
-        map.entry(key).or_default().push(entry);
-    }
-
-    map.into_iter().collect()
-}
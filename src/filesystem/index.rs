@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::worker::{self, WorkerEvent, WorkerHandle};
+use crate::filesystem::gitignore::Gitignore;
+
+// ═══════════════════════════════════════════════
+//  Recursive File Index
+// ═══════════════════════════════════════════════
+//
+// Walks a whole tree on a std thread for the quick finder, streaming
+// batches of entries to the main loop like `listing`. The walk itself
+// (`walk_tree`) also drives the recursive search. Symlinked folders
+// are listed but not entered (no cycles), hidden entries are skipped
+// unless asked for, and with `respect_gitignore` every `.gitignore` from
+// the enclosing repository's root down prunes the walk (`.git` itself
+// is never indexed then). The walk stops after `MAX_ENTRIES`.
+
+const BATCH_SIZE: usize = 256;
+const BATCH_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Upper bound on indexed entries, so `/` cannot exhaust memory.
+const MAX_ENTRIES: usize = 200_000;
+
+/// One indexed file or folder.
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub path: PathBuf,
+    /// Path relative to the index root, '/'-separated.
+    pub rel: String,
+    pub is_dir: bool,
+    pub modified: Option<SystemTime>,
+}
+
+pub enum IndexEvent {
+    Batch(Vec<IndexEntry>),
+    /// The walk is over; `truncated` if it stopped at `MAX_ENTRIES`.
+    Finished {
+        truncated: bool,
+    },
+}
+
+impl WorkerEvent for IndexEvent {
+    fn is_final(&self) -> bool {
+        matches!(self, IndexEvent::Finished { .. })
+    }
+}
+
+/// Starts indexing everything below `root` on a background thread.
+///
+/// Must be called from the GTK main thread; `on_event` runs there for
+/// every batch and once when the walk is over.
+pub fn start_index(
+    root: PathBuf,
+    include_hidden: bool,
+    respect_gitignore: bool,
+    on_event: impl Fn(IndexEvent) + 'static,
+) -> WorkerHandle {
+    worker::spawn(on_event, move |emitter| {
+        let mut batch = Vec::new();
+        let mut last_sent = Instant::now();
+        let mut count = 0;
+        let complete = walk_tree(
+            &root,
+            include_hidden,
+            respect_gitignore,
+            emitter.cancel_flag(),
+            |entry, path, is_dir| {
+                if count >= MAX_ENTRIES {
+                    return false;
+                }
+                count += 1;
+                let rel = path
+                    .strip_prefix(&root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string();
+                let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+                batch.push(IndexEntry {
+                    path,
+                    rel,
+                    is_dir,
+                    modified,
+                });
+                if batch.len() >= BATCH_SIZE || last_sent.elapsed() >= BATCH_INTERVAL {
+                    emitter.emit(IndexEvent::Batch(std::mem::take(&mut batch)));
+                    last_sent = Instant::now();
+                }
+                true
+            },
+        );
+        if emitter.is_cancelled() {
+            return;
+        }
+        if !batch.is_empty() {
+            emitter.emit(IndexEvent::Batch(batch));
+        }
+        emitter.emit(IndexEvent::Finished {
+            truncated: !complete,
+        });
+    })
+}
+
+// ─── Tree Walk ───
+
+/// A `.gitignore` and the folder its patterns are relative to.
+type IgnoreFile = (PathBuf, Rc<Gitignore>);
+
+/// Visits everything below `root` depth-first, folders in directory
+/// order, with the entry, its path and whether it is a folder (symlinks
+/// resolved). `visit` returns false to stop. Returns false if the walk
+/// was cut short by `visit` or `cancelled`. Shared with `search`.
+pub(super) fn walk_tree(
+    root: &Path,
+    include_hidden: bool,
+    respect_gitignore: bool,
+    cancelled: &AtomicBool,
+    mut visit: impl FnMut(&fs::DirEntry, PathBuf, bool) -> bool,
+) -> bool {
+    let root_ignores = if respect_gitignore {
+        enclosing_gitignores(root)
+    } else {
+        Vec::new()
+    };
+    let mut stack = vec![(root.to_path_buf(), root_ignores)];
+    while let Some((dir, mut ignores)) = stack.pop() {
+        if respect_gitignore {
+            if let Some(own) = Gitignore::load(&dir) {
+                ignores.push((dir.clone(), Rc::new(own)));
+            }
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut subdirs = Vec::new();
+        for entry in entries.flatten() {
+            if cancelled.load(Ordering::SeqCst) {
+                return false;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !include_hidden && name.starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            let file_type = entry.file_type().ok();
+            let is_dir = file_type.is_some_and(|t| t.is_dir())
+                || (file_type.is_some_and(|t| t.is_symlink()) && path.is_dir());
+            if respect_gitignore && (name == ".git" || is_ignored(&ignores, &path, is_dir)) {
+                continue;
+            }
+            // Symlinked folders are listed, not entered
+            if file_type.is_some_and(|t| t.is_dir()) {
+                subdirs.push(path.clone());
+            }
+            if !visit(&entry, path, is_dir) {
+                return false;
+            }
+        }
+        // Reversed, so the stack pops them in directory order
+        stack.extend(subdirs.into_iter().rev().map(|d| (d, ignores.clone())));
+    }
+    true
+}
+
+/// True if the innermost `.gitignore` with a matching rule ignores `path`.
+fn is_ignored(ignores: &[IgnoreFile], path: &Path, is_dir: bool) -> bool {
+    ignores
+        .iter()
+        .rev()
+        .find_map(|(base, rules)| {
+            let rel = path.strip_prefix(base).ok()?.to_string_lossy().to_string();
+            rules.matched(&rel, is_dir)
+        })
+        .unwrap_or(false)
+}
+
+/// The `.gitignore` files of `root`'s ancestors up to the repository root
+/// (the folder holding `.git`), outermost first. None outside a repository.
+fn enclosing_gitignores(root: &Path) -> Vec<IgnoreFile> {
+    let mut found = Vec::new();
+    let mut dir = root.parent();
+    if root.join(".git").exists() {
+        return found;
+    }
+    while let Some(d) = dir {
+        if let Some(rules) = Gitignore::load(d) {
+            found.push((d.to_path_buf(), Rc::new(rules)));
+        }
+        if d.join(".git").exists() {
+            found.reverse();
+            return found;
+        }
+        dir = d.parent();
+    }
+    Vec::new()
+}
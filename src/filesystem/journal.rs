@@ -0,0 +1,725 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use super::trash::{move_to_trash, restore_trashed};
+
+// ═══════════════════════════════════════════════
+//  Undo / Redo Journal
+// ═══════════════════════════════════════════════
+//
+// Every reversible file operation is recorded as a `FileOp`:
+//   Rename → rename back
+//   Create → remove the new item (trashed if it is no longer empty)
+//   Trash  → restore from the trash
+//   Move   → move every item back
+//   Batch  → several ops from one action, undone as a single step
+//
+// Undo pops from the undo stack and pushes onto the redo stack; any
+// newly recorded operation clears the redo stack. The journal lives in
+// a main-thread `thread_local!`, like the transfer listeners, and is
+// optionally saved to `~/.config/diptych/journal.toml`.
+
+/// Maximum number of operations kept on each stack.
+const MAX_ENTRIES: usize = 100;
+
+/// One top-level item of a move.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MovedItem {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// A reversible file operation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum FileOp {
+    Rename { from: PathBuf, to: PathBuf },
+    Create { path: PathBuf, is_dir: bool },
+    Trash { original: PathBuf, trashed: PathBuf },
+    Move { items: Vec<MovedItem> },
+    Batch { ops: Vec<FileOp> },
+}
+
+impl FileOp {
+    /// Short description for status messages, e.g. "Rename “a.txt”".
+    pub fn description(&self) -> String {
+        match self {
+            FileOp::Rename { from, .. } => format!("Rename “{}”", file_name(from)),
+            FileOp::Create { path, .. } => format!("Create “{}”", file_name(path)),
+            FileOp::Trash { original, .. } => format!("Trash “{}”", file_name(original)),
+            FileOp::Move { items } => match items.as_slice() {
+                [item] => format!("Move “{}”", file_name(&item.from)),
+                _ => format!("Move {} items", items.len()),
+            },
+            FileOp::Batch { ops } => match ops.as_slice() {
+                [op] => op.description(),
+                [FileOp::Trash { .. }, ..] => format!("Trash {} items", ops.len()),
+                [FileOp::Rename { .. }, ..] => format!("Rename {} items", ops.len()),
+                _ => format!("{} operations", ops.len()),
+            },
+        }
+    }
+
+    /// Whether every path in the op is valid UTF-8.
+    fn is_utf8(&self) -> bool {
+        let utf8 = |p: &PathBuf| p.to_str().is_some();
+        match self {
+            FileOp::Rename { from, to } => utf8(from) && utf8(to),
+            FileOp::Create { path, .. } => utf8(path),
+            FileOp::Trash { original, trashed } => utf8(original) && utf8(trashed),
+            FileOp::Move { items } => items.iter().all(|i| utf8(&i.from) && utf8(&i.to)),
+            FileOp::Batch { ops } => ops.iter().all(FileOp::is_utf8),
+        }
+    }
+}
+
+/// Items of a move that could not be renamed back because they crossed
+/// filesystems. The caller moves them with the transfer engine, which
+/// keeps their names.
+pub type DeferredMoves = Vec<MovedItem>;
+
+/// What an undo or redo did.
+pub struct Outcome {
+    /// The operation undone or redone: for a batch that failed part way,
+    /// the part that went through.
+    pub op: Option<FileOp>,
+    pub deferred: DeferredMoves,
+    pub error: Option<io::Error>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Journal {
+    undo: Vec<FileOp>,
+    redo: Vec<FileOp>,
+    #[serde(skip)]
+    persistent: bool,
+}
+
+thread_local! {
+    static JOURNAL: RefCell<Journal> = RefCell::new(Journal::default());
+}
+
+// ─── Setup ───
+
+/// Enables or disables saving the journal across restarts.
+/// Enabling loads the saved journal; disabling deletes the file.
+pub fn set_persistent(persistent: bool) {
+    JOURNAL.with(|j| {
+        let mut j = j.borrow_mut();
+        if persistent && !j.persistent {
+            if let Some(saved) = load() {
+                j.undo = saved.undo;
+                j.redo = saved.redo;
+            }
+        }
+        j.persistent = persistent;
+        if !persistent {
+            let _ = fs::remove_file(journal_path());
+        }
+    });
+}
+
+// ─── Recording ───
+
+/// Records a completed operation. Clears the redo stack.
+pub fn record(op: FileOp) {
+    JOURNAL.with(|j| {
+        let mut j = j.borrow_mut();
+        j.undo.push(op);
+        trim(&mut j.undo);
+        j.redo.clear();
+        j.save();
+    });
+}
+
+/// Records the ops of one user action as a single undo step.
+pub fn record_all(ops: Vec<FileOp>) {
+    if let Some(op) = batch(ops) {
+        record(op);
+    }
+}
+
+// ─── Undo / Redo ───
+
+/// Reverts the most recent operation.
+/// Returns `None` when there is nothing to undo. A failed operation is
+/// dropped from the journal, since the files changed since. A batch that
+/// fails part way keeps the ops it did not get to on the undo stack, and
+/// the reverted ones go onto the redo stack.
+pub fn undo() -> Option<Outcome> {
+    let op = JOURNAL.with(|j| j.borrow_mut().undo.pop())?;
+    let (outcome, left) = match &op {
+        FileOp::Batch { ops } => revert_batch(ops),
+        _ => (single(revert(&op)), None),
+    };
+    JOURNAL.with(|j| {
+        let mut j = j.borrow_mut();
+        if let Some(left) = left {
+            j.undo.push(left);
+        }
+        if let Some(reverted) = &outcome.op {
+            j.redo.push(reverted.clone());
+            trim(&mut j.redo);
+        }
+        j.save();
+    });
+    Some(outcome)
+}
+
+/// Re-applies the most recently undone operation, with the same
+/// handling of failures as `undo`.
+pub fn redo() -> Option<Outcome> {
+    let op = JOURNAL.with(|j| j.borrow_mut().redo.pop())?;
+    let (outcome, left) = match &op {
+        FileOp::Batch { ops } => reapply_batch(ops),
+        _ => (single(reapply(&op)), None),
+    };
+    JOURNAL.with(|j| {
+        let mut j = j.borrow_mut();
+        if let Some(left) = left {
+            j.redo.push(left);
+        }
+        if let Some(reapplied) = &outcome.op {
+            j.undo.push(reapplied.clone());
+            trim(&mut j.undo);
+        }
+        j.save();
+    });
+    Some(outcome)
+}
+
+fn single(result: io::Result<(FileOp, DeferredMoves)>) -> Outcome {
+    match result {
+        Ok((op, deferred)) => Outcome {
+            op: Some(op),
+            deferred,
+            error: None,
+        },
+        Err(e) => Outcome {
+            op: None,
+            deferred: Vec::new(),
+            error: Some(e),
+        },
+    }
+}
+
+/// Undoes a batch back to front, stopping at the first failure.
+/// Also returns the ops still to undo, if it stopped early.
+fn revert_batch(ops: &[FileOp]) -> (Outcome, Option<FileOp>) {
+    let mut deferred = Vec::new();
+    for (i, inner) in ops.iter().enumerate().rev() {
+        match revert(inner) {
+            Ok((_, moves)) => deferred.extend(moves),
+            Err(e) => {
+                let outcome = Outcome {
+                    op: batch(ops[i + 1..].to_vec()),
+                    deferred,
+                    error: Some(e),
+                };
+                return (outcome, batch(ops[..i].to_vec()));
+            }
+        }
+    }
+    let outcome = Outcome {
+        op: Some(FileOp::Batch { ops: ops.to_vec() }),
+        deferred,
+        error: None,
+    };
+    (outcome, None)
+}
+
+/// Redoes a batch front to back, stopping at the first failure.
+/// Also returns the ops still to redo, if it stopped early.
+fn reapply_batch(ops: &[FileOp]) -> (Outcome, Option<FileOp>) {
+    let mut reapplied = Vec::with_capacity(ops.len());
+    let mut deferred = Vec::new();
+    for (i, inner) in ops.iter().enumerate() {
+        match reapply(inner) {
+            Ok((done, moves)) => {
+                reapplied.push(done);
+                deferred.extend(moves);
+            }
+            Err(e) => {
+                let outcome = Outcome {
+                    op: batch(reapplied),
+                    deferred,
+                    error: Some(e),
+                };
+                return (outcome, batch(ops[i + 1..].to_vec()));
+            }
+        }
+    }
+    let outcome = Outcome {
+        op: Some(FileOp::Batch { ops: reapplied }),
+        deferred,
+        error: None,
+    };
+    (outcome, None)
+}
+
+/// The ops as one journal entry, if any.
+fn batch(mut ops: Vec<FileOp>) -> Option<FileOp> {
+    match ops.len() {
+        0 => None,
+        1 => Some(ops.remove(0)),
+        _ => Some(FileOp::Batch { ops }),
+    }
+}
+
+/// Undoes `op`. Returns the op to put on the redo stack.
+fn revert(op: &FileOp) -> io::Result<(FileOp, DeferredMoves)> {
+    match op {
+        FileOp::Rename { from, to } => {
+            rename_no_clobber(to, from)?;
+            Ok((op.clone(), Vec::new()))
+        }
+        FileOp::Create { path, is_dir } => {
+            // Only delete outright while it is still empty
+            let empty = if *is_dir {
+                fs::read_dir(path)?.next().is_none()
+            } else {
+                fs::metadata(path)?.len() == 0
+            };
+            if empty && *is_dir {
+                fs::remove_dir(path)?;
+            } else if empty {
+                fs::remove_file(path)?;
+            } else {
+                move_to_trash(path)?;
+            }
+            Ok((op.clone(), Vec::new()))
+        }
+        FileOp::Trash { original, trashed } => {
+            restore_trashed(trashed, original)?;
+            Ok((op.clone(), Vec::new()))
+        }
+        FileOp::Move { items } => {
+            let reversed: Vec<MovedItem> = items
+                .iter()
+                .map(|i| MovedItem {
+                    from: i.to.clone(),
+                    to: i.from.clone(),
+                })
+                .collect();
+            let deferred = move_items(&reversed)?;
+            Ok((op.clone(), deferred))
+        }
+        FileOp::Batch { ops } => {
+            let mut deferred = Vec::new();
+            for inner in ops.iter().rev() {
+                deferred.extend(revert(inner)?.1);
+            }
+            Ok((op.clone(), deferred))
+        }
+    }
+}
+
+/// Redoes `op`. Returns the op to put back on the undo stack.
+fn reapply(op: &FileOp) -> io::Result<(FileOp, DeferredMoves)> {
+    match op {
+        FileOp::Rename { from, to } => {
+            rename_no_clobber(from, to)?;
+            Ok((op.clone(), Vec::new()))
+        }
+        FileOp::Create { path, is_dir } => {
+            if *is_dir {
+                fs::create_dir(path)?;
+            } else {
+                fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(path)?;
+            }
+            Ok((op.clone(), Vec::new()))
+        }
+        FileOp::Trash { original, .. } => {
+            // The item gets a fresh place in the trash
+            let trashed = move_to_trash(original)?;
+            Ok((
+                FileOp::Trash {
+                    original: original.clone(),
+                    trashed,
+                },
+                Vec::new(),
+            ))
+        }
+        FileOp::Move { items } => {
+            let deferred = move_items(items)?;
+            Ok((op.clone(), deferred))
+        }
+        FileOp::Batch { ops } => {
+            let mut reapplied = Vec::with_capacity(ops.len());
+            let mut deferred = Vec::new();
+            for inner in ops {
+                let (done, moves) = reapply(inner)?;
+                reapplied.push(done);
+                deferred.extend(moves);
+            }
+            Ok((FileOp::Batch { ops: reapplied }, deferred))
+        }
+    }
+}
+
+/// Renames every item; items on another filesystem are handed back as deferred.
+/// The transfer engine keeps names, so an item that also changed name
+/// cannot be deferred: that fails the move before anything is touched.
+/// Any other failure moves the renamed items back before returning.
+fn move_items(items: &[MovedItem]) -> io::Result<DeferredMoves> {
+    for item in items {
+        if item.from.file_name() != item.to.file_name() && !same_filesystem(&item.from, &item.to) {
+            return Err(io::Error::new(
+                io::ErrorKind::CrossesDevices,
+                format!(
+                    "cannot move “{}” across filesystems under a new name",
+                    file_name(&item.from)
+                ),
+            ));
+        }
+    }
+
+    let mut deferred = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        match rename_no_clobber(&item.from, &item.to) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::CrossesDevices => deferred.push(item.clone()),
+            Err(e) => {
+                roll_back(&items[..i], &deferred);
+                return Err(e);
+            }
+        }
+    }
+    Ok(deferred)
+}
+
+/// Puts back the items of a failed move that were renamed, so the move
+/// fails as a whole.
+fn roll_back(tried: &[MovedItem], deferred: &[MovedItem]) {
+    for item in tried.iter().rev() {
+        if deferred.iter().any(|d| d.from == item.from) {
+            continue;
+        }
+        if let Err(e) = rename_no_clobber(&item.to, &item.from) {
+            eprintln!("[journal] Failed to move {} back: {}", item.to.display(), e);
+        }
+    }
+}
+
+/// Whether `path` is on the filesystem of the folder `dest` goes into.
+fn same_filesystem(path: &Path, dest: &Path) -> bool {
+    let folder = dest.parent().unwrap_or(dest);
+    match (fs::symlink_metadata(path), fs::metadata(folder)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        // Let the rename report what is missing
+        _ => true,
+    }
+}
+
+/// `fs::rename` that refuses to replace an existing destination.
+fn rename_no_clobber(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(to).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    fs::rename(from, to)
+}
+
+// ─── Persistence ───
+
+impl Journal {
+    fn save(&self) {
+        if !self.persistent {
+            return;
+        }
+        let path = journal_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        // TOML strings are UTF-8: ops on other paths stay in memory only
+        let saved = Journal {
+            undo: self
+                .undo
+                .iter()
+                .filter(|op| op.is_utf8())
+                .cloned()
+                .collect(),
+            redo: self
+                .redo
+                .iter()
+                .filter(|op| op.is_utf8())
+                .cloned()
+                .collect(),
+            persistent: true,
+        };
+        match toml::to_string_pretty(&saved) {
+            Ok(content) => {
+                if let Err(e) = fs::write(&path, content) {
+                    eprintln!("[journal] Failed to write: {}", e);
+                }
+            }
+            Err(e) => eprintln!("[journal] Serialization error: {}", e),
+        }
+    }
+}
+
+fn load() -> Option<Journal> {
+    let content = fs::read_to_string(journal_path()).ok()?;
+    match toml::from_str::<Journal>(&content) {
+        Ok(journal) => Some(journal),
+        Err(e) => {
+            eprintln!("[journal] Parse error, starting empty: {}", e);
+            None
+        }
+    }
+}
+
+/// `~/.config/diptych/journal.toml`
+fn journal_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("diptych")
+        .join("journal.toml")
+}
+
+// ─── Helpers ───
+
+fn trim(stack: &mut Vec<FileOp>) {
+    if stack.len() > MAX_ENTRIES {
+        let excess = stack.len() - MAX_ENTRIES;
+        stack.drain(..excess);
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::ffi::OsStringExt;
+
+    /// A fresh, empty directory under the system temp dir.
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("diptych-journal-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn rename(from: &Path, to: &Path) -> FileOp {
+        FileOp::Rename {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        }
+    }
+
+    fn stack_sizes() -> (usize, usize) {
+        JOURNAL.with(|j| {
+            let j = j.borrow();
+            (j.undo.len(), j.redo.len())
+        })
+    }
+
+    #[test]
+    fn undoes_and_redoes_a_rename() {
+        let dir = fixture_dir("rename");
+        let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+        fs::write(&b, "x").unwrap();
+        record(rename(&a, &b));
+
+        let outcome = undo().unwrap();
+        assert!(outcome.error.is_none());
+        assert!(a.exists() && !b.exists());
+        assert_eq!(stack_sizes(), (0, 1));
+
+        let outcome = redo().unwrap();
+        assert!(outcome.error.is_none());
+        assert!(!a.exists() && b.exists());
+        assert_eq!(stack_sizes(), (1, 0));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let dir = fixture_dir("clear");
+        let (a, b) = (dir.join("a"), dir.join("b"));
+        fs::write(&b, "").unwrap();
+        record(rename(&a, &b));
+        undo().unwrap();
+        record(FileOp::Create {
+            path: dir.join("c"),
+            is_dir: false,
+        });
+        assert_eq!(stack_sizes(), (1, 0));
+        assert!(redo().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn undoes_empty_creations_and_recreates_them() {
+        let dir = fixture_dir("create");
+        let (file, folder) = (dir.join("new.txt"), dir.join("New Folder"));
+        fs::write(&file, "").unwrap();
+        fs::create_dir(&folder).unwrap();
+        let file_op = FileOp::Create {
+            path: file.clone(),
+            is_dir: false,
+        };
+        let folder_op = FileOp::Create {
+            path: folder.clone(),
+            is_dir: true,
+        };
+
+        revert(&file_op).unwrap();
+        revert(&folder_op).unwrap();
+        assert!(!file.exists() && !folder.exists());
+
+        reapply(&file_op).unwrap();
+        reapply(&folder_op).unwrap();
+        assert!(file.is_file() && folder.is_dir());
+        // Recreating never replaces what took the name since
+        assert!(reapply(&file_op).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn undoes_a_move_by_moving_back() {
+        let dir = fixture_dir("move");
+        fs::create_dir(dir.join("dest")).unwrap();
+        let item = MovedItem {
+            from: dir.join("a"),
+            to: dir.join("dest/a"),
+        };
+        fs::write(&item.to, "").unwrap();
+
+        let (_, deferred) = revert(&FileOp::Move {
+            items: vec![item.clone()],
+        })
+        .unwrap();
+        assert!(deferred.is_empty());
+        assert!(item.from.exists() && !item.to.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rolls_back_a_move_that_fails_part_way() {
+        let dir = fixture_dir("rollback");
+        fs::create_dir(dir.join("dest")).unwrap();
+        for name in ["a", "b", "dest/b"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+        let items = [
+            MovedItem {
+                from: dir.join("a"),
+                to: dir.join("dest/a"),
+            },
+            // Taken: the move stops here
+            MovedItem {
+                from: dir.join("b"),
+                to: dir.join("dest/b"),
+            },
+        ];
+
+        let err = move_items(&items).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(dir.join("a").exists() && !dir.join("dest/a").exists());
+        assert_eq!(fs::read_to_string(dir.join("dest/b")).unwrap(), "dest/b");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn undoes_a_batch_up_to_the_first_failure() {
+        let dir = fixture_dir("batch");
+        // Renamed 1 → 2, 3 → 4 and 5 → 6, but "6" has gone since: undoing
+        // starts from the last rename and fails right away
+        for name in ["2", "4"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let ops: Vec<FileOp> = [("1", "2"), ("3", "4"), ("5", "6")]
+            .iter()
+            .map(|(from, to)| rename(&dir.join(from), &dir.join(to)))
+            .collect();
+        record_all(ops);
+
+        let outcome = undo().unwrap();
+        assert!(outcome.error.is_some());
+        assert!(outcome.op.is_none());
+        // The failed rename is dropped; the ones before it stay undoable
+        assert_eq!(stack_sizes(), (1, 0));
+
+        let outcome = undo().unwrap();
+        assert!(outcome.error.is_none());
+        assert_eq!(outcome.op.unwrap().description(), "Rename 2 items");
+        assert!(dir.join("1").exists() && dir.join("3").exists());
+        assert_eq!(stack_sizes(), (0, 1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_undone_part_of_a_failed_batch() {
+        let dir = fixture_dir("partial");
+        // "2" has gone, so undoing stops after 6 → 5 and 4 → 3
+        for name in ["4", "6"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let ops: Vec<FileOp> = [("1", "2"), ("3", "4"), ("5", "6")]
+            .iter()
+            .map(|(from, to)| rename(&dir.join(from), &dir.join(to)))
+            .collect();
+
+        let (outcome, left) = revert_batch(&ops);
+        assert!(outcome.error.is_some());
+        match outcome.op {
+            Some(FileOp::Batch { ops: done }) => assert_eq!(done.len(), 2),
+            other => panic!("expected the reverted renames, got {:?}", other),
+        }
+        // The failed rename was the last one to undo
+        assert!(left.is_none());
+        assert!(dir.join("3").exists() && dir.join("5").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn leaves_non_utf8_ops_out_of_the_saved_journal() {
+        let odd = PathBuf::from(std::ffi::OsString::from_vec(b"/tmp/caf\xe9".to_vec()));
+        assert!(!rename(&odd, Path::new("/tmp/cafe")).is_utf8());
+        assert!(!FileOp::Batch {
+            ops: vec![
+                rename(Path::new("/a"), Path::new("/b")),
+                FileOp::Move {
+                    items: vec![MovedItem {
+                        from: odd.clone(),
+                        to: PathBuf::from("/c"),
+                    }],
+                },
+            ],
+        }
+        .is_utf8());
+        assert!(rename(Path::new("/tmp/café"), Path::new("/tmp/cafe")).is_utf8());
+    }
+
+    #[test]
+    fn describes_ops() {
+        let op = rename(Path::new("/x/a.txt"), Path::new("/x/b.txt"));
+        assert_eq!(op.description(), "Rename “a.txt”");
+        let trash = |name: &str| FileOp::Trash {
+            original: PathBuf::from(name),
+            trashed: PathBuf::from(name),
+        };
+        assert_eq!(batch(vec![trash("/a")]).unwrap().description(), "Trash “a”");
+        assert_eq!(
+            batch(vec![trash("/a"), trash("/b")]).unwrap().description(),
+            "Trash 2 items"
+        );
+        assert!(batch(Vec::new()).is_none());
+    }
+}
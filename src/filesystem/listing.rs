@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::config::SortOrder;
+use crate::core::worker::{self, WorkerEvent, WorkerHandle};
+use crate::filesystem::ops::read_entry;
+use crate::filesystem::{sort_entries, Entry};
+
+// ═══════════════════════════════════════════════
+//  Streaming Directory Listing
+// ═══════════════════════════════════════════════
+//
+// Large or slow directories (NFS, USB drives) are read on a std thread.
+// Entries are sent to the main loop in sorted batches, so the view fills
+// in while the rest is still being read (see `core::worker`).
+
+/// Entries per batch, unless `BATCH_INTERVAL` elapses first.
+const BATCH_SIZE: usize = 256;
+const BATCH_INTERVAL: Duration = Duration::from_millis(80);
+
+pub enum ListingEvent {
+    /// Newly read entries, sorted among themselves.
+    Batch(Vec<Entry>),
+    /// The whole directory has been read.
+    Finished,
+}
+
+impl WorkerEvent for ListingEvent {
+    fn is_final(&self) -> bool {
+        matches!(self, ListingEvent::Finished)
+    }
+}
+
+/// Starts reading `dir` on a background thread.
+///
+/// Must be called from the GTK main thread. `on_event` is invoked on the
+/// main thread for every batch and once when the listing is complete.
+pub fn start_listing(
+    dir: PathBuf,
+    include_hidden: bool,
+    order: SortOrder,
+    on_event: impl Fn(ListingEvent) + 'static,
+) -> WorkerHandle {
+    worker::spawn(on_event, move |emitter| {
+        match fs::read_dir(&dir) {
+            Ok(entries) => {
+                let mut batch = Vec::new();
+                let mut last_sent = Instant::now();
+                for entry in entries.flatten() {
+                    if emitter.is_cancelled() {
+                        return;
+                    }
+                    if !include_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                        continue;
+                    }
+                    batch.push(read_entry(&entry));
+
+                    if batch.len() >= BATCH_SIZE || last_sent.elapsed() >= BATCH_INTERVAL {
+                        sort_entries(&mut batch, &order);
+                        emitter.emit(ListingEvent::Batch(std::mem::take(&mut batch)));
+                        last_sent = Instant::now();
+                    }
+                }
+                if !batch.is_empty() {
+                    sort_entries(&mut batch, &order);
+                    emitter.emit(ListingEvent::Batch(batch));
+                }
+            }
+            Err(e) => eprintln!("[listing] Cannot read {}: {}", dir.display(), e),
+        }
+        emitter.emit(ListingEvent::Finished);
+    })
+}
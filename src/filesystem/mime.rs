@@ -0,0 +1,114 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+
+// ═══════════════════════════════════════════════
+//  MIME Type Detection
+// ═══════════════════════════════════════════════
+//
+// Types come from the shared-mime-info database through GIO: the file
+// name is matched against its globs (case-insensitive, so ".JPG" and
+// ".jpeg" agree), then the first bytes are sniffed against its magic
+// rules. Sniffing catches extensionless scripts and misnamed files; a
+// confident sniff overrides the name unless the named type is a more
+// specific kind of it (an .odt is also a zip archive).
+//
+// Listings only match names (`guess_mime`): sniffing opens every file,
+// which adds up in big folders and on network mounts. Names that tell
+// nothing come out as `UNKNOWN_MIME`, and are sniffed when their type is
+// actually needed — in the background for icons, directly to preview or
+// open a file.
+
+/// Bytes read for magic sniffing (what GIO itself reads).
+const SNIFF_LEN: usize = 4096;
+
+/// Types a sniff falls back to when no magic rule matched.
+const GENERIC: [&str; 2] = ["application/octet-stream", "text/plain"];
+
+/// What `guess_mime` says when the name tells nothing.
+pub const UNKNOWN_MIME: &str = "application/octet-stream";
+
+/// Guesses the MIME type of `path` from its name alone, without touching
+/// the file.
+pub fn guess_mime(path: &Path, is_dir: bool) -> String {
+    if is_dir {
+        return "inode/directory".to_string();
+    }
+    let name = path.file_name().map(Path::new);
+    let (by_name, _) = gio::content_type_guess(name, None::<&[u8]>);
+    to_mime(&by_name)
+}
+
+/// Detects the MIME type of `path`. Reads the first bytes of regular files.
+pub fn detect_mime(path: &Path, is_dir: bool) -> String {
+    if is_dir {
+        return "inode/directory".to_string();
+    }
+
+    let name = path.file_name().map(Path::new);
+    let (by_name, uncertain) = gio::content_type_guess(name, None::<&[u8]>);
+
+    let Some(data) = read_head(path) else {
+        return to_mime(&by_name);
+    };
+    if data.is_empty() {
+        return if uncertain {
+            "application/x-zerosize".to_string()
+        } else {
+            to_mime(&by_name)
+        };
+    }
+
+    let (sniffed, sniff_uncertain) = gio::content_type_guess(None::<&Path>, &data[..]);
+    let confident_sniff = !sniff_uncertain
+        && !GENERIC.contains(&sniffed.as_str())
+        && !gio::content_type_is_a(&by_name, &sniffed);
+    if uncertain || confident_sniff {
+        to_mime(&sniffed)
+    } else {
+        to_mime(&by_name)
+    }
+}
+
+/// Detects the type of `path` on a background thread, then calls `done`
+/// with it on that thread. Requests are handled one at a time, in order.
+pub fn detect_mime_in_background(path: &Path, done: impl FnOnce(String) + Send + 'static) {
+    type Job = (PathBuf, Box<dyn FnOnce(String) + Send>);
+    static JOBS: OnceLock<Sender<Job>> = OnceLock::new();
+    let jobs = JOBS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        std::thread::spawn(move || {
+            for (path, done) in rx {
+                done(detect_mime(&path, false));
+            }
+        });
+        tx
+    });
+    let _ = jobs.send((path.to_path_buf(), Box::new(done)));
+}
+
+/// First `SNIFF_LEN` bytes of a regular file.
+fn read_head(path: &Path) -> Option<Vec<u8>> {
+    // Opening a FIFO or device could block
+    if !fs::metadata(path).ok()?.is_file() {
+        return None;
+    }
+    let file = File::open(path).ok()?;
+    let mut data = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut data).ok()?;
+    Some(data)
+}
+
+/// GIO content types are MIME types on Unix; this guards the odd case.
+fn to_mime(content_type: &str) -> String {
+    gio::content_type_get_mime_type(content_type)
+        .map(|m| m.to_string())
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+/// Human-readable name of a MIME type, e.g. "PNG image".
+pub fn mime_description(mime: &str) -> String {
+    gio::content_type_get_description(mime).to_string()
+}
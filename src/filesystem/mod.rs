@@ -1,10 +1,31 @@
 // ─── Filesystem Module ───
-// File entry types, directory operations, and grouping logic.
+// File entry types, MIME detection, directory operations, sorting and
+// grouping, the trash, the undo / redo journal, streaming listings, live
+// directory watching, the recursive index behind the quick finder,
+// recursive search, and the type-to-filter name filter.
 
 mod entry;
+pub mod filter;
+pub mod fuzzy;
+mod gitignore;
 mod grouping;
+pub mod index;
+pub mod journal;
+pub mod listing;
+mod mime;
 mod ops;
+pub mod search;
+mod sorting;
+mod trash;
+pub mod watch;
 
-pub use entry::Entry;
-pub use grouping::group_entries;
-pub use ops::{create_directory, create_file, list_directory};
+pub use entry::{format_size, format_time, parse_size, Entry};
+pub use mime::{
+    detect_mime, detect_mime_in_background, guess_mime, mime_description, UNKNOWN_MIME,
+};
+pub use ops::{create_directory, create_file, create_link, list_directory, unique_path};
+pub use sorting::{sort_entries, sort_key, EntrySortKey};
+pub use trash::{
+    delete_permanently, empty_trash, is_trash_location, list_trash, move_to_trash, purge, restore,
+    trash_location, TrashItem,
+};
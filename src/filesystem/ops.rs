@@ -1,58 +1,77 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::filesystem::Entry;
+use crate::config::SortOrder;
+use crate::filesystem::{guess_mime, sort_entries, Entry};
 
 // ═══════════════════════════════════════════════
 //  Directory Operations
 // ═══════════════════════════════════════════════
 
 /// Lists files in the given directory and returns them as a vector of `Entry`.
-pub fn list_directory(path: &Path, include_hidden: bool) -> Vec<Entry> {
+/// Blocks on the filesystem; the main views stream with `listing::start_listing`.
+pub fn list_directory(path: &Path, include_hidden: bool, order: &SortOrder) -> Vec<Entry> {
     let mut file_list = Vec::new();
 
     match fs::read_dir(path) {
         Ok(entries) => {
             for entry in entries.flatten() {
-                let path = entry.path();
-                let file_name = entry.file_name().to_string_lossy().to_string();
-                let is_dir = path.is_dir();
-
-                if !include_hidden && file_name.starts_with('.') {
+                if !include_hidden && entry.file_name().to_string_lossy().starts_with('.') {
                     continue;
                 }
-
-                let metadata = fs::metadata(&path).ok();
-                let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-                let modified = metadata.and_then(|m| m.modified().ok());
-                let extension = path
-                    .extension()
-                    .map(|e| e.to_string_lossy().to_string())
-                    .unwrap_or_default();
-
-                file_list.push(Entry {
-                    name: file_name,
-                    path,
-                    is_dir,
-                    size,
-                    modified,
-                    extension,
-                });
+                file_list.push(read_entry(&entry));
             }
         }
         Err(e) => eprintln!("Failed to read directory entries: {}", e),
     }
 
-    // Sort: directories first, then files alphabetically
-    file_list.sort_by(|a, b| match (a.is_dir, b.is_dir) {
-        (true, false) => std::cmp::Ordering::Less,
-        (false, true) => std::cmp::Ordering::Greater,
-        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-    });
+    sort_entries(&mut file_list, order);
 
     file_list
 }
 
+/// Builds an `Entry` from a directory item. The type comes from the
+/// directory itself (`DirEntry::file_type`); only symlinks are followed.
+pub(super) fn read_entry(entry: &fs::DirEntry) -> Entry {
+    let path = entry.path();
+    let file_type = entry.file_type().ok();
+    let is_symlink = file_type.is_some_and(|t| t.is_symlink());
+
+    let metadata = if is_symlink {
+        fs::metadata(&path).ok()
+    } else {
+        entry.metadata().ok()
+    };
+    let is_dir = if is_symlink {
+        metadata.as_ref().is_some_and(|m| m.is_dir())
+    } else {
+        file_type.is_some_and(|t| t.is_dir())
+    };
+
+    let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let created = metadata.as_ref().and_then(|m| m.created().ok());
+    let accessed = metadata.and_then(|m| m.accessed().ok());
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mime = guess_mime(&path, is_dir);
+
+    Entry {
+        name: entry.file_name().to_string_lossy().to_string(),
+        path,
+        is_dir,
+        size,
+        modified,
+        created,
+        accessed,
+        extension,
+        mime,
+    }
+}
+
 /// Creates a new directory inside `parent`.
 pub fn create_directory(parent: &Path, name: &str) -> std::io::Result<PathBuf> {
     let new_path = parent.join(name);
@@ -66,3 +85,36 @@ pub fn create_file(parent: &Path, name: &str) -> std::io::Result<PathBuf> {
     fs::File::create(&new_path)?;
     Ok(new_path)
 }
+
+/// Creates a symbolic link to `target` inside `parent`, named after the
+/// target (with a " (N)" suffix if that name is taken).
+pub fn create_link(target: &Path, parent: &Path) -> std::io::Result<PathBuf> {
+    let name = target.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "target has no file name")
+    })?;
+    let mut link = parent.join(name);
+    if fs::symlink_metadata(&link).is_ok() {
+        link = unique_path(&link);
+    }
+    std::os::unix::fs::symlink(target, &link)?;
+    Ok(link)
+}
+
+/// Returns `path` with a " (N)" suffix before the extension that does not exist yet.
+/// Used for "Keep Both" style conflict resolution.
+pub fn unique_path(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or_else(|| Path::new("/"));
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| parent.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| path.to_path_buf())
+}
@@ -0,0 +1,446 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::config::{DateFilter, NameMatch, SearchQuery, TypeFilter};
+use crate::core::worker::{self, WorkerEvent, WorkerHandle};
+use crate::filesystem::gitignore::glob_match;
+use crate::filesystem::index::walk_tree;
+use crate::filesystem::ops::read_entry;
+use crate::filesystem::{detect_mime, Entry, UNKNOWN_MIME};
+
+// ═══════════════════════════════════════════════
+//  Recursive Search
+// ═══════════════════════════════════════════════
+//
+// Walks the tree below a folder on a std thread (the same walk as the
+// quick finder's index) and streams the entries matching a
+// `SearchQuery` to the main loop in batches, like `listing`. Tests run
+// cheapest first: the name, then size / date / type from the metadata,
+// and the file contents last. A search stops after `MAX_HITS` hits.
+
+const BATCH_SIZE: usize = 64;
+const BATCH_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Hits reported at most, so a search for everything stays usable.
+const MAX_HITS: usize = 10_000;
+
+/// Bytes read at a time when searching file contents.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// One matching file or folder.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub entry: Entry,
+    /// Path relative to the searched folder, '/'-separated.
+    pub rel: String,
+}
+
+pub enum SearchEvent {
+    Batch(Vec<SearchHit>),
+    /// The walk is over; `truncated` if it stopped at `MAX_HITS`.
+    Finished {
+        truncated: bool,
+    },
+}
+
+impl WorkerEvent for SearchEvent {
+    fn is_final(&self) -> bool {
+        matches!(self, SearchEvent::Finished { .. })
+    }
+}
+
+/// Checks `query` before it is run: a bad regex or date is reported as a
+/// message for the search bar.
+pub fn validate(query: &SearchQuery) -> Result<(), String> {
+    Matcher::new(query).map(|_| ())
+}
+
+/// Starts searching below `root` on a background thread.
+///
+/// Must be called from the GTK main thread; `on_event` runs there for
+/// every batch of hits and once when the search is over. The query
+/// should have passed `validate`; otherwise the search finds nothing.
+pub fn start_search(
+    root: PathBuf,
+    query: SearchQuery,
+    include_hidden: bool,
+    on_event: impl Fn(SearchEvent) + 'static,
+) -> WorkerHandle {
+    worker::spawn(on_event, move |emitter| {
+        // GRegex is not Send, so the matcher is built on this thread
+        let matcher = match Matcher::new(&query) {
+            Ok(matcher) => matcher,
+            Err(e) => {
+                eprintln!("[search] {}", e);
+                emitter.emit(SearchEvent::Finished { truncated: false });
+                return;
+            }
+        };
+
+        let mut batch = Vec::new();
+        let mut last_sent = Instant::now();
+        let mut hits = 0;
+        let mut truncated = false;
+        walk_tree(
+            &root,
+            include_hidden,
+            false,
+            emitter.cancel_flag(),
+            |entry, path, is_dir| {
+                let Some(hit) = matcher.test(entry, is_dir, emitter.cancel_flag()) else {
+                    return true;
+                };
+                let rel = path
+                    .strip_prefix(&root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .to_string();
+                batch.push(SearchHit { entry: hit, rel });
+                hits += 1;
+                if batch.len() >= BATCH_SIZE || last_sent.elapsed() >= BATCH_INTERVAL {
+                    emitter.emit(SearchEvent::Batch(std::mem::take(&mut batch)));
+                    last_sent = Instant::now();
+                }
+                truncated = hits >= MAX_HITS;
+                !truncated
+            },
+        );
+        if emitter.is_cancelled() {
+            return;
+        }
+        if !batch.is_empty() {
+            emitter.emit(SearchEvent::Batch(batch));
+        }
+        emitter.emit(SearchEvent::Finished { truncated });
+    })
+}
+
+// ═══════════════════════════════════════════════
+//  Matching
+// ═══════════════════════════════════════════════
+
+enum NameTest {
+    Any,
+    Substring(String),
+    Glob(Vec<char>),
+    Regex(glib::Regex),
+}
+
+/// A `SearchQuery` compiled for testing entries.
+struct Matcher {
+    name: NameTest,
+    kind: TypeFilter,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    /// Modified at or after / before.
+    after: Option<SystemTime>,
+    before: Option<SystemTime>,
+    /// Lowercased needle of a contents search.
+    contents: Option<Vec<u8>>,
+}
+
+impl Matcher {
+    fn new(query: &SearchQuery) -> Result<Self, String> {
+        let pattern = query.name.trim();
+        let name = if pattern.is_empty() {
+            NameTest::Any
+        } else {
+            match query.name_match {
+                NameMatch::Substring => NameTest::Substring(pattern.to_lowercase()),
+                NameMatch::Glob => NameTest::Glob(pattern.to_lowercase().chars().collect()),
+                NameMatch::Regex => glib::Regex::new(
+                    pattern,
+                    glib::RegexCompileFlags::CASELESS | glib::RegexCompileFlags::OPTIMIZE,
+                    glib::RegexMatchFlags::empty(),
+                )
+                .map_err(|e| format!("Invalid regex: {}", e.message()))?
+                .map_or(NameTest::Any, NameTest::Regex),
+            }
+        };
+        if let (Some(min), Some(max)) = (query.min_size, query.max_size) {
+            if min > max {
+                return Err("The minimum size is above the maximum".to_string());
+            }
+        }
+        let (after, before) = date_bounds(&query.modified)?;
+        let contents = query.contents.trim();
+
+        Ok(Self {
+            name,
+            kind: query.kind,
+            min_size: query.min_size,
+            max_size: query.max_size,
+            after,
+            before,
+            contents: (!contents.is_empty()).then(|| contents.to_ascii_lowercase().into_bytes()),
+        })
+    }
+
+    /// The entry for `dir_entry` if it matches.
+    fn test(
+        &self,
+        dir_entry: &fs::DirEntry,
+        is_dir: bool,
+        cancelled: &AtomicBool,
+    ) -> Option<Entry> {
+        let name = dir_entry.file_name().to_string_lossy().to_string();
+        if !self.name_matches(&name) {
+            return None;
+        }
+        match self.kind {
+            TypeFilter::Folder if !is_dir => return None,
+            TypeFilter::Any | TypeFilter::Folder => {}
+            _ if is_dir => return None,
+            _ => {}
+        }
+        let sized = self.min_size.is_some() || self.max_size.is_some();
+        if is_dir && (sized || self.contents.is_some()) {
+            return None;
+        }
+
+        let mut entry = read_entry(dir_entry);
+        if self.min_size.is_some_and(|min| entry.size < min)
+            || self.max_size.is_some_and(|max| entry.size > max)
+        {
+            return None;
+        }
+        if self.after.is_some() || self.before.is_some() {
+            let modified = entry.modified?;
+            if self.after.is_some_and(|t| modified < t)
+                || self.before.is_some_and(|t| modified >= t)
+            {
+                return None;
+            }
+        }
+        // A name that tells nothing is sniffed only to filter by kind
+        let by_kind = !matches!(
+            self.kind,
+            TypeFilter::Any | TypeFilter::Folder | TypeFilter::File
+        );
+        if by_kind && entry.mime == UNKNOWN_MIME {
+            entry.mime = detect_mime(&entry.path, false);
+        }
+        if !kind_matches(self.kind, &entry.mime) {
+            return None;
+        }
+        if let Some(needle) = &self.contents {
+            if !file_contains(&entry.path, needle, cancelled) {
+                return None;
+            }
+        }
+        Some(entry)
+    }
+
+    fn name_matches(&self, name: &str) -> bool {
+        match &self.name {
+            NameTest::Any => true,
+            NameTest::Substring(s) => name.to_lowercase().contains(s.as_str()),
+            NameTest::Glob(pattern) => {
+                let name: Vec<char> = name.to_lowercase().chars().collect();
+                glob_match(pattern, &name)
+            }
+            NameTest::Regex(regex) => regex
+                .match_(
+                    glib::GString::from(name).as_gstr(),
+                    glib::RegexMatchFlags::empty(),
+                )
+                .is_ok_and(|m| m.matches()),
+        }
+    }
+}
+
+/// True if a file of type `mime` belongs to `kind` (folders were
+/// sorted out by the caller).
+fn kind_matches(kind: TypeFilter, mime: &str) -> bool {
+    let is_a = |parent: &str| gio::content_type_is_a(mime, parent);
+    match kind {
+        TypeFilter::Any | TypeFilter::Folder | TypeFilter::File => true,
+        TypeFilter::Image => mime.starts_with("image/"),
+        TypeFilter::Audio => mime.starts_with("audio/"),
+        TypeFilter::Video => mime.starts_with("video/"),
+        TypeFilter::Text => mime.starts_with("text/") || is_a("text/plain"),
+        TypeFilter::Document => {
+            matches!(
+                mime,
+                "application/pdf"
+                    | "application/rtf"
+                    | "application/msword"
+                    | "application/epub+zip"
+                    | "application/vnd.ms-excel"
+                    | "application/vnd.ms-powerpoint"
+            ) || mime.starts_with("application/vnd.oasis.opendocument.")
+                || mime.starts_with("application/vnd.openxmlformats-officedocument.")
+        }
+        TypeFilter::Archive => {
+            matches!(
+                mime,
+                "application/zip"
+                    | "application/x-tar"
+                    | "application/gzip"
+                    | "application/x-bzip2"
+                    | "application/x-xz"
+                    | "application/zstd"
+                    | "application/x-7z-compressed"
+                    | "application/vnd.rar"
+                    | "application/x-rar"
+            ) || mime.starts_with("application/x-compressed-tar")
+                || mime.starts_with("application/x-bzip")
+        }
+    }
+}
+
+/// The modified-time bounds of `filter`: at or after the first, before
+/// the second. Days start at local midnight.
+fn date_bounds(filter: &DateFilter) -> Result<(Option<SystemTime>, Option<SystemTime>), String> {
+    let today = Local::now().date_naive();
+    let days_ago = |days: i64| Some(midnight(today - chrono::Duration::days(days)));
+    Ok(match filter {
+        DateFilter::Any => (None, None),
+        DateFilter::Today => (days_ago(0), None),
+        DateFilter::PastWeek => (days_ago(7), None),
+        DateFilter::PastMonth => (days_ago(30), None),
+        DateFilter::PastYear => (days_ago(365), None),
+        DateFilter::Between { from, to } => {
+            let from = parse_date(from.as_deref())?;
+            let to = parse_date(to.as_deref())?;
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    return Err("The start date is after the end date".to_string());
+                }
+            }
+            (
+                from.map(midnight),
+                to.map(|d| midnight(d + chrono::Duration::days(1))),
+            )
+        }
+    })
+}
+
+fn parse_date(text: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    match text.map(str::trim).filter(|t| !t.is_empty()) {
+        None => Ok(None),
+        Some(t) => NaiveDate::parse_from_str(t, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| format!("Invalid date “{}” (use YYYY-MM-DD)", t)),
+    }
+}
+
+fn midnight(date: NaiveDate) -> SystemTime {
+    let naive = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(SystemTime::from)
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// True if the file at `path` contains `needle` (lowercased), ignoring
+/// ASCII case. Reads in chunks, keeping an overlap so a match across two
+/// chunks is found.
+fn file_contains(path: &std::path::Path, needle: &[u8], cancelled: &AtomicBool) -> bool {
+    // Opening a FIFO or device could block the search for good
+    if !fs::metadata(path).is_ok_and(|m| m.is_file()) {
+        return false;
+    }
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let keep = needle.len().saturating_sub(1);
+    let mut buffer = vec![0u8; CHUNK_SIZE + keep];
+    let mut filled = 0;
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            return false;
+        }
+        let read = match file.read(&mut buffer[filled..]) {
+            Ok(0) | Err(_) => return false,
+            Ok(n) => n,
+        };
+        buffer[filled..filled + read].make_ascii_lowercase();
+        filled += read;
+        if buffer[..filled].windows(needle.len()).any(|w| w == needle) {
+            return true;
+        }
+        // Carry the tail over into the next chunk
+        let tail = filled.saturating_sub(keep);
+        buffer.copy_within(tail..filled, 0);
+        filled -= tail;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn between(from: &str, to: &str) -> DateFilter {
+        let date = |d: &str| (!d.is_empty()).then(|| d.to_string());
+        DateFilter::Between {
+            from: date(from),
+            to: date(to),
+        }
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_date(None), Ok(None));
+        assert_eq!(parse_date(Some("  ")), Ok(None));
+        assert_eq!(
+            parse_date(Some(" 2024-02-29 ")),
+            Ok(Some(day("2024-02-29")))
+        );
+        assert!(parse_date(Some("2023-02-29")).is_err());
+        assert!(parse_date(Some("29/02/2024")).is_err());
+    }
+
+    #[test]
+    fn between_covers_whole_days() {
+        let (after, before) = date_bounds(&between("2024-03-01", "2024-03-03")).unwrap();
+        assert_eq!(after, Some(midnight(day("2024-03-01"))));
+        // The end date is included up to its last second
+        assert_eq!(before, Some(midnight(day("2024-03-04"))));
+
+        let (after, before) = date_bounds(&between("2024-03-01", "2024-03-01")).unwrap();
+        assert!(after < before);
+    }
+
+    #[test]
+    fn between_may_be_open_ended() {
+        let (after, before) = date_bounds(&between("2024-03-01", "")).unwrap();
+        assert_eq!(after, Some(midnight(day("2024-03-01"))));
+        assert_eq!(before, None);
+
+        let (after, before) = date_bounds(&between("", "2024-03-01")).unwrap();
+        assert_eq!(after, None);
+        assert_eq!(before, Some(midnight(day("2024-03-02"))));
+    }
+
+    #[test]
+    fn rejects_bad_ranges() {
+        assert!(date_bounds(&between("2024-03-02", "2024-03-01")).is_err());
+        assert!(date_bounds(&between("yesterday", "")).is_err());
+    }
+
+    #[test]
+    fn relative_filters_start_at_local_midnight() {
+        let today = midnight(Local::now().date_naive());
+        assert_eq!(date_bounds(&DateFilter::Any).unwrap(), (None, None));
+        assert_eq!(
+            date_bounds(&DateFilter::Today).unwrap(),
+            (Some(today), None)
+        );
+
+        let (week, _) = date_bounds(&DateFilter::PastWeek).unwrap();
+        let (month, _) = date_bounds(&DateFilter::PastMonth).unwrap();
+        let (year, _) = date_bounds(&DateFilter::PastYear).unwrap();
+        assert!(year < month && month < week && week < Some(today));
+    }
+}
@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::time::SystemTime;
+
+use crate::config::{SortKey, SortOrder};
+use crate::filesystem::Entry;
+
+// ═══════════════════════════════════════════════
+//  Sorting
+// ═══════════════════════════════════════════════
+//
+// Entries sort by a key built once per entry: the folder rank (when
+// folders come first), the primary value of the chosen `SortKey`, then
+// the name. Names compare with GLib collation keys, so the order follows
+// the user's locale; `Natural` uses the filename variant, which also
+// compares digit runs as numbers. The direction flips everything but
+// the folder rank.
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Primary {
+    None,
+    Size(u64),
+    Modified(Option<SystemTime>),
+    Created(Option<SystemTime>),
+    Accessed(Option<SystemTime>),
+    Type(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum NameKey {
+    Plain(glib::CollationKey),
+    Natural(glib::FilenameCollationKey),
+}
+
+/// Precomputed sort position of one entry (see `sort_key`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrySortKey {
+    folder_rank: u8,
+    primary: Primary,
+    name: NameKey,
+    descending: bool,
+}
+
+impl Ord for EntrySortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folder_rank.cmp(&other.folder_rank).then_with(|| {
+            let order = self
+                .primary
+                .cmp(&other.primary)
+                .then_with(|| self.name.cmp(&other.name));
+            if self.descending {
+                order.reverse()
+            } else {
+                order
+            }
+        })
+    }
+}
+
+impl PartialOrd for EntrySortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Builds the sort key of `entry`. Live updates compare keys to find
+/// where a new entry goes; build the new entry's key once, collation
+/// keys are not free.
+pub fn sort_key(entry: &Entry, order: &SortOrder) -> EntrySortKey {
+    let primary = match order.key {
+        SortKey::Name | SortKey::Natural => Primary::None,
+        SortKey::Size => Primary::Size(if entry.is_dir { 0 } else { entry.size }),
+        SortKey::Modified => Primary::Modified(entry.modified),
+        SortKey::Created => Primary::Created(entry.created),
+        SortKey::Accessed => Primary::Accessed(entry.accessed),
+        SortKey::Type if entry.is_dir => Primary::Type(String::new()),
+        SortKey::Type => Primary::Type(entry.mime.clone()),
+    };
+    let name = match order.key {
+        SortKey::Name => NameKey::Plain(glib::CollationKey::from(&entry.name)),
+        _ => NameKey::Natural(glib::FilenameCollationKey::from(&entry.name)),
+    };
+    EntrySortKey {
+        folder_rank: u8::from(order.folders_first && !entry.is_dir),
+        primary,
+        name,
+        descending: order.descending,
+    }
+}
+
+pub fn sort_entries(entries: &mut [Entry], order: &SortOrder) {
+    entries.sort_by_cached_key(|e| sort_key(e, order));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn file(name: &str, size: u64, age_secs: u64, mime: &str) -> Entry {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs);
+        Entry {
+            name: name.to_string(),
+            path: PathBuf::from("/fixture").join(name),
+            is_dir: false,
+            size,
+            modified: Some(time),
+            created: Some(time),
+            accessed: Some(time),
+            extension: String::new(),
+            mime: mime.to_string(),
+        }
+    }
+
+    fn folder(name: &str) -> Entry {
+        Entry {
+            is_dir: true,
+            size: 4096,
+            mime: "inode/directory".to_string(),
+            ..file(name, 0, 0, "")
+        }
+    }
+
+    fn order(key: SortKey, descending: bool, folders_first: bool) -> SortOrder {
+        SortOrder {
+            key,
+            descending,
+            folders_first,
+        }
+    }
+
+    fn sorted(mut entries: Vec<Entry>, order: &SortOrder) -> Vec<String> {
+        sort_entries(&mut entries, order);
+        entries.into_iter().map(|e| e.name).collect()
+    }
+
+    #[test]
+    fn natural_order_compares_numbers() {
+        let entries = vec![
+            file("file10", 0, 0, ""),
+            file("file2", 0, 0, ""),
+            file("file1", 0, 0, ""),
+        ];
+        assert_eq!(
+            sorted(entries.clone(), &order(SortKey::Natural, false, false)),
+            ["file1", "file2", "file10"]
+        );
+        assert_eq!(
+            sorted(entries, &order(SortKey::Name, false, false)),
+            ["file1", "file10", "file2"]
+        );
+    }
+
+    #[test]
+    fn folders_first_holds_in_both_directions() {
+        let entries = vec![
+            file("a", 0, 0, ""),
+            folder("z"),
+            file("m", 0, 0, ""),
+            folder("b"),
+        ];
+        assert_eq!(
+            sorted(entries.clone(), &order(SortKey::Name, false, true)),
+            ["b", "z", "a", "m"]
+        );
+        assert_eq!(
+            sorted(entries.clone(), &order(SortKey::Name, true, true)),
+            ["z", "b", "m", "a"]
+        );
+        assert_eq!(
+            sorted(entries, &order(SortKey::Name, false, false)),
+            ["a", "b", "m", "z"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_size_with_folders_as_empty() {
+        let entries = vec![
+            file("big", 300, 0, ""),
+            file("small", 10, 0, ""),
+            folder("dir"),
+            file("also-small", 10, 0, ""),
+        ];
+        // Equal sizes fall back to the name
+        assert_eq!(
+            sorted(entries.clone(), &order(SortKey::Size, false, false)),
+            ["dir", "also-small", "small", "big"]
+        );
+        assert_eq!(
+            sorted(entries, &order(SortKey::Size, true, false)),
+            ["big", "small", "also-small", "dir"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_dates() {
+        let entries = vec![
+            file("new", 0, 10, ""),
+            file("old", 0, 1000, ""),
+            file("mid", 0, 100, ""),
+        ];
+        for key in [SortKey::Modified, SortKey::Created, SortKey::Accessed] {
+            assert_eq!(
+                sorted(entries.clone(), &order(key, false, false)),
+                ["old", "mid", "new"]
+            );
+            assert_eq!(
+                sorted(entries.clone(), &order(key, true, false)),
+                ["new", "mid", "old"]
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_dates_sort_first() {
+        let mut unknown = file("unknown", 0, 0, "");
+        unknown.modified = None;
+        let entries = vec![file("known", 0, 10, ""), unknown];
+        assert_eq!(
+            sorted(entries, &order(SortKey::Modified, false, false)),
+            ["unknown", "known"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_type_with_folders_first() {
+        let entries = vec![
+            file("song", 0, 0, "audio/ogg"),
+            file("notes", 0, 0, "text/plain"),
+            folder("dir"),
+            file("photo", 0, 0, "image/png"),
+        ];
+        assert_eq!(
+            sorted(entries, &order(SortKey::Type, false, false)),
+            ["dir", "song", "photo", "notes"]
+        );
+    }
+
+    #[test]
+    fn keys_agree_with_sort_entries() {
+        let order = SortOrder::default();
+        let a = sort_key(&file("a2", 0, 0, ""), &order);
+        let b = sort_key(&file("a10", 0, 0, ""), &order);
+        let dir = sort_key(&folder("zz"), &order);
+        assert!(a < b);
+        assert!(dir < a);
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+    }
+}
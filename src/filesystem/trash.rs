@@ -0,0 +1,627 @@
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+// ═══════════════════════════════════════════════
+//  Freedesktop Trash
+// ═══════════════════════════════════════════════
+//
+// Implements the freedesktop.org Trash specification:
+//   • Home trash   — $XDG_DATA_HOME/Trash (usually ~/.local/share/Trash)
+//   • Mount trash  — $topdir/.Trash/$uid (admin-created, sticky) or
+//                    $topdir/.Trash-$uid, for files on other filesystems
+//
+// Every trash dir has `files/` (the trashed items) and `info/`
+// (one `<name>.trashinfo` per item with the original path and the
+// deletion date). The info file is created first with O_EXCL, which
+// reserves the name in `files/` as well.
+//
+// The Trash place browses all of them at once: `list_trash` merges the
+// home trash with every mount trash found in /proc/mounts.
+
+/// Moves `path` into the matching trash directory.
+/// Returns where the item now lives (inside the trash's `files/`).
+pub fn move_to_trash(path: &Path) -> io::Result<PathBuf> {
+    let path = absolute(path)?;
+    // Don't follow a symlink — trash the link itself
+    let meta = fs::symlink_metadata(&path)?;
+
+    let (trash_dir, topdir) = trash_dir_for(&path, &meta)?;
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    // Home trash stores absolute paths, mount trashes store paths relative to the topdir
+    let stored_path = match &topdir {
+        Some(top) => path.strip_prefix(top).unwrap_or(&path).to_path_buf(),
+        None => path.clone(),
+    };
+
+    let (name, mut info_file) = reserve_info_file(&info_dir, &files_dir, &path)?;
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(&stored_path),
+        chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+    let info_path = info_dir.join(info_file_name(&name));
+
+    let trashed_path = files_dir.join(&name);
+    let moved = info_file
+        .write_all(info.as_bytes())
+        .and_then(|_| fs::rename(&path, &trashed_path));
+    if let Err(e) = moved {
+        // Don't leave an orphaned .trashinfo behind
+        let _ = fs::remove_file(&info_path);
+        return Err(e);
+    }
+    Ok(trashed_path)
+}
+
+/// Deletes `path` for good (recursively for folders).
+pub fn delete_permanently(path: &Path) -> io::Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+// ─── Browsing ───
+
+/// One trashed item, parsed from its `.trashinfo`.
+#[derive(Clone, Debug)]
+pub struct TrashItem {
+    /// Name inside `files/` (may carry a " (N)" suffix).
+    pub name: String,
+    /// Where the item lived before it was trashed.
+    pub original_path: PathBuf,
+    pub deletion_date: Option<chrono::NaiveDateTime>,
+    /// The trashed item itself, inside `files/`.
+    pub trashed_path: PathBuf,
+    pub info_path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+impl TrashItem {
+    /// Display name: the original file name.
+    pub fn display_name(&self) -> String {
+        self.original_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.name.clone())
+    }
+
+    pub fn deletion_display(&self) -> String {
+        match self.deletion_date {
+            Some(d) => d.format("%Y-%m-%d %H:%M").to_string(),
+            None => "—".to_string(),
+        }
+    }
+}
+
+/// The location a pane navigates to for the Trash place (`files/` of the home trash).
+pub fn trash_location() -> Option<PathBuf> {
+    home_trash_dir().map(|d| d.join("files"))
+}
+
+/// Whether `path` is the Trash place.
+pub fn is_trash_location(path: &Path) -> bool {
+    trash_location().is_some_and(|t| t == path)
+}
+
+/// Lists every trashed item: home trash first, then the trashes of mounted filesystems.
+/// Newest deletions come first.
+pub fn list_trash() -> Vec<TrashItem> {
+    let mut items = Vec::new();
+    for (trash_dir, topdir) in all_trash_dirs() {
+        let Ok(rd) = fs::read_dir(trash_dir.join("info")) else {
+            continue;
+        };
+        for info in rd.flatten() {
+            let info_path = info.path();
+            if info_path.extension().is_none_or(|e| e != "trashinfo") {
+                continue;
+            }
+            if let Some(item) = parse_trash_info(&trash_dir, topdir.as_deref(), &info_path) {
+                items.push(item);
+            }
+        }
+    }
+    items.sort_by_key(|i| std::cmp::Reverse(i.deletion_date));
+    items
+}
+
+/// Moves a trashed item back to `dest` (normally its original path)
+/// and removes its `.trashinfo`. Missing parent folders are recreated.
+pub fn restore(item: &TrashItem, dest: &Path) -> io::Result<()> {
+    restore_trashed(&item.trashed_path, dest)
+}
+
+/// Like `restore`, starting from the path returned by `move_to_trash`.
+pub fn restore_trashed(trashed_path: &Path, dest: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(dest).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dest.display()),
+        ));
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(trashed_path, dest)?;
+
+    // <trash>/files/<name>  →  <trash>/info/<name>.trashinfo
+    if let (Some(files_dir), Some(name)) = (trashed_path.parent(), trashed_path.file_name()) {
+        if let Some(trash_dir) = files_dir.parent() {
+            let _ = fs::remove_file(trash_dir.join("info").join(info_file_name(name)));
+        }
+    }
+    Ok(())
+}
+
+/// Deletes a trashed item for good.
+pub fn purge(item: &TrashItem) -> io::Result<()> {
+    if fs::symlink_metadata(&item.trashed_path).is_ok() {
+        delete_permanently(&item.trashed_path)?;
+    }
+    fs::remove_file(&item.info_path)
+}
+
+/// Purges every trashed item. Returns the number of items that could not be removed.
+pub fn empty_trash() -> usize {
+    let mut failed = 0;
+    for item in list_trash() {
+        if let Err(e) = purge(&item) {
+            eprintln!(
+                "[trash] Failed to purge {}: {}",
+                item.trashed_path.display(),
+                e
+            );
+            failed += 1;
+        }
+    }
+    failed
+}
+
+fn parse_trash_info(
+    trash_dir: &Path,
+    topdir: Option<&Path>,
+    info_path: &Path,
+) -> Option<TrashItem> {
+    let content = fs::read_to_string(info_path).ok()?;
+    let mut in_section = false;
+    let mut stored_path = None;
+    let mut deletion_date = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line == "[Trash Info]";
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("Path=") {
+            stored_path = Some(decode_path(value));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deletion_date = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok();
+        }
+    }
+
+    let stored_path = stored_path?;
+    let original_path = match topdir {
+        Some(top) if stored_path.is_relative() => top.join(stored_path),
+        _ => stored_path,
+    };
+
+    // The stem keeps its bytes: `files/` holds the item under the same name
+    let stem = info_path.file_stem()?;
+    let trashed_path = trash_dir.join("files").join(stem);
+    let name = stem.to_string_lossy().to_string();
+    let meta = fs::symlink_metadata(&trashed_path).ok();
+
+    Some(TrashItem {
+        name,
+        original_path,
+        deletion_date,
+        trashed_path,
+        info_path: info_path.to_path_buf(),
+        is_dir: meta.as_ref().is_some_and(|m| m.is_dir()),
+        size: meta.map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+// ─── Trash Directory Lookup ───
+
+/// The per-user home trash directory.
+fn home_trash_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("Trash"))
+}
+
+/// Every existing trash directory: the home trash plus one per mount point.
+fn all_trash_dirs() -> Vec<(PathBuf, Option<PathBuf>)> {
+    let mut dirs = Vec::new();
+    if let Some(home) = home_trash_dir() {
+        dirs.push((home, None));
+    }
+
+    #[cfg(unix)]
+    {
+        let uid = current_uid();
+        for topdir in mount_points() {
+            for dir in [
+                topdir.join(".Trash").join(uid.to_string()),
+                topdir.join(format!(".Trash-{}", uid)),
+            ] {
+                if dir.join("info").is_dir() && !dirs.iter().any(|(d, _)| *d == dir) {
+                    dirs.push((dir, Some(topdir.clone())));
+                }
+            }
+        }
+    }
+    dirs
+}
+
+/// Mount points from `/proc/mounts` (octal escapes like `\040` decoded).
+#[cfg(unix)]
+fn mount_points() -> Vec<PathBuf> {
+    let Ok(mounts) = fs::read_to_string("/proc/mounts") else {
+        return Vec::new();
+    };
+    mounts
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(|field| {
+            let mut bytes = Vec::new();
+            let raw = field.as_bytes();
+            let mut i = 0;
+            while i < raw.len() {
+                let octal = raw.get(i + 1..i + 4).and_then(|o| {
+                    std::str::from_utf8(o)
+                        .ok()
+                        .and_then(|o| u8::from_str_radix(o, 8).ok())
+                });
+                match (raw[i], octal) {
+                    (b'\\', Some(b)) => {
+                        bytes.push(b);
+                        i += 4;
+                    }
+                    (b, _) => {
+                        bytes.push(b);
+                        i += 1;
+                    }
+                }
+            }
+            PathBuf::from(String::from_utf8_lossy(&bytes).to_string())
+        })
+        .collect()
+}
+
+/// Picks the trash directory for `path`.
+/// Returns the trash dir and, for mount trashes, the mount's top directory.
+fn trash_dir_for(path: &Path, meta: &fs::Metadata) -> io::Result<(PathBuf, Option<PathBuf>)> {
+    let home_trash = home_trash_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    #[cfg(unix)]
+    {
+        // The home trash may not exist yet — compare against its closest existing ancestor
+        let home_dev = home_trash
+            .ancestors()
+            .find_map(|p| fs::metadata(p).ok())
+            .map(|m| m.dev());
+        if home_dev == Some(meta.dev()) {
+            return Ok((home_trash, None));
+        }
+
+        let topdir = mount_topdir(path, meta.dev());
+        let uid = current_uid();
+
+        // 1. Admin-provided $topdir/.Trash (must be sticky and not a symlink)
+        let shared = topdir.join(".Trash");
+        if let Ok(m) = fs::symlink_metadata(&shared) {
+            if m.is_dir() && m.mode() & 0o1000 != 0 {
+                let dir = shared.join(uid.to_string());
+                if fs::create_dir_all(&dir).is_ok() {
+                    return Ok((dir, Some(topdir)));
+                }
+            }
+        }
+
+        // 2. Per-user $topdir/.Trash-$uid
+        let dir = topdir.join(format!(".Trash-{}", uid));
+        fs::create_dir_all(&dir)?;
+        Ok((dir, Some(topdir)))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (path, meta);
+        Ok((home_trash, None))
+    }
+}
+
+/// Walks up from `path` to the top directory of the filesystem it lives on.
+#[cfg(unix)]
+fn mount_topdir(path: &Path, dev: u64) -> PathBuf {
+    let mut top = path.to_path_buf();
+    for ancestor in path.ancestors().skip(1) {
+        match fs::metadata(ancestor) {
+            Ok(m) if m.dev() == dev => top = ancestor.to_path_buf(),
+            _ => break,
+        }
+    }
+    top
+}
+
+/// The real uid of this process (owner of `/proc/self`), falling back to the home dir owner.
+#[cfg(unix)]
+fn current_uid() -> u32 {
+    fs::metadata("/proc/self")
+        .or_else(|_| fs::metadata(dirs::home_dir().unwrap_or_default()))
+        .map(|m| m.uid())
+        .unwrap_or(0)
+}
+
+/// `<name>.trashinfo`, keeping the name's bytes.
+fn info_file_name(name: &OsStr) -> OsString {
+    let mut info_name = name.to_os_string();
+    info_name.push(".trashinfo");
+    info_name
+}
+
+/// Creates `info/<name>.trashinfo` exclusively, adding a " (N)" suffix on clashes.
+fn reserve_info_file(
+    info_dir: &Path,
+    files_dir: &Path,
+    path: &Path,
+) -> io::Result<(OsString, fs::File)> {
+    let base = path
+        .file_name()
+        .unwrap_or(OsStr::new("untitled"))
+        .to_os_string();
+    let base_path = Path::new(&base);
+    let stem = base_path.file_stem().unwrap_or(&base).to_os_string();
+    let ext = base_path.extension();
+
+    for n in 1.. {
+        let name = if n == 1 {
+            base.clone()
+        } else {
+            let mut name = stem.clone();
+            name.push(format!(" ({})", n));
+            if let Some(ext) = ext {
+                name.push(".");
+                name.push(ext);
+            }
+            name
+        };
+        // A stray item without info file still occupies the name
+        if fs::symlink_metadata(files_dir.join(&name)).is_ok() {
+            continue;
+        }
+        let result = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(info_dir.join(info_file_name(&name)));
+        match result {
+            Ok(file) => return Ok((name, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!()
+}
+
+// ─── Path Encoding ───
+
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+/// Reverses `encode_path`.
+#[cfg(unix)]
+fn decode_path(value: &str) -> PathBuf {
+    PathBuf::from(OsString::from_vec(percent_decode(value)))
+}
+
+/// Reverses `encode_path`.
+#[cfg(not(unix))]
+fn decode_path(value: &str) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&percent_decode(value)).into_owned())
+}
+
+/// Percent-encodes a path the way `.trashinfo` expects (RFC 2396 style, '/' kept).
+#[cfg(unix)]
+fn encode_path(path: &Path) -> String {
+    // Bytes, not text: names need not be UTF-8
+    percent_encode(path.as_os_str().as_bytes())
+}
+
+/// Percent-encodes a path the way `.trashinfo` expects (RFC 2396 style, '/' kept).
+#[cfg(not(unix))]
+fn encode_path(path: &Path) -> String {
+    percent_encode(path.to_string_lossy().as_bytes())
+}
+
+fn percent_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for &byte in bytes {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'/'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~'
+            | b'!'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn percent_decode(value: &str) -> Vec<u8> {
+    let raw = value.as_bytes();
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        let hex = raw
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (raw[i], hex) {
+            (b'%', Some(b)) => {
+                bytes.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                bytes.push(b);
+                i += 1;
+            }
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh trash directory (with `files/` and `info/`) under the system temp dir.
+    fn fixture_trash(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("diptych-trash-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("files")).unwrap();
+        fs::create_dir_all(dir.join("info")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn encodes_paths() {
+        assert_eq!(
+            encode_path(Path::new("/home/a b/x.txt")),
+            "/home/a%20b/x.txt"
+        );
+        assert_eq!(encode_path(Path::new("/tmp/é#%")), "/tmp/%C3%A9%23%25");
+        assert_eq!(encode_path(Path::new("rel/~(1)!")), "rel/~(1)!");
+    }
+
+    #[test]
+    fn decodes_paths() {
+        assert_eq!(
+            decode_path("/home/a%20b/x.txt"),
+            Path::new("/home/a b/x.txt")
+        );
+        assert_eq!(decode_path("/tmp/%c3%a9"), Path::new("/tmp/é"));
+        // Stray percent signs are kept as they are
+        assert_eq!(decode_path("/100%"), Path::new("/100%"));
+        assert_eq!(decode_path("/%zz%4"), Path::new("/%zz%4"));
+    }
+
+    #[test]
+    fn round_trips_non_utf8_paths() {
+        let path = PathBuf::from(OsString::from_vec(b"/tmp/caf\xe9 \xff.txt".to_vec()));
+        let encoded = encode_path(&path);
+        assert_eq!(encoded, "/tmp/caf%E9%20%FF.txt");
+        assert_eq!(decode_path(&encoded), path);
+    }
+
+    #[test]
+    fn parses_info_files() {
+        let trash = fixture_trash("parse");
+        fs::write(trash.join("files/notes.txt"), "hello").unwrap();
+        let info_path = trash.join("info/notes.txt.trashinfo");
+        fs::write(
+            &info_path,
+            "[Trash Info]\nPath=/home/me/my%20notes.txt\nDeletionDate=2024-03-01T12:30:00\n",
+        )
+        .unwrap();
+
+        let item = parse_trash_info(&trash, None, &info_path).unwrap();
+        assert_eq!(item.name, "notes.txt");
+        assert_eq!(item.original_path, Path::new("/home/me/my notes.txt"));
+        assert_eq!(item.display_name(), "my notes.txt");
+        assert_eq!(item.trashed_path, trash.join("files/notes.txt"));
+        assert_eq!(
+            item.deletion_date,
+            chrono::NaiveDate::from_ymd_opt(2024, 3, 1).and_then(|d| d.and_hms_opt(12, 30, 0))
+        );
+        assert!(!item.is_dir);
+        assert_eq!(item.size, 5);
+        fs::remove_dir_all(&trash).unwrap();
+    }
+
+    #[test]
+    fn parses_mount_trash_paths_relative_to_the_topdir() {
+        let trash = fixture_trash("topdir");
+        let info_path = trash.join("info/photos.trashinfo");
+        fs::write(&info_path, "[Trash Info]\nPath=pics/photos\n").unwrap();
+
+        let item = parse_trash_info(&trash, Some(Path::new("/mnt/usb")), &info_path).unwrap();
+        assert_eq!(item.original_path, Path::new("/mnt/usb/pics/photos"));
+        assert_eq!(item.deletion_date, None);
+        fs::remove_dir_all(&trash).unwrap();
+    }
+
+    #[test]
+    fn ignores_keys_outside_the_trash_info_section() {
+        let trash = fixture_trash("section");
+        let info_path = trash.join("info/x.trashinfo");
+        fs::write(&info_path, "[Other]\nPath=/x\n").unwrap();
+        assert!(parse_trash_info(&trash, None, &info_path).is_none());
+        fs::remove_dir_all(&trash).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_bytes_of_non_utf8_names() {
+        let trash = fixture_trash("bytes");
+        let name = OsString::from_vec(b"caf\xe9".to_vec());
+        let info_path = trash.join("info").join(info_file_name(&name));
+        fs::write(&info_path, "[Trash Info]\nPath=/tmp/caf%E9\n").unwrap();
+
+        let item = parse_trash_info(&trash, None, &info_path).unwrap();
+        assert_eq!(item.trashed_path, trash.join("files").join(&name));
+        assert_eq!(item.original_path, Path::new("/tmp").join(&name));
+        fs::remove_dir_all(&trash).unwrap();
+    }
+
+    #[test]
+    fn reserves_unique_names() {
+        let trash = fixture_trash("reserve");
+        let (info_dir, files_dir) = (trash.join("info"), trash.join("files"));
+        let path = Path::new("/somewhere/report.tar.gz");
+
+        let (first, _) = reserve_info_file(&info_dir, &files_dir, path).unwrap();
+        assert_eq!(first, "report.tar.gz");
+        let (second, _) = reserve_info_file(&info_dir, &files_dir, path).unwrap();
+        assert_eq!(second, "report.tar (2).gz");
+        // An item left in files/ without its info file also takes a name
+        fs::write(files_dir.join("report.tar (3).gz"), "").unwrap();
+        let (third, _) = reserve_info_file(&info_dir, &files_dir, path).unwrap();
+        assert_eq!(third, "report.tar (4).gz");
+        assert!(info_dir.join("report.tar (4).gz.trashinfo").exists());
+        fs::remove_dir_all(&trash).unwrap();
+    }
+}
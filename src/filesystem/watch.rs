@@ -0,0 +1,140 @@
+use gio::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+// ═══════════════════════════════════════════════
+//  Live Directory Watching
+// ═══════════════════════════════════════════════
+//
+// Each visible directory gets a gio `FileMonitor`. Raw monitor events
+// are collected for a short moment and delivered as one batch of
+// touched paths. Consumers then look at the disk to decide what
+// happened to each path:
+//   exists, not shown → insert
+//   exists, shown     → update
+//   missing, shown    → remove
+// which also covers renames and moves (old path gone, new path there).
+
+/// Delay used to coalesce bursts of events (builds, extractions…).
+const COALESCE_MS: u64 = 150;
+
+type ChangeCallback = Rc<dyn Fn(&[PathBuf])>;
+
+/// One directory monitor. Dropping it stops the monitor.
+struct DirWatcher {
+    monitor: gio::FileMonitor,
+    alive: Rc<Cell<bool>>,
+}
+
+impl DirWatcher {
+    fn new(dir: &Path, on_change: ChangeCallback) -> Option<Self> {
+        let monitor = match gio::File::for_path(dir)
+            .monitor_directory(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+        {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("[watch] Cannot watch {}: {}", dir.display(), e);
+                return None;
+            }
+        };
+
+        let alive = Rc::new(Cell::new(true));
+        let pending: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
+
+        {
+            let alive = alive.clone();
+            monitor.connect_changed(move |_, file, other, event| {
+                use gio::FileMonitorEvent as E;
+                // Content changes are picked up once the writer is done
+                if matches!(event, E::Changed | E::PreUnmount | E::Unmounted) {
+                    return;
+                }
+
+                let was_idle = pending.borrow().is_empty();
+                {
+                    let mut pending = pending.borrow_mut();
+                    for path in [Some(file), other]
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|f| f.path())
+                    {
+                        if !pending.contains(&path) {
+                            pending.push(path);
+                        }
+                    }
+                }
+                if !was_idle || pending.borrow().is_empty() {
+                    return;
+                }
+
+                let pending = pending.clone();
+                let alive = alive.clone();
+                let on_change = on_change.clone();
+                glib::timeout_add_local_once(Duration::from_millis(COALESCE_MS), move || {
+                    let paths = std::mem::take(&mut *pending.borrow_mut());
+                    if alive.get() {
+                        on_change(&paths);
+                    }
+                });
+            });
+        }
+
+        Some(Self { monitor, alive })
+    }
+}
+
+impl Drop for DirWatcher {
+    fn drop(&mut self) {
+        self.alive.set(false);
+        self.monitor.cancel();
+    }
+}
+
+/// The set of directories watched by one view, keyed by path.
+#[derive(Default)]
+pub struct WatchSet {
+    watchers: RefCell<HashMap<PathBuf, DirWatcher>>,
+}
+
+impl WatchSet {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+
+    /// Starts watching `dir` unless it is already watched.
+    /// `on_change` receives the paths touched inside `dir` (and `dir`
+    /// itself when it is deleted or moved away).
+    pub fn watch(&self, dir: &Path, on_change: impl Fn(&[PathBuf]) + 'static) {
+        if self.is_watching(dir) {
+            return;
+        }
+        if let Some(watcher) = DirWatcher::new(dir, Rc::new(on_change)) {
+            self.watchers
+                .borrow_mut()
+                .insert(dir.to_path_buf(), watcher);
+        }
+    }
+
+    pub fn is_watching(&self, dir: &Path) -> bool {
+        self.watchers.borrow().contains_key(dir)
+    }
+
+    /// Stops watching every directory for which `keep` returns false.
+    pub fn retain(&self, keep: impl Fn(&Path) -> bool) {
+        let removed: Vec<DirWatcher> = {
+            let mut watchers = self.watchers.borrow_mut();
+            let gone: Vec<PathBuf> = watchers.keys().filter(|p| !keep(p)).cloned().collect();
+            gone.iter().filter_map(|p| watchers.remove(p)).collect()
+        };
+        // Dropped outside the borrow: callbacks may touch the set again
+        drop(removed);
+    }
+
+    pub fn clear(&self) {
+        let removed = std::mem::take(&mut *self.watchers.borrow_mut());
+        drop(removed);
+    }
+}
@@ -2,6 +2,7 @@ mod config;
 mod core;
 mod filesystem;
 mod thumbnail;
+mod transfer;
 mod ui;
 
 use gtk4::prelude::*;
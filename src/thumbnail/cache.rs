@@ -89,7 +89,6 @@ impl ThumbnailCache {
     }
 
     /// Total number of cached thumbnails (for diagnostics / settings UI).
-    #[allow(dead_code)]
     pub fn entry_count(&self) -> usize {
         fs::read_dir(&self.cache_dir)
             .map(|rd| rd.count())
@@ -97,7 +96,6 @@ impl ThumbnailCache {
     }
 
     /// Deletes all cached thumbnails.
-    #[allow(dead_code)]
     pub fn clear(&self) {
         if let Ok(rd) = fs::read_dir(&self.cache_dir) {
             for entry in rd.flatten() {
@@ -167,16 +167,11 @@ pub fn generate_video_thumbnail(source: &Path, out_path: &Path, width: u32, heig
 
 // ─── Convenience ───
 
-/// Auto-dispatches to the correct generator based on extension.
-pub fn generate_thumbnail(source: &Path, out_path: &Path) -> bool {
-    let ext = source
-        .extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-
-    if super::is_thumbable_image(&ext) {
+/// Auto-dispatches to the correct generator based on the MIME type.
+pub fn generate_thumbnail(source: &Path, mime: &str, out_path: &Path) -> bool {
+    if super::is_thumbable_image(mime) {
         generate_image_thumbnail(source, out_path, THUMB_WIDTH, THUMB_HEIGHT)
-    } else if super::is_thumbable_video(&ext) {
+    } else if super::is_thumbable_video(mime) {
         generate_video_thumbnail(source, out_path, THUMB_WIDTH, THUMB_HEIGHT)
     } else {
         false
@@ -19,15 +19,27 @@ pub use worker::request_thumbnail;
 pub const THUMB_WIDTH: u32 = 192;
 pub const THUMB_HEIGHT: u32 = 192;
 
-/// File extensions that support thumbnail generation.
-pub fn supports_thumbnail(ext: &str) -> bool {
-    is_thumbable_image(ext) || is_thumbable_video(ext)
+/// MIME types that support thumbnail generation.
+pub fn supports_thumbnail(mime: &str) -> bool {
+    is_thumbable_image(mime) || is_thumbable_video(mime)
 }
 
-pub fn is_thumbable_image(ext: &str) -> bool {
-    matches!(ext, "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "ico")
+pub fn is_thumbable_image(mime: &str) -> bool {
+    matches!(
+        mime,
+        "image/png"
+            | "image/jpeg"
+            | "image/gif"
+            | "image/bmp"
+            | "image/webp"
+            | "image/vnd.microsoft.icon"
+            | "image/x-icon"
+    )
 }
 
-pub fn is_thumbable_video(ext: &str) -> bool {
-    matches!(ext, "mp4" | "mkv" | "avi" | "mov" | "webm")
+pub fn is_thumbable_video(mime: &str) -> bool {
+    matches!(
+        mime,
+        "video/mp4" | "video/x-matroska" | "video/x-msvideo" | "video/quicktime" | "video/webm"
+    )
 }
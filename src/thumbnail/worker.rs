@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
 
 use glib::object::ObjectExt;
 use gtk4::gdk_pixbuf::Pixbuf;
@@ -17,9 +18,13 @@ use super::generator;
 //
 //   1. Check the disk cache — if a fresh thumbnail exists, load it
 //      synchronously (fast path, just a PNG read).
-//   2. Otherwise, spawn the heavy work (decode + resize / FFmpeg)
-//      on a background **std thread**, and once finished, schedule
-//      a UI update on the main thread with `glib::idle_add_local_once`.
+//   2. Otherwise, queue the heavy work (decode + resize / FFmpeg)
+//      for a small pool of background **std threads**, and once
+//      finished, schedule a UI update on the main thread.
+//
+// The queue is last-in-first-out: the newest requests belong to the
+// items currently on screen. Requests whose `Image` was dropped in the
+// meantime (scrolled away in a virtualized view) are skipped.
 //
 // The caller receives an `Image` widget immediately. It starts as
 // a placeholder icon and swaps to the thumbnail once ready.
@@ -28,27 +33,72 @@ use super::generator;
 // (a thread-safe wrapper around `WeakRef`) to pass the widget handle
 // safely to the background thread's idle callback.
 
+/// Number of background generator threads.
+const WORKERS: usize = 4;
+
 /// Global, lazily-initialised cache handle (thread-safe).
 fn cache() -> &'static ThumbnailCache {
     static INSTANCE: OnceLock<ThumbnailCache> = OnceLock::new();
     INSTANCE.get_or_init(ThumbnailCache::new)
 }
 
-/// Request a thumbnail for `source_path`.
+/// One pending generation job.
+struct Job {
+    source: PathBuf,
+    mime: String,
+    dest: PathBuf,
+    /// Cleared when the requesting `Image` is destroyed.
+    wanted: Arc<AtomicBool>,
+    on_done: Box<dyn FnOnce(bool) + Send>,
+}
+
+/// Shared LIFO job queue, lazily started with its worker threads.
+fn queue() -> &'static (Mutex<Vec<Job>>, Condvar) {
+    static QUEUE: OnceLock<(Mutex<Vec<Job>>, Condvar)> = OnceLock::new();
+    QUEUE.get_or_init(|| {
+        for _ in 0..WORKERS {
+            std::thread::spawn(worker_loop);
+        }
+        (Mutex::new(Vec::new()), Condvar::new())
+    })
+}
+
+fn worker_loop() {
+    let (jobs, ready) = queue();
+    loop {
+        let job = {
+            let mut jobs = jobs.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                if let Some(job) = jobs.pop() {
+                    break job;
+                }
+                jobs = ready.wait(jobs).unwrap_or_else(|e| e.into_inner());
+            }
+        };
+        if !job.wanted.load(Ordering::Relaxed) {
+            continue;
+        }
+        let ok = generator::generate_thumbnail(&job.source, &job.mime, &job.dest);
+        (job.on_done)(ok);
+    }
+}
+
+fn enqueue(job: Job) {
+    let (jobs, ready) = queue();
+    jobs.lock().unwrap_or_else(|e| e.into_inner()).push(job);
+    ready.notify_one();
+}
+
+/// Request a thumbnail for `source_path`, a file of type `mime`.
 ///
 /// Returns an `Image` widget that will initially show a placeholder icon.
 /// When the thumbnail is ready (cache hit or freshly generated) it will
 /// be swapped in automatically on the GTK main thread.
 ///
 /// `icon_size` controls the pixel size of the placeholder while waiting.
-pub fn request_thumbnail(source_path: &Path, icon_size: i32) -> Image {
-    let ext = source_path
-        .extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-
+pub fn request_thumbnail(source_path: &Path, mime: &str, icon_size: i32) -> Image {
     // Determine the right placeholder icon
-    let placeholder_icon = if super::is_thumbable_video(&ext) {
+    let placeholder_icon = if super::is_thumbable_video(mime) {
         "video-x-generic-symbolic"
     } else {
         "image-x-generic-symbolic"
@@ -72,39 +122,50 @@ pub fn request_thumbnail(source_path: &Path, icon_size: i32) -> Image {
     }
 
     // ── Slow path: generate in background ──
-    let source = source_path.to_path_buf();
     let thumb_dest = cache().thumb_path(source_path);
     let pixel_size = icon_size;
 
+    // Scrolling away drops the image; its job is then skipped
+    let wanted = Arc::new(AtomicBool::new(true));
+    {
+        let wanted = wanted.clone();
+        image.connect_destroy(move |_| wanted.store(false, Ordering::Relaxed));
+    }
+
     // `SendWeakRef` is a Send+Sync wrapper around glib::WeakRef.
-    // It can be safely moved into a std::thread closure.
+    // It can be safely moved into the worker's completion closure.
     let send_weak: glib::SendWeakRef<Image> = image.downgrade().into();
-
-    std::thread::spawn(move || {
-        let ok = generator::generate_thumbnail(&source, &thumb_dest);
-
-        // Schedule UI update on the main GTK thread.
-        // `MainContext::default().invoke()` is the thread-safe way to
-        // dispatch a closure to the GLib main loop from any thread.
-        glib::MainContext::default().invoke(move || {
-            let Some(image) = send_weak.upgrade() else {
-                return; // widget was dropped
-            };
-
-            if ok {
-                if let Some(pb) = load_pixbuf_scaled(&thumb_dest, pixel_size) {
-                    image.set_from_pixbuf(Some(&pb));
-                    image.remove_css_class("thumbnail-placeholder");
-                    image.add_css_class("thumbnail-loaded");
-                    return;
+    let dest = thumb_dest.clone();
+
+    enqueue(Job {
+        source: source_path.to_path_buf(),
+        mime: mime.to_string(),
+        dest: thumb_dest,
+        wanted,
+        on_done: Box::new(move |ok| {
+            // Schedule UI update on the main GTK thread.
+            // `MainContext::default().invoke()` is the thread-safe way to
+            // dispatch a closure to the GLib main loop from any thread.
+            glib::MainContext::default().invoke(move || {
+                let Some(image) = send_weak.upgrade() else {
+                    return; // widget was dropped
+                };
+
+                if ok {
+                    if let Some(pb) = load_pixbuf_scaled(&dest, pixel_size) {
+                        image.set_from_pixbuf(Some(&pb));
+                        image.remove_css_class("thumbnail-placeholder");
+                        image.add_css_class("thumbnail-loaded");
+                        return;
+                    }
                 }
-            }
 
-            // Generation failed — show error icon so the user knows
-            image.set_icon_name(Some("dialog-warning-symbolic"));
-            image.remove_css_class("thumbnail-placeholder");
-            image.add_css_class("thumbnail-error");
-        });
+                // Generation failed — show error icon so the user knows
+                image.set_icon_name(Some("dialog-warning-symbolic"));
+                image.remove_css_class("thumbnail-placeholder");
+                image.add_css_class("thumbnail-error");
+            });
+        }),
     });
 
     image
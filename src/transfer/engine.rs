@@ -0,0 +1,431 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use crate::filesystem::unique_path;
+
+use super::job::Control;
+use super::{
+    ConflictChoice, Resolution, TransferEvent, TransferKind, TransferProgress, TransferReport,
+};
+
+// ═══════════════════════════════════════════════
+//  Copy / Move Engine (background thread)
+// ═══════════════════════════════════════════════
+//
+//   1. Plan   — walk every source once to get file + byte totals.
+//   2. Run    — recurse into directories, copy files in 256 KiB chunks
+//               so progress, pause and cancel stay responsive.
+//   3. Moves  — try `rename` first; when that fails (e.g. EXDEV across
+//               filesystems) fall back to copy + delete.
+//
+// Existing directories are merged silently. Existing files raise a
+// `Conflict` event and block until the UI answers through the
+// resolution channel. An item copied into its own folder gets a new
+// name up front instead.
+
+/// Chunk size for streamed file copies.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Minimum delay between two progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Returned up the call stack when the job was cancelled.
+struct Cancelled;
+
+type Step = Result<(), Cancelled>;
+
+struct Run<'a> {
+    kind: TransferKind,
+    control: &'a Control,
+    answers: &'a Receiver<Resolution>,
+    emit: &'a dyn Fn(TransferEvent),
+    progress: TransferProgress,
+    report: TransferReport,
+    sticky: Option<ConflictChoice>,
+    /// Final destination of the top-level item being transferred
+    /// (differs from the plain join when "Keep Both" renamed it).
+    top_dest: Option<PathBuf>,
+    last_emit: Instant,
+}
+
+/// Executes a whole job. Always finishes with a `Finished` event.
+pub(super) fn run(
+    kind: TransferKind,
+    sources: &[PathBuf],
+    dest_dir: &Path,
+    control: &Control,
+    answers: &Receiver<Resolution>,
+    emit: &dyn Fn(TransferEvent),
+) {
+    let mut progress = TransferProgress::default();
+    for src in sources {
+        let (files, bytes) = tree_size(src);
+        progress.files_total += files;
+        progress.bytes_total += bytes;
+    }
+
+    let mut run = Run {
+        kind,
+        control,
+        answers,
+        emit,
+        progress,
+        report: TransferReport::default(),
+        sticky: None,
+        top_dest: None,
+        last_emit: Instant::now(),
+    };
+    run.emit_progress(true);
+
+    let mut outcome = Ok(());
+    for src in sources {
+        outcome = run.transfer_top(src, dest_dir);
+        if outcome.is_err() {
+            break;
+        }
+    }
+
+    run.report.cancelled = outcome.is_err();
+    run.report.files_done = run.progress.files_done;
+    run.report.bytes_done = run.progress.bytes_done;
+    run.emit_progress(true);
+    (run.emit)(TransferEvent::Finished(run.report));
+}
+
+impl Run<'_> {
+    // ─── Top Level ───
+
+    fn transfer_top(&mut self, src: &Path, dest_dir: &Path) -> Step {
+        let Some(name) = src.file_name() else {
+            self.fail(src, "source has no file name");
+            return Ok(());
+        };
+        if dest_dir.starts_with(src) {
+            self.fail(src, "cannot copy a folder into itself");
+            return Ok(());
+        }
+        let mut dest = dest_dir.join(name);
+        if dest == src {
+            if self.kind == TransferKind::Move {
+                // Moving onto itself is a no-op
+                self.skip(src);
+                return Ok(());
+            }
+            // Copying into its own folder keeps both, like "Keep Both"
+            dest = unique_path(&dest);
+        }
+        self.top_dest = None;
+        self.transfer(src, &dest)?;
+
+        // Record fully completed items (a partial move leaves the source behind)
+        if let Some(dest) = self.top_dest.take() {
+            let done = fs::symlink_metadata(&dest).is_ok()
+                && (self.kind == TransferKind::Copy || fs::symlink_metadata(src).is_err());
+            if done {
+                self.report.transferred.push((src.to_path_buf(), dest));
+            }
+        }
+        Ok(())
+    }
+
+    // ─── Recursive Transfer ───
+
+    fn transfer(&mut self, src: &Path, dest: &Path) -> Step {
+        self.checkpoint()?;
+
+        let meta = match fs::symlink_metadata(src) {
+            Ok(m) => m,
+            Err(e) => {
+                self.fail(src, &e.to_string());
+                return Ok(());
+            }
+        };
+
+        if meta.is_dir() {
+            self.transfer_dir(src, dest)
+        } else {
+            self.transfer_file(src, dest, meta.len(), meta.file_type().is_symlink())
+        }
+    }
+
+    fn transfer_dir(&mut self, src: &Path, dest: &Path) -> Step {
+        let mut dest = dest.to_path_buf();
+
+        match fs::symlink_metadata(&dest) {
+            // Existing folder → merge contents
+            Ok(m) if m.is_dir() => {}
+            // A file is in the way → ask
+            Ok(_) => match self.resolve_conflict(src, &dest)? {
+                ConflictChoice::Skip => {
+                    self.skip(src);
+                    return Ok(());
+                }
+                ConflictChoice::Rename => dest = unique_path(&dest),
+                _ => {
+                    if let Err(e) = fs::remove_file(&dest) {
+                        self.fail(&dest, &e.to_string());
+                        return Ok(());
+                    }
+                }
+            },
+            Err(_) => {}
+        }
+        self.top_dest.get_or_insert_with(|| dest.clone());
+
+        // Fast path: same-filesystem move of a whole tree
+        if self.kind == TransferKind::Move && !dest.exists() {
+            let (files, bytes) = tree_size(src);
+            if fs::rename(src, &dest).is_ok() {
+                self.advance(files, bytes);
+                return Ok(());
+            }
+        }
+
+        if !dest.exists() {
+            if let Err(e) = fs::create_dir(&dest) {
+                self.fail(&dest, &e.to_string());
+                return Ok(());
+            }
+        }
+
+        let children = match fs::read_dir(src) {
+            Ok(rd) => rd.flatten().collect::<Vec<_>>(),
+            Err(e) => {
+                self.fail(src, &e.to_string());
+                return Ok(());
+            }
+        };
+        for child in children {
+            self.transfer(&child.path(), &dest.join(child.file_name()))?;
+        }
+
+        if self.kind == TransferKind::Move {
+            // Only succeeds once every child was moved out
+            let _ = fs::remove_dir(src);
+        }
+        Ok(())
+    }
+
+    fn transfer_file(&mut self, src: &Path, dest: &Path, size: u64, is_symlink: bool) -> Step {
+        let mut dest = dest.to_path_buf();
+
+        if fs::symlink_metadata(&dest).is_ok() {
+            match self.resolve_conflict(src, &dest)? {
+                ConflictChoice::Skip => {
+                    self.skip(src);
+                    return Ok(());
+                }
+                ConflictChoice::Rename => dest = unique_path(&dest),
+                _ if dest == src => {
+                    // Overwriting a file with itself would truncate it
+                    self.skip(src);
+                    return Ok(());
+                }
+                _ => {
+                    let removed = if dest.is_dir() {
+                        fs::remove_dir_all(&dest)
+                    } else {
+                        fs::remove_file(&dest)
+                    };
+                    if let Err(e) = removed {
+                        self.fail(&dest, &e.to_string());
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        self.top_dest.get_or_insert_with(|| dest.clone());
+        self.progress.current = Some(src.to_path_buf());
+
+        if self.kind == TransferKind::Move && fs::rename(src, &dest).is_ok() {
+            self.advance(1, size);
+            return Ok(());
+        }
+
+        let copied = if is_symlink {
+            copy_symlink(src, &dest)
+        } else {
+            self.copy_file_chunked(src, &dest)?
+        };
+        if let Err(e) = copied {
+            self.fail(src, &e.to_string());
+            return Ok(());
+        }
+
+        if self.kind == TransferKind::Move {
+            if let Err(e) = fs::remove_file(src) {
+                self.fail(src, &e.to_string());
+            }
+        }
+
+        self.advance(1, if is_symlink { size } else { 0 });
+        Ok(())
+    }
+
+    /// Streams `src` into `dest`, updating byte progress per chunk.
+    /// A cancelled copy removes the partial destination file.
+    fn copy_file_chunked(
+        &mut self,
+        src: &Path,
+        dest: &Path,
+    ) -> Result<std::io::Result<()>, Cancelled> {
+        let reader = match fs::File::open(src) {
+            Ok(f) => f,
+            Err(e) => return Ok(Err(e)),
+        };
+        let writer = match fs::File::create(dest) {
+            Ok(f) => f,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let copied = self.copy_chunks(reader, writer);
+        if !matches!(copied, Ok(Ok(()))) {
+            // Don't leave a partial copy behind
+            let _ = fs::remove_file(dest);
+            return copied;
+        }
+
+        // Keep the permission bits of the original
+        if let Ok(meta) = fs::metadata(src) {
+            let _ = fs::set_permissions(dest, meta.permissions());
+        }
+        Ok(Ok(()))
+    }
+
+    /// Streams `reader` into `writer`, reporting progress per chunk.
+    fn copy_chunks(
+        &mut self,
+        mut reader: fs::File,
+        mut writer: fs::File,
+    ) -> Result<std::io::Result<()>, Cancelled> {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        loop {
+            self.checkpoint()?;
+
+            let n = match reader.read(&mut buf) {
+                Ok(0) => return Ok(Ok(())),
+                Ok(n) => n,
+                Err(e) => return Ok(Err(e)),
+            };
+            if let Err(e) = writer.write_all(&buf[..n]) {
+                return Ok(Err(e));
+            }
+            self.progress.bytes_done += n as u64;
+            self.emit_progress(false);
+        }
+    }
+
+    // ─── Conflicts ───
+
+    fn resolve_conflict(&mut self, src: &Path, dest: &Path) -> Result<ConflictChoice, Cancelled> {
+        if let Some(choice) = self.sticky {
+            return Ok(choice);
+        }
+
+        self.emit_progress(true);
+        (self.emit)(TransferEvent::Conflict {
+            source: src.to_path_buf(),
+            dest: dest.to_path_buf(),
+        });
+
+        let answer = self.answers.recv().unwrap_or(Resolution {
+            choice: ConflictChoice::Cancel,
+            apply_to_all: false,
+        });
+        if answer.choice == ConflictChoice::Cancel || self.is_cancelled() {
+            return Err(Cancelled);
+        }
+        if answer.apply_to_all {
+            self.sticky = Some(answer.choice);
+        }
+        Ok(answer.choice)
+    }
+
+    // ─── Flow Control ───
+
+    fn is_cancelled(&self) -> bool {
+        self.control.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Blocks while paused; errors out once cancelled.
+    fn checkpoint(&self) -> Step {
+        while self.control.paused.load(Ordering::SeqCst) && !self.is_cancelled() {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    // ─── Bookkeeping ───
+
+    fn advance(&mut self, files: u64, bytes: u64) {
+        self.progress.files_done += files;
+        self.progress.bytes_done += bytes;
+        self.emit_progress(false);
+    }
+
+    fn skip(&mut self, src: &Path) {
+        let (files, bytes) = tree_size(src);
+        self.report.skipped += files.max(1);
+        // Skipped work still counts towards the progress bar
+        self.progress.files_total = self.progress.files_total.saturating_sub(files);
+        self.progress.bytes_total = self.progress.bytes_total.saturating_sub(bytes);
+        self.emit_progress(false);
+    }
+
+    fn fail(&mut self, path: &Path, message: &str) {
+        eprintln!("[transfer] {}: {}", path.display(), message);
+        self.report
+            .errors
+            .push(format!("{}: {}", path.display(), message));
+    }
+
+    fn emit_progress(&mut self, force: bool) {
+        if force || self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.last_emit = Instant::now();
+            (self.emit)(TransferEvent::Progress(self.progress.clone()));
+        }
+    }
+}
+
+// ─── Helpers ───
+
+/// Counts regular files (and symlinks) plus their total size below `path`.
+fn tree_size(path: &Path) -> (u64, u64) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if !meta.is_dir() {
+        return (1, meta.len());
+    }
+    let mut totals = (0, 0);
+    if let Ok(rd) = fs::read_dir(path) {
+        for entry in rd.flatten() {
+            let (f, b) = tree_size(&entry.path());
+            totals.0 += f;
+            totals.1 += b;
+        }
+    }
+    totals
+}
+
+/// Recreates a symlink instead of following it.
+fn copy_symlink(src: &Path, dest: &Path) -> std::io::Result<()> {
+    let target = fs::read_link(src)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&target, dest)
+    }
+    #[cfg(not(unix))]
+    {
+        fs::copy(&target, dest).map(|_| ())
+    }
+}
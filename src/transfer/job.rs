@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+
+use super::engine;
+use super::{ConflictChoice, Resolution, TransferEvent, TransferKind};
+use crate::core::worker::{self, WorkerEvent};
+
+// ═══════════════════════════════════════════════
+//  Transfer Jobs & Main-Loop Hand-off
+// ═══════════════════════════════════════════════
+//
+// The engine runs on a `core::worker` thread and emits `TransferEvent`s
+// to the GLib main loop. A job is not cancelled through the worker
+// handle: the engine stops on its own `Control` flag and still reports
+// what it did in `Finished`.
+
+/// Flags shared between the UI and the engine thread.
+pub(super) struct Control {
+    pub cancelled: AtomicBool,
+    pub paused: AtomicBool,
+    resolutions: Mutex<Sender<Resolution>>,
+}
+
+/// UI-side handle to a running job. Cheap to clone.
+#[derive(Clone)]
+pub struct TransferHandle {
+    control: Arc<Control>,
+}
+
+impl TransferHandle {
+    /// Aborts the job. A pending conflict prompt is answered with "Cancel".
+    pub fn cancel(&self) {
+        self.control.cancelled.store(true, Ordering::SeqCst);
+        self.control.paused.store(false, Ordering::SeqCst);
+        self.resolve(Resolution {
+            choice: ConflictChoice::Cancel,
+            apply_to_all: false,
+        });
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.control.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::SeqCst)
+    }
+
+    /// Answers the conflict the engine is currently blocked on.
+    pub fn resolve(&self, resolution: Resolution) {
+        if let Ok(tx) = self.control.resolutions.lock() {
+            let _ = tx.send(resolution);
+        }
+    }
+}
+
+impl WorkerEvent for TransferEvent {
+    fn is_final(&self) -> bool {
+        matches!(self, TransferEvent::Finished(_))
+    }
+}
+
+/// Starts copying / moving `sources` into `dest_dir` on a background thread.
+///
+/// Must be called from the GTK main thread. `on_event` is invoked on the
+/// main thread for every progress update, conflict and the final report.
+pub fn start_transfer(
+    kind: TransferKind,
+    sources: Vec<PathBuf>,
+    dest_dir: PathBuf,
+    on_event: impl Fn(TransferEvent) + 'static,
+) -> TransferHandle {
+    let (tx, rx) = mpsc::channel();
+    let control = Arc::new(Control {
+        cancelled: AtomicBool::new(false),
+        paused: AtomicBool::new(false),
+        resolutions: Mutex::new(tx),
+    });
+
+    let control_c = control.clone();
+    worker::spawn(on_event, move |emitter| {
+        let emit = |event: TransferEvent| emitter.emit(event);
+        engine::run(kind, &sources, &dest_dir, &control_c, &rx, &emit);
+    });
+
+    TransferHandle { control }
+}
@@ -0,0 +1,99 @@
+// ─── Transfer Module ───
+// Background copy / move engine with progress, pause, cancel and
+// per-item conflict resolution.
+//
+// Architecture:
+//   engine.rs — Runs on a std thread: plans the job, copies in chunks,
+//               falls back to copy + delete for cross-filesystem moves
+//   job.rs    — `TransferHandle` (cancel / pause / answer conflicts) and
+//               the hand-off of events to the GTK main loop via
+//               `core::worker`
+
+mod engine;
+pub mod job;
+
+use std::path::PathBuf;
+
+pub use job::start_transfer;
+
+/// Copy leaves the sources in place, Move removes them afterwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferKind {
+    Copy,
+    Move,
+}
+
+impl TransferKind {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            TransferKind::Copy => "Copying",
+            TransferKind::Move => "Moving",
+        }
+    }
+}
+
+/// How to handle a destination that already exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Overwrite,
+    Skip,
+    /// Keep both: the new item gets a "name (2).ext" style name.
+    Rename,
+    /// Abort the whole job.
+    Cancel,
+}
+
+/// The user's answer to a conflict prompt.
+#[derive(Clone, Copy, Debug)]
+pub struct Resolution {
+    pub choice: ConflictChoice,
+    /// Reuse `choice` for every later conflict of this job.
+    pub apply_to_all: bool,
+}
+
+/// Running totals, streamed to the UI while the job runs.
+#[derive(Clone, Debug, Default)]
+pub struct TransferProgress {
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+    pub current: Option<PathBuf>,
+}
+
+impl TransferProgress {
+    /// Completion ratio in `0.0..=1.0`, by bytes (falls back to file count).
+    pub fn fraction(&self) -> f64 {
+        if self.bytes_total > 0 {
+            (self.bytes_done as f64 / self.bytes_total as f64).min(1.0)
+        } else if self.files_total > 0 {
+            (self.files_done as f64 / self.files_total as f64).min(1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Final outcome of a job.
+#[derive(Clone, Debug, Default)]
+pub struct TransferReport {
+    pub files_done: u64,
+    pub bytes_done: u64,
+    pub skipped: u64,
+    /// Top-level `(source, destination)` pairs that completed.
+    pub transferred: Vec<(PathBuf, PathBuf)>,
+    pub errors: Vec<String>,
+    pub cancelled: bool,
+}
+
+/// Everything the engine reports back to the main thread.
+#[derive(Clone, Debug)]
+pub enum TransferEvent {
+    Progress(TransferProgress),
+    /// The engine is blocked until `TransferHandle::resolve` is called.
+    Conflict {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+    Finished(TransferReport),
+}
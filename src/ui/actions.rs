@@ -0,0 +1,167 @@
+use crate::config::{Action, Keymap};
+use crate::ui::keyboard;
+use gtk4::prelude::*;
+use gtk4::{ApplicationWindow, Widget};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Action Registry
+// ═══════════════════════════════════════════════
+//
+// Every command is a `gio::SimpleAction` on its window
+// ("win.toggle-hidden"), recorded in the window's `Registry` together
+// with the title the command palette lists it under. Key bindings, the
+// hamburger menu, the context menus, the settings panel and the palette
+// all run commands by activating these actions, so each window's
+// commands act on that window's panes.
+//
+//   add         plain command
+//   add_motion  cursor movement; a boolean parameter extends the selection
+//   add_choice  pick one of several values ("win.theme" with "Nord"),
+//               listed once per choice
+
+/// One command palette entry.
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// Detailed action name, e.g. "win.theme".
+    pub action: String,
+    pub target: Option<glib::Variant>,
+    pub title: String,
+    /// Display forms of the keys bound to it.
+    pub keys: Vec<String>,
+}
+
+/// The commands registered on one window.
+#[derive(Default)]
+pub struct Registry {
+    commands: RefCell<Vec<Command>>,
+}
+
+/// The action name of a bindable `Action` ("go-parent").
+pub fn action_name(action: Action) -> String {
+    action.name().replace('_', "-")
+}
+
+impl Registry {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+
+    /// Registers `win.<name>`, listed as `title`.
+    pub fn add(
+        &self,
+        window: &ApplicationWindow,
+        name: &str,
+        title: &str,
+        keys: Vec<String>,
+        run: impl Fn() + 'static,
+    ) {
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| run());
+        window.add_action(&action);
+        self.push(name, None, title, keys);
+    }
+
+    /// Registers a movement `win.<name>`; its parameter says whether to
+    /// extend the selection (the palette never does).
+    pub fn add_motion(
+        &self,
+        window: &ApplicationWindow,
+        name: &str,
+        title: &str,
+        keys: Vec<String>,
+        run: impl Fn(bool) + 'static,
+    ) {
+        let action = gio::SimpleAction::new(name, Some(glib::VariantTy::BOOLEAN));
+        action.connect_activate(move |_, param| {
+            run(param.and_then(|p| p.get::<bool>()).unwrap_or(false));
+        });
+        window.add_action(&action);
+        self.push(name, Some(false.to_variant()), title, keys);
+    }
+
+    /// Registers `win.<name>` taking one of `choices` (value, title).
+    pub fn add_choice(
+        &self,
+        window: &ApplicationWindow,
+        name: &str,
+        choices: &[(String, String)],
+        run: impl Fn(&str) + 'static,
+    ) {
+        let action = gio::SimpleAction::new(name, Some(glib::VariantTy::STRING));
+        action.connect_activate(move |_, param| {
+            if let Some(value) = param.and_then(|p| p.str()) {
+                run(value);
+            }
+        });
+        window.add_action(&action);
+        for (value, title) in choices {
+            self.push(name, Some(value.to_variant()), title, Vec::new());
+        }
+    }
+
+    fn push(&self, name: &str, target: Option<glib::Variant>, title: &str, keys: Vec<String>) {
+        self.commands.borrow_mut().push(Command {
+            action: format!("win.{}", name),
+            target,
+            title: title.to_string(),
+            keys,
+        });
+    }
+
+    /// Every registered command, in registration order.
+    pub fn commands(&self) -> Vec<Command> {
+        self.commands.borrow().clone()
+    }
+}
+
+/// Tells GTK which keys run which `win.` action, so anything that shows
+/// accelerators (menus, accessibility) lists the configured keys.
+///
+/// The window's key controller still does the dispatching: it handles
+/// sequences ("g g"), keeps plain keys to the pane and leaves text
+/// entries their keys. GTK's own application shortcuts would run before
+/// it in the capture phase, so they are switched off on `window`.
+pub fn register_accels(window: &ApplicationWindow, keymap: &Keymap) {
+    let Some(app) = window.application() else {
+        return;
+    };
+    for action in Action::all() {
+        let name = action_name(action);
+        let detailed = match keyboard::motion(action) {
+            Some(_) => format!("win.{}(false)", name),
+            None => format!("win.{}", name),
+        };
+        let accels = keymap.accels(action);
+        let accels: Vec<&str> = accels.iter().map(String::as_str).collect();
+        app.set_accels_for_action(&detailed, &accels);
+    }
+
+    let controllers = window.observe_controllers();
+    for controller in controllers.iter::<gtk4::EventController>().flatten() {
+        if controller.name().as_deref() == Some("gtk-application-shortcuts") {
+            controller.set_propagation_phase(gtk4::PropagationPhase::None);
+        }
+    }
+}
+
+/// Runs `win.<name>` from `widget` (anything inside the window).
+pub fn activate(widget: &impl IsA<Widget>, name: &str, target: Option<&glib::Variant>) {
+    let detailed = format!("win.{}", name);
+    if let Err(e) = widget.activate_action(&detailed, target) {
+        eprintln!("[actions] Cannot run {}: {}", detailed, e);
+    }
+}
+
+/// Runs the action a key is bound to.
+pub fn activate_bound(widget: &impl IsA<Widget>, action: Action, extend: bool) {
+    let target = keyboard::motion(action).map(|_| extend.to_variant());
+    activate(widget, &action_name(action), target.as_ref());
+}
+
+/// Runs a palette entry.
+pub fn run_command(widget: &impl IsA<Widget>, command: &Command) {
+    let name = command.action.trim_start_matches("win.");
+    activate(widget, name, command.target.as_ref());
+}
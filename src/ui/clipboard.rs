@@ -0,0 +1,246 @@
+use crate::config::AppConfig;
+use crate::transfer::TransferReport;
+use crate::ui::pane::{self, Pane};
+use gtk4::gdk;
+use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  File Clipboard
+// ═══════════════════════════════════════════════
+//
+// Cut / copy put the selected files on the GDK clipboard in the formats
+// other file managers exchange:
+//
+//   x-special/gnome-copied-files   "copy" or "cut", then one URI per line
+//                                  (Nautilus, Nemo, Caja)
+//   text/uri-list                  URIs, CRLF-terminated (always a copy)
+//   text/plain                     paths, for pasting into text fields
+//
+// Paste reads the first of those it finds and hands the files to the
+// transfer engine, so conflicts are resolved like any other copy / move.
+// Files cut here are dimmed until they are pasted or another application
+// takes over the clipboard. Once a cut is pasted, the files that were
+// moved leave the clipboard: it is cleared when all of them were, unless
+// something else was put on it in the meantime.
+
+const GNOME_COPIED_FILES: &str = "x-special/gnome-copied-files";
+pub const URI_LIST: &str = "text/uri-list";
+const PLAIN_TEXT: &str = "text/plain;charset=utf-8";
+
+type CutListener = Box<dyn Fn()>;
+
+thread_local! {
+    /// Files cut to the clipboard by this process.
+    static CUT: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+    static CUT_LISTENERS: RefCell<Vec<CutListener>> = const { RefCell::new(Vec::new()) };
+    static WATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Counts clipboard changes, so a finished paste can tell whether the
+    /// clipboard still holds what it pasted.
+    static CHANGES: Cell<u64> = const { Cell::new(0) };
+}
+
+// ─── Cut State ───
+
+/// True if `path` was cut and not yet pasted.
+pub fn is_cut(path: &Path) -> bool {
+    CUT.with(|c| c.borrow().iter().any(|p| p == path))
+}
+
+/// Registers a callback fired whenever the set of cut files changes.
+pub fn connect_cut_changed(f: impl Fn() + 'static) {
+    CUT_LISTENERS.with(|l| l.borrow_mut().push(Box::new(f)));
+}
+
+fn set_cut(paths: Vec<PathBuf>) {
+    let changed = CUT.with(|c| {
+        let mut cut = c.borrow_mut();
+        if *cut == paths {
+            return false;
+        }
+        *cut = paths;
+        true
+    });
+    if changed {
+        CUT_LISTENERS.with(|l| l.borrow().iter().for_each(|f| f()));
+    }
+}
+
+/// Forgets cut files once another application owns the clipboard.
+fn watch_clipboard(clipboard: &gdk::Clipboard) {
+    if WATCHING.with(|w| w.replace(true)) {
+        return;
+    }
+    clipboard.connect_changed(|clipboard| {
+        CHANGES.with(|c| c.set(c.get() + 1));
+        if !clipboard.is_local() {
+            set_cut(Vec::new());
+        }
+    });
+}
+
+// ─── Cut / Copy ───
+
+/// Copies `paths` to the clipboard of `widget`'s display.
+pub fn copy(widget: &impl IsA<gtk4::Widget>, paths: &[PathBuf]) {
+    put(widget.as_ref(), paths, false);
+}
+
+/// Cuts `paths`: a later paste moves them.
+pub fn cut(widget: &impl IsA<gtk4::Widget>, paths: &[PathBuf]) {
+    put(widget.as_ref(), paths, true);
+}
+
+fn put(widget: &gtk4::Widget, paths: &[PathBuf], cut: bool) {
+    if paths.is_empty() {
+        return;
+    }
+    let uris: Vec<String> = paths
+        .iter()
+        .map(|p| gio::File::for_path(p).uri().to_string())
+        .collect();
+
+    let gnome = format!("{}\n{}", if cut { "cut" } else { "copy" }, uris.join("\n"));
+    let uri_list: String = uris.iter().map(|u| format!("{}\r\n", u)).collect();
+    let text = paths
+        .iter()
+        .map(|p| p.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let provider = gdk::ContentProvider::new_union(&[
+        gdk::ContentProvider::for_bytes(GNOME_COPIED_FILES, &glib::Bytes::from_owned(gnome)),
+        gdk::ContentProvider::for_bytes(URI_LIST, &glib::Bytes::from_owned(uri_list)),
+        gdk::ContentProvider::for_bytes(PLAIN_TEXT, &glib::Bytes::from_owned(text)),
+    ]);
+
+    let clipboard = widget.clipboard();
+    watch_clipboard(&clipboard);
+    if let Err(e) = clipboard.set_content(Some(&provider)) {
+        eprintln!("[clipboard] Cannot set clipboard: {}", e);
+        return;
+    }
+    set_cut(if cut { paths.to_vec() } else { Vec::new() });
+}
+
+// ─── Paste ───
+
+/// True if the clipboard holds files.
+pub fn can_paste(widget: &impl IsA<gtk4::Widget>) -> bool {
+    let formats = widget.as_ref().clipboard().formats();
+    formats.contain_mime_type(GNOME_COPIED_FILES) || formats.contain_mime_type(URI_LIST)
+}
+
+/// Pastes the clipboard's files into `dest_dir`: moves them if they were
+/// cut, copies them otherwise.
+pub fn paste(pane: &Pane, dest_dir: PathBuf, config: Rc<RefCell<AppConfig>>) {
+    let clipboard = pane.root.clipboard();
+    watch_clipboard(&clipboard);
+
+    let pane = pane.clone();
+    clipboard.read_async(
+        &[GNOME_COPIED_FILES, URI_LIST],
+        glib::Priority::DEFAULT,
+        gio::Cancellable::NONE,
+        move |result| {
+            let (stream, mime) = match result {
+                Ok(read) => read,
+                Err(e) => {
+                    eprintln!("[clipboard] Nothing to paste: {}", e);
+                    return;
+                }
+            };
+            read_text(stream, move |text| {
+                let Some(text) = text else {
+                    return;
+                };
+                let (sources, move_items) = if mime == GNOME_COPIED_FILES {
+                    parse_gnome_copied_files(&text)
+                } else {
+                    (parse_uri_list(&text), false)
+                };
+                if sources.is_empty() {
+                    return;
+                }
+                if !move_items {
+                    pane::transfer_into(&pane, &sources, dest_dir, false, config);
+                    return;
+                }
+                let changes = CHANGES.with(Cell::get);
+                let widget = pane.root.clone().upcast::<gtk4::Widget>();
+                let cut_sources = sources.clone();
+                pane::transfer_into_then(&pane, &sources, dest_dir, true, config, move |report| {
+                    finish_cut(&widget, &cut_sources, report, changes);
+                });
+            });
+        },
+    );
+}
+
+/// Takes the files a pasted cut moved off the clipboard. `changes` is
+/// the change count when the paste started: if the clipboard changed
+/// since, only the dimming is updated.
+fn finish_cut(widget: &gtk4::Widget, sources: &[PathBuf], report: &TransferReport, changes: u64) {
+    let moved = |path: &PathBuf| report.transferred.iter().any(|(from, _)| from == path);
+    let still_cut: Vec<PathBuf> =
+        CUT.with(|c| c.borrow().iter().filter(|p| !moved(p)).cloned().collect());
+    set_cut(still_cut);
+    if CHANGES.with(Cell::get) != changes {
+        return;
+    }
+
+    let left: Vec<PathBuf> = sources.iter().filter(|p| !moved(p)).cloned().collect();
+    let clipboard = widget.clipboard();
+    if left.is_empty() {
+        if let Err(e) = clipboard.set_content(None::<&gdk::ContentProvider>) {
+            eprintln!("[clipboard] Cannot clear clipboard: {}", e);
+        }
+    } else if left.len() < sources.len() && clipboard.is_local() {
+        put(widget, &left, true);
+    }
+}
+
+/// Reads `stream` to the end without blocking and passes on its text
+/// (`None` if reading failed). Shared with drops, which arrive the same way.
+pub fn read_text(stream: gio::InputStream, f: impl FnOnce(Option<String>) + 'static) {
+    let buffer = gio::MemoryOutputStream::new_resizable();
+    let buffer_c = buffer.clone();
+    buffer.splice_async(
+        &stream,
+        gio::OutputStreamSpliceFlags::CLOSE_SOURCE | gio::OutputStreamSpliceFlags::CLOSE_TARGET,
+        glib::Priority::DEFAULT,
+        gio::Cancellable::NONE,
+        move |result| match result {
+            Ok(_) => {
+                let data = buffer_c.steal_as_bytes();
+                f(Some(String::from_utf8_lossy(&data).into_owned()));
+            }
+            Err(e) => {
+                eprintln!("[clipboard] Cannot read data: {}", e);
+                f(None);
+            }
+        },
+    );
+}
+
+/// Parses "copy\nfile:///a\n…" (or "cut\n…") into paths and whether to move.
+fn parse_gnome_copied_files(text: &str) -> (Vec<PathBuf>, bool) {
+    let mut lines = text.lines();
+    let move_items = lines.next().map(str::trim) == Some("cut");
+    (uris_to_paths(lines), move_items)
+}
+
+/// Parses a text/uri-list, skipping comments.
+pub fn parse_uri_list(text: &str) -> Vec<PathBuf> {
+    uris_to_paths(text.lines().filter(|l| !l.starts_with('#')))
+}
+
+/// Local paths of the given URIs; anything without a path is skipped.
+fn uris_to_paths<'a>(uris: impl Iterator<Item = &'a str>) -> Vec<PathBuf> {
+    uris.map(str::trim)
+        .filter(|u| !u.is_empty())
+        .filter_map(|u| gio::File::for_uri(u).path())
+        .collect()
+}
@@ -0,0 +1,210 @@
+use crate::filesystem::fuzzy::FuzzyMatcher;
+use crate::ui::actions::{self, Command};
+use crate::ui::pane::Pane;
+use crate::ui::quick_finder::scroll_into_view;
+use gtk4::gdk::Key;
+use gtk4::prelude::*;
+use gtk4::{
+    Box, EventControllerKey, Label, ListBox, ListBoxRow, Orientation, Popover, PositionType,
+    PropagationPhase, ScrolledWindow, SearchEntry,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Command Palette
+// ═══════════════════════════════════════════════
+//
+// Ctrl+Shift+P lists every registered command (see `actions`) with its
+// key bindings, fuzzy-matched by title as you type, in a popover over the
+// active pane like the quick finder.
+//
+//   ↑ / ↓    move through the commands
+//   Enter    run the highlighted one
+//   Escape   close
+
+/// Opens the command palette over `pane`, listing `commands`.
+pub fn show_command_palette(pane: &Pane, commands: Vec<Command>) {
+    let commands: Vec<Command> = commands
+        .into_iter()
+        .filter(|c| c.action != "win.command-palette")
+        .collect();
+
+    let anchor = pane.content_box.clone();
+    let popover = Popover::builder()
+        .has_arrow(false)
+        .position(PositionType::Bottom)
+        .css_classes(vec!["quick-finder".to_string()])
+        .build();
+    popover.set_parent(&anchor);
+    popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(
+        anchor.width() / 2,
+        8,
+        1,
+        1,
+    )));
+    {
+        let popover_c = popover.clone();
+        anchor.connect_destroy(move |_| popover_c.unparent());
+    }
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .width_request(480)
+        .build();
+
+    let search = SearchEntry::builder()
+        .placeholder_text("Run a command…")
+        .hexpand(true)
+        .build();
+    vbox.append(&search);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::Browse)
+        .css_classes(vec!["quick-finder-list".to_string()])
+        .build();
+    let scrolled = ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(420)
+        .build();
+    vbox.append(&scrolled);
+    popover.set_child(Some(&vbox));
+
+    // Indices into `commands` of the rows shown, in row order
+    let shown: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let render: Rc<dyn Fn()> = {
+        let list = list.clone();
+        let scrolled = scrolled.clone();
+        let search = search.clone();
+        let shown = shown.clone();
+        let commands = commands.clone();
+        Rc::new(move || {
+            let mut matcher = FuzzyMatcher::new(&search.text());
+            let mut ranked: Vec<(i64, usize)> = commands
+                .iter()
+                .enumerate()
+                .filter_map(|(i, c)| {
+                    if matcher.is_empty() {
+                        Some((0, i))
+                    } else {
+                        matcher.score(&c.title).map(|score| (score, i))
+                    }
+                })
+                .collect();
+            // Best first; equal scores keep the registration order
+            ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+            while let Some(child) = list.first_child() {
+                list.remove(&child);
+            }
+            for &(_, i) in &ranked {
+                list.append(&command_row(&commands[i]));
+            }
+            if let Some(row) = list.row_at_index(0) {
+                list.select_row(Some(&row));
+            }
+            scrolled.vadjustment().set_value(0.0);
+            *shown.borrow_mut() = ranked.into_iter().map(|(_, i)| i).collect();
+        })
+    };
+    {
+        let render = render.clone();
+        search.connect_search_changed(move |_| render());
+    }
+
+    // ── Running ──
+    let run: Rc<dyn Fn()> = {
+        let popover = popover.clone();
+        let list = list.clone();
+        Rc::new(move || {
+            let Some(command) = list
+                .selected_row()
+                .and_then(|row| shown.borrow().get(row.index() as usize).copied())
+                .map(|i| commands[i].clone())
+            else {
+                return;
+            };
+            popover.popdown();
+            actions::run_command(&anchor, &command);
+        })
+    };
+    {
+        let run = run.clone();
+        list.connect_row_activated(move |_, _| run());
+    }
+
+    // Arrows move through the commands while typing; Enter runs one
+    {
+        let list = list.clone();
+        let scrolled = scrolled.clone();
+        let key = EventControllerKey::new();
+        key.set_propagation_phase(PropagationPhase::Capture);
+        key.connect_key_pressed(move |_, key, _code, _state| match key {
+            Key::Down | Key::Up => {
+                let current = list.selected_row().map_or(-1, |r| r.index());
+                let next = if key == Key::Down {
+                    current + 1
+                } else {
+                    current - 1
+                };
+                if let Some(row) = list.row_at_index(next.max(0)) {
+                    list.select_row(Some(&row));
+                    scroll_into_view(&scrolled, &list, &row);
+                }
+                glib::Propagation::Stop
+            }
+            Key::Return | Key::KP_Enter => {
+                run();
+                glib::Propagation::Stop
+            }
+            _ => glib::Propagation::Proceed,
+        });
+        search.add_controller(key);
+    }
+
+    popover.connect_closed(move |p| {
+        let p = p.clone();
+        glib::idle_add_local_once(move || p.unparent());
+    });
+
+    render();
+    popover.popup();
+    search.grab_focus();
+}
+
+/// One command: its title, and its keys on the right.
+fn command_row(command: &Command) -> ListBoxRow {
+    let row_box = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    row_box.append(
+        &Label::builder()
+            .label(&command.title)
+            .css_classes(vec!["quick-finder-name".to_string()])
+            .hexpand(true)
+            .xalign(0.0)
+            .build(),
+    );
+    if !command.keys.is_empty() {
+        row_box.append(
+            &Label::builder()
+                .label(command.keys.join(", "))
+                .css_classes(vec!["command-palette-keys".to_string()])
+                .build(),
+        );
+    }
+    ListBoxRow::builder().child(&row_box).build()
+}
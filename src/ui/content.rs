@@ -1,226 +1,255 @@
-use crate::config::{AppConfig, GroupBy, ViewMode};
+use crate::config::{AppConfig, ViewMode};
 use crate::filesystem;
-use crate::ui::{context_menu, graph_view, preview, tree_view, widgets};
+use crate::ui::pane::Pane;
+use crate::ui::{
+    context_menu, drag_source, drop_target, graph_view, history, item_view, open_with, preview,
+    selection, tabs, trash_view, tree_view,
+};
 use gtk4::prelude::*;
-use gtk4::{Align, Box, Button, FlowBox, Label};
+use gtk4::{Button, GestureClick, PropagationPhase, ScrolledWindow};
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 // ═══════════════════════════════════════════════
 //  Content Area Refresh
 // ═══════════════════════════════════════════════
 
-/// Refreshes the main content area (grid or list mode).
-pub fn refresh_content(
-    container: &Box,
-    current_path: Rc<RefCell<PathBuf>>,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    config: Rc<RefCell<AppConfig>>,
-) {
+/// Refreshes the content area of `pane` using the pane's own view mode.
+pub fn refresh_content(pane: &Pane, config: Rc<RefCell<AppConfig>>) {
+    let container = &pane.content_box;
+    let path = pane.current_path.borrow().clone();
+
+    // Leaving a location records it (with the old view's scroll offset)
+    history::record_visit(pane, &path);
+
     // Clear
     while let Some(child) = container.first_child() {
         container.remove(&child);
     }
 
-    let path = current_path.borrow().clone();
-    let cfg = config.borrow().clone();
+    pane.update_breadcrumb();
+
+    // A filter typed in another folder is cleared; the view below
+    // re-registers how it applies the filter
+    *pane.refilter.borrow_mut() = None;
+    pane.filter_bar.follow(&path);
+    pane.filter_bar.set_count(None);
+
+    let view_mode = pane.view_mode.borrow().clone();
+
+    // Item widgets are re-registered by whichever view renders below
+    pane.selection.begin_view(&path);
+
+    // Every view re-registers the directories it watches, and a listing
+    // or search still streaming into the previous view is dropped
+    pane.watches.clear();
+    pane.cancel_streams();
+
+    // The Trash place has its own listing, whatever the view mode
+    if filesystem::is_trash_location(&path) {
+        container.append(&scrolled(&trash_view::build_trash_view(
+            pane,
+            config.clone(),
+        )));
+        let pane_c = pane.clone();
+        let cfg_c = config.clone();
+        pane.watches
+            .watch(&path, move |_| refresh_content(&pane_c, cfg_c.clone()));
+        return;
+    }
 
     // Graph mode gets its own special view
-    if cfg.view_mode == ViewMode::Graph {
-        let graph = graph_view::build_graph_view(current_path.clone(), config.clone());
+    if view_mode == ViewMode::Graph {
+        let graph = graph_view::build_graph_view(pane, config.clone());
         container.append(&graph);
         return;
     }
 
     // Tree mode: hierarchical expand/collapse view
-    if cfg.view_mode == ViewMode::Tree {
-        let cp = current_path.clone();
-        let cont = container.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
+    if view_mode == ViewMode::Tree {
+        let pane_c = pane.clone();
         let cfg_rc = config.clone();
 
         let on_navigate: Rc<dyn Fn(PathBuf)> = Rc::new(move |new_path: PathBuf| {
-            *cp.borrow_mut() = new_path;
-            refresh_content(
-                &cont,
-                cp.clone(),
-                &info,
-                sel.clone(),
-                cfg_rc.clone(),
-            );
+            *pane_c.current_path.borrow_mut() = new_path;
+            refresh_content(&pane_c, cfg_rc.clone());
         });
 
-        let tree = tree_view::build_tree_view(
-            current_path.clone(),
-            config.clone(),
-            inspector_info,
-            selected_file_path.clone(),
-            on_navigate,
-        );
-        container.append(&tree);
+        let tree = tree_view::build_tree_view(pane, config.clone(), on_navigate);
+        container.append(&scrolled(&tree));
         return;
     }
 
-    let files = filesystem::list_directory(&path, cfg.show_hidden);
+    container.append(&item_view::build_item_view(
+        pane,
+        &view_mode,
+        config.clone(),
+    ));
+}
+
+/// Wraps a non-virtualized view in its own scrolled window.
+fn scrolled(child: &impl IsA<gtk4::Widget>) -> ScrolledWindow {
+    ScrolledWindow::builder()
+        .child(child)
+        .vexpand(true)
+        .hexpand(true)
+        .build()
+}
+
+// ═══════════════════════════════════════════════
+//  Click Wiring
+// ═══════════════════════════════════════════════
+
+/// Wires clicks, drag-and-drop, the context menu and the tooltip of an
+/// item widget. The caller registers the widget with the selection.
+pub fn wire_content_click(
+    btn: &Button,
+    entry: &filesystem::Entry,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    ItemWiring::new(btn, pane, config).bind(entry);
+}
 
-    // Group files
-    let grouped = filesystem::group_entries(&files, &cfg.grouping);
+/// The controllers of an item widget, attached once. Virtualized views
+/// keep one per recycled widget and `bind` it to whichever entry the
+/// widget shows; the controllers look the entry up when they fire.
+#[derive(Clone)]
+pub struct ItemWiring {
+    widget: Button,
+    entry: Rc<RefCell<Option<filesystem::Entry>>>,
+    /// Middle-click and the drop target, switched off for files.
+    folder_only: Vec<gtk4::EventController>,
+}
 
-    for (group_name, entries) in &grouped {
-        // Group header (if grouping is active)
-        if cfg.grouping != GroupBy::None && !group_name.is_empty() {
-            let header = Label::builder()
-                .label(group_name)
-                .css_classes(vec!["group-header".to_string()])
-                .halign(Align::Start)
-                .build();
-            container.append(&header);
+impl ItemWiring {
+    pub fn new(btn: &Button, pane: &Pane, config: Rc<RefCell<AppConfig>>) -> Self {
+        let entry: Rc<RefCell<Option<filesystem::Entry>>> = Rc::new(RefCell::new(None));
+        let current = {
+            let entry = entry.clone();
+            move || entry.borrow().as_ref().map(|e| e.path.clone())
+        };
+
+        // Click selects (Ctrl / Shift extend), double-click navigates or opens
+        {
+            let pane_c = pane.clone();
+            let cfg = config.clone();
+            selection::attach_current_item_clicks(
+                btn,
+                current.clone(),
+                pane.selection.clone(),
+                move |path| activate_path(&pane_c, path, cfg.clone()),
+            );
         }
 
-        match cfg.view_mode {
-            ViewMode::Grid => {
-                let flow = FlowBox::builder()
-                    .selection_mode(gtk4::SelectionMode::None)
-                    .homogeneous(false)
-                    .row_spacing(6)
-                    .column_spacing(6)
-                    .margin_start(4)
-                    .margin_end(4)
-                    .margin_bottom(8)
-                    .min_children_per_line(2)
-                    .max_children_per_line(20)
-                    .build();
-
-                for entry in entries {
-                    let card = widgets::create_file_card(entry, &cfg);
-                    wire_content_click(
-                        &card,
-                        entry,
-                        current_path.clone(),
-                        container,
-                        inspector_info,
-                        selected_file_path.clone(),
-                        config.clone(),
-                    );
-                    flow.insert(&card, -1);
+        // Middle-click opens a folder in a new tab
+        let middle = GestureClick::builder().button(2).build();
+        {
+            let current = current.clone();
+            let pane_c = pane.clone();
+            middle.connect_released(move |_, _, _, _| {
+                if let Some(path) = current() {
+                    tabs::open_tab(&pane_c, path);
                 }
-                container.append(&flow);
-            }
-            ViewMode::List => {
-                for entry in entries {
-                    let row = widgets::create_file_row(entry, &cfg);
-                    wire_content_click(
-                        &row,
-                        entry,
-                        current_path.clone(),
-                        container,
-                        inspector_info,
-                        selected_file_path.clone(),
-                        config.clone(),
-                    );
-                    container.append(&row);
+            });
+        }
+        btn.add_controller(middle.clone());
+
+        // Dragging a selected item drags the whole selection
+        drag_source::attach_current_drag_source(btn, current.clone(), Some(pane.selection.clone()));
+
+        // Folders take drops, and spring open when a drag lingers
+        let drop = {
+            let pane_c = pane.clone();
+            let cfg = config.clone();
+            let spring: drop_target::Spring = Rc::new(move |path: &Path| {
+                activate_path(&pane_c, path, cfg.clone());
+            });
+            let current = current.clone();
+            drop_target::attach_drop_target(
+                btn,
+                move |_, _| current(),
+                pane,
+                config.clone(),
+                Some(spring),
+            )
+        };
+
+        // Right-click context menu (Rename, Delete, Open)
+        context_menu::attach_file_context_menu(btn, current, pane.clone(), config.clone());
+
+        // Hover tooltip with image preview for supported formats
+        {
+            let entry = entry.clone();
+            btn.connect_query_tooltip(move |_widget, _x, _y, _keyboard, tooltip| {
+                let entry = entry.borrow();
+                let Some(entry) = entry.as_ref() else {
+                    return false;
+                };
+                if let Some(preview_img) = preview::build_tooltip_preview(&entry.path, &entry.mime)
+                {
+                    tooltip.set_custom(Some(&preview_img));
+                    return true;
                 }
-            }
-            ViewMode::Graph => {
-                // Graph mode is handled at the top of refresh_content
-                unreachable!("Graph mode should be handled before grouping");
-            }
-            ViewMode::Tree => {
-                // Tree mode is handled at the top of refresh_content
-                unreachable!("Tree mode should be handled before grouping");
-            }
+                false
+            });
+        }
+
+        Self {
+            widget: btn.clone(),
+            entry,
+            folder_only: vec![middle.upcast(), drop.upcast()],
+        }
+    }
+
+    /// Points the controllers at `entry`.
+    pub fn bind(&self, entry: &filesystem::Entry) {
+        let phase = if entry.is_dir {
+            PropagationPhase::Bubble
+        } else {
+            PropagationPhase::None
+        };
+        for controller in &self.folder_only {
+            controller.set_propagation_phase(phase);
         }
+        self.widget
+            .set_has_tooltip(preview::supports_preview(&entry.mime));
+        *self.entry.borrow_mut() = Some(entry.clone());
     }
 
-    // Empty state
-    if files.is_empty() {
-        let empty = Label::builder()
-            .label("This folder is empty")
-            .css_classes(vec!["inspector-subtitle".to_string()])
-            .halign(Align::Center)
-            .valign(Align::Center)
-            .vexpand(true)
-            .build();
-        container.append(&empty);
+    /// Detaches the controllers from the entry until the next `bind`.
+    pub fn unbind(&self) {
+        *self.entry.borrow_mut() = None;
     }
 }
 
 // ═══════════════════════════════════════════════
-//  Click Wiring
+//  Activation
 // ═══════════════════════════════════════════════
 
-fn wire_content_click(
-    btn: &Button,
-    entry: &filesystem::Entry,
-    current_path: Rc<RefCell<PathBuf>>,
-    container: &Box,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    config: Rc<RefCell<AppConfig>>,
-) {
-    let entry_path = entry.path.clone();
-    let is_dir = entry.is_dir;
-    let name = entry.name.clone();
-    let size_display = entry.size_display();
-    let mod_display = entry.modified_display();
-
-    let cp = current_path.clone();
-    let cont = container.clone();
-    let info = inspector_info.clone();
-    let sel = selected_file_path.clone();
-    let cfg = config.clone();
-
-    // Left-click: navigate or open + show preview
-    let entry_path_click = entry_path.clone();
-    let info_click = info.clone();
-    let sel_click = sel.clone();
-    btn.connect_clicked(move |_| {
-        if is_dir {
-            *cp.borrow_mut() = entry_path_click.clone();
-            refresh_content(
-                &cont,
-                cp.clone(),
-                &info_click,
-                sel_click.clone(),
-                cfg.clone(),
-            );
-        } else {
-            info_click.set_label(&format!(
-                "{}  •  {}  •  {}",
-                name, size_display, mod_display
-            ));
-            *sel_click.borrow_mut() = Some(entry_path_click.clone());
-            if let Err(e) = open::that(&entry_path_click) {
-                eprintln!("Failed to open file: {}", e);
+/// Navigates into `path` if it is a directory, otherwise opens it
+/// with the default application.
+pub fn activate_path(pane: &Pane, path: &Path, config: Rc<RefCell<AppConfig>>) {
+    if path.is_dir() {
+        *pane.current_path.borrow_mut() = path.to_path_buf();
+        refresh_content(pane, config);
+    } else {
+        open_with::open_default(path);
+    }
+}
+
+/// Activates the selection (Enter): a single folder is entered,
+/// files are opened. Folders in a multi-selection are skipped.
+pub fn open_selection(pane: &Pane, config: Rc<RefCell<AppConfig>>) {
+    let paths = pane.selection.paths();
+    match paths.as_slice() {
+        [] => {}
+        [single] => activate_path(pane, single, config),
+        _ => {
+            for path in paths.iter().filter(|p| !p.is_dir()) {
+                open_with::open_default(path);
             }
         }
-    });
-
-    // Right-click context menu (Rename, Delete, Open)
-    context_menu::attach_file_context_menu(
-        btn,
-        entry.path.clone(),
-        entry.name.clone(),
-        current_path.clone(),
-        container.clone(),
-        inspector_info.clone(),
-        selected_file_path.clone(),
-        config.clone(),
-    );
-
-    // Hover tooltip with image preview for supported formats
-    if preview::supports_preview(&entry.path) {
-        let entry_path_tooltip = entry.path.clone();
-        btn.set_has_tooltip(true);
-        btn.connect_query_tooltip(move |_widget, _x, _y, _keyboard, tooltip| {
-            if let Some(preview_img) = preview::build_tooltip_preview(&entry_path_tooltip) {
-                tooltip.set_custom(Some(&preview_img));
-                return true;
-            }
-            false
-        });
     }
 }
@@ -1,13 +1,19 @@
-use crate::config::AppConfig;
+use crate::config::{Action, AppConfig};
 use crate::filesystem;
+use crate::filesystem::journal::{self, FileOp};
+use crate::ui::actions;
+use crate::ui::clipboard;
 use crate::ui::content::refresh_content;
+use crate::ui::open_with;
+use crate::ui::pane::Pane;
 use gtk4::prelude::*;
 use gtk4::{
     Align, Box, Button, Entry as GtkEntry, GestureClick, Label, Orientation, Popover, Separator,
     Widget,
 };
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf};
 use std::rc::Rc;
 
 // ═══════════════════════════════════════════════
@@ -16,31 +22,24 @@ use std::rc::Rc;
 //
 // Two context menus:
 //   1. Background context menu — right-click on empty space
-//      → "New Folder", "New File"
+//      → "New Folder", "New File", "Paste"
 //   2. File/item context menu  — right-click on a file entry
-//      → "Open", "Rename", "Delete"
+//      → "Open", "Open With…", "Rename", "Cut", "Copy", "Paste Into Folder",
+//        "Copy/Move to Other Pane",
+//        "Move to Trash", "Delete Permanently"
 
 // ═══════════════════════════════════════════════
 //  Background Context Menu (empty area)
 // ═══════════════════════════════════════════════
 
 /// Attaches a right-click context menu to the content area background.
-/// Provides "New Folder" and "New File" options.
+/// Provides "New Folder", "New File" and "Paste" options.
 pub fn attach_background_context_menu(
     target: &impl IsA<Widget>,
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
+    pane: Pane,
     config: Rc<RefCell<AppConfig>>,
 ) {
-    let popover = build_background_popover(
-        current_path,
-        content_box,
-        inspector_info,
-        selected_file_path,
-        config,
-    );
+    let popover = build_background_popover(pane, config);
 
     popover.set_parent(target.as_ref());
     popover.set_has_arrow(true);
@@ -66,13 +65,7 @@ pub fn attach_background_context_menu(
     target.as_ref().add_controller(gesture);
 }
 
-fn build_background_popover(
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    config: Rc<RefCell<AppConfig>>,
-) -> Popover {
+fn build_background_popover(pane: Pane, config: Rc<RefCell<AppConfig>>) -> Popover {
     let popover = Popover::builder()
         .css_classes(vec!["context-menu".to_string()])
         .build();
@@ -96,75 +89,47 @@ fn build_background_popover(
         .margin_top(4)
         .margin_bottom(4)
         .build();
+    // ── "Paste" button (enabled while the clipboard holds files) ──
+    let paste_btn = context_menu_button("edit-paste-symbolic", "Paste");
     // ── "Refresh" button ──
     let refresh_btn = context_menu_button("view-refresh-symbolic", "Refresh");
 
     menu_box.append(&new_folder_btn);
     menu_box.append(&new_file_btn);
     menu_box.append(&sep);
+    menu_box.append(&paste_btn);
     menu_box.append(&refresh_btn);
     popover.set_child(Some(&menu_box));
 
-    // Wire: New Folder
-    {
+    // Wire: New Folder / New File / Refresh (registered actions; the
+    // right-click made this pane the active one)
+    for (btn, action) in [
+        (&new_folder_btn, Action::NewFolder),
+        (&new_file_btn, Action::NewFile),
+        (&refresh_btn, Action::Refresh),
+    ] {
         let popover_c = popover.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
-        let cfg = config.clone();
-
-        new_folder_btn.connect_clicked(move |_| {
+        btn.connect_clicked(move |btn| {
             popover_c.popdown();
-            show_name_input_dialog(
-                &popover_c,
-                "Create Folder",
-                true,
-                cp.clone(),
-                cb.clone(),
-                info.clone(),
-                sel.clone(),
-                cfg.clone(),
-            );
+            actions::activate_bound(btn, action, false);
         });
     }
 
-    // Wire: New File
+    // Wire: Paste
     {
-        let popover_c = popover.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
-        let cfg = config.clone();
-
-        new_file_btn.connect_clicked(move |_| {
-            popover_c.popdown();
-            show_name_input_dialog(
-                &popover_c,
-                "Create File",
-                false,
-                cp.clone(),
-                cb.clone(),
-                info.clone(),
-                sel.clone(),
-                cfg.clone(),
-            );
+        let paste_btn_c = paste_btn.clone();
+        popover.connect_show(move |_| {
+            paste_btn_c.set_sensitive(clipboard::can_paste(&paste_btn_c));
         });
-    }
 
-    // Wire: Refresh
-    {
         let popover_c = popover.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
+        let pane_c = pane.clone();
         let cfg = config.clone();
 
-        refresh_btn.connect_clicked(move |_| {
+        paste_btn.connect_clicked(move |_| {
             popover_c.popdown();
-            refresh_content(&cb, cp.clone(), &info, sel.clone(), cfg.clone());
+            let dest = pane_c.current_path.borrow().clone();
+            clipboard::paste(&pane_c, dest, cfg.clone());
         });
     }
 
@@ -176,17 +141,18 @@ fn build_background_popover(
 // ═══════════════════════════════════════════════
 
 /// Attaches a right-click context menu to a file/folder widget.
-/// Provides "Open", "Rename", "Delete" options.
+/// Provides "Open", "Rename", clipboard, "Copy/Move to Other Pane" and
+/// "Delete" options.
+/// When the item is part of the pane's selection, every action applies
+/// to the whole selection. `current` returns the item's path when the
+/// menu opens (virtualized views rebind the widget to other items).
 pub fn attach_file_context_menu(
     target: &impl IsA<Widget>,
-    file_path: PathBuf,
-    _file_name: String,
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
+    current: impl Fn() -> Option<PathBuf> + 'static,
+    pane: Pane,
     config: Rc<RefCell<AppConfig>>,
 ) {
+    let current = Rc::new(current);
     let popover = Popover::builder()
         .css_classes(vec!["context-menu".to_string()])
         .build();
@@ -210,18 +176,44 @@ pub fn attach_file_context_menu(
         .build();
 
     let open_btn = context_menu_button("document-open-symbolic", "Open");
+    let open_with_btn = context_menu_button("system-run-symbolic", "Open With…");
     let rename_btn = context_menu_button("document-edit-symbolic", "Rename");
+    let clip_sep = Separator::builder()
+        .orientation(Orientation::Horizontal)
+        .margin_top(4)
+        .margin_bottom(4)
+        .build();
+    let cut_btn = context_menu_button("edit-cut-symbolic", "Cut");
+    let copy_btn = context_menu_button("edit-copy-symbolic", "Copy");
+    let paste_into_btn = context_menu_button("edit-paste-symbolic", "Paste Into Folder");
+    let pane_sep = Separator::builder()
+        .orientation(Orientation::Horizontal)
+        .margin_top(4)
+        .margin_bottom(4)
+        .build();
+    let copy_other_btn = context_menu_button("edit-copy-symbolic", "Copy to Other Pane");
+    let move_other_btn = context_menu_button("go-next-symbolic", "Move to Other Pane");
     let sep = Separator::builder()
         .orientation(Orientation::Horizontal)
         .margin_top(4)
         .margin_bottom(4)
         .build();
-    let delete_btn = context_menu_button("user-trash-symbolic", "Delete");
+    let trash_btn = context_menu_button("user-trash-symbolic", "Move to Trash");
+    let delete_btn = context_menu_button("edit-delete-symbolic", "Delete Permanently");
     delete_btn.add_css_class("context-menu-danger");
 
     menu_box.append(&open_btn);
+    menu_box.append(&open_with_btn);
     menu_box.append(&rename_btn);
+    menu_box.append(&clip_sep);
+    menu_box.append(&cut_btn);
+    menu_box.append(&copy_btn);
+    menu_box.append(&paste_into_btn);
+    menu_box.append(&pane_sep);
+    menu_box.append(&copy_other_btn);
+    menu_box.append(&move_other_btn);
     menu_box.append(&sep);
+    menu_box.append(&trash_btn);
     menu_box.append(&delete_btn);
     popover.set_child(Some(&menu_box));
 
@@ -229,7 +221,14 @@ pub fn attach_file_context_menu(
     let gesture = GestureClick::builder().button(3).build();
 
     let popover_c = popover.clone();
+    let paste_into_btn_c = paste_into_btn.clone();
+    let current_c = current.clone();
     gesture.connect_pressed(move |_gesture, _n, _x, _y| {
+        let Some(file_path) = current_c() else {
+            return;
+        };
+        paste_into_btn_c.set_visible(file_path.is_dir());
+        paste_into_btn_c.set_sensitive(clipboard::can_paste(&paste_into_btn_c));
         popover_c.popup();
     });
 
@@ -237,94 +236,196 @@ pub fn attach_file_context_menu(
 
     // Wire: Open
     {
-        let file_path_c = file_path.clone();
+        let current_c = current.clone();
         let popover_c = popover.clone();
+        let pane_c = pane.clone();
         open_btn.connect_clicked(move |_| {
             popover_c.popdown();
-            if let Err(e) = open::that(&file_path_c) {
-                eprintln!("Failed to open file: {}", e);
+            let Some(file_path) = current_c() else {
+                return;
+            };
+            for path in action_targets(&pane_c, &file_path) {
+                open_with::open_default(&path);
             }
         });
     }
 
-    // Wire: Rename
+    // Wire: Open With (applications for the file's type)
     {
-        let file_path_c = file_path.clone();
+        let current_c = current.clone();
         let popover_c = popover.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
+        let pane_c = pane.clone();
+        open_with_btn.connect_clicked(move |_| {
+            popover_c.popdown();
+            let Some(file_path) = current_c() else {
+                return;
+            };
+            if let Some(anchor) = popover_c.parent() {
+                open_with::show_open_with_menu(&anchor, action_targets(&pane_c, &file_path));
+            }
+        });
+    }
+
+    // Wire: Rename (batch rename for several items)
+    {
+        let current_c = current.clone();
+        let popover_c = popover.clone();
+        let pane_c = pane.clone();
         let cfg = config.clone();
 
         rename_btn.connect_clicked(move |_| {
             popover_c.popdown();
-            show_rename_dialog(
-                &popover_c,
-                &file_path_c,
-                cp.clone(),
-                cb.clone(),
-                info.clone(),
-                sel.clone(),
-                cfg.clone(),
-            );
+            let Some(file_path) = current_c() else {
+                return;
+            };
+            if let Some(anchor) = popover_c.parent() {
+                let targets = action_targets(&pane_c, &file_path);
+                rename_items(&anchor, targets, pane_c.clone(), cfg.clone());
+            }
+        });
+    }
+
+    // Wire: Cut / Copy, Copy / Move to Other Pane, Move to Trash. These are
+    // registered actions on the selection, which the right-click made
+    // include this item.
+    for (btn, action) in [
+        (&cut_btn, Action::Cut),
+        (&copy_btn, Action::Copy),
+        (&copy_other_btn, Action::CopyToOtherPane),
+        (&move_other_btn, Action::MoveToOtherPane),
+        (&trash_btn, Action::Trash),
+    ] {
+        let popover_c = popover.clone();
+        btn.connect_clicked(move |btn| {
+            popover_c.popdown();
+            actions::activate_bound(btn, action, false);
         });
     }
 
-    // Wire: Delete
+    // Wire: Paste Into Folder
     {
-        let file_path_c = file_path.clone();
+        let current_c = current.clone();
         let popover_c = popover.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
+        let pane_c = pane.clone();
+        let cfg = config.clone();
+
+        paste_into_btn.connect_clicked(move |_| {
+            popover_c.popdown();
+            let Some(file_path) = current_c() else {
+                return;
+            };
+            clipboard::paste(&pane_c, file_path, cfg.clone());
+        });
+    }
+
+    // Wire: Delete Permanently (asks first)
+    {
+        let current_c = current.clone();
+        let popover_c = popover.clone();
+        let pane_c = pane.clone();
         let cfg = config.clone();
 
         delete_btn.connect_clicked(move |_| {
             popover_c.popdown();
-            // Perform deletion
-            let result = if file_path_c.is_dir() {
-                std::fs::remove_dir_all(&file_path_c)
-            } else {
-                std::fs::remove_file(&file_path_c)
+            let Some(file_path) = current_c() else {
+                return;
             };
-            match result {
-                Ok(_) => {
-                    refresh_content(&cb, cp.clone(), &info, sel.clone(), cfg.clone());
+            let Some(anchor) = popover_c.parent() else {
+                return;
+            };
+            let targets = action_targets(&pane_c, &file_path);
+            let message = match targets.as_slice() {
+                [single] => format!("Permanently delete “{}”?", display_name(single)),
+                _ => format!("Permanently delete {} items?", targets.len()),
+            };
+            let pane_d = pane_c.clone();
+            let cfg_d = cfg.clone();
+            show_confirm_dialog(&anchor, &message, "Delete", move || {
+                for path in &targets {
+                    if let Err(e) = filesystem::delete_permanently(path) {
+                        eprintln!("Failed to delete: {}", e);
+                    }
                 }
-                Err(e) => eprintln!("Failed to delete: {}", e),
-            }
+                refresh_content(&pane_d, cfg_d.clone());
+            });
         });
     }
 }
 
+/// Items a file-menu action applies to: the whole selection when the
+/// clicked item is part of it, otherwise just the clicked item.
+fn action_targets(pane: &Pane, clicked: &Path) -> Vec<PathBuf> {
+    if pane.selection.contains(clicked) {
+        pane.selection.paths()
+    } else {
+        vec![clicked.to_path_buf()]
+    }
+}
+
+/// Asks for new names in a popover on `anchor`: one name for a single
+/// item, a numbered pattern for several (F2 or "Rename").
+pub fn rename_items(
+    anchor: &Widget,
+    paths: Vec<PathBuf>,
+    pane: Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    match paths.as_slice() {
+        [] => {}
+        [single] => show_rename_dialog(anchor, single, pane, config),
+        _ => show_batch_rename_dialog(anchor, paths, pane, config),
+    }
+}
+
+/// Moves `paths` to the trash as one undoable step (Delete or "Move to Trash").
+pub fn trash_items(paths: Vec<PathBuf>, pane: &Pane, config: Rc<RefCell<AppConfig>>) {
+    let mut ops = Vec::new();
+    for path in paths {
+        match filesystem::move_to_trash(&path) {
+            Ok(trashed) => ops.push(FileOp::Trash {
+                original: path,
+                trashed,
+            }),
+            Err(e) => eprintln!("Failed to move to trash: {}", e),
+        }
+    }
+    journal::record_all(ops);
+    refresh_content(pane, config);
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
 // ═══════════════════════════════════════════════
 //  Dialogs
 // ═══════════════════════════════════════════════
 
+/// Asks for the name of a new folder (or file) in the pane's folder.
+pub fn create_item(pane: &Pane, is_dir: bool, config: Rc<RefCell<AppConfig>>) {
+    let title = if is_dir {
+        "Create Folder"
+    } else {
+        "Create File"
+    };
+    let anchor = pane.content_box.clone().upcast();
+    show_name_input_dialog(&anchor, title, is_dir, pane.clone(), config);
+}
+
 /// Shows a small inline popover to input a name for new file/folder creation.
 fn show_name_input_dialog(
-    parent_popover: &Popover,
+    parent_widget: &Widget,
     title: &str,
     is_dir: bool,
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
+    pane: Pane,
     config: Rc<RefCell<AppConfig>>,
 ) {
     let dialog = Popover::builder()
         .css_classes(vec!["context-menu".to_string()])
         .build();
-    let parent_widget = match parent_popover.parent() {
-        Some(w) => w,
-        None => {
-            eprintln!("[context_menu] No parent widget found for dialog");
-            return;
-        }
-    };
-    dialog.set_parent(&parent_widget);
+    dialog.set_parent(parent_widget);
 
     // Ensure dialog popover is unparented when its parent is destroyed
     let dialog_destroy = dialog.clone();
@@ -362,25 +463,23 @@ fn show_name_input_dialog(
     // Clone everything before the first closure
     let dialog_c = dialog.clone();
     let entry_c = entry.clone();
-    let cp1 = current_path.clone();
-    let cb1 = content_box.clone();
-    let info1 = inspector_info.clone();
-    let sel1 = selected_file_path.clone();
+    let pane1 = pane.clone();
     let cfg1 = config.clone();
 
     create_btn.connect_clicked(move |_| {
         let name = entry_c.text();
         if !name.is_empty() {
-            let parent = cp1.borrow();
+            let parent = pane1.current_path.borrow().clone();
             let result = if is_dir {
                 filesystem::create_directory(&parent, &name)
             } else {
                 filesystem::create_file(&parent, &name)
             };
             match result {
-                Ok(_) => {
+                Ok(path) => {
+                    journal::record(FileOp::Create { path, is_dir });
                     dialog_c.popdown();
-                    refresh_content(&cb1, cp1.clone(), &info1, sel1.clone(), cfg1.clone());
+                    refresh_content(&pane1, cfg1.clone());
                 }
                 Err(e) => eprintln!("Creation failed: {}", e),
             }
@@ -389,24 +488,22 @@ fn show_name_input_dialog(
 
     // Also allow Enter key
     let dialog_c2 = dialog.clone();
-    let cp2 = current_path.clone();
-    let cb2 = content_box.clone();
-    let info2 = inspector_info.clone();
-    let sel2 = selected_file_path.clone();
+    let pane2 = pane.clone();
     let cfg2 = config.clone();
     entry.connect_activate(move |e| {
         let name = e.text();
         if !name.is_empty() {
-            let parent = cp2.borrow();
+            let parent = pane2.current_path.borrow().clone();
             let result = if is_dir {
                 filesystem::create_directory(&parent, &name)
             } else {
                 filesystem::create_file(&parent, &name)
             };
             match result {
-                Ok(_) => {
+                Ok(path) => {
+                    journal::record(FileOp::Create { path, is_dir });
                     dialog_c2.popdown();
-                    refresh_content(&cb2, cp2.clone(), &info2, sel2.clone(), cfg2.clone());
+                    refresh_content(&pane2, cfg2.clone());
                 }
                 Err(e) => eprintln!("Creation failed: {}", e),
             }
@@ -418,25 +515,15 @@ fn show_name_input_dialog(
 
 /// Shows a rename dialog popover.
 fn show_rename_dialog(
-    parent_popover: &Popover,
+    parent_widget: &Widget,
     file_path: &PathBuf,
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
+    pane: Pane,
     config: Rc<RefCell<AppConfig>>,
 ) {
     let dialog = Popover::builder()
         .css_classes(vec!["context-menu".to_string()])
         .build();
-    let parent_widget = match parent_popover.parent() {
-        Some(w) => w,
-        None => {
-            eprintln!("[context_menu] No parent widget found for rename dialog");
-            return;
-        }
-    };
-    dialog.set_parent(&parent_widget);
+    dialog.set_parent(parent_widget);
 
     // Ensure dialog popover is unparented when its parent is destroyed
     let dialog_destroy = dialog.clone();
@@ -480,10 +567,7 @@ fn show_rename_dialog(
     let file_path_c1 = file_path.clone();
     let dialog_c1 = dialog.clone();
     let entry_c1 = entry.clone();
-    let cp1 = current_path.clone();
-    let cb1 = content_box.clone();
-    let info1 = inspector_info.clone();
-    let sel1 = selected_file_path.clone();
+    let pane1 = pane.clone();
     let cfg1 = config.clone();
 
     rename_btn.connect_clicked(move |_| {
@@ -493,8 +577,12 @@ fn show_rename_dialog(
                 let new_path = parent.join(new_name.as_str());
                 match std::fs::rename(&file_path_c1, &new_path) {
                     Ok(_) => {
+                        journal::record(FileOp::Rename {
+                            from: file_path_c1.clone(),
+                            to: new_path,
+                        });
                         dialog_c1.popdown();
-                        refresh_content(&cb1, cp1.clone(), &info1, sel1.clone(), cfg1.clone());
+                        refresh_content(&pane1, cfg1.clone());
                     }
                     Err(e) => eprintln!("Rename failed: {}", e),
                 }
@@ -505,10 +593,7 @@ fn show_rename_dialog(
     let old_name2 = old_name.clone();
     let file_path_c2 = file_path.clone();
     let dialog_c2 = dialog.clone();
-    let cp2 = current_path.clone();
-    let cb2 = content_box.clone();
-    let info2 = inspector_info.clone();
-    let sel2 = selected_file_path.clone();
+    let pane2 = pane.clone();
     let cfg2 = config.clone();
 
     entry.connect_activate(move |e| {
@@ -518,8 +603,12 @@ fn show_rename_dialog(
                 let new_path = parent.join(new_name.as_str());
                 match std::fs::rename(&file_path_c2, &new_path) {
                     Ok(_) => {
+                        journal::record(FileOp::Rename {
+                            from: file_path_c2.clone(),
+                            to: new_path,
+                        });
                         dialog_c2.popdown();
-                        refresh_content(&cb2, cp2.clone(), &info2, sel2.clone(), cfg2.clone());
+                        refresh_content(&pane2, cfg2.clone());
                     }
                     Err(e) => eprintln!("Rename failed: {}", e),
                 }
@@ -530,12 +619,230 @@ fn show_rename_dialog(
     dialog.popup();
 }
 
+/// Shows a batch rename popover for several items.
+/// `#` in the pattern is replaced by a running number; every item
+/// keeps its extension. The renames are undone as one step.
+fn show_batch_rename_dialog(
+    parent_widget: &Widget,
+    paths: Vec<PathBuf>,
+    pane: Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    let dialog = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    dialog.set_parent(parent_widget);
+
+    // Ensure dialog popover is unparented when its parent is destroyed
+    let dialog_destroy = dialog.clone();
+    parent_widget.connect_destroy(move |_| {
+        dialog_destroy.unparent();
+    });
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+
+    let label = Label::builder()
+        .label(format!("Rename {} items", paths.len()))
+        .css_classes(vec!["context-menu-title".to_string()])
+        .halign(Align::Start)
+        .build();
+
+    let entry = GtkEntry::builder().placeholder_text("Name #").build();
+
+    let hint = Label::builder()
+        .label("# is replaced by 1, 2, 3…")
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .halign(Align::Start)
+        .build();
+
+    let rename_btn = Button::builder()
+        .label("Rename")
+        .css_classes(vec!["btn-primary".to_string()])
+        .build();
+
+    vbox.append(&label);
+    vbox.append(&entry);
+    vbox.append(&hint);
+    vbox.append(&rename_btn);
+    dialog.set_child(Some(&vbox));
+
+    let apply: Rc<dyn Fn()> = {
+        let entry = entry.clone();
+        let dialog = dialog.clone();
+        Rc::new(move || {
+            let pattern = entry.text();
+            if pattern.is_empty() {
+                return;
+            }
+            if !is_plain_name(OsStr::new(&pattern.replace('#', "1"))) {
+                entry.add_css_class("error");
+                return;
+            }
+            dialog.popdown();
+            let ops = batch_rename(&paths, &pattern);
+            journal::record_all(ops);
+            refresh_content(&pane, config.clone());
+        })
+    };
+
+    {
+        let apply = apply.clone();
+        rename_btn.connect_clicked(move |_| apply());
+    }
+    entry.connect_activate(move |_| apply());
+    entry.connect_changed(|entry| entry.remove_css_class("error"));
+
+    dialog.popup();
+}
+
+/// Renames `paths` after `pattern` and returns the completed renames.
+/// Existing names are never overwritten.
+fn batch_rename(paths: &[PathBuf], pattern: &str) -> Vec<FileOp> {
+    let width = paths.len().to_string().len();
+    let mut ops = Vec::new();
+
+    for (i, path) in paths.iter().enumerate() {
+        let Some(parent) = path.parent() else {
+            continue;
+        };
+        let number = format!("{:0width$}", i + 1, width = width);
+        let mut name = OsString::from(if pattern.contains('#') {
+            pattern.replace('#', &number)
+        } else {
+            format!("{} {}", pattern, number)
+        });
+        if !path.is_dir() {
+            if let Some(ext) = path.extension() {
+                name.push(".");
+                name.push(ext);
+            }
+        }
+        if !is_plain_name(&name) {
+            eprintln!("Rename skipped: {:?} is not a file name", name);
+            continue;
+        }
+
+        let new_path = parent.join(&name);
+        if new_path == *path {
+            continue;
+        }
+        // A dangling symlink still takes the name
+        if std::fs::symlink_metadata(&new_path).is_ok() {
+            eprintln!("Rename skipped: {} already exists", new_path.display());
+            continue;
+        }
+        match std::fs::rename(path, &new_path) {
+            Ok(_) => ops.push(FileOp::Rename {
+                from: path.clone(),
+                to: new_path,
+            }),
+            Err(e) => eprintln!("Rename failed: {}", e),
+        }
+    }
+    ops
+}
+
+/// Whether `name` names an entry of a folder: no separator, not `.` or
+/// `..`, not absolute.
+fn is_plain_name(name: &OsStr) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(c)) if c == name)
+        && components.next().is_none()
+}
+
+/// Shows a confirmation popover for a destructive action, pointing at `anchor`.
+/// `on_confirm` runs only when the danger button is clicked.
+pub fn show_confirm_dialog(
+    anchor: &impl IsA<Widget>,
+    message: &str,
+    confirm_label: &str,
+    on_confirm: impl Fn() + 'static,
+) {
+    let dialog = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    let parent_widget = anchor.as_ref().clone();
+    dialog.set_parent(&parent_widget);
+
+    // Ensure dialog popover is unparented when its parent is destroyed
+    let dialog_destroy = dialog.clone();
+    parent_widget.connect_destroy(move |_| {
+        dialog_destroy.unparent();
+    });
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+
+    let label = Label::builder()
+        .label(message)
+        .css_classes(vec!["context-menu-title".to_string()])
+        .halign(Align::Start)
+        .wrap(true)
+        .max_width_chars(32)
+        .build();
+
+    let hint = Label::builder()
+        .label("This cannot be undone.")
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .halign(Align::Start)
+        .build();
+
+    let button_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(Align::End)
+        .build();
+    let cancel_btn = Button::builder()
+        .label("Cancel")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let confirm_btn = Button::builder()
+        .label(confirm_label)
+        .css_classes(vec!["btn-danger".to_string()])
+        .build();
+    button_row.append(&cancel_btn);
+    button_row.append(&confirm_btn);
+
+    vbox.append(&label);
+    vbox.append(&hint);
+    vbox.append(&button_row);
+    dialog.set_child(Some(&vbox));
+
+    let dialog_c = dialog.clone();
+    cancel_btn.connect_clicked(move |_| {
+        dialog_c.popdown();
+    });
+
+    let dialog_c = dialog.clone();
+    confirm_btn.connect_clicked(move |_| {
+        dialog_c.popdown();
+        on_confirm();
+    });
+
+    dialog.popup();
+    // Safer default: Enter cancels
+    cancel_btn.grab_focus();
+}
+
 // ═══════════════════════════════════════════════
 //  Helpers
 // ═══════════════════════════════════════════════
 
 /// Creates a styled context menu button with icon + label.
-fn context_menu_button(icon_name: &str, label_text: &str) -> Button {
+pub fn context_menu_button(icon_name: &str, label_text: &str) -> Button {
     let hbox = Box::builder()
         .orientation(Orientation::Horizontal)
         .spacing(10)
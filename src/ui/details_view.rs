@@ -0,0 +1,453 @@
+use crate::config::{AppConfig, ColumnLayout, DetailsColumn, SortKey};
+use crate::filesystem::{self, Entry};
+use crate::ui::content::{activate_path, refresh_content, wire_content_click};
+use crate::ui::item_view::ContentItem;
+use crate::ui::pane::Pane;
+use crate::ui::{selection, widgets};
+use glib::BoxedAnyObject;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, ColumnView, ColumnViewColumn, CustomSorter, Label, ListItem, NoSelection,
+    SignalListItemFactory, SortType, Widget,
+};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+
+// ═══════════════════════════════════════════════
+//  Details View
+// ═══════════════════════════════════════════════
+//
+// The List mode: a GtkColumnView over the item view's model. Columns can
+// be resized and dragged into another order, and hidden from the header's
+// right-click menu; the layout is kept in the config's `details_columns`.
+// Entries stay sorted by the model (`filesystem::sort_key`), so clicking
+// a sortable header only changes the configured sort order.
+//
+// Selection highlights whole rows: the row widget around an entry's name
+// cell is what gets bound to the pane's selection. The name cell carries
+// the item's full wiring (menu, drag and drop); the other cells only
+// select and activate.
+//
+// Permissions, owner, group and link target are not part of `Entry`:
+// they are read for on-screen rows only, on a worker thread, and the
+// cell is filled in when they arrive.
+
+/// Builds the column view for `store` (a list of `ContentItem`s).
+pub fn build_details_view(
+    pane: &Pane,
+    store: &gio::ListStore,
+    config: Rc<RefCell<AppConfig>>,
+) -> ColumnView {
+    let cfg = config.borrow().clone();
+    let view = ColumnView::builder()
+        .model(&NoSelection::new(Some(store.clone())))
+        .reorderable(true)
+        .css_classes(vec!["content-details".to_string()])
+        .build();
+
+    let actions = gio::SimpleActionGroup::new();
+    let header_menu = gio::Menu::new();
+    let mut columns: Vec<(ColumnViewColumn, DetailsColumn)> = Vec::new();
+    let probed: Rc<Cell<Option<DetailsColumn>>> = Rc::new(Cell::new(None));
+
+    for layout in cfg.details_layout() {
+        let column = ColumnViewColumn::builder()
+            .title(layout.column.title())
+            .factory(&cell_factory(pane, layout.column, config.clone()))
+            .resizable(true)
+            .expand(layout.column == DetailsColumn::Name)
+            .fixed_width(layout.width)
+            .visible(layout.visible)
+            .header_menu(&header_menu)
+            .build();
+        if sort_key_of(layout.column).is_some() {
+            // The model is already sorted: this sorter only runs when
+            // `sort_state` probes the view's sorter
+            let probed = probed.clone();
+            let kind = layout.column;
+            column.set_sorter(Some(&CustomSorter::new(move |_, _| {
+                probed.set(Some(kind));
+                gtk4::Ordering::Smaller
+            })));
+        }
+        view.append_column(&column);
+        columns.push((column, layout.column));
+    }
+    let columns = Rc::new(columns);
+
+    // ─── Show / Hide ───
+
+    for (column, kind) in columns.iter() {
+        if *kind == DetailsColumn::Name {
+            continue;
+        }
+        let action =
+            gio::SimpleAction::new_stateful(kind.id(), None, &column.is_visible().to_variant());
+        let column = column.clone();
+        let view_c = view.downgrade();
+        let columns_c = columns.clone();
+        let config_c = config.clone();
+        action.connect_activate(move |action, _| {
+            let visible = !column.is_visible();
+            column.set_visible(visible);
+            action.set_state(&visible.to_variant());
+            if let Some(view) = view_c.upgrade() {
+                save_layout(&view, &columns_c, &config_c, true);
+            }
+        });
+        actions.add_action(&action);
+        header_menu.append(Some(kind.title()), Some(&format!("details.{}", kind.id())));
+    }
+    view.insert_action_group("details", Some(&actions));
+
+    // ─── Reorder / Resize ───
+
+    {
+        let view_c = view.downgrade();
+        let columns_c = columns.clone();
+        let config_c = config.clone();
+        view.columns().connect_items_changed(move |model, _, _, _| {
+            // A move is a removal then an insertion; only the final state
+            // (and not the teardown of the view) is saved
+            if model.n_items() as usize == columns_c.len() {
+                if let Some(view) = view_c.upgrade() {
+                    save_layout(&view, &columns_c, &config_c, true);
+                }
+            }
+        });
+    }
+    for (column, _) in columns.iter() {
+        let view_c = view.downgrade();
+        let columns_c = columns.clone();
+        let config_c = config.clone();
+        // Resizing fires continuously: widths are saved with the config
+        // on the next write (at the latest when the window closes)
+        column.connect_fixed_width_notify(move |_| {
+            if let Some(view) = view_c.upgrade() {
+                save_layout(&view, &columns_c, &config_c, false);
+            }
+        });
+    }
+
+    // ─── Sorting ───
+
+    let active = columns.iter().find(|(_, kind)| match kind {
+        DetailsColumn::Name => matches!(cfg.sort_key, SortKey::Name | SortKey::Natural),
+        _ => sort_key_of(*kind) == Some(cfg.sort_key),
+    });
+    if let Some((column, _)) = active {
+        let direction = if cfg.sort_descending {
+            SortType::Descending
+        } else {
+            SortType::Ascending
+        };
+        view.sort_by_column(Some(column), direction);
+    }
+    if let Some(sorter) = view.sorter() {
+        let pane = pane.clone();
+        sorter.connect_changed(move |sorter, _| {
+            let Some((kind, descending)) = sort_state(sorter, &probed) else {
+                return;
+            };
+            {
+                let mut cfg = config.borrow_mut();
+                let key = match (kind, cfg.sort_key) {
+                    (DetailsColumn::Name, SortKey::Name) => SortKey::Name,
+                    (kind, _) => sort_key_of(kind).unwrap_or(SortKey::Natural),
+                };
+                if cfg.sort_key == key && cfg.sort_descending == descending {
+                    return;
+                }
+                cfg.sort_key = key;
+                cfg.sort_descending = descending;
+                cfg.save();
+            }
+            // The sort order is global: both panes re-sort. Deferred, as
+            // this view is replaced in the process.
+            let pane = pane.clone();
+            let config = config.clone();
+            glib::idle_add_local_once(move || {
+                refresh_content(&pane, config.clone());
+                if let Some(other) = pane.other() {
+                    refresh_content(&other, config);
+                }
+            });
+        });
+    }
+
+    view
+}
+
+/// The sorted column and direction of the view's sorter. The column
+/// sorters always answer "smaller" and record which of them was asked;
+/// a descending column flips the answer.
+fn sort_state(
+    sorter: &gtk4::Sorter,
+    probed: &Cell<Option<DetailsColumn>>,
+) -> Option<(DetailsColumn, bool)> {
+    let probe = glib::Object::new::<glib::Object>();
+    probed.set(None);
+    let order = sorter.compare(&probe, &probe);
+    probed
+        .take()
+        .map(|kind| (kind, order == gtk4::Ordering::Larger))
+}
+
+/// The sort key a column header stands for, if it is sortable.
+fn sort_key_of(column: DetailsColumn) -> Option<SortKey> {
+    match column {
+        DetailsColumn::Name => Some(SortKey::Natural),
+        DetailsColumn::Size => Some(SortKey::Size),
+        DetailsColumn::Modified => Some(SortKey::Modified),
+        DetailsColumn::Created => Some(SortKey::Created),
+        DetailsColumn::Accessed => Some(SortKey::Accessed),
+        DetailsColumn::Type => Some(SortKey::Type),
+        _ => None,
+    }
+}
+
+/// Writes the current column order, visibility and widths to the config.
+fn save_layout(
+    view: &ColumnView,
+    columns: &[(ColumnViewColumn, DetailsColumn)],
+    config: &Rc<RefCell<AppConfig>>,
+    persist: bool,
+) {
+    let model = view.columns();
+    let layout: Vec<ColumnLayout> = (0..model.n_items())
+        .filter_map(|i| model.item(i).and_downcast::<ColumnViewColumn>())
+        .filter_map(|column| {
+            let (_, kind) = columns.iter().find(|(c, _)| *c == column)?;
+            Some(ColumnLayout {
+                column: *kind,
+                visible: column.is_visible(),
+                width: column.fixed_width(),
+            })
+        })
+        .collect();
+
+    let mut cfg = config.borrow_mut();
+    cfg.details_columns = layout;
+    if persist {
+        cfg.save();
+    }
+}
+
+// ─── Cells ───
+
+fn cell_factory(
+    pane: &Pane,
+    column: DetailsColumn,
+    config: Rc<RefCell<AppConfig>>,
+) -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(|_, obj| {
+        if let Some(list_item) = obj.downcast_ref::<ListItem>() {
+            list_item.set_activatable(false);
+            list_item.set_selectable(false);
+        }
+    });
+    {
+        let pane = pane.clone();
+        factory.connect_bind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            let Some(boxed) = list_item.item().and_downcast::<BoxedAnyObject>() else {
+                return;
+            };
+            let widget: Widget = match &*boxed.borrow::<ContentItem>() {
+                // Group titles sit in the name column, other cells stay empty
+                ContentItem::Header(name) if column == DetailsColumn::Name => Label::builder()
+                    .label(name)
+                    .css_classes(vec!["group-header".to_string()])
+                    .halign(Align::Start)
+                    .build()
+                    .upcast(),
+                ContentItem::Entry(entry) => {
+                    let cell = build_cell(entry, column, &config.borrow());
+                    if column == DetailsColumn::Name {
+                        wire_content_click(&cell, entry, &pane, config.clone());
+                    } else {
+                        let entry_path = entry.path.clone();
+                        let pane_c = pane.clone();
+                        let cfg = config.clone();
+                        selection::attach_item_clicks(
+                            &cell,
+                            &entry.path,
+                            pane.selection.clone(),
+                            move || activate_path(&pane_c, &entry_path, cfg.clone()),
+                        );
+                    }
+                    cell.upcast()
+                }
+                _ => Label::new(None).upcast(),
+            };
+            list_item.set_child(Some(&widget));
+
+            if column == DetailsColumn::Name {
+                if let ContentItem::Entry(entry) = &*boxed.borrow::<ContentItem>() {
+                    pane.selection.bind(&entry.path, &row_of(&widget));
+                }
+            }
+        });
+    }
+    {
+        let selection = pane.selection.clone();
+        factory.connect_unbind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            if let (DetailsColumn::Name, Some(boxed), Some(child)) = (
+                column,
+                list_item.item().and_downcast::<BoxedAnyObject>(),
+                list_item.child(),
+            ) {
+                if let ContentItem::Entry(entry) = &*boxed.borrow::<ContentItem>() {
+                    selection.unbind(&entry.path, &row_of(&child));
+                }
+            }
+            list_item.set_child(Widget::NONE);
+        });
+    }
+    factory
+}
+
+/// The row widget around a cell's content.
+fn row_of(widget: &Widget) -> Widget {
+    widget
+        .parent()
+        .and_then(|cell| cell.parent())
+        .unwrap_or_else(|| widget.clone())
+}
+
+fn build_cell(entry: &Entry, column: DetailsColumn, config: &AppConfig) -> Button {
+    if column == DetailsColumn::Name {
+        let row_config = AppConfig {
+            show_file_size: false,
+            show_modified_date: false,
+            ..config.clone()
+        };
+        let btn = widgets::create_file_row(entry, &row_config);
+        btn.add_css_class("details-cell");
+        return btn;
+    }
+
+    let label = Label::builder()
+        .xalign(if column == DetailsColumn::Size {
+            1.0
+        } else {
+            0.0
+        })
+        .ellipsize(gtk4::pango::EllipsizeMode::End)
+        .css_classes(vec!["file-row-meta".to_string()])
+        .build();
+    match cell_text(entry, column) {
+        Some(text) => label.set_label(&text),
+        None => fill_in_background(&label, &entry.path, column),
+    }
+    Button::builder()
+        .child(&label)
+        .has_frame(false)
+        .css_classes(vec!["details-cell".to_string()])
+        .build()
+}
+
+/// Text of a metadata cell taken from the entry, or `None` if it has to
+/// be read from disk (see `read_cell_text`).
+fn cell_text(entry: &Entry, column: DetailsColumn) -> Option<String> {
+    match column {
+        DetailsColumn::Name => Some(entry.name.clone()),
+        DetailsColumn::Size => Some(entry.size_display()),
+        DetailsColumn::Modified => Some(entry.modified_display()),
+        DetailsColumn::Created => Some(filesystem::format_time(entry.created)),
+        DetailsColumn::Accessed => Some(filesystem::format_time(entry.accessed)),
+        DetailsColumn::Type => Some(filesystem::mime_description(&entry.mime)),
+        _ => None,
+    }
+}
+
+/// Reads a cell's text on the details worker, then sets it on `label`
+/// (if the row is still around).
+fn fill_in_background(label: &Label, path: &Path, column: DetailsColumn) {
+    type Job = (PathBuf, DetailsColumn, glib::SendWeakRef<Label>);
+    static JOBS: OnceLock<Sender<Job>> = OnceLock::new();
+    let jobs = JOBS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        std::thread::spawn(move || {
+            for (path, column, label) in rx {
+                let text = read_cell_text(&path, column);
+                glib::MainContext::default().invoke(move || {
+                    let Some(label) = label.upgrade() else {
+                        return;
+                    };
+                    label.set_label(&text);
+                    if column == DetailsColumn::LinkTarget {
+                        label.set_tooltip_text(Some(&text));
+                    }
+                });
+            }
+        });
+        tx
+    });
+    let _ = jobs.send((path.to_path_buf(), column, label.downgrade().into()));
+}
+
+/// Text of a cell that needs a read from disk. Runs on the worker.
+fn read_cell_text(path: &Path, column: DetailsColumn) -> String {
+    match column {
+        DetailsColumn::Permissions => permissions(path),
+        DetailsColumn::Owner => owner_attribute(path, "owner::user"),
+        DetailsColumn::Group => owner_attribute(path, "owner::group"),
+        DetailsColumn::LinkTarget => fs::read_link(path)
+            .map(|target| target.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// `ls -l` style mode string, e.g. "drwxr-xr-x".
+fn permissions(path: &Path) -> String {
+    let Ok(link) = fs::symlink_metadata(path) else {
+        return "—".to_string();
+    };
+    let kind = if link.file_type().is_symlink() {
+        'l'
+    } else if link.is_dir() {
+        'd'
+    } else {
+        '-'
+    };
+    let mode = fs::metadata(path)
+        .map(|m| m.permissions().mode())
+        .unwrap_or_else(|_| link.permissions().mode());
+
+    let mut text = String::with_capacity(10);
+    text.push(kind);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    text
+}
+
+/// Owner or group name via GIO (resolves ids through NSS).
+fn owner_attribute(path: &Path, attribute: &str) -> String {
+    gio::File::for_path(path)
+        .query_info(
+            attribute,
+            gio::FileQueryInfoFlags::NONE,
+            gio::Cancellable::NONE,
+        )
+        .ok()
+        .and_then(|info| info.attribute_string(attribute))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
@@ -1,6 +1,10 @@
+use crate::thumbnail::ThumbnailCache;
+use crate::ui::selection::Selection;
 use gtk4::gdk;
 use gtk4::prelude::*;
-use std::path::Path;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 // ═══════════════════════════════════════════════
 //  External Drag & Drop Source
@@ -12,163 +16,290 @@ use std::path::Path;
 //
 // The drag ghost is rendered via a cairo ImageSurface
 // converted to GdkTexture — this always works because
-// it doesn't depend on widget realization.
+// it doesn't depend on widget realization. It stacks the
+// dragged items' cached thumbnails, with a count badge.
+//
+// The paths of a drag in progress are kept in `DRAGGING`, so Diptych's
+// own drop targets know what hovers over them before the drop.
+
+thread_local! {
+    static DRAGGING: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Paths of the drag Diptych itself is running (empty if none).
+pub fn dragging_paths() -> Vec<PathBuf> {
+    DRAGGING.with(|d| d.borrow().clone())
+}
 
 /// Attach an external drag source to any GTK4 widget.
 ///
 /// Works for both files AND directories.
-/// Shows a drag ghost with icon emoji + filename.
+/// With a `selection`, dragging an item that is part of it drags the
+/// whole selection as one `text/uri-list`. The ghost stacks the items'
+/// thumbnails, with a count badge for several items.
 pub fn attach_file_drag_source(
     widget: &impl IsA<gtk4::Widget>,
     file_path: &Path,
-    _icon_name: &str,
-    is_dir: bool,
+    selection: Option<Rc<Selection>>,
+) {
+    let file_path = file_path.to_path_buf();
+    attach_current_drag_source(widget, move || Some(file_path.clone()), selection);
+}
+
+/// Like `attach_file_drag_source`, for a widget a virtualized view
+/// rebinds: `current` returns the path it shows when a drag starts.
+pub fn attach_current_drag_source(
+    widget: &impl IsA<gtk4::Widget>,
+    current: impl Fn() -> Option<PathBuf> + 'static,
+    selection: Option<Rc<Selection>>,
 ) {
     let drag_source = gtk4::DragSource::new();
-    drag_source.set_actions(gdk::DragAction::COPY | gdk::DragAction::MOVE);
+    drag_source.set_actions(gdk::DragAction::COPY | gdk::DragAction::MOVE | gdk::DragAction::LINK);
 
-    let file_uri = path_to_file_uri(file_path);
-    let path_owned = file_path.to_path_buf();
+    // The grabbed item and the paths of the current drag, resolved in `prepare`
+    let grabbed: Rc<RefCell<PathBuf>> = Rc::new(RefCell::new(PathBuf::new()));
+    let dragged: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
 
     // ── Prepare content ──
     {
-        let file_uri = file_uri.clone();
-        let path_owned = path_owned.clone();
+        let grabbed = grabbed.clone();
+        let dragged = dragged.clone();
         drag_source.connect_prepare(move |_source, _x, _y| {
-            let g_file = gtk4::gio::File::for_path(&path_owned);
-            let file_content = gdk::ContentProvider::for_value(&g_file.to_value());
+            let path_owned = current()?;
+            let paths = match &selection {
+                Some(sel) if sel.len() > 1 && sel.contains(&path_owned) => sel.paths(),
+                _ => vec![path_owned.clone()],
+            };
 
-            let uri_list = format!("{}\r\n", file_uri);
-            let uri_content = gdk::ContentProvider::for_value(&uri_list.to_value());
+            let uri_list: String = paths
+                .iter()
+                .map(|p| format!("{}\r\n", gio::File::for_path(p).uri()))
+                .collect();
+            let uri_content = gdk::ContentProvider::for_bytes(
+                "text/uri-list",
+                &glib::Bytes::from_owned(uri_list.into_bytes()),
+            );
 
-            let union = gdk::ContentProvider::new_union(&[file_content, uri_content]);
-            Some(union)
+            // A lone item is also offered as a GFile
+            let providers = match paths.as_slice() {
+                [single] => {
+                    let g_file = gio::File::for_path(single);
+                    vec![
+                        gdk::ContentProvider::for_value(&g_file.to_value()),
+                        uri_content,
+                    ]
+                }
+                _ => vec![uri_content],
+            };
+            DRAGGING.with(|d| *d.borrow_mut() = paths.clone());
+            *grabbed.borrow_mut() = path_owned;
+            *dragged.borrow_mut() = paths;
+            Some(gdk::ContentProvider::new_union(&providers))
         });
     }
 
     // ── Ghost image via cairo → GdkTexture ──
-    {
-        let display_name = file_path
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| "file".to_string());
-
-        // Truncate long names for the ghost
-        let short_name = if display_name.len() > 30 {
-            format!("{}…", &display_name[..29])
-        } else {
-            display_name.clone()
-        };
-
-        let icon_emoji = if is_dir { "📁" } else { "📄" };
-        let ghost_text = format!(" {} {} ", icon_emoji, short_name);
+    drag_source.connect_drag_begin(move |source, _drag| {
+        if let Some((texture, hot_x, hot_y)) =
+            render_ghost_texture(&grabbed.borrow(), &dragged.borrow())
+        {
+            source.set_icon(Some(&texture), hot_x, hot_y);
+        }
+    });
 
-        drag_source.connect_drag_begin(move |source, _drag| {
-            // Render the ghost label to a cairo surface → GdkTexture
-            if let Some(texture) = render_ghost_texture(&ghost_text) {
-                source.set_icon(Some(&texture), 16, 16);
-            }
-        });
-    }
+    drag_source.connect_drag_end(|_, _, _| {
+        DRAGGING.with(|d| d.borrow_mut().clear());
+    });
 
     widget.add_controller(drag_source);
 }
 
-/// Renders a text label onto a cairo ImageSurface and returns
-/// it as a GdkTexture that can be used as a drag icon.
-///
-/// This approach bypasses widget realization issues entirely.
-fn render_ghost_texture(text: &str) -> Option<gdk::Texture> {
-    // Measure text with pango
-    let font_map = pangocairo::FontMap::default();
-    let context = font_map.create_context();
-    let layout = pango::Layout::new(&context);
-    layout.set_text(text);
+// ─── Drag Ghost ───
 
-    let font_desc = pango::FontDescription::from_string("Inter Semi-Bold 12");
-    layout.set_font_description(Some(&font_desc));
+/// Side of one card of the ghost stack (pixels).
+const CARD: f64 = 72.0;
 
-    let (text_w, text_h) = layout.pixel_size();
-    let pad_x = 12;
-    let pad_y = 8;
-    let w = text_w + pad_x * 2;
-    let h = text_h + pad_y * 2;
-    let radius = 10.0;
+/// Offset between stacked cards.
+const STACK_OFFSET: f64 = 7.0;
 
-    // Create cairo surface
-    let mut surface = cairo::ImageSurface::create(cairo::Format::ARgb32, w, h).ok()?;
-    let cr = cairo::Context::new(&surface).ok()?;
+/// At most this many cards are stacked.
+const MAX_CARDS: usize = 3;
 
-    // Rounded rect background
-    let (fw, fh) = (w as f64, h as f64);
-    cr.new_sub_path();
-    cr.arc(
-        fw - radius,
-        radius,
-        radius,
-        -std::f64::consts::FRAC_PI_2,
-        0.0,
-    );
-    cr.arc(
-        fw - radius,
-        fh - radius,
-        radius,
-        0.0,
-        std::f64::consts::FRAC_PI_2,
-    );
-    cr.arc(
-        radius,
-        fh - radius,
-        radius,
-        std::f64::consts::FRAC_PI_2,
-        std::f64::consts::PI,
-    );
-    cr.arc(
-        radius,
-        radius,
-        radius,
-        std::f64::consts::PI,
-        3.0 * std::f64::consts::FRAC_PI_2,
-    );
-    cr.close_path();
+/// Room around the stack, also taken by the count badge.
+const MARGIN: f64 = 12.0;
 
-    // Fill with dark bg
-    cr.set_source_rgba(0.12, 0.12, 0.18, 0.92);
-    let _ = cr.fill_preserve();
+/// Widest file name shown under a single card.
+const CAPTION_WIDTH: i32 = 180;
 
-    // Border
-    cr.set_source_rgba(0.45, 0.55, 0.75, 0.4);
-    cr.set_line_width(1.0);
-    let _ = cr.stroke();
+/// Renders the drag ghost onto a cairo ImageSurface and returns it as a
+/// GdkTexture, with the hotspot (the middle of the front card).
+///
+/// Up to `MAX_CARDS` cards are stacked, the grabbed item in front; each
+/// shows the item's cached thumbnail, or a folder / file glyph when there
+/// is none. A single item gets its name underneath, several get a badge
+/// with their count. This bypasses widget realization issues entirely.
+fn render_ghost_texture(grabbed: &Path, paths: &[PathBuf]) -> Option<(gdk::Texture, i32, i32)> {
+    // Front to back: the grabbed item, then the other dragged items
+    let cards: Vec<&Path> = std::iter::once(grabbed)
+        .chain(paths.iter().map(PathBuf::as_path).filter(|p| *p != grabbed))
+        .take(MAX_CARDS)
+        .collect();
+    let count = paths.len().max(1);
+    let layers = cards.len() as f64;
+    let stack = CARD + STACK_OFFSET * (layers - 1.0);
+
+    let font_map = pangocairo::FontMap::default();
+    let context = font_map.create_context();
+    let caption = (count == 1).then(|| {
+        let layout = pango::Layout::new(&context);
+        layout.set_text(
+            &grabbed
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "file".to_string()),
+        );
+        layout.set_font_description(Some(&pango::FontDescription::from_string(
+            "Inter Semi-Bold 10",
+        )));
+        layout.set_width(CAPTION_WIDTH * pango::SCALE);
+        layout.set_ellipsize(pango::EllipsizeMode::Middle);
+        layout
+    });
+    let (caption_w, caption_h) = caption
+        .as_ref()
+        .map(|l| l.pixel_size())
+        .map(|(w, h)| (w as f64 + 16.0, h as f64 + 8.0))
+        .unwrap_or((0.0, 0.0));
+
+    let w = (stack + MARGIN * 2.0).max(caption_w + 4.0).ceil();
+    let h = (stack + MARGIN * 2.0 + caption_h).ceil();
+    let mut surface =
+        cairo::ImageSurface::create(cairo::Format::ARgb32, w as i32, h as i32).ok()?;
+    let cr = cairo::Context::new(&surface).ok()?;
+
+    // Cards, back to front; back cards peek out up and to the right
+    let x0 = ((w - stack) / 2.0).floor();
+    let y0 = MARGIN;
+    let cache = ThumbnailCache::new();
+    for (i, path) in cards.iter().enumerate().rev() {
+        let offset = i as f64 * STACK_OFFSET;
+        let y = y0 + (layers - 1.0) * STACK_OFFSET - offset;
+        draw_card(&cr, &context, x0 + offset, y, path, &cache);
+    }
 
-    // Text
-    cr.set_source_rgba(0.85, 0.87, 0.92, 1.0);
-    cr.move_to(pad_x as f64, pad_y as f64);
-    pangocairo::functions::show_layout(&cr, &layout);
+    if let (Some(layout), true) = (&caption, caption_h > 0.0) {
+        let (text_w, _) = layout.pixel_size();
+        let bx = ((w - caption_w) / 2.0).floor();
+        let by = y0 + stack + 4.0;
+        rounded_rect(&cr, bx, by, caption_w, caption_h - 4.0, 8.0);
+        cr.set_source_rgba(0.12, 0.12, 0.18, 0.92);
+        let _ = cr.fill();
+        cr.set_source_rgba(0.85, 0.87, 0.92, 1.0);
+        cr.move_to(((w - text_w as f64) / 2.0).floor(), by + 2.0);
+        pangocairo::functions::show_layout(&cr, layout);
+    } else if count > 1 {
+        let label = if count > 99 {
+            "99+".to_string()
+        } else {
+            count.to_string()
+        };
+        draw_badge(&cr, &context, x0 + stack, y0, &label);
+    }
 
     drop(cr);
-    let _ = surface.flush();
+    surface.flush();
 
     // Convert cairo surface → GdkTexture via GBytes
+    let stride = surface.stride() as usize;
     let data = surface.data().ok()?;
     let bytes = glib::Bytes::from(&*data);
     let texture = gdk::MemoryTexture::new(
-        w,
-        h,
+        w as i32,
+        h as i32,
         gdk::MemoryFormat::B8g8r8a8Premultiplied,
         &bytes,
-        (w * 4) as usize,
+        stride,
     );
-    Some(texture.upcast())
+    let hot_x = (x0 + CARD / 2.0) as i32;
+    let hot_y = (y0 + (layers - 1.0) * STACK_OFFSET + CARD / 2.0) as i32;
+    Some((texture.upcast(), hot_x, hot_y))
 }
 
-/// Converts an absolute filesystem path to a `file:///` URI.
-fn path_to_file_uri(path: &Path) -> String {
-    let abs = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-    let encoded = abs
-        .to_string_lossy()
-        .replace(' ', "%20")
-        .replace('#', "%23")
-        .replace('?', "%3F");
-    format!("file://{}", encoded)
+/// One card: the cached thumbnail of `path`, else a folder / file glyph.
+fn draw_card(
+    cr: &cairo::Context,
+    context: &pango::Context,
+    x: f64,
+    y: f64,
+    path: &Path,
+    cache: &ThumbnailCache,
+) {
+    rounded_rect(cr, x, y, CARD, CARD, 10.0);
+    cr.set_source_rgba(0.12, 0.12, 0.18, 0.96);
+    let _ = cr.fill_preserve();
+    cr.set_source_rgba(0.45, 0.55, 0.75, 0.5);
+    cr.set_line_width(1.0);
+    let _ = cr.stroke();
+
+    let thumb = cache.get(path).and_then(|thumb| {
+        let mut file = std::fs::File::open(thumb).ok()?;
+        cairo::ImageSurface::create_from_png(&mut file).ok()
+    });
+    match thumb {
+        Some(thumb) if thumb.width() > 0 && thumb.height() > 0 => {
+            let inner = CARD - 8.0;
+            let (tw, th) = (thumb.width() as f64, thumb.height() as f64);
+            let scale = (inner / tw).min(inner / th);
+            cr.save().ok();
+            rounded_rect(cr, x + 4.0, y + 4.0, inner, inner, 7.0);
+            cr.clip();
+            cr.translate(x + (CARD - tw * scale) / 2.0, y + (CARD - th * scale) / 2.0);
+            cr.scale(scale, scale);
+            let _ = cr.set_source_surface(&thumb, 0.0, 0.0);
+            let _ = cr.paint();
+            cr.restore().ok();
+        }
+        _ => {
+            let layout = pango::Layout::new(context);
+            layout.set_text(if path.is_dir() { "📁" } else { "📄" });
+            layout.set_font_description(Some(&pango::FontDescription::from_string("30")));
+            let (gw, gh) = layout.pixel_size();
+            cr.set_source_rgba(0.85, 0.87, 0.92, 1.0);
+            cr.move_to(x + (CARD - gw as f64) / 2.0, y + (CARD - gh as f64) / 2.0);
+            pangocairo::functions::show_layout(cr, &layout);
+        }
+    }
+}
+
+/// Round count badge centered on (`cx`, `cy`).
+fn draw_badge(cr: &cairo::Context, context: &pango::Context, cx: f64, cy: f64, label: &str) {
+    let layout = pango::Layout::new(context);
+    layout.set_text(label);
+    layout.set_font_description(Some(&pango::FontDescription::from_string("Inter Bold 10")));
+    let (tw, th) = layout.pixel_size();
+
+    // A pill once the number no longer fits a circle
+    let r = MARGIN - 1.0;
+    let half = ((tw as f64 + 10.0) / 2.0).max(r);
+    rounded_rect(cr, cx - half, cy - r, half * 2.0, r * 2.0, r);
+    cr.set_source_rgba(0.40, 0.52, 0.95, 1.0);
+    let _ = cr.fill_preserve();
+    cr.set_source_rgba(0.12, 0.12, 0.18, 1.0);
+    cr.set_line_width(1.5);
+    let _ = cr.stroke();
+
+    cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+    cr.move_to(cx - tw as f64 / 2.0, cy - th as f64 / 2.0);
+    pangocairo::functions::show_layout(cr, &layout);
+}
+
+/// Adds a rounded rectangle path.
+fn rounded_rect(cr: &cairo::Context, x: f64, y: f64, w: f64, h: f64, radius: f64) {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    cr.new_sub_path();
+    cr.arc(x + w - radius, y + radius, radius, -FRAC_PI_2, 0.0);
+    cr.arc(x + w - radius, y + h - radius, radius, 0.0, FRAC_PI_2);
+    cr.arc(x + radius, y + h - radius, radius, FRAC_PI_2, PI);
+    cr.arc(x + radius, y + radius, radius, PI, 3.0 * FRAC_PI_2);
+    cr.close_path();
 }
@@ -0,0 +1,295 @@
+use crate::config::AppConfig;
+use crate::filesystem;
+use crate::filesystem::journal::{self, FileOp};
+use crate::ui::pane::{self, Pane};
+use crate::ui::{clipboard, drag_source};
+use gtk4::gdk::{self, DragAction, ModifierType};
+use gtk4::prelude::*;
+use gtk4::DropTargetAsync;
+use std::cell::RefCell;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+// ═══════════════════════════════════════════════
+//  Drop Targets
+// ═══════════════════════════════════════════════
+//
+// Files dragged from other applications, or from Diptych's own views,
+// can be dropped onto the content background (the folder shown), folder
+// cards / rows, tree rows, graph folder nodes and sidebar places.
+//
+//   no modifier        move within a filesystem, copy across filesystems
+//   Ctrl               copy
+//   Shift              move
+//   Ctrl+Shift / Alt   link
+//
+// Copies and moves go through the transfer engine (with its conflict
+// handling); links are created directly. Dropping onto the Trash place
+// trashes the files. Hovering a folder for `SPRING_DELAY` springs it
+// open, so a drag can travel down the tree before it is released.
+
+/// Hover time before a folder springs open.
+const SPRING_DELAY: Duration = Duration::from_millis(800);
+
+/// CSS class of a widget while a drop would land on it.
+const DROP_HOVER_CLASS: &str = "drop-hover";
+
+/// Opens a folder a drag hovers over (navigates into it or expands it).
+pub type Spring = Rc<dyn Fn(&Path)>;
+
+/// The folder a spring timer runs for, and the timer.
+type SpringTimer = Rc<RefCell<Option<(PathBuf, glib::SourceId)>>>;
+
+/// Accepts drops into `folder` on `widget`.
+pub fn attach_folder_drop_target(
+    widget: &impl IsA<gtk4::Widget>,
+    folder: &Path,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+    spring: Option<Spring>,
+) {
+    let folder = folder.to_path_buf();
+    attach_drop_target(
+        widget,
+        move |_, _| Some(folder.clone()),
+        pane,
+        config,
+        spring,
+    );
+}
+
+/// Accepts drops on `widget` into the folder `resolve` returns for the
+/// pointer position (`None` refuses the drop there). Returns the
+/// controller, which can be switched off with its propagation phase.
+pub fn attach_drop_target(
+    widget: &impl IsA<gtk4::Widget>,
+    resolve: impl Fn(f64, f64) -> Option<PathBuf> + 'static,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+    spring: Option<Spring>,
+) -> DropTargetAsync {
+    let target = DropTargetAsync::new(
+        Some(gdk::ContentFormats::new(&[clipboard::URI_LIST])),
+        DragAction::COPY | DragAction::MOVE | DragAction::LINK,
+    );
+
+    let resolve = Rc::new(resolve);
+    let spring_timer: SpringTimer = Rc::new(RefCell::new(None));
+    let widget = widget.as_ref().clone();
+
+    // ── Hover: highlight, pick the action, arm the spring timer ──
+    {
+        let resolve = resolve.clone();
+        let spring_timer = spring_timer.clone();
+        let widget = widget.clone();
+        let on_motion = move |target: &DropTargetAsync, drop: &gdk::Drop, x: f64, y: f64| {
+            let sources = hovering_paths(drop);
+            let choice = resolve(x, y).map(|dest| {
+                let state = target.current_event_state();
+                (choose_action(state, drop, &sources, &dest), dest)
+            });
+            let Some((action, dest)) = choice.filter(|(a, d)| accepts(&sources, d, *a)) else {
+                widget.remove_css_class(DROP_HOVER_CLASS);
+                cancel_spring(&spring_timer);
+                return DragAction::empty();
+            };
+            widget.add_css_class(DROP_HOVER_CLASS);
+            if let Some(spring) = &spring {
+                arm_spring(&spring_timer, dest, spring.clone());
+            }
+            action
+        };
+        target.connect_drag_enter(on_motion.clone());
+        target.connect_drag_motion(on_motion);
+    }
+    {
+        let spring_timer = spring_timer.clone();
+        let widget = widget.clone();
+        target.connect_drag_leave(move |_, _| {
+            widget.remove_css_class(DROP_HOVER_CLASS);
+            cancel_spring(&spring_timer);
+        });
+    }
+
+    // ── Drop: read the URI list, then copy / move / link ──
+    {
+        let pane = pane.clone();
+        let widget = widget.clone();
+        target.connect_drop(move |target, drop, x, y| {
+            widget.remove_css_class(DROP_HOVER_CLASS);
+            cancel_spring(&spring_timer);
+
+            let Some(dest) = resolve(x, y) else {
+                return false;
+            };
+            // Modifiers must be read now; the data arrives later
+            let state = target.current_event_state();
+            let pane = pane.clone();
+            let config = config.clone();
+            let drop_c = drop.clone();
+            drop.read_async(
+                &[clipboard::URI_LIST],
+                glib::Priority::DEFAULT,
+                gio::Cancellable::NONE,
+                move |result| {
+                    let stream = match result {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            eprintln!("[drop] Cannot read dropped files: {}", e);
+                            drop_c.finish(DragAction::empty());
+                            return;
+                        }
+                    };
+                    clipboard::read_text(stream, move |text| {
+                        let sources = text
+                            .map(|t| clipboard::parse_uri_list(&t))
+                            .unwrap_or_default();
+                        let action = choose_action(state, &drop_c, &sources, &dest);
+                        if sources.is_empty() || !accepts(&sources, &dest, action) {
+                            drop_c.finish(DragAction::empty());
+                            return;
+                        }
+                        perform_drop(&pane, sources, dest, action, config);
+                        drop_c.finish(action);
+                    });
+                },
+            );
+            true
+        });
+    }
+
+    widget.add_controller(target.clone());
+    target
+}
+
+// ─── Action Choice ───
+
+/// The action for the held modifiers; without any, files move within a
+/// filesystem and copy across filesystems. Falls back to an action the
+/// source offers (e.g. a browser only copies).
+fn choose_action(
+    state: ModifierType,
+    drop: &gdk::Drop,
+    sources: &[PathBuf],
+    dest: &Path,
+) -> DragAction {
+    let ctrl = state.contains(ModifierType::CONTROL_MASK);
+    let shift = state.contains(ModifierType::SHIFT_MASK);
+    let alt = state.contains(ModifierType::ALT_MASK);
+    let preferred = if alt || (ctrl && shift) {
+        DragAction::LINK
+    } else if ctrl {
+        DragAction::COPY
+    } else if shift || filesystem::is_trash_location(dest) || same_filesystem(sources, dest) {
+        DragAction::MOVE
+    } else {
+        DragAction::COPY
+    };
+
+    let offered = drop.actions();
+    [
+        preferred,
+        DragAction::COPY,
+        DragAction::MOVE,
+        DragAction::LINK,
+    ]
+    .into_iter()
+    .find(|a| offered.contains(*a))
+    .unwrap_or(DragAction::empty())
+}
+
+fn same_filesystem(sources: &[PathBuf], dest: &Path) -> bool {
+    let Ok(dest_dev) = std::fs::metadata(dest).map(|m| m.dev()) else {
+        return false;
+    };
+    !sources.is_empty()
+        && sources.iter().all(|s| {
+            std::fs::symlink_metadata(s)
+                .map(|m| m.dev() == dest_dev)
+                .unwrap_or(false)
+        })
+}
+
+/// Refuses drops of a folder into itself, and moves that would leave
+/// every file where it is.
+fn accepts(sources: &[PathBuf], dest: &Path, action: DragAction) -> bool {
+    if sources.iter().any(|s| dest.starts_with(s)) {
+        return false;
+    }
+    let all_in_dest = !sources.is_empty() && sources.iter().all(|s| s.parent() == Some(dest));
+    !(action == DragAction::MOVE && all_in_dest)
+}
+
+/// Files of a drag still hovering. Only Diptych's own drags are known
+/// before the drop; other applications' files are read when dropped.
+fn hovering_paths(drop: &gdk::Drop) -> Vec<PathBuf> {
+    if drop.drag().is_some() {
+        drag_source::dragging_paths()
+    } else {
+        Vec::new()
+    }
+}
+
+// ─── Spring-Loaded Folders ───
+
+/// Starts the spring timer for `dest`, unless it already runs for it.
+fn arm_spring(timer: &SpringTimer, dest: PathBuf, spring: Spring) {
+    if timer.borrow().as_ref().is_some_and(|(p, _)| *p == dest) {
+        return;
+    }
+    cancel_spring(timer);
+
+    let timer_c = timer.clone();
+    let path = dest.clone();
+    let id = glib::timeout_add_local_once(SPRING_DELAY, move || {
+        // The source is done once fired; forget it before springing
+        timer_c.borrow_mut().take();
+        spring(&path);
+    });
+    *timer.borrow_mut() = Some((dest, id));
+}
+
+fn cancel_spring(timer: &SpringTimer) {
+    if let Some((_, id)) = timer.borrow_mut().take() {
+        id.remove();
+    }
+}
+
+// ─── Performing the Drop ───
+
+fn perform_drop(
+    pane: &Pane,
+    sources: Vec<PathBuf>,
+    dest: PathBuf,
+    action: DragAction,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    if sources.is_empty() {
+        return;
+    }
+    if filesystem::is_trash_location(&dest) {
+        let mut ops = Vec::new();
+        for path in sources {
+            match filesystem::move_to_trash(&path) {
+                Ok(trashed) => ops.push(FileOp::Trash {
+                    original: path,
+                    trashed,
+                }),
+                Err(e) => eprintln!("[drop] Cannot trash {}: {}", path.display(), e),
+            }
+        }
+        journal::record_all(ops);
+        pane.refresh_both(&config);
+    } else if action == DragAction::LINK {
+        for path in &sources {
+            if let Err(e) = filesystem::create_link(path, &dest) {
+                eprintln!("[drop] Cannot link {}: {}", path.display(), e);
+            }
+        }
+        pane.refresh_both(&config);
+    } else {
+        pane::transfer_into(pane, &sources, dest, action == DragAction::MOVE, config);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::filesystem::filter::{FilterMode, NameFilter};
+use gtk4::prelude::*;
+use gtk4::{Box, Button, DropDown, Label, Orientation, Revealer, SearchEntry};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Filter Bar
+// ═══════════════════════════════════════════════
+//
+// Narrows the current folder as you type. Typing in the content area (or
+// Ctrl+F) opens it; every keystroke recompiles the `NameFilter` and runs
+// the `connect_changed` callback, which lets the pane's view re-apply
+// it. The view reports back how many entries match. Escape, the close
+// button or leaving the folder clears the filter.
+
+type ChangedCallback = Rc<dyn Fn()>;
+
+/// The filter bar widgets and state. Cheap to clone — every field is a handle.
+#[derive(Clone)]
+pub struct FilterBar {
+    pub root: Revealer,
+    entry: SearchEntry,
+    mode: DropDown,
+    count: Label,
+    filter: Rc<RefCell<NameFilter>>,
+    /// Folder the filter was typed in.
+    dir: Rc<RefCell<Option<PathBuf>>>,
+    on_changed: Rc<RefCell<Option<ChangedCallback>>>,
+}
+
+impl FilterBar {
+    pub fn new() -> Self {
+        let entry = SearchEntry::builder()
+            .placeholder_text("Filter this folder…")
+            .hexpand(true)
+            .build();
+        let titles: Vec<&str> = FilterMode::all().iter().map(|m| m.title()).collect();
+        let mode = DropDown::from_strings(&titles);
+        mode.set_tooltip_text(Some("How names are matched"));
+        let count = Label::builder()
+            .css_classes(vec!["filter-count".to_string()])
+            .build();
+        let close_btn = Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Clear Filter (Escape)")
+            .css_classes(vec!["toolbar-btn".to_string()])
+            .build();
+
+        let row = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .css_classes(vec!["filter-bar".to_string()])
+            .build();
+        row.append(&entry);
+        row.append(&count);
+        row.append(&mode);
+        row.append(&close_btn);
+        let root = Revealer::builder().child(&row).build();
+
+        let bar = Self {
+            root,
+            entry,
+            mode,
+            count,
+            filter: Rc::new(RefCell::new(NameFilter::default())),
+            dir: Rc::new(RefCell::new(None)),
+            on_changed: Rc::new(RefCell::new(None)),
+        };
+
+        {
+            let bar_c = bar.clone();
+            bar.entry.connect_changed(move |_| bar_c.changed());
+        }
+        {
+            let bar_c = bar.clone();
+            bar.mode.connect_selected_notify(move |_| bar_c.changed());
+        }
+        {
+            let bar_c = bar.clone();
+            bar.entry.connect_stop_search(move |_| bar_c.close());
+        }
+        {
+            let bar_c = bar.clone();
+            close_btn.connect_clicked(move |_| bar_c.close());
+        }
+        bar
+    }
+
+    /// Registers the callback run whenever the filter changed.
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.on_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.root.reveals_child()
+    }
+
+    /// The current filter (inactive while the bar is closed or empty).
+    pub fn filter(&self) -> NameFilter {
+        self.filter.borrow().clone()
+    }
+
+    /// Opens the bar for the folder `dir`, appending `typed` to the text,
+    /// and focuses it with the cursor at the end.
+    pub fn open(&self, dir: &Path, typed: &str) {
+        *self.dir.borrow_mut() = Some(dir.to_path_buf());
+        self.root.set_reveal_child(true);
+        self.entry.grab_focus();
+        if !typed.is_empty() {
+            let text = format!("{}{}", self.entry.text(), typed);
+            self.entry.set_text(&text);
+        }
+        self.entry.set_position(-1);
+    }
+
+    /// Clears the filter and hides the bar.
+    pub fn close(&self) {
+        *self.dir.borrow_mut() = None;
+        self.root.set_reveal_child(false);
+        if self.entry.text().is_empty() {
+            return;
+        }
+        // Emits the change
+        self.entry.set_text("");
+    }
+
+    /// Closes the bar if the pane now shows another folder than the one
+    /// the filter was typed in.
+    pub fn follow(&self, dir: &Path) {
+        let moved = self.dir.borrow().as_deref().is_some_and(|d| d != dir);
+        if moved {
+            self.close();
+        }
+    }
+
+    /// Shows "shown of total" matches, or nothing for `None`.
+    pub fn set_count(&self, count: Option<(usize, usize)>) {
+        let text = match count {
+            Some((shown, total)) if self.filter.borrow().is_active() => {
+                format!("{} of {}", shown, total)
+            }
+            _ => String::new(),
+        };
+        self.count.set_label(&text);
+    }
+
+    fn changed(&self) {
+        let mode = FilterMode::all()
+            .get(self.mode.selected() as usize)
+            .copied()
+            .unwrap_or_default();
+        *self.filter.borrow_mut() = NameFilter::new(&self.entry.text(), mode);
+        let callback = self.on_changed.borrow().clone();
+        if let Some(cb) = callback {
+            cb();
+        }
+    }
+}
@@ -1,11 +1,16 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, SortOrder};
 use crate::filesystem;
+use crate::filesystem::filter::NameFilter;
+use crate::filesystem::watch::WatchSet;
+use crate::ui::filter_bar::FilterBar;
+use crate::ui::pane::Pane;
+use crate::ui::{drop_target, open_with};
 use gtk4::prelude::*;
 use gtk4::{DrawingArea, EventControllerMotion, EventControllerScroll, GestureClick, GestureDrag};
 use rand::Rng;
 use std::cell::RefCell;
 use std::f64::consts::PI;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 // ═══════════════════════════════════════════════
@@ -19,6 +24,9 @@ use std::rc::Rc;
 //   • Mouse zoom (scroll) and pan (drag background)
 //   • Node dragging
 //   • Click on folder node → expand children
+//   • Drop files onto a folder node (or the background, for the root);
+//     a folder node a drag lingers on expands
+//   • The pane's filter dims the nodes it does not match
 //   • Smooth 60 fps animation via glib tick callback
 
 // ─── Data Structures ───
@@ -75,6 +83,8 @@ pub struct GraphState {
     pub hovered_node: Option<usize>,
     // Physics toggle
     pub physics_enabled: bool,
+    /// The pane's filter; nodes it rejects are drawn dimmed.
+    pub filter: NameFilter,
 }
 
 impl GraphState {
@@ -96,6 +106,7 @@ impl GraphState {
             cam_start_y: 0.0,
             hovered_node: None,
             physics_enabled: true,
+            filter: NameFilter::default(),
         }
     }
 
@@ -143,51 +154,64 @@ impl GraphState {
             n.is_expanded = true;
         }
 
-        let entries = filesystem::list_directory(&path, false);
+        let entries = filesystem::list_directory(&path, false, &SortOrder::default());
         let count = entries.len();
         let mut rng = rand::thread_rng();
 
         for (i, entry) in entries.iter().enumerate() {
-            let child_id = self.next_id;
-            self.next_id += 1;
-
             // Place children in a circle around the parent
             let angle = if count > 0 {
                 (i as f64 / count as f64) * 2.0 * PI
             } else {
                 0.0
             };
-            let dist = 120.0 + rng.gen_range(-20.0..20.0);
-            let cx = parent_x + angle.cos() * dist;
-            let cy = parent_y + angle.sin() * dist;
+            self.add_child(node_id, entry, parent_x, parent_y, angle, &mut rng);
+        }
+    }
 
-            let radius = if entry.is_dir { 22.0 } else { 14.0 };
-            let color = if entry.is_dir {
-                dir_color()
-            } else {
-                file_color_for_ext(&entry.extension)
-            };
+    /// Adds one child node of `parent_id` at `angle` around the parent.
+    fn add_child(
+        &mut self,
+        parent_id: usize,
+        entry: &filesystem::Entry,
+        parent_x: f64,
+        parent_y: f64,
+        angle: f64,
+        rng: &mut impl Rng,
+    ) {
+        let child_id = self.next_id;
+        self.next_id += 1;
 
-            self.nodes.push(GraphNode {
-                id: child_id,
-                label: entry.name.clone(),
-                path: entry.path.clone(),
-                is_dir: entry.is_dir,
-                is_expanded: false,
-                parent_id: Some(node_id),
-                x: cx,
-                y: cy,
-                vx: 0.0,
-                vy: 0.0,
-                radius,
-                color,
-            });
+        let dist = 120.0 + rng.gen_range(-20.0..20.0);
+        let cx = parent_x + angle.cos() * dist;
+        let cy = parent_y + angle.sin() * dist;
 
-            self.edges.push(GraphEdge {
-                from: node_id,
-                to: child_id,
-            });
-        }
+        let radius = if entry.is_dir { 22.0 } else { 14.0 };
+        let color = if entry.is_dir {
+            dir_color()
+        } else {
+            file_color_for_ext(&entry.extension)
+        };
+
+        self.nodes.push(GraphNode {
+            id: child_id,
+            label: entry.name.clone(),
+            path: entry.path.clone(),
+            is_dir: entry.is_dir,
+            is_expanded: false,
+            parent_id: Some(parent_id),
+            x: cx,
+            y: cy,
+            vx: 0.0,
+            vy: 0.0,
+            radius,
+            color,
+        });
+
+        self.edges.push(GraphEdge {
+            from: parent_id,
+            to: child_id,
+        });
     }
 
     /// Collapse a node: remove all descendants and their edges.
@@ -224,6 +248,88 @@ impl GraphState {
             .retain(|e| !to_remove.contains(&e.from) && !to_remove.contains(&e.to));
     }
 
+    /// Applies watcher events for the expanded directory node `dir`:
+    /// new entries get a node next to their parent, deleted entries
+    /// lose their node and subtree. Returns true if anything changed.
+    fn apply_changes(&mut self, dir: &Path, changed: &[PathBuf]) -> bool {
+        let Some(parent) = self
+            .nodes
+            .iter()
+            .find(|n| n.is_expanded && n.path == dir)
+            .map(|n| (n.id, n.x, n.y))
+        else {
+            return false;
+        };
+        let (parent_id, parent_x, parent_y) = parent;
+        let mut rng = rand::thread_rng();
+        let mut touched = false;
+
+        for path in changed {
+            if path.parent() != Some(dir) {
+                continue;
+            }
+            let existing = self
+                .nodes
+                .iter()
+                .find(|n| n.parent_id == Some(parent_id) && n.path == *path)
+                .map(|n| n.id);
+            // Hidden entries are never shown in the graph
+            let entry = filesystem::Entry::from_path(path).filter(|e| !e.name.starts_with('.'));
+
+            match (existing, entry) {
+                (None, Some(entry)) => {
+                    let angle = rng.gen_range(0.0..2.0 * PI);
+                    self.add_child(parent_id, &entry, parent_x, parent_y, angle, &mut rng);
+                    touched = true;
+                }
+                (Some(id), None) => {
+                    self.remove_node(id);
+                    touched = true;
+                }
+                _ => {}
+            }
+        }
+        touched
+    }
+
+    /// Removes a node together with its subtree and edges.
+    fn remove_node(&mut self, node_id: usize) {
+        self.collapse_node(node_id);
+        self.nodes.retain(|n| n.id != node_id);
+        self.edges.retain(|e| e.from != node_id && e.to != node_id);
+        if self.hovered_node == Some(node_id) {
+            self.hovered_node = None;
+        }
+        if self.dragged_node == Some(node_id) {
+            self.dragged_node = None;
+        }
+    }
+
+    /// True if `node` is drawn dimmed: it is not the root and the
+    /// filter rejects it.
+    fn is_dimmed(&self, node: &GraphNode) -> bool {
+        node.parent_id.is_some() && !self.filter.matches(&node.label)
+    }
+
+    /// (matching, total) nodes below the root.
+    fn filter_counts(&self) -> (usize, usize) {
+        let children = self.nodes.iter().filter(|n| n.parent_id.is_some());
+        let total = children.clone().count();
+        (
+            total - children.filter(|n| self.is_dimmed(n)).count(),
+            total,
+        )
+    }
+
+    /// Paths of the expanded directory nodes.
+    fn expanded_dirs(&self) -> Vec<PathBuf> {
+        self.nodes
+            .iter()
+            .filter(|n| n.is_expanded)
+            .map(|n| n.path.clone())
+            .collect()
+    }
+
     /// One step of the force-directed physics simulation.
     fn physics_step(&mut self) {
         if !self.physics_enabled {
@@ -332,12 +438,15 @@ impl GraphState {
 //  Build the Graph View Widget
 // ═══════════════════════════════════════════════
 
-/// Creates the full interactive graph view widget for the given directory.
-pub fn build_graph_view(
-    current_path: Rc<RefCell<PathBuf>>,
-    _config: Rc<RefCell<AppConfig>>,
-) -> DrawingArea {
+/// Creates the full interactive graph view widget for the pane's directory.
+/// Expanded directory nodes are watched in the pane's watch set and gain
+/// or lose child nodes as entries appear or disappear on disk.
+pub fn build_graph_view(pane: &Pane, config: Rc<RefCell<AppConfig>>) -> DrawingArea {
+    let current_path = pane.current_path.clone();
+    let watches = pane.watches.clone();
     let state = Rc::new(RefCell::new(GraphState::new()));
+    let filter_bar = pane.filter_bar.clone();
+    state.borrow_mut().filter = filter_bar.filter();
 
     // Initialise with root node (expanded)
     {
@@ -353,6 +462,20 @@ pub fn build_graph_view(
         .css_classes(vec!["graph-view".to_string()])
         .build();
 
+    sync_watches(&state, &watches, &area, &filter_bar);
+
+    // ── Filter → re-dim nodes (drawn on the next tick) ──
+    {
+        let state_w = Rc::downgrade(&state);
+        let filter_bar_c = filter_bar.clone();
+        *pane.refilter.borrow_mut() = Some(Rc::new(move || {
+            if let Some(state) = state_w.upgrade() {
+                state.borrow_mut().filter = filter_bar_c.filter();
+                filter_bar_c.set_count(Some(state.borrow().filter_counts()));
+            }
+        }));
+    }
+
     // ── Draw callback ──
     {
         let state_c = state.clone();
@@ -403,12 +526,56 @@ pub fn build_graph_view(
         area.add_controller(motion_ctrl);
     }
 
+    // ── Drop → into the folder node under the pointer, else the root ──
+    {
+        let state_c = state.clone();
+        let area_c = area.clone();
+        let resolve = move |x: f64, y: f64| {
+            let s = state_c.borrow();
+            let (wx, wy) = s.screen_to_world(x, y, area_c.width() as f64, area_c.height() as f64);
+            let node = s
+                .node_at(wx, wy)
+                .and_then(|nid| s.nodes.iter().find(|n| n.id == nid))
+                .filter(|n| n.is_dir);
+            Some(match node {
+                Some(node) => node.path.clone(),
+                None => current_path.borrow().clone(),
+            })
+        };
+
+        let state_c = state.clone();
+        let watches_c = watches.clone();
+        let area_c = area.clone();
+        let filter_bar_c = filter_bar.clone();
+        let spring: drop_target::Spring = Rc::new(move |path: &Path| {
+            let nid = state_c
+                .borrow()
+                .nodes
+                .iter()
+                .find(|n| n.path == path)
+                .map(|n| n.id);
+            if let Some(nid) = nid {
+                state_c.borrow_mut().expand_node(nid);
+                sync_watches(&state_c, &watches_c, &area_c, &filter_bar_c);
+            }
+        });
+        drop_target::attach_drop_target(&area, resolve, pane, config, Some(spring));
+    }
+
     // ── Click → expand/collapse directories ──
     {
         let click_ctrl = GestureClick::builder().button(1).build();
         let state_c = state.clone();
         let area_c = area.clone();
         click_ctrl.connect_released(move |_, _n, x, y| {
+            // Expanding / collapsing changes which directories are watched
+            glib::idle_add_local_once({
+                let state_c = state_c.clone();
+                let watches = watches.clone();
+                let area_c = area_c.clone();
+                let filter_bar = filter_bar.clone();
+                move || sync_watches(&state_c, &watches, &area_c, &filter_bar)
+            });
             let mut s = state_c.borrow_mut();
             let w = area_c.width() as f64;
             let h = area_c.height() as f64;
@@ -425,7 +592,7 @@ pub fn build_graph_view(
                 } else {
                     // Open file on click
                     if let Some(node) = s.nodes.iter().find(|n| n.id == nid) {
-                        let _ = open::that(&node.path);
+                        open_with::open_default(&node.path);
                     }
                 }
             }
@@ -500,6 +667,37 @@ pub fn build_graph_view(
     area
 }
 
+/// Watches exactly the expanded directory nodes of `state`, and updates
+/// the filter's match count for the nodes now shown.
+fn sync_watches(
+    state: &Rc<RefCell<GraphState>>,
+    watches: &Rc<WatchSet>,
+    area: &DrawingArea,
+    filter_bar: &FilterBar,
+) {
+    filter_bar.set_count(Some(state.borrow().filter_counts()));
+    let dirs = state.borrow().expanded_dirs();
+    watches.retain(|p| dirs.iter().any(|d| d == p));
+    for dir in dirs {
+        let state_w = Rc::downgrade(state);
+        let watches_w = Rc::downgrade(watches);
+        let area_c = area.clone();
+        let filter_bar_c = filter_bar.clone();
+        let dir_c = dir.clone();
+        watches.watch(&dir, move |changed| {
+            let (Some(state), Some(watches)) = (state_w.upgrade(), watches_w.upgrade()) else {
+                return;
+            };
+            let touched = state.borrow_mut().apply_changes(&dir_c, changed);
+            if touched {
+                // Removed subtrees may have taken expanded nodes along
+                sync_watches(&state, &watches, &area_c, &filter_bar_c);
+                area_c.queue_draw();
+            }
+        });
+    }
+}
+
 // ═══════════════════════════════════════════════
 //  Cairo Drawing
 // ═══════════════════════════════════════════════
@@ -527,9 +725,10 @@ fn draw_graph(cr: &gtk4::cairo::Context, state: &GraphState, width: f64, height:
         }
     }
 
-    // Draw nodes
+    // Draw nodes (the ones the filter rejects faded)
     for node in &state.nodes {
         let is_hovered = state.hovered_node == Some(node.id);
+        let fade = if state.is_dimmed(node) { 0.2 } else { 1.0 };
         let r = if is_hovered {
             node.radius * 1.2
         } else {
@@ -541,18 +740,18 @@ fn draw_graph(cr: &gtk4::cairo::Context, state: &GraphState, width: f64, height:
 
         // Fill
         let alpha = if is_hovered { 1.0 } else { 0.85 };
-        cr.set_source_rgba(node.color.r, node.color.g, node.color.b, alpha);
+        cr.set_source_rgba(node.color.r, node.color.g, node.color.b, alpha * fade);
         cr.fill_preserve().ok();
 
         // Stroke
         let stroke_alpha = if is_hovered { 0.9 } else { 0.4 };
-        cr.set_source_rgba(1.0, 1.0, 1.0, stroke_alpha);
+        cr.set_source_rgba(1.0, 1.0, 1.0, stroke_alpha * fade);
         cr.set_line_width(if is_hovered { 2.5 } else { 1.2 });
         cr.stroke().ok();
 
         // Expand indicator for directories
         if node.is_dir && !node.is_expanded {
-            cr.set_source_rgba(1.0, 1.0, 1.0, 0.7);
+            cr.set_source_rgba(1.0, 1.0, 1.0, 0.7 * fade);
             cr.arc(node.x, node.y, 4.0, 0.0, 2.0 * PI);
             cr.fill().ok();
         }
@@ -560,7 +759,7 @@ fn draw_graph(cr: &gtk4::cairo::Context, state: &GraphState, width: f64, height:
         // Label
         let font_size = if is_hovered { 11.0 } else { 9.0 };
         cr.set_font_size(font_size / state.zoom.max(0.3));
-        cr.set_source_rgba(0.9, 0.9, 0.95, if is_hovered { 1.0 } else { 0.8 });
+        cr.set_source_rgba(0.9, 0.9, 0.95, if is_hovered { 1.0 } else { 0.8 } * fade);
 
         let label = truncate_label(&node.label, 18);
         if let Ok(extents) = cr.text_extents(&label) {
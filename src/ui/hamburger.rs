@@ -1,9 +1,8 @@
-use crate::config::AppConfig;
-use crate::ui::content::refresh_content;
+use crate::config::{Action, AppConfig};
+use crate::ui::actions;
 use gtk4::prelude::*;
 use gtk4::{Align, Box, Button, Label, MenuButton, Orientation, Popover, Separator};
 use std::cell::RefCell;
-use std::path::PathBuf;
 use std::rc::Rc;
 
 // ═══════════════════════════════════════════════
@@ -13,18 +12,13 @@ use std::rc::Rc;
 // Top-right hamburger button that opens a popover with quick actions:
 //   • Open Settings Panel
 //   • Toggle Hidden Files
+//   • Command Palette
 //   • About
+//
+// Each entry runs a registered action (see `actions`).
 
 /// Builds the hamburger menu button and returns it.
-/// `on_open_settings` is called when the user clicks "Settings".
-pub fn build_hamburger_menu(
-    config: Rc<RefCell<AppConfig>>,
-    current_path: Rc<RefCell<PathBuf>>,
-    content_box: Box,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    on_open_settings: Rc<dyn Fn()>,
-) -> MenuButton {
+pub fn build_hamburger_menu(config: Rc<RefCell<AppConfig>>) -> MenuButton {
     let popover = Popover::builder()
         .css_classes(vec!["context-menu".to_string()])
         .build();
@@ -60,14 +54,13 @@ pub fn build_hamburger_menu(
     menu_box.append(&settings_btn);
 
     // ── Toggle hidden files ──
-    let hidden_label = if config.borrow().show_hidden {
-        "Hide Hidden Files"
-    } else {
-        "Show Hidden Files"
-    };
-    let hidden_btn = hamburger_item("view-reveal-symbolic", hidden_label);
+    let hidden_btn = hamburger_item("view-reveal-symbolic", hidden_label(&config.borrow()));
     menu_box.append(&hidden_btn);
 
+    // ── Command palette ──
+    let palette_btn = hamburger_item("system-run-symbolic", "Command Palette");
+    menu_box.append(&palette_btn);
+
     let sep2 = Separator::builder()
         .orientation(Orientation::Horizontal)
         .margin_top(4)
@@ -88,74 +81,31 @@ pub fn build_hamburger_menu(
         .css_classes(vec!["toolbar-btn".to_string()])
         .build();
 
-    // Wire: Settings
-    {
+    for (btn, action) in [
+        (&settings_btn, Action::Settings),
+        (&hidden_btn, Action::ToggleHidden),
+        (&palette_btn, Action::CommandPalette),
+        (&about_btn, Action::About),
+    ] {
         let popover_c = popover.clone();
-        let on_settings = on_open_settings.clone();
-        settings_btn.connect_clicked(move |_| {
+        btn.connect_clicked(move |btn| {
             popover_c.popdown();
-            on_settings();
+            actions::activate_bound(btn, action, false);
         });
     }
 
-    // Wire: Toggle hidden files
+    // The hidden files entry says what it will do
     {
-        let popover_c = popover.clone();
-        let config_c = config.clone();
-        let cp = current_path.clone();
-        let cb = content_box.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
         let hidden_btn_c = hidden_btn.clone();
-
-        hidden_btn.connect_clicked(move |_| {
-            {
-                let mut cfg = config_c.borrow_mut();
-                cfg.show_hidden = !cfg.show_hidden;
-                cfg.save();
-            }
-            // Update button label
-            let new_label = if config_c.borrow().show_hidden {
-                "Hide Hidden Files"
-            } else {
-                "Show Hidden Files"
+        popover.connect_show(move |_| {
+            let Some(label) = hidden_btn_c
+                .child()
+                .and_then(|hbox| hbox.last_child())
+                .and_downcast::<Label>()
+            else {
+                return;
             };
-            if let Some(child) = hidden_btn_c.child() {
-                if let Some(hbox) = child.downcast_ref::<Box>() {
-                    // Second child is the label
-                    if let Some(lbl_widget) = hbox.first_child().and_then(|c| c.next_sibling()) {
-                        if let Some(lbl) = lbl_widget.downcast_ref::<Label>() {
-                            lbl.set_label(new_label);
-                        }
-                    }
-                }
-            }
-            popover_c.popdown();
-            refresh_content(&cb, cp.clone(), &info, sel.clone(), config_c.clone());
-        });
-    }
-
-    // Wire: About
-    {
-        let popover_c = popover.clone();
-        let menu_btn_c = menu_button.clone();
-        about_btn.connect_clicked(move |_| {
-            popover_c.popdown();
-            // Show a proper About dialog
-            if let Some(root) = menu_btn_c.root() {
-                if let Some(win) = root.downcast_ref::<gtk4::Window>() {
-                    let about = gtk4::AboutDialog::builder()
-                        .transient_for(win)
-                        .modal(true)
-                        .program_name("Diptych")
-                        .version("0.1.0")
-                        .comments("A modern GTK4 file manager built with Rust.")
-                        .website("https://github.com/flear/diptych")
-                        .license_type(gtk4::License::MitX11)
-                        .build();
-                    about.present();
-                }
-            }
+            label.set_label(hidden_label(&config.borrow()));
         });
     }
 
@@ -164,6 +114,14 @@ pub fn build_hamburger_menu(
 
 // ─── Helper ───
 
+fn hidden_label(config: &AppConfig) -> &'static str {
+    if config.show_hidden {
+        "Hide Hidden Files"
+    } else {
+        "Show Hidden Files"
+    }
+}
+
 fn hamburger_item(icon_name: &str, label_text: &str) -> Button {
     let hbox = Box::builder()
         .orientation(Orientation::Horizontal)
@@ -0,0 +1,330 @@
+use crate::ui::pane::{display_path, Pane};
+use gtk4::prelude::*;
+use gtk4::{
+    Box, Button, EventSequenceState, GestureClick, GestureLongPress, Label, Orientation, Popover,
+    ScrolledWindow, Widget,
+};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+// ═══════════════════════════════════════════════
+//  Back / Forward History
+// ═══════════════════════════════════════════════
+//
+// Every pane keeps its own back and forward stacks. `refresh_content`
+// reports each directory it renders; moving to a different one pushes
+// the location being left, together with its scroll offset and
+// selection, so going back puts the user where they were.
+
+/// Locations kept per stack.
+const MAX_ENTRIES: usize = 50;
+/// Locations listed in the long-press menu.
+const MENU_ENTRIES: usize = 12;
+
+/// A location left behind, as it looked when leaving it.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub path: PathBuf,
+    pub scroll: f64,
+    pub selection: Vec<PathBuf>,
+}
+
+/// The stacks of a tab in the background (see `ui::tabs`).
+#[derive(Default)]
+pub struct HistoryState {
+    back: Vec<HistoryEntry>,
+    forward: Vec<HistoryEntry>,
+    current: Option<PathBuf>,
+}
+
+type ChangeCallback = Rc<dyn Fn()>;
+
+/// Back / forward stacks of one pane. The last element is the nearest.
+#[derive(Default)]
+pub struct History {
+    back: RefCell<Vec<HistoryEntry>>,
+    forward: RefCell<Vec<HistoryEntry>>,
+    /// Directory the pane currently renders.
+    current: RefCell<Option<PathBuf>>,
+    on_change: RefCell<Option<ChangeCallback>>,
+}
+
+impl History {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+
+    /// Registers the callback fired whenever either stack changes.
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.on_change.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.borrow().is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.borrow().is_empty()
+    }
+
+    /// Paths one stack leads to, nearest first.
+    pub fn locations(&self, forward: bool) -> Vec<PathBuf> {
+        let stack = if forward { &self.forward } else { &self.back };
+        stack
+            .borrow()
+            .iter()
+            .rev()
+            .map(|e| e.path.clone())
+            .collect()
+    }
+
+    /// Takes the stacks out, e.g. when the pane switches to another tab.
+    pub fn take_state(&self) -> HistoryState {
+        HistoryState {
+            back: self.back.take(),
+            forward: self.forward.take(),
+            current: self.current.take(),
+        }
+    }
+
+    /// Installs the stacks of the tab being switched to.
+    pub fn set_state(&self, state: HistoryState) {
+        *self.back.borrow_mut() = state.back;
+        *self.forward.borrow_mut() = state.forward;
+        *self.current.borrow_mut() = state.current;
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let callback = self.on_change.borrow().clone();
+        if let Some(cb) = callback {
+            cb();
+        }
+    }
+}
+
+fn push_bounded(stack: &RefCell<Vec<HistoryEntry>>, entry: HistoryEntry) {
+    let mut stack = stack.borrow_mut();
+    stack.push(entry);
+    if stack.len() > MAX_ENTRIES {
+        stack.remove(0);
+    }
+}
+
+// ─── Recording / Stepping ───
+
+/// Called before `path` is rendered. A different location than the one
+/// shown pushes the latter onto the back stack and drops the forward one.
+pub fn record_visit(pane: &Pane, path: &Path) {
+    let history = &pane.history;
+    let previous = history.current.replace(Some(path.to_path_buf()));
+    let Some(previous) = previous.filter(|p| p != path) else {
+        return;
+    };
+    push_bounded(&history.back, snapshot(pane, previous));
+    history.forward.borrow_mut().clear();
+    history.notify();
+}
+
+/// Moves `steps` locations back (or forward) and returns the entry to
+/// show. The caller navigates the pane there and calls `restore`.
+pub fn step(pane: &Pane, forward: bool, steps: usize) -> Option<HistoryEntry> {
+    let history = &pane.history;
+    let current = history.current.borrow().clone()?;
+    let (from, to) = if forward {
+        (&history.forward, &history.back)
+    } else {
+        (&history.back, &history.forward)
+    };
+    if steps == 0 || steps > from.borrow().len() {
+        return None;
+    }
+
+    // Locations skipped over land on the opposite stack, in order
+    push_bounded(to, snapshot(pane, current));
+    for _ in 1..steps {
+        let skipped = from.borrow_mut().pop()?;
+        push_bounded(to, skipped);
+    }
+    let target = from.borrow_mut().pop()?;
+
+    *history.current.borrow_mut() = Some(target.path.clone());
+    history.notify();
+    Some(target)
+}
+
+/// Re-applies the selection and scroll offset of `entry` to the freshly
+/// rendered pane.
+pub fn restore(pane: &Pane, entry: &HistoryEntry) {
+    let selection: Vec<PathBuf> = entry
+        .selection
+        .iter()
+        .filter(|p| std::fs::symlink_metadata(p).is_ok())
+        .cloned()
+        .collect();
+    if !selection.is_empty() {
+        pane.selection.set(selection);
+    }
+    if let Some(scroller) = find_scroller(&pane.content_box) {
+        restore_scroll(&scroller, entry.scroll);
+    }
+}
+
+/// Scroll offset and selection of the location `pane` currently shows.
+pub fn capture(pane: &Pane) -> Option<HistoryEntry> {
+    let current = pane.history.current.borrow().clone()?;
+    Some(snapshot(pane, current))
+}
+
+/// The state of the location shown by `pane`, which is about to be left.
+fn snapshot(pane: &Pane, path: PathBuf) -> HistoryEntry {
+    HistoryEntry {
+        path,
+        scroll: find_scroller(&pane.content_box)
+            .map(|s| s.vadjustment().value())
+            .unwrap_or(0.0),
+        selection: pane.selection.paths(),
+    }
+}
+
+// ─── Scroll Position ───
+
+/// The scrolled window of the view rendered in `container`, if any.
+fn find_scroller(container: &impl IsA<Widget>) -> Option<ScrolledWindow> {
+    let mut child = container.as_ref().first_child();
+    while let Some(widget) = child {
+        if let Some(scroller) = widget.downcast_ref::<ScrolledWindow>() {
+            return Some(scroller.clone());
+        }
+        if let Some(scroller) = find_scroller(&widget) {
+            return Some(scroller);
+        }
+        child = widget.next_sibling();
+    }
+    None
+}
+
+/// Scrolls to `value` as soon as the view is tall enough. Listings
+/// stream in, so this waits for the content for a short while.
+fn restore_scroll(scroller: &ScrolledWindow, value: f64) {
+    if value <= 0.0 {
+        return;
+    }
+    let reachable = move |adj: &gtk4::Adjustment| {
+        let fits = adj.upper() - adj.page_size() >= value;
+        if fits {
+            adj.set_value(value);
+        }
+        fits
+    };
+
+    let adj = scroller.vadjustment();
+    if reachable(&adj) {
+        return;
+    }
+
+    let handler: Rc<RefCell<Option<glib::SignalHandlerId>>> = Rc::new(RefCell::new(None));
+    {
+        let handler_c = handler.clone();
+        let id = adj.connect_changed(move |adj| {
+            if reachable(adj) {
+                if let Some(id) = handler_c.borrow_mut().take() {
+                    adj.disconnect(id);
+                }
+            }
+        });
+        *handler.borrow_mut() = Some(id);
+    }
+    glib::timeout_add_local_once(Duration::from_secs(2), move || {
+        if let Some(id) = handler.borrow_mut().take() {
+            adj.disconnect(id);
+        }
+    });
+}
+
+// ═══════════════════════════════════════════════
+//  Recent Locations Menu
+// ═══════════════════════════════════════════════
+
+/// Long-pressing (or right-clicking) `button` lists the locations of one
+/// stack of the pane returned by `pane`. `on_pick` receives the number
+/// of steps to the chosen location.
+pub fn attach_history_menu(
+    button: &Button,
+    forward: bool,
+    pane: impl Fn() -> Pane + 'static,
+    on_pick: Rc<dyn Fn(usize)>,
+) {
+    let show: Rc<dyn Fn(&Button)> = Rc::new(move |button| {
+        let locations = pane().history.locations(forward);
+        if !locations.is_empty() {
+            show_history_popover(button, &locations, on_pick.clone());
+        }
+    });
+
+    let long_press = GestureLongPress::new();
+    {
+        let button_c = button.clone();
+        let show = show.clone();
+        long_press.connect_pressed(move |g, _, _| {
+            // Keeps the release from also triggering a plain click
+            g.set_state(EventSequenceState::Claimed);
+            show(&button_c);
+        });
+    }
+    button.add_controller(long_press);
+
+    let right_click = GestureClick::builder().button(3).build();
+    {
+        let button_c = button.clone();
+        right_click.connect_pressed(move |_, _, _, _| show(&button_c));
+    }
+    button.add_controller(right_click);
+}
+
+fn show_history_popover(button: &Button, locations: &[PathBuf], on_pick: Rc<dyn Fn(usize)>) {
+    let popover = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    popover.set_parent(button);
+    popover.connect_closed(|p| {
+        let p = p.clone();
+        glib::idle_add_local_once(move || p.unparent());
+    });
+
+    let menu_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(4)
+        .margin_end(4)
+        .build();
+
+    for (i, path) in locations.iter().take(MENU_ENTRIES).enumerate() {
+        let label = Label::builder()
+            .label(display_path(path))
+            .xalign(0.0)
+            .max_width_chars(48)
+            .ellipsize(gtk4::pango::EllipsizeMode::Start)
+            .build();
+        let item = Button::builder()
+            .child(&label)
+            .has_frame(false)
+            .css_classes(vec!["context-menu-item".to_string()])
+            .build();
+
+        let popover_c = popover.clone();
+        let on_pick = on_pick.clone();
+        item.connect_clicked(move |_| {
+            popover_c.popdown();
+            on_pick(i + 1);
+        });
+        menu_box.append(&item);
+    }
+
+    popover.set_child(Some(&menu_box));
+    popover.popup();
+}
@@ -0,0 +1,784 @@
+use crate::config::{AppConfig, GroupBy, SortOrder, ViewMode};
+use crate::filesystem::filter::NameFilter;
+use crate::filesystem::listing::{self, ListingEvent};
+use crate::filesystem::{self, Entry, EntrySortKey};
+use crate::ui::content::{refresh_content, ItemWiring};
+use crate::ui::details_view;
+use crate::ui::pane::Pane;
+use crate::ui::selection::Selection;
+use crate::ui::widgets::FileCard;
+use glib::BoxedAnyObject;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, GridView, Label, ListItem, ListView, NoSelection, Orientation, ScrolledWindow,
+    SignalListItemFactory, Spinner, Widget,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Virtualized Grid / List Views
+// ═══════════════════════════════════════════════
+//
+// Grid and List modes render a `gio::ListStore` through GtkGridView /
+// GtkListView, so only the items on screen have widgets (and request
+// thumbnails). Those widgets are built once, with their controllers,
+// and rebound to other items as the view scrolls. Each model item is a `BoxedAnyObject` holding a
+// `ContentItem`:
+//   Entry  — one card (Grid) or row (List, see `details_view`)
+//   Header — a group title (List with grouping)
+//   Line   — one row of cards (Grid with grouping, since GtkGridView
+//            cannot mix headers and cards)
+// Entries the pane's filter rejects are kept aside, out of the groups.
+
+pub enum ContentItem {
+    Header(String),
+    Entry(Entry),
+    Line(Vec<Entry>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Layout {
+    Grid,
+    List,
+    GroupedGrid,
+}
+
+/// Horizontal space taken by one card in a grouped-grid line
+/// (card margins plus line spacing).
+const CARD_GAP: i32 = 14;
+
+// ─── Model ───
+
+/// A listed entry with its sort key, computed once.
+struct Item {
+    key: EntrySortKey,
+    entry: Entry,
+}
+
+impl Item {
+    fn new(entry: Entry, order: &SortOrder) -> Self {
+        Self {
+            key: filesystem::sort_key(&entry, order),
+            entry,
+        }
+    }
+}
+
+/// The entries shown by the view, per group, mirrored into `store`.
+struct ItemModel {
+    layout: Layout,
+    grouping: GroupBy,
+    order: SortOrder,
+    groups: Vec<(String, Vec<Item>)>,
+    /// Entries hidden by `filter`.
+    hidden: Vec<Item>,
+    filter: NameFilter,
+    /// Every path in `groups` and `hidden` (listing batches may repeat
+    /// watcher inserts).
+    paths: HashSet<PathBuf>,
+    store: gio::ListStore,
+    /// Cards per line (grouped grid only).
+    columns: Cell<usize>,
+}
+
+impl ItemModel {
+    fn new(layout: Layout, grouping: GroupBy, order: SortOrder, filter: NameFilter) -> Self {
+        Self {
+            layout,
+            grouping,
+            order,
+            groups: Vec::new(),
+            hidden: Vec::new(),
+            filter,
+            paths: HashSet::new(),
+            store: gio::ListStore::new::<BoxedAnyObject>(),
+            columns: Cell::new(4),
+        }
+    }
+
+    fn has_header(&self) -> bool {
+        self.grouping != GroupBy::None
+    }
+
+    /// Model items of group `g`, header included.
+    fn group_items(&self, g: usize) -> Vec<BoxedAnyObject> {
+        let (name, items) = &self.groups[g];
+        let mut objects = Vec::new();
+        if self.has_header() {
+            objects.push(BoxedAnyObject::new(ContentItem::Header(name.clone())));
+        }
+        objects.extend(self.entry_items(items));
+        objects
+    }
+
+    /// Model items for `items` alone: one per entry, or one per line.
+    fn entry_items(&self, items: &[Item]) -> Vec<BoxedAnyObject> {
+        match self.layout {
+            Layout::GroupedGrid => items
+                .chunks(self.columns.get())
+                .map(|line| {
+                    let entries = line.iter().map(|i| i.entry.clone()).collect();
+                    BoxedAnyObject::new(ContentItem::Line(entries))
+                })
+                .collect(),
+            Layout::Grid | Layout::List => items
+                .iter()
+                .map(|i| BoxedAnyObject::new(ContentItem::Entry(i.entry.clone())))
+                .collect(),
+        }
+    }
+
+    /// Number of model items used by `n` entries.
+    fn entry_len(&self, n: usize) -> u32 {
+        match self.layout {
+            Layout::GroupedGrid => n.div_ceil(self.columns.get()) as u32,
+            Layout::Grid | Layout::List => n as u32,
+        }
+    }
+
+    /// Store position of the first item (header) of group `g`.
+    fn group_start(&self, g: usize) -> u32 {
+        let header = self.has_header() as u32;
+        self.groups[..g]
+            .iter()
+            .map(|(_, items)| header + self.entry_len(items.len()))
+            .sum()
+    }
+
+    /// Rebuilds the whole store.
+    fn reset(&self) {
+        let items: Vec<BoxedAnyObject> = (0..self.groups.len())
+            .flat_map(|g| self.group_items(g))
+            .collect();
+        self.store.splice(0, self.store.n_items(), &items);
+    }
+
+    /// Changes the cards per line. Returns true if the store was rebuilt.
+    fn set_columns(&self, columns: usize) -> bool {
+        if self.layout != Layout::GroupedGrid || columns == self.columns.get() {
+            return false;
+        }
+        self.columns.set(columns);
+        self.reset();
+        true
+    }
+
+    fn find(&self, path: &Path) -> Option<(usize, usize)> {
+        self.groups.iter().enumerate().find_map(|(g, (_, items))| {
+            items
+                .iter()
+                .position(|i| i.entry.path == path)
+                .map(|i| (g, i))
+        })
+    }
+
+    fn entry(&self, (g, i): (usize, usize)) -> &Entry {
+        &self.groups[g].1[i].entry
+    }
+
+    fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// (shown, total) entries.
+    fn counts(&self) -> (usize, usize) {
+        let shown = self.groups.iter().map(|(_, e)| e.len()).sum::<usize>();
+        (shown, shown + self.hidden.len())
+    }
+
+    fn order(&self) -> Vec<PathBuf> {
+        self.groups
+            .iter()
+            .flat_map(|(_, items)| items.iter().map(|i| i.entry.path.clone()))
+            .collect()
+    }
+
+    /// Index of the group named `key`, added (empty, and not yet in the
+    /// store) if missing. The flag tells whether it was added.
+    fn group_index(&mut self, key: String) -> (usize, bool) {
+        match self.groups.iter().position(|(name, _)| *name == key) {
+            Some(g) => (g, false),
+            None => {
+                let g = self.groups.partition_point(|(name, _)| *name < key);
+                self.groups.insert(g, (key, Vec::new()));
+                (g, true)
+            }
+        }
+    }
+
+    /// Inserts `entry` at its sorted position, creating its group if needed.
+    fn insert(&mut self, entry: Entry) {
+        if !self.paths.insert(entry.path.clone()) {
+            return;
+        }
+        let item = Item::new(entry, &self.order);
+        if !self.filter.matches(&item.entry.name) {
+            self.hidden.push(item);
+            return;
+        }
+        let (g, added) = self.group_index(item.entry.group_key(&self.grouping));
+        if added {
+            self.groups[g].1.push(item);
+            let objects = self.group_items(g);
+            self.store.splice(self.group_start(g), 0, &objects);
+            return;
+        }
+
+        let start = self.group_start(g) + self.has_header() as u32;
+        let items = &self.groups[g].1;
+        let old_len = self.entry_len(items.len());
+        let pos = items.partition_point(|i| i.key < item.key);
+        let object = BoxedAnyObject::new(ContentItem::Entry(item.entry.clone()));
+        self.groups[g].1.insert(pos, item);
+        match self.layout {
+            Layout::GroupedGrid => {
+                // Lines after the insertion point all shift by one card
+                let objects = self.entry_items(&self.groups[g].1);
+                self.store.splice(start, old_len, &objects);
+            }
+            Layout::Grid | Layout::List => self.store.insert(start + pos as u32, &object),
+        }
+    }
+
+    /// Removes the entry at `(g, i)`, dropping its group once empty.
+    fn remove(&mut self, (g, i): (usize, usize)) {
+        let group_start = self.group_start(g);
+        let header = self.has_header() as u32;
+        let old_len = self.entry_len(self.groups[g].1.len());
+        let item = self.groups[g].1.remove(i);
+        self.paths.remove(&item.entry.path);
+
+        if self.groups[g].1.is_empty() {
+            self.groups.remove(g);
+            self.store
+                .splice(group_start, header + old_len, &[] as &[BoxedAnyObject]);
+            return;
+        }
+        match self.layout {
+            Layout::GroupedGrid => {
+                let objects = self.entry_items(&self.groups[g].1);
+                self.store.splice(group_start + header, old_len, &objects);
+            }
+            Layout::Grid | Layout::List => self.store.remove(group_start + header + i as u32),
+        }
+    }
+
+    /// Drops `path` if the filter hides it. Returns true if it did.
+    fn forget_hidden(&mut self, path: &Path) -> bool {
+        let Some(i) = self.hidden.iter().position(|i| i.entry.path == path) else {
+            return false;
+        };
+        self.hidden.remove(i);
+        self.paths.remove(path);
+        true
+    }
+
+    /// Re-sorts every entry into the groups or `hidden` after the filter
+    /// changed, and rebuilds the store.
+    fn set_filter(&mut self, filter: NameFilter) {
+        self.filter = filter;
+        let mut items = std::mem::take(&mut self.hidden);
+        items.extend(
+            std::mem::take(&mut self.groups)
+                .into_iter()
+                .flat_map(|(_, items)| items),
+        );
+        let (shown, hidden): (Vec<Item>, Vec<Item>) = items
+            .into_iter()
+            .partition(|i| self.filter.matches(&i.entry.name));
+        self.hidden = hidden;
+
+        let mut groups: HashMap<String, Vec<Item>> = HashMap::new();
+        for item in shown {
+            let key = item.entry.group_key(&self.grouping);
+            groups.entry(key).or_default().push(item);
+        }
+        self.groups = groups.into_iter().collect();
+        self.groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, items) in &mut self.groups {
+            items.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+        self.reset();
+    }
+
+    /// Adds a batch of listed entries. Each touched group takes the batch
+    /// in one merge and one store splice.
+    fn extend(&mut self, entries: Vec<Entry>) {
+        let mut incoming: HashMap<String, Vec<Item>> = HashMap::new();
+        for entry in entries {
+            if !self.paths.insert(entry.path.clone()) {
+                continue;
+            }
+            let item = Item::new(entry, &self.order);
+            if !self.filter.matches(&item.entry.name) {
+                self.hidden.push(item);
+                continue;
+            }
+            let key = item.entry.group_key(&self.grouping);
+            incoming.entry(key).or_default().push(item);
+        }
+
+        let header = self.has_header() as u32;
+        for (key, mut new) in incoming {
+            new.sort_by(|a, b| a.key.cmp(&b.key));
+            let (g, added) = self.group_index(key);
+            let start = self.group_start(g);
+            let old = std::mem::take(&mut self.groups[g].1);
+            let old_count = old.len();
+            // Items before the first new one keep their store position
+            let first = old.partition_point(|i| i.key < new[0].key);
+            self.groups[g].1 = merge(old, new);
+
+            if added || self.layout == Layout::GroupedGrid {
+                let old_len = if added {
+                    0
+                } else {
+                    header + self.entry_len(old_count)
+                };
+                let objects = self.group_items(g);
+                self.store.splice(start, old_len, &objects);
+            } else {
+                let objects = self.entry_items(&self.groups[g].1[first..]);
+                self.store.splice(
+                    start + header + first as u32,
+                    (old_count - first) as u32,
+                    &objects,
+                );
+            }
+        }
+    }
+}
+
+/// Merges two runs sorted by key into one.
+fn merge(a: Vec<Item>, b: Vec<Item>) -> Vec<Item> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    loop {
+        let take_a = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) => x.key <= y.key,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+        merged.extend(if take_a { a.next() } else { b.next() });
+    }
+    merged
+}
+
+// ─── Item Widgets ───
+
+/// The widgets of one list item, built in the factory's `setup` and
+/// rebound to other model items as the view scrolls.
+struct ItemWidgets {
+    root: Widget,
+    /// Group title (grouped grid).
+    header: Label,
+    /// Line of cards (grouped grid); a plain grid item is a single card.
+    line: Box,
+    /// Cards and their wiring; a grouped grid adds cards as lines need them.
+    cards: RefCell<Vec<(FileCard, ItemWiring)>>,
+    /// Paths shown by the cards, in order.
+    bound: RefCell<Vec<PathBuf>>,
+}
+
+impl ItemWidgets {
+    fn new(layout: Layout, pane: &Pane, config: &Rc<RefCell<AppConfig>>) -> Self {
+        let header = Label::builder()
+            .css_classes(vec!["group-header".to_string()])
+            .halign(Align::Start)
+            .visible(false)
+            .build();
+        let line = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .margin_start(4)
+            .margin_end(4)
+            .build();
+        let mut cards = Vec::new();
+        let root: Widget = if layout == Layout::GroupedGrid {
+            let root = Box::builder().orientation(Orientation::Vertical).build();
+            root.append(&header);
+            root.append(&line);
+            root.upcast()
+        } else {
+            let card = new_card(pane, config);
+            let root = card.0.button.clone().upcast();
+            cards.push(card);
+            root
+        };
+        Self {
+            root,
+            header,
+            line,
+            cards: RefCell::new(cards),
+            bound: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Shows `content`: updates the labels and icons of the existing
+    /// cards, adding cards only when a line is longer than any before.
+    fn bind(&self, content: &ContentItem, pane: &Pane, config: &Rc<RefCell<AppConfig>>) {
+        let entries = match content {
+            ContentItem::Header(name) => {
+                self.header.set_label(name);
+                self.header.set_visible(true);
+                self.line.set_visible(false);
+                return;
+            }
+            ContentItem::Entry(entry) => std::slice::from_ref(entry),
+            ContentItem::Line(entries) => {
+                self.header.set_visible(false);
+                self.line.set_visible(true);
+                entries.as_slice()
+            }
+        };
+
+        let mut cards = self.cards.borrow_mut();
+        while cards.len() < entries.len() {
+            let card = new_card(pane, config);
+            self.line.append(&card.0.button);
+            cards.push(card);
+        }
+        let cfg = config.borrow();
+        let mut bound = self.bound.borrow_mut();
+        for (i, (card, wiring)) in cards.iter().enumerate() {
+            let Some(entry) = entries.get(i) else {
+                card.button.set_visible(false);
+                continue;
+            };
+            card.bind(entry, &cfg);
+            wiring.bind(entry);
+            card.button.set_visible(true);
+            pane.selection.bind(&entry.path, &card.button);
+            bound.push(entry.path.clone());
+        }
+    }
+
+    /// Detaches the cards from the entries they showed.
+    fn unbind(&self, selection: &Selection) {
+        let cards = self.cards.borrow();
+        for ((card, wiring), path) in cards.iter().zip(self.bound.borrow_mut().drain(..)) {
+            selection.unbind(&path, &card.button);
+            wiring.unbind();
+        }
+    }
+}
+
+/// A card wired to the pane, not yet bound to an entry.
+fn new_card(pane: &Pane, config: &Rc<RefCell<AppConfig>>) -> (FileCard, ItemWiring) {
+    let card = FileCard::new(None, &config.borrow());
+    let wiring = ItemWiring::new(&card.button, pane, config.clone());
+    (card, wiring)
+}
+
+// ─── View ───
+
+/// Builds the Grid / List view of the pane's directory. Entries stream
+/// in from a background listing; the view then follows the directory.
+pub fn build_item_view(pane: &Pane, view_mode: &ViewMode, config: Rc<RefCell<AppConfig>>) -> Box {
+    let cfg = config.borrow().clone();
+    let dir = pane.current_path.borrow().clone();
+
+    let layout = match (view_mode, cfg.grouping != GroupBy::None) {
+        (ViewMode::List, _) => Layout::List,
+        (_, false) => Layout::Grid,
+        (_, true) => Layout::GroupedGrid,
+    };
+    let model = Rc::new(RefCell::new(ItemModel::new(
+        layout,
+        cfg.grouping.clone(),
+        cfg.sort_order(),
+        pane.filter_bar.filter(),
+    )));
+    let store = model.borrow().store.clone();
+    {
+        // The selection reads the visible order only when it needs it
+        let model = Rc::downgrade(&model);
+        pane.selection.set_order_source(move || {
+            model
+                .upgrade()
+                .map(|m| m.borrow().order())
+                .unwrap_or_default()
+        });
+    }
+
+    // Item widgets are built once per list item and rebound as the view
+    // scrolls; `widgets` finds them again from the list item
+    let widgets: Rc<RefCell<HashMap<ListItem, Rc<ItemWidgets>>>> =
+        Rc::new(RefCell::new(HashMap::new()));
+    let factory = SignalListItemFactory::new();
+    {
+        let pane = pane.clone();
+        let config = config.clone();
+        let widgets = widgets.clone();
+        factory.connect_setup(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            // Clicks are handled by the item widgets themselves
+            list_item.set_activatable(false);
+            list_item.set_selectable(false);
+            let item_widgets = ItemWidgets::new(layout, &pane, &config);
+            list_item.set_child(Some(&item_widgets.root));
+            widgets
+                .borrow_mut()
+                .insert(list_item.clone(), Rc::new(item_widgets));
+        });
+    }
+    {
+        let pane = pane.clone();
+        let config = config.clone();
+        let widgets = widgets.clone();
+        factory.connect_bind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            let Some(boxed) = list_item.item().and_downcast::<BoxedAnyObject>() else {
+                return;
+            };
+            let item_widgets = widgets.borrow().get(list_item).cloned();
+            if let Some(item_widgets) = item_widgets {
+                item_widgets.bind(&boxed.borrow::<ContentItem>(), &pane, &config);
+            }
+        });
+    }
+    {
+        let selection = pane.selection.clone();
+        let widgets_c = widgets.clone();
+        factory.connect_unbind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            if let Some(item_widgets) = widgets_c.borrow().get(list_item) {
+                item_widgets.unbind(&selection);
+            }
+        });
+    }
+    factory.connect_teardown(move |_, obj| {
+        if let Some(list_item) = obj.downcast_ref::<ListItem>() {
+            widgets.borrow_mut().remove(list_item);
+        }
+    });
+
+    let selection_model = NoSelection::new(Some(store.clone()));
+    let view: Widget = match layout {
+        Layout::Grid => GridView::builder()
+            .model(&selection_model)
+            .factory(&factory)
+            .min_columns(2)
+            .max_columns(20)
+            .css_classes(vec!["content-grid".to_string()])
+            .build()
+            .upcast(),
+        Layout::List => details_view::build_details_view(pane, &store, config.clone()).upcast(),
+        Layout::GroupedGrid => ListView::builder()
+            .model(&selection_model)
+            .factory(&factory)
+            .css_classes(vec!["content-list".to_string()])
+            .build()
+            .upcast(),
+    };
+
+    let scrolled = ScrolledWindow::builder()
+        .child(&view)
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+
+    // Grouped grid: re-chunk the lines whenever the width fits another
+    // number of cards
+    if layout == Layout::GroupedGrid {
+        let model = model.clone();
+        let cell = cfg.icon_size.max(48) + 40 + CARD_GAP;
+        // Re-chunk lines on the next frame after the viewport width changes
+        let pending = Rc::new(Cell::new(false));
+        let scrolled_w = scrolled.downgrade();
+        let relayout = move || {
+            if pending.replace(true) {
+                return;
+            }
+            let Some(scrolled) = scrolled_w.upgrade() else {
+                return;
+            };
+            let model = model.clone();
+            let pending = pending.clone();
+            scrolled.add_tick_callback(move |w, _| {
+                pending.set(false);
+                let width = w.width() - 8;
+                if width > 0 {
+                    let columns = (width / cell).max(1) as usize;
+                    model.borrow().set_columns(columns);
+                }
+                glib::ControlFlow::Break
+            });
+        };
+        relayout();
+        scrolled
+            .hadjustment()
+            .connect_page_size_notify(move |_| relayout());
+    }
+
+    let empty = Label::builder()
+        .label("This folder is empty")
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .halign(Align::Center)
+        .valign(Align::Center)
+        .vexpand(true)
+        .visible(false)
+        .build();
+
+    // Loading indicator, shown until the listing is complete
+    let spinner = Spinner::builder().spinning(true).build();
+    let loading = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .halign(Align::Center)
+        .margin_top(4)
+        .margin_bottom(4)
+        .build();
+    loading.append(&spinner);
+    loading.append(
+        &Label::builder()
+            .label("Loading…")
+            .css_classes(vec!["inspector-subtitle".to_string()])
+            .build(),
+    );
+
+    let root = Box::builder()
+        .orientation(Orientation::Vertical)
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+    root.append(&loading);
+    root.append(&scrolled);
+    root.append(&empty);
+
+    let update_placeholders: Rc<dyn Fn()> = {
+        let model = model.clone();
+        let loading = loading.clone();
+        let filter_bar = pane.filter_bar.clone();
+        Rc::new(move || {
+            let model = model.borrow();
+            let is_empty = model.is_empty();
+            let (_, total) = model.counts();
+            scrolled.set_visible(!is_empty);
+            empty.set_visible(is_empty && !loading.is_visible());
+            empty.set_label(if total > 0 {
+                "No matching items"
+            } else {
+                "This folder is empty"
+            });
+            filter_bar.set_count(Some(model.counts()));
+        })
+    };
+
+    // The filter bar re-sorts the entries in place
+    {
+        let model = model.clone();
+        let pane_c = pane.clone();
+        let update = update_placeholders.clone();
+        *pane.refilter.borrow_mut() = Some(Rc::new(move || {
+            model.borrow_mut().set_filter(pane_c.filter_bar.filter());
+            update();
+        }));
+    }
+
+    // Live updates: only the touched entries change in the model.
+    // Watching starts right away so nothing is missed while listing.
+    {
+        let pane_c = pane.clone();
+        let model = model.clone();
+        let update = update_placeholders.clone();
+        let watched = dir.clone();
+        pane.watches.watch(&watched, move |changed| {
+            apply_changes(&pane_c, &dir, &model, changed, config.clone());
+            update();
+        });
+    }
+
+    let handle = listing::start_listing(
+        pane.current_path.borrow().clone(),
+        cfg.show_hidden,
+        cfg.sort_order(),
+        move |event| {
+            match event {
+                ListingEvent::Batch(entries) => {
+                    model.borrow_mut().extend(entries);
+                }
+                ListingEvent::Finished => {
+                    spinner.set_spinning(false);
+                    loading.set_visible(false);
+                }
+            }
+            update_placeholders();
+        },
+    );
+    *pane.listing.borrow_mut() = Some(handle);
+
+    root
+}
+
+// ═══════════════════════════════════════════════
+//  Live Updates
+// ═══════════════════════════════════════════════
+
+/// Applies a batch of watcher events to the model of `dir`.
+fn apply_changes(
+    pane: &Pane,
+    dir: &Path,
+    model: &Rc<RefCell<ItemModel>>,
+    changed: &[PathBuf],
+    config: Rc<RefCell<AppConfig>>,
+) {
+    // The directory itself went away: show the nearest existing parent
+    if !dir.is_dir() {
+        let mut target = dir.to_path_buf();
+        while !target.is_dir() {
+            match target.parent() {
+                Some(parent) => target = parent.to_path_buf(),
+                None => return,
+            }
+        }
+        *pane.current_path.borrow_mut() = target;
+        refresh_content(pane, config);
+        return;
+    }
+
+    let show_hidden = config.borrow().show_hidden;
+    for path in changed {
+        if path.parent() != Some(dir) {
+            continue;
+        }
+        let entry = Entry::from_path(path).filter(|e| show_hidden || !e.name.starts_with('.'));
+        // A filtered-out entry is simply listed again, shown or not
+        model.borrow_mut().forget_hidden(path);
+        let found = model.borrow().find(path);
+
+        match (found, entry) {
+            (None, None) => {}
+            (Some(at), None) => {
+                model.borrow_mut().remove(at);
+                pane.selection.unregister(path);
+            }
+            (Some((g, i)), Some(entry)) => {
+                let unchanged = {
+                    let model = model.borrow();
+                    let old = model.entry((g, i));
+                    old.is_dir == entry.is_dir
+                        && old.size == entry.size
+                        && old.modified == entry.modified
+                };
+                if !unchanged {
+                    let mut model = model.borrow_mut();
+                    model.remove((g, i));
+                    model.insert(entry);
+                }
+            }
+            (None, Some(entry)) => model.borrow_mut().insert(entry),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::config::keybindings::{Action, Chord, KeyMatch, Keymap};
+use crate::ui::pane::Pane;
+use gtk4::gdk::{Key, ModifierType};
+use gtk4::graphene::Rect;
+use gtk4::prelude::*;
+use gtk4::ScrolledWindow;
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// ═══════════════════════════════════════════════
+//  Keyboard Navigation
+// ═══════════════════════════════════════════════
+//
+// `KeyReader` turns key presses into the actions bound in the keymap,
+// collecting the keys of sequences ("g g") as they are typed. Movement
+// walks the selection's display order: left / right step through it,
+// up / down go to the nearest item of the next row as laid out on
+// screen. Shift with a movement key extends the selection instead.
+
+/// How long a sequence waits for its next key.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// What a key press amounts to.
+pub enum KeyPress {
+    /// A bound action; `extend` for a movement key pressed with Shift.
+    Action {
+        action: Action,
+        extend: bool,
+    },
+    /// Part of a sequence, waiting for its next key.
+    Pending,
+    /// A lone modifier key.
+    Modifier,
+    Unbound,
+}
+
+pub struct KeyReader {
+    keymap: Keymap,
+    /// Keys of the sequence typed so far.
+    typed: RefCell<Vec<Chord>>,
+    last: Cell<Option<Instant>>,
+}
+
+impl KeyReader {
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            keymap,
+            typed: RefCell::new(Vec::new()),
+            last: Cell::new(None),
+        }
+    }
+
+    pub fn press(&self, key: Key, state: ModifierType) -> KeyPress {
+        let Some(chord) = Chord::from_event(key, state) else {
+            return KeyPress::Modifier;
+        };
+        let now = Instant::now();
+        let stale = self
+            .last
+            .replace(Some(now))
+            .is_some_and(|t| now - t > SEQUENCE_TIMEOUT);
+        let mut typed = self.typed.borrow_mut();
+        if stale {
+            typed.clear();
+        }
+
+        typed.push(chord);
+        let mut found = self.keymap.lookup(&typed);
+        if matches!(found, KeyMatch::None) && typed.len() > 1 {
+            // A broken sequence: the last key may start a new one
+            typed.clear();
+            typed.push(chord);
+            found = self.keymap.lookup(&typed);
+        }
+        match found {
+            KeyMatch::Action(action) => {
+                typed.clear();
+                KeyPress::Action {
+                    action,
+                    extend: false,
+                }
+            }
+            KeyMatch::Prefix => KeyPress::Pending,
+            KeyMatch::None => {
+                typed.clear();
+                if !chord.mods.contains(ModifierType::SHIFT_MASK) {
+                    return KeyPress::Unbound;
+                }
+                let plain = Chord {
+                    key: chord.key,
+                    mods: chord.mods.difference(ModifierType::SHIFT_MASK),
+                };
+                match self.keymap.lookup(&[plain]) {
+                    KeyMatch::Action(action) if motion(action).is_some() => KeyPress::Action {
+                        action,
+                        extend: true,
+                    },
+                    _ => KeyPress::Unbound,
+                }
+            }
+        }
+    }
+
+    /// Drops a half-typed sequence.
+    pub fn reset(&self) {
+        self.typed.borrow_mut().clear();
+    }
+}
+
+// ─── Cursor Movement ───
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+}
+
+/// The movement an action stands for, if any.
+pub fn motion(action: Action) -> Option<Motion> {
+    match action {
+        Action::MoveLeft => Some(Motion::Left),
+        Action::MoveRight => Some(Motion::Right),
+        Action::MoveUp => Some(Motion::Up),
+        Action::MoveDown => Some(Motion::Down),
+        Action::PageUp => Some(Motion::PageUp),
+        Action::PageDown => Some(Motion::PageDown),
+        Action::First => Some(Motion::First),
+        Action::Last => Some(Motion::Last),
+        _ => None,
+    }
+}
+
+/// Moves the keyboard cursor of `pane` and selects the item it lands on;
+/// with `extend`, the range from the selection anchor instead.
+pub fn move_cursor(pane: &Pane, motion: Motion, extend: bool) {
+    let order = pane.selection.order();
+    if order.is_empty() {
+        return;
+    }
+    let last = order.len() - 1;
+    let current = pane
+        .selection
+        .cursor()
+        .and_then(|c| order.iter().position(|p| *p == c));
+
+    let target = match (current, motion) {
+        (_, Motion::First) => 0,
+        (_, Motion::Last) => last,
+        // Nothing under the cursor yet: start at the top
+        (None, _) => 0,
+        (Some(i), Motion::Left) => i.saturating_sub(1),
+        (Some(i), Motion::Right) => (i + 1).min(last),
+        (Some(i), Motion::Up) => row_neighbour(pane, &order, i, false),
+        (Some(i), Motion::Down) => row_neighbour(pane, &order, i, true),
+        (Some(i), Motion::PageUp) => i.saturating_sub(page_step(pane, &order, i)),
+        (Some(i), Motion::PageDown) => (i + page_step(pane, &order, i)).min(last),
+    };
+
+    let path = &order[target];
+    if extend {
+        pane.selection.extend_to(path, false);
+    } else {
+        pane.selection.select_only(path);
+    }
+    scroll_to(pane, &order, target);
+}
+
+/// The scrolled window of the pane's view.
+fn scroller(pane: &Pane) -> Option<ScrolledWindow> {
+    pane.selection.widgets().into_iter().find_map(|(_, w)| {
+        w.ancestor(ScrolledWindow::static_type())
+            .and_downcast::<ScrolledWindow>()
+    })
+}
+
+/// Where the shown item widgets are, relative to the scrolled window.
+fn layout(pane: &Pane, scroller: &ScrolledWindow) -> Vec<(PathBuf, Rect)> {
+    pane.selection
+        .widgets()
+        .into_iter()
+        .filter(|(_, w)| w.is_mapped())
+        .filter_map(|(p, w)| w.compute_bounds(scroller).map(|r| (p, r)))
+        .collect()
+}
+
+/// Items per row: the longest run of widgets sharing a top edge.
+fn columns(boxes: &[(PathBuf, Rect)]) -> usize {
+    boxes
+        .iter()
+        .map(|(_, a)| {
+            boxes
+                .iter()
+                .filter(|(_, b)| (a.y() - b.y()).abs() < 1.0)
+                .count()
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+/// The item above or below `order[i]`: the one nearest horizontally in
+/// the next row on screen, or a row's worth further in display order
+/// when that row has no widgets yet.
+fn row_neighbour(pane: &Pane, order: &[PathBuf], i: usize, down: bool) -> usize {
+    let last = order.len() - 1;
+    let boxes = scroller(pane).map(|s| layout(pane, &s)).unwrap_or_default();
+    let current = boxes.iter().find(|(p, _)| *p == order[i]).map(|(_, r)| *r);
+
+    if let Some(cur) = current {
+        let center = cur.x() + cur.width() / 2.0;
+        let gap = |r: &Rect| {
+            if down {
+                r.y() - cur.y()
+            } else {
+                cur.y() - r.y()
+            }
+        };
+        let nearest = boxes
+            .iter()
+            .filter(|(_, r)| gap(r) >= cur.height() - 1.0)
+            .min_by(|(_, a), (_, b)| {
+                if (gap(a) - gap(b)).abs() > 1.0 {
+                    gap(a).total_cmp(&gap(b))
+                } else {
+                    let da = (a.x() + a.width() / 2.0 - center).abs();
+                    let db = (b.x() + b.width() / 2.0 - center).abs();
+                    da.total_cmp(&db)
+                }
+            });
+        if let Some(index) = nearest.and_then(|(p, _)| order.iter().position(|o| o == p)) {
+            return index;
+        }
+    }
+
+    let step = columns(&boxes);
+    if down {
+        (i + step).min(last)
+    } else {
+        i.saturating_sub(step)
+    }
+}
+
+/// Items in a screenful, less one row so the old position stays in view.
+fn page_step(pane: &Pane, order: &[PathBuf], i: usize) -> usize {
+    let Some(scroller) = scroller(pane) else {
+        return 1;
+    };
+    let boxes = layout(pane, &scroller);
+    let row_height = boxes
+        .iter()
+        .find(|(p, _)| *p == order[i])
+        .or(boxes.first())
+        .map(|(_, r)| r.height() as f64)
+        .filter(|h| *h > 0.0);
+    let Some(row_height) = row_height else {
+        return 1;
+    };
+    let rows = (scroller.vadjustment().page_size() / row_height) as usize;
+    rows.saturating_sub(1).max(1) * columns(&boxes)
+}
+
+/// Scrolls the view just enough to show `order[index]`.
+fn scroll_to(pane: &Pane, order: &[PathBuf], index: usize) {
+    let Some(scroller) = scroller(pane) else {
+        return;
+    };
+    let adj = scroller.vadjustment();
+    let page = adj.page_size();
+    let bounds = pane
+        .selection
+        .widget(&order[index])
+        .and_then(|w| w.compute_bounds(&scroller));
+    match bounds {
+        Some(r) => {
+            let top = r.y() as f64;
+            let bottom = (r.y() + r.height()) as f64;
+            if top < 0.0 {
+                adj.set_value(adj.value() + top);
+            } else if bottom > page {
+                adj.set_value(adj.value() + bottom - page);
+            }
+        }
+        // Not laid out (virtualized views): jump to its estimated spot
+        None => {
+            let fraction = index as f64 / order.len() as f64;
+            adj.set_value(fraction * adj.upper() - page / 2.0);
+        }
+    }
+}
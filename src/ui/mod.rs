@@ -1,15 +1,34 @@
 // ─── UI Module ───
 // GTK4 widgets, layout panels, and window assembly.
 
+pub mod actions;
+pub mod clipboard;
+pub mod command_palette;
 pub mod content;
 pub mod context_menu;
+pub mod details_view;
 pub mod drag_source;
+pub mod drop_target;
+pub mod filter_bar;
 pub mod graph_view;
 pub mod hamburger;
+pub mod history;
 pub mod inspector;
+pub mod item_view;
+pub mod keyboard;
+pub mod open_with;
+pub mod pane;
+pub mod path_bar;
 pub mod preview;
+pub mod quick_finder;
+pub mod search_bar;
+pub mod search_view;
+pub mod selection;
 pub mod settings;
 pub mod sidebar;
+pub mod tabs;
+pub mod transfer_dialog;
+pub mod trash_view;
 pub mod tree_view;
 pub mod widgets;
 pub mod window;
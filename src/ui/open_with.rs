@@ -0,0 +1,387 @@
+use crate::core::apps::{self, DesktopApp};
+use crate::filesystem;
+use crate::ui::context_menu::context_menu_button;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, Button, CheckButton, Image, Label, ListBox, ListBoxRow, Orientation, Popover,
+    ScrolledWindow, SearchEntry, Separator, Widget, Window,
+};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Opening Files
+// ═══════════════════════════════════════════════
+//
+// Files open with the default application for their detected MIME type,
+// so an extensionless script or a misnamed image still goes to the right
+// program. Applications are read from the XDG `.desktop` files (see
+// `core::apps`); without a default, `open` (xdg-open) decides.
+//
+// The file menu's "Open With…" lists the applications declaring the
+// file's type, each with a "Set as Default" star, and "Other
+// Application…" opens a searchable list of everything installed.
+
+/// Opens `path` with the default application for its type.
+pub fn open_default(path: &Path) {
+    let mime = filesystem::detect_mime(path, path.is_dir());
+    let apps = apps::load_apps();
+    let default =
+        apps::default_app_id(&apps, &mime).and_then(|id| apps.into_iter().find(|a| a.id == id));
+    match default {
+        Some(app) => launch(&app, &[path.to_path_buf()]),
+        None => {
+            if let Err(e) = open::that(path) {
+                eprintln!("[open-with] Cannot open {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Launches `app` with `paths`.
+pub fn launch(app: &DesktopApp, paths: &[PathBuf]) {
+    let Some(info) = gio::DesktopAppInfo::from_filename(&app.path) else {
+        eprintln!("[open-with] Invalid desktop file {}", app.path.display());
+        return;
+    };
+    let files: Vec<gio::File> = paths.iter().map(gio::File::for_path).collect();
+    if let Err(e) = info.launch(&files, gio::AppLaunchContext::NONE) {
+        eprintln!("[open-with] Cannot launch {}: {}", app.name, e);
+    }
+}
+
+fn set_default(mime: &str, app: &DesktopApp) {
+    if let Err(e) = apps::set_default(mime, &app.id) {
+        eprintln!(
+            "[open-with] Cannot set {} as default for {}: {}",
+            app.id, mime, e
+        );
+    }
+}
+
+fn app_icon(app: &DesktopApp, size: i32) -> Image {
+    let image = match app.icon.as_deref() {
+        Some(icon) if icon.starts_with('/') => Image::from_file(icon),
+        Some(icon) => Image::from_icon_name(icon),
+        None => Image::from_icon_name("application-x-executable-symbolic"),
+    };
+    image.set_pixel_size(size);
+    image
+}
+
+// ═══════════════════════════════════════════════
+//  "Open With" Menu
+// ═══════════════════════════════════════════════
+
+/// Shows the applications for `paths` (typed by the first one) in a
+/// popover on `anchor`.
+pub fn show_open_with_menu(anchor: &Widget, paths: Vec<PathBuf>) {
+    let Some(first) = paths.first() else {
+        return;
+    };
+    let mime = filesystem::detect_mime(first, first.is_dir());
+    let all_apps = apps::load_apps();
+    let handlers = apps::apps_for_mime(&all_apps, &mime);
+    let default = apps::default_app_id(&all_apps, &mime);
+
+    let popover = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    popover.set_parent(anchor);
+    {
+        let popover_c = popover.clone();
+        anchor.connect_destroy(move |_| popover_c.unparent());
+    }
+    // A new popover is built every time; drop it once closed
+    popover.connect_closed(|p| {
+        let p = p.clone();
+        glib::idle_add_local_once(move || p.unparent());
+    });
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(4)
+        .margin_end(4)
+        .build();
+    vbox.append(
+        &Label::builder()
+            .label(format!(
+                "Open With ({})",
+                filesystem::mime_description(&mime)
+            ))
+            .css_classes(vec!["context-menu-title".to_string()])
+            .halign(Align::Start)
+            .margin_start(8)
+            .build(),
+    );
+
+    if handlers.is_empty() {
+        vbox.append(
+            &Label::builder()
+                .label("No applications for this type")
+                .css_classes(vec!["inspector-subtitle".to_string()])
+                .halign(Align::Start)
+                .margin_start(8)
+                .build(),
+        );
+    }
+
+    let paths = Rc::new(paths);
+    for app in handlers {
+        let is_default = default.as_deref() == Some(app.id.as_str());
+        let row = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(2)
+            .build();
+
+        let content = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(10)
+            .build();
+        content.append(&app_icon(&app, 16));
+        content.append(
+            &Label::builder()
+                .label(&app.name)
+                .xalign(0.0)
+                .hexpand(true)
+                .build(),
+        );
+        let open_btn = Button::builder()
+            .child(&content)
+            .has_frame(false)
+            .hexpand(true)
+            .css_classes(vec!["context-menu-item".to_string()])
+            .build();
+
+        let default_btn = Button::builder()
+            .icon_name(if is_default {
+                "starred-symbolic"
+            } else {
+                "non-starred-symbolic"
+            })
+            .tooltip_text(if is_default {
+                "Default Application"
+            } else {
+                "Set as Default"
+            })
+            .has_frame(false)
+            .sensitive(!is_default)
+            .css_classes(vec!["context-menu-item".to_string()])
+            .build();
+
+        let app = Rc::new(app);
+        {
+            let app = app.clone();
+            let paths = paths.clone();
+            let popover_c = popover.clone();
+            open_btn.connect_clicked(move |_| {
+                popover_c.popdown();
+                launch(&app, &paths);
+            });
+        }
+        {
+            let mime = mime.clone();
+            let popover_c = popover.clone();
+            default_btn.connect_clicked(move |_| {
+                popover_c.popdown();
+                set_default(&mime, &app);
+            });
+        }
+
+        row.append(&open_btn);
+        row.append(&default_btn);
+        vbox.append(&row);
+    }
+
+    vbox.append(
+        &Separator::builder()
+            .orientation(Orientation::Horizontal)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build(),
+    );
+    let other_btn = context_menu_button("view-more-symbolic", "Other Application…");
+    {
+        let popover_c = popover.clone();
+        let anchor = anchor.clone();
+        other_btn.connect_clicked(move |_| {
+            popover_c.popdown();
+            show_app_chooser(&anchor, paths.to_vec(), &mime);
+        });
+    }
+    vbox.append(&other_btn);
+
+    popover.set_child(Some(&vbox));
+    popover.popup();
+}
+
+// ═══════════════════════════════════════════════
+//  "Other Application" Dialog
+// ═══════════════════════════════════════════════
+
+/// Lets the user pick any installed application, with a search filter
+/// and an option to make it the default for `mime`.
+pub fn show_app_chooser(anchor: &Widget, paths: Vec<PathBuf>, mime: &str) {
+    let apps: Rc<Vec<DesktopApp>> = Rc::new(
+        apps::load_apps()
+            .into_iter()
+            .filter(|a| !a.no_display)
+            .collect(),
+    );
+
+    let window = Window::builder()
+        .title("Open With")
+        .default_width(400)
+        .default_height(480)
+        .modal(true)
+        .css_classes(vec!["transfer-dialog".to_string()])
+        .build();
+    if let Some(parent) = anchor.root().and_downcast::<Window>() {
+        window.set_transient_for(Some(&parent));
+    }
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(10)
+        .margin_top(16)
+        .margin_bottom(16)
+        .margin_start(16)
+        .margin_end(16)
+        .build();
+
+    let subject = match paths.as_slice() {
+        [single] => format!(
+            "Open “{}” with",
+            single
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        ),
+        _ => format!("Open {} items with", paths.len()),
+    };
+    vbox.append(
+        &Label::builder()
+            .label(&subject)
+            .css_classes(vec!["context-menu-title".to_string()])
+            .halign(Align::Start)
+            .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+            .build(),
+    );
+
+    let search = SearchEntry::builder()
+        .placeholder_text("Search applications…")
+        .build();
+    vbox.append(&search);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::Single)
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    for app in apps.iter() {
+        let row_box = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(10)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+        row_box.append(&app_icon(app, 24));
+        row_box.append(
+            &Label::builder()
+                .label(&app.name)
+                .xalign(0.0)
+                .hexpand(true)
+                .build(),
+        );
+        list.append(&ListBoxRow::builder().child(&row_box).build());
+    }
+    {
+        let apps = apps.clone();
+        let search = search.clone();
+        list.set_filter_func(move |row| {
+            let query = search.text().to_lowercase();
+            let Some(app) = apps.get(row.index() as usize) else {
+                return false;
+            };
+            query.is_empty() || app.name.to_lowercase().contains(&query)
+        });
+    }
+    {
+        let list_c = list.clone();
+        search.connect_search_changed(move |_| list_c.invalidate_filter());
+    }
+    vbox.append(
+        &ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .build(),
+    );
+
+    let make_default = CheckButton::builder()
+        .label(format!(
+            "Always use for {}",
+            filesystem::mime_description(mime)
+        ))
+        .build();
+    vbox.append(&make_default);
+
+    let button_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(Align::End)
+        .build();
+    let cancel_btn = Button::builder()
+        .label("Cancel")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let open_btn = Button::builder()
+        .label("Open")
+        .sensitive(false)
+        .css_classes(vec!["btn-primary".to_string()])
+        .build();
+    button_row.append(&cancel_btn);
+    button_row.append(&open_btn);
+    vbox.append(&button_row);
+    window.set_child(Some(&vbox));
+
+    // Open with the selected row (button, Enter or double-click)
+    let confirm: Rc<dyn Fn()> = {
+        let window = window.clone();
+        let list = list.clone();
+        let mime = mime.to_string();
+        Rc::new(move || {
+            let Some(app) = list
+                .selected_row()
+                .and_then(|row| apps.get(row.index() as usize))
+            else {
+                return;
+            };
+            if make_default.is_active() {
+                set_default(&mime, app);
+            }
+            launch(app, &paths);
+            window.close();
+        })
+    };
+    {
+        let open_btn = open_btn.clone();
+        list.connect_row_selected(move |_, row| open_btn.set_sensitive(row.is_some()));
+    }
+    {
+        let confirm = confirm.clone();
+        list.connect_row_activated(move |_, _| confirm());
+    }
+    open_btn.connect_clicked(move |_| confirm());
+    {
+        let window = window.clone();
+        cancel_btn.connect_clicked(move |_| window.close());
+    }
+
+    window.present();
+    search.grab_focus();
+}
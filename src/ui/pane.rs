@@ -0,0 +1,442 @@
+use crate::config::{AppConfig, ViewMode};
+use crate::core::worker::WorkerHandle;
+use crate::filesystem::journal::{self, FileOp, MovedItem};
+use crate::filesystem::watch::WatchSet;
+use crate::filesystem::{self, Entry};
+use crate::transfer::{TransferKind, TransferReport};
+use crate::ui::clipboard;
+use crate::ui::content::refresh_content;
+use crate::ui::filter_bar::FilterBar;
+use crate::ui::history::History;
+use crate::ui::path_bar::PathBar;
+use crate::ui::search_bar::SearchBar;
+use crate::ui::selection::{self, Selection};
+use crate::ui::tabs::{self, Tabs};
+use crate::ui::transfer_dialog::run_transfer;
+use gtk4::prelude::*;
+use gtk4::{Box, GestureClick, Label, Orientation, Overlay, PropagationPhase};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+
+// ═══════════════════════════════════════════════
+//  Dual-Pane State
+// ═══════════════════════════════════════════════
+//
+// A window hosts two independent panes. Each pane owns its path,
+// view mode, selection, back / forward history and tabs, plus the
+// widgets that render them.
+// `Panes` tracks which pane has focus — the toolbar, the sidebar and
+// keyboard shortcuts always act on the active pane, while "copy/move
+// to other pane" targets the inactive one.
+
+/// Identifies one of the two panes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+// ─── Single Pane ───
+
+type Refilter = Rc<dyn Fn()>;
+
+/// Per-pane state and widgets. Cheap to clone — every field is a handle.
+#[derive(Clone)]
+pub struct Pane {
+    pub side: Side,
+    pub current_path: Rc<RefCell<PathBuf>>,
+    pub selection: Rc<Selection>,
+    pub history: Rc<History>,
+    pub tabs: Rc<Tabs>,
+    /// Directories the current view watches for live updates.
+    pub watches: Rc<WatchSet>,
+    /// Background listing feeding the current Grid / List view.
+    pub listing: Rc<RefCell<Option<WorkerHandle>>>,
+    /// Search whose results the pane shows instead of its folder.
+    pub search: Rc<RefCell<Option<WorkerHandle>>>,
+    pub view_mode: Rc<RefCell<ViewMode>>,
+    pub root: Box,
+    pub content_box: Box,
+    pub path_bar: PathBar,
+    pub search_bar: SearchBar,
+    pub filter_bar: FilterBar,
+    /// Re-applies the filter bar's filter to the current view (set by
+    /// the views that support filtering).
+    pub refilter: Rc<RefCell<Option<Refilter>>>,
+    pub inspector_info: Label,
+    panes: Weak<Panes>,
+}
+
+impl Pane {
+    fn new(
+        side: Side,
+        start_path: PathBuf,
+        view_mode: ViewMode,
+        inspector_info: Label,
+        panes: Weak<Panes>,
+    ) -> Self {
+        let root = Box::builder()
+            .orientation(Orientation::Vertical)
+            .hexpand(true)
+            .vexpand(true)
+            .css_classes(vec!["pane".to_string()])
+            .build();
+
+        // Pane header: path bar of this pane
+        let pane_header = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(8)
+            .css_classes(vec!["pane-header".to_string()])
+            .build();
+
+        let path_bar = PathBar::new();
+        pane_header.append(&path_bar.root);
+        root.append(&pane_header);
+
+        // Tab row of this pane
+        let tabs = Tabs::new();
+        root.append(&tabs.bar);
+
+        // Search bar, hidden until opened
+        let search_bar = SearchBar::new();
+        root.append(&search_bar.root);
+
+        // Type-to-filter bar, hidden until typed into
+        let filter_bar = FilterBar::new();
+        root.append(&filter_bar.root);
+        let refilter: Rc<RefCell<Option<Refilter>>> = Rc::new(RefCell::new(None));
+        {
+            let refilter = refilter.clone();
+            filter_bar.connect_changed(move || {
+                let apply = refilter.borrow().clone();
+                if let Some(apply) = apply {
+                    apply();
+                }
+            });
+        }
+
+        // Content area. Every view brings its own scrolled window, so the
+        // Grid / List views can virtualize their items.
+        let content_box = Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .margin_top(8)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_bottom(8)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        // Overlay hosts the rubber-band rectangle above the items
+        let content_overlay = Overlay::builder()
+            .child(&content_box)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+        root.append(&content_overlay);
+
+        let view_mode = Rc::new(RefCell::new(view_mode));
+        let selection = Selection::new();
+
+        {
+            let view_mode = view_mode.clone();
+            selection::attach_rubber_band(&content_overlay, selection.clone(), move || {
+                matches!(*view_mode.borrow(), ViewMode::Grid | ViewMode::List)
+            });
+        }
+        {
+            let selection = Rc::downgrade(&selection);
+            clipboard::connect_cut_changed(move || {
+                if let Some(selection) = selection.upgrade() {
+                    selection.refresh_cut_marks();
+                }
+            });
+        }
+        {
+            let info = inspector_info.clone();
+            selection.connect_changed(move |paths| {
+                info.set_label(&describe_selection(paths));
+            });
+        }
+
+        Self {
+            side,
+            current_path: Rc::new(RefCell::new(start_path)),
+            selection,
+            history: History::new(),
+            tabs,
+            watches: WatchSet::new(),
+            listing: Rc::new(RefCell::new(None)),
+            search: Rc::new(RefCell::new(None)),
+            view_mode,
+            root,
+            content_box,
+            path_bar,
+            search_bar,
+            filter_bar,
+            refilter,
+            inspector_info,
+            panes,
+        }
+    }
+
+    /// The pane on the opposite side of the window.
+    pub fn other(&self) -> Option<Pane> {
+        self.panes
+            .upgrade()
+            .map(|p| p.pane(self.side.opposite()).clone())
+    }
+
+    /// Re-renders this pane and the other one (after files changed in both).
+    pub fn refresh_both(&self, config: &Rc<RefCell<AppConfig>>) {
+        if let Some(panes) = self.panes.upgrade() {
+            panes.refresh_both(config);
+        }
+    }
+
+    /// Stops the listing or search feeding the current view.
+    pub fn cancel_streams(&self) {
+        if let Some(listing) = self.listing.borrow_mut().take() {
+            listing.cancel();
+        }
+        if let Some(search) = self.search.borrow_mut().take() {
+            search.cancel();
+        }
+    }
+
+    /// Shows the current path in the pane header.
+    pub fn update_breadcrumb(&self) {
+        self.path_bar.set_path(&self.current_path.borrow());
+        tabs::update_label(self);
+    }
+}
+
+/// Inspector text for the current selection.
+fn describe_selection(paths: &[PathBuf]) -> String {
+    match paths {
+        [] => "Select a file to inspect".to_string(),
+        [path] => match Entry::from_path(path) {
+            Some(entry) => format!(
+                "{}  •  {}  •  {}",
+                entry.name,
+                entry.size_display(),
+                entry.modified_display()
+            ),
+            None => path.to_string_lossy().to_string(),
+        },
+        _ => {
+            let total: u64 = paths
+                .iter()
+                .filter_map(|p| std::fs::metadata(p).ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum();
+            format!(
+                "{} items selected  •  {}",
+                paths.len(),
+                filesystem::format_size(total)
+            )
+        }
+    }
+}
+
+/// Abbreviates the home directory prefix as `~`.
+pub fn display_path(path: &Path) -> String {
+    let home = dirs::home_dir().unwrap_or_default();
+    if path.starts_with(&home) {
+        format!("~/{}", path.strip_prefix(&home).unwrap_or(path).display())
+    } else {
+        path.to_string_lossy().to_string()
+    }
+}
+
+// ─── Pane Pair ───
+
+/// Callback fired when focus moves to a pane.
+type PaneCallback = Rc<dyn Fn(&Pane)>;
+
+/// Both panes of a window plus which one has focus.
+pub struct Panes {
+    pub left: Pane,
+    pub right: Pane,
+    active: Cell<Side>,
+    on_activate: RefCell<Option<PaneCallback>>,
+}
+
+impl Panes {
+    /// Builds both panes at `start_path`. The left pane starts focused.
+    pub fn new(start_path: PathBuf, view_mode: ViewMode, inspector_info: Label) -> Rc<Self> {
+        let panes = Rc::new_cyclic(|weak: &Weak<Panes>| Panes {
+            left: Pane::new(
+                Side::Left,
+                start_path.clone(),
+                view_mode.clone(),
+                inspector_info.clone(),
+                weak.clone(),
+            ),
+            right: Pane::new(
+                Side::Right,
+                start_path.clone(),
+                view_mode.clone(),
+                inspector_info.clone(),
+                weak.clone(),
+            ),
+            active: Cell::new(Side::Left),
+            on_activate: RefCell::new(None),
+        });
+
+        tabs::init(&panes.left);
+        tabs::init(&panes.right);
+
+        // Any click inside a pane gives it focus (capture phase, so the
+        // click still reaches the card/row underneath).
+        for pane in [&panes.left, &panes.right] {
+            let gesture = GestureClick::builder()
+                .button(0)
+                .propagation_phase(PropagationPhase::Capture)
+                .build();
+            let weak = Rc::downgrade(&panes);
+            let side = pane.side;
+            gesture.connect_pressed(move |_, _, _, _| {
+                if let Some(p) = weak.upgrade() {
+                    p.set_active(side);
+                }
+            });
+            pane.root.add_controller(gesture);
+        }
+
+        panes.left.root.add_css_class("pane-active");
+        panes
+    }
+
+    pub fn pane(&self, side: Side) -> &Pane {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    pub fn active_side(&self) -> Side {
+        self.active.get()
+    }
+
+    /// The focused pane.
+    pub fn active(&self) -> Pane {
+        self.pane(self.active.get()).clone()
+    }
+
+    /// Moves focus to `side` and notifies the activation callback.
+    pub fn set_active(&self, side: Side) {
+        if self.active.get() == side {
+            return;
+        }
+        self.active.set(side);
+        self.pane(side).root.add_css_class("pane-active");
+        self.pane(side.opposite())
+            .root
+            .remove_css_class("pane-active");
+
+        let callback = self.on_activate.borrow().clone();
+        if let Some(cb) = callback {
+            cb(self.pane(side));
+        }
+    }
+
+    /// Registers the callback invoked whenever focus moves to another pane.
+    pub fn connect_activate(&self, f: impl Fn(&Pane) + 'static) {
+        *self.on_activate.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Shows or hides the right pane. Hiding it moves focus to the left.
+    pub fn set_dual(&self, dual: bool) {
+        self.right.root.set_visible(dual);
+        if !dual {
+            self.set_active(Side::Left);
+        }
+    }
+
+    /// Re-renders both panes (e.g. after a global setting changed).
+    pub fn refresh_both(&self, config: &Rc<RefCell<AppConfig>>) {
+        refresh_content(&self.left, config.clone());
+        refresh_content(&self.right, config.clone());
+    }
+}
+
+// ═══════════════════════════════════════════════
+//  Copy / Move to Other Pane
+// ═══════════════════════════════════════════════
+
+/// Copies (or moves) `sources` into the directory shown by the other pane.
+/// Does nothing in single-pane mode, where the other pane is hidden.
+pub fn transfer_to_other_pane(
+    pane: &Pane,
+    sources: &[PathBuf],
+    move_items: bool,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    let Some(other) = pane.other().filter(|o| o.root.is_visible()) else {
+        return;
+    };
+    let dest_dir = other.current_path.borrow().clone();
+    transfer_into(pane, sources, dest_dir, move_items, config);
+}
+
+/// Copies (or moves) `sources` into `dest_dir` with the transfer dialog,
+/// records completed moves for undo, then refreshes both panes.
+pub fn transfer_into(
+    pane: &Pane,
+    sources: &[PathBuf],
+    dest_dir: PathBuf,
+    move_items: bool,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    transfer_into_then(pane, sources, dest_dir, move_items, config, |_| ());
+}
+
+/// Like `transfer_into`, then calls `after` with the job's report.
+pub fn transfer_into_then(
+    pane: &Pane,
+    sources: &[PathBuf],
+    dest_dir: PathBuf,
+    move_items: bool,
+    config: Rc<RefCell<AppConfig>>,
+    after: impl Fn(&TransferReport) + 'static,
+) {
+    let kind = if move_items {
+        TransferKind::Move
+    } else {
+        TransferKind::Copy
+    };
+
+    let pane_c = pane.clone();
+    let on_done: Rc<dyn Fn(&TransferReport)> = Rc::new(move |report| {
+        if move_items && !report.transferred.is_empty() {
+            journal::record(FileOp::Move {
+                items: report
+                    .transferred
+                    .iter()
+                    .map(|(from, to)| MovedItem {
+                        from: from.clone(),
+                        to: to.clone(),
+                    })
+                    .collect(),
+            });
+        }
+        pane_c.refresh_both(&config);
+        after(report);
+    });
+
+    let parent = pane.root.root().and_downcast::<gtk4::Window>();
+    run_transfer(parent.as_ref(), kind, sources.to_vec(), dest_dir, on_done);
+}
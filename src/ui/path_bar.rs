@@ -0,0 +1,436 @@
+use crate::filesystem;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, Button, EventControllerFocus, EventControllerKey, Label, Orientation, Popover,
+    PropagationPhase, ScrolledWindow, Stack,
+};
+use std::cell::RefCell;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Path Bar
+// ═══════════════════════════════════════════════
+//
+// Segmented location bar in each pane header. Every path component is a
+// button that navigates there; the arrow next to it lists the sibling
+// directories. Ctrl+L swaps the segments for a text entry that accepts
+// absolute and relative paths, `~` and `file://` URIs, with Tab
+// completing directory names.
+
+type NavigateCallback = Rc<dyn Fn(PathBuf)>;
+
+/// The path bar widgets. Cheap to clone — every field is a handle.
+#[derive(Clone)]
+pub struct PathBar {
+    pub root: Stack,
+    segments: Box,
+    scroller: ScrolledWindow,
+    entry: gtk4::Entry,
+    current: Rc<RefCell<PathBuf>>,
+    on_navigate: Rc<RefCell<Option<NavigateCallback>>>,
+}
+
+impl PathBar {
+    pub fn new() -> Self {
+        let segments = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(0)
+            .build();
+        let scroller = ScrolledWindow::builder()
+            .child(&segments)
+            .hscrollbar_policy(gtk4::PolicyType::External)
+            .vscrollbar_policy(gtk4::PolicyType::Never)
+            .hexpand(true)
+            .build();
+
+        let entry = gtk4::Entry::builder()
+            .hexpand(true)
+            .css_classes(vec!["path-entry".to_string()])
+            .build();
+
+        let root = Stack::builder().hexpand(true).build();
+        root.add_named(&scroller, Some("segments"));
+        root.add_named(&entry, Some("entry"));
+        root.set_visible_child_name("segments");
+
+        let bar = Self {
+            root,
+            segments,
+            scroller,
+            entry,
+            current: Rc::new(RefCell::new(PathBuf::new())),
+            on_navigate: Rc::new(RefCell::new(None)),
+        };
+        bar.wire_entry();
+        bar
+    }
+
+    /// Registers the callback invoked with the directory to show.
+    pub fn connect_navigate(&self, f: impl Fn(PathBuf) + 'static) {
+        *self.on_navigate.borrow_mut() = Some(Rc::new(f));
+    }
+
+    fn navigate(&self, path: PathBuf) {
+        let callback = self.on_navigate.borrow().clone();
+        if let Some(cb) = callback {
+            cb(path);
+        }
+    }
+
+    /// Shows `path` as segments (and leaves edit mode).
+    pub fn set_path(&self, path: &Path) {
+        *self.current.borrow_mut() = path.to_path_buf();
+        self.stop_editing();
+
+        while let Some(child) = self.segments.first_child() {
+            self.segments.remove(&child);
+        }
+
+        if filesystem::is_trash_location(path) {
+            self.segments.append(&segment_label("Trash"));
+            return;
+        }
+
+        let segments = split_segments(path);
+        let last = segments.len().saturating_sub(1);
+        for (i, (name, target)) in segments.into_iter().enumerate() {
+            let btn = Button::builder()
+                .label(&name)
+                .has_frame(false)
+                .css_classes(vec!["path-segment".to_string()])
+                .build();
+            if i == last {
+                btn.add_css_class("path-segment-current");
+            }
+            {
+                let bar = self.clone();
+                let target = target.clone();
+                btn.connect_clicked(move |_| bar.navigate(target.clone()));
+            }
+            self.segments.append(&btn);
+
+            if target.parent().is_some() {
+                let arrow = Button::builder()
+                    .icon_name("pan-down-symbolic")
+                    .has_frame(false)
+                    .tooltip_text("Sibling folders")
+                    .css_classes(vec!["path-segment-arrow".to_string()])
+                    .build();
+                let bar = self.clone();
+                arrow.connect_clicked(move |arrow| bar.show_siblings(arrow, &target));
+                self.segments.append(&arrow);
+            }
+        }
+
+        // Keep the current folder in view on long paths
+        let scroller = self.scroller.clone();
+        glib::idle_add_local_once(move || {
+            let adj = scroller.hadjustment();
+            adj.set_value(adj.upper() - adj.page_size());
+        });
+    }
+
+    // ─── Edit Mode ───
+
+    /// Switches to the text entry with the current path selected (Ctrl+L).
+    pub fn start_editing(&self) {
+        let path = self.current.borrow().clone();
+        let mut text = path.to_string_lossy().to_string();
+        if path.is_dir() && !text.ends_with('/') {
+            text.push('/');
+        }
+        self.entry.set_text(&text);
+        self.entry.remove_css_class("error");
+        self.root.set_visible_child_name("entry");
+        self.entry.grab_focus();
+        self.entry.select_region(0, -1);
+    }
+
+    pub fn stop_editing(&self) {
+        self.root.set_visible_child_name("segments");
+    }
+
+    fn is_editing(&self) -> bool {
+        self.root.visible_child_name().as_deref() == Some("entry")
+    }
+
+    fn wire_entry(&self) {
+        {
+            let bar = self.clone();
+            self.entry.connect_activate(move |entry| {
+                let base = bar.current.borrow().clone();
+                match resolve_location(&entry.text(), &base) {
+                    Some(path) if path.is_dir() => {
+                        bar.stop_editing();
+                        bar.navigate(path);
+                    }
+                    _ => {
+                        entry.add_css_class("error");
+                        entry.error_bell();
+                    }
+                }
+            });
+        }
+        self.entry
+            .connect_changed(|entry| entry.remove_css_class("error"));
+
+        // Tab completes, Escape cancels
+        let keys = EventControllerKey::new();
+        keys.set_propagation_phase(PropagationPhase::Capture);
+        {
+            let bar = self.clone();
+            keys.connect_key_pressed(move |_, key, _, _| match key {
+                gtk4::gdk::Key::Tab | gtk4::gdk::Key::ISO_Left_Tab => {
+                    bar.complete();
+                    glib::Propagation::Stop
+                }
+                gtk4::gdk::Key::Escape => {
+                    bar.stop_editing();
+                    glib::Propagation::Stop
+                }
+                _ => glib::Propagation::Proceed,
+            });
+        }
+        self.entry.add_controller(keys);
+
+        // Clicking elsewhere leaves edit mode
+        let focus = EventControllerFocus::new();
+        {
+            let bar = self.clone();
+            focus.connect_leave(move |_| {
+                if bar.is_editing() {
+                    bar.stop_editing();
+                }
+            });
+        }
+        self.entry.add_controller(focus);
+    }
+
+    /// Completes the last component of the entry text to the longest
+    /// prefix shared by the matching directories.
+    fn complete(&self) {
+        let text = self.entry.text().to_string();
+        let (dir_text, prefix) = match text.rfind('/') {
+            Some(i) => (&text[..=i], &text[i + 1..]),
+            None => ("", text.as_str()),
+        };
+        let base = self.current.borrow().clone();
+        let dir = if dir_text.is_empty() {
+            Some(base.clone())
+        } else {
+            resolve_location(dir_text, &base)
+        };
+        let Some(dir) = dir else {
+            self.entry.error_bell();
+            return;
+        };
+
+        let mut matches: Vec<String> = std::fs::read_dir(&dir)
+            .map(|rd| {
+                rd.flatten()
+                    .filter(is_dir_entry)
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .filter(|name| name.starts_with(prefix))
+                    .filter(|name| prefix.starts_with('.') || !name.starts_with('.'))
+                    .collect()
+            })
+            .unwrap_or_default();
+        matches.sort();
+
+        let completion = match matches.as_slice() {
+            [] => {
+                self.entry.error_bell();
+                return;
+            }
+            [single] => format!("{}/", single),
+            _ => {
+                let common = common_prefix(&matches);
+                if common.len() == prefix.len() {
+                    self.entry.error_bell();
+                }
+                common
+            }
+        };
+
+        let completed = format!("{}{}", dir_text, completion);
+        self.entry.set_text(&completed);
+        self.entry.set_position(-1);
+    }
+
+    // ─── Sibling Dropdown ───
+
+    fn show_siblings(&self, arrow: &Button, target: &Path) {
+        let Some(parent) = target.parent() else {
+            return;
+        };
+        let mut siblings: Vec<PathBuf> = std::fs::read_dir(parent)
+            .map(|rd| {
+                rd.flatten()
+                    .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+                    .filter(is_dir_entry)
+                    .map(|e| e.path())
+                    .collect()
+            })
+            .unwrap_or_default();
+        siblings.sort_by_key(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        });
+
+        let popover = Popover::builder()
+            .css_classes(vec!["context-menu".to_string()])
+            .build();
+        popover.set_parent(arrow);
+        popover.connect_closed(|p| {
+            let p = p.clone();
+            glib::idle_add_local_once(move || p.unparent());
+        });
+
+        let menu_box = Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(2)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(4)
+            .margin_end(4)
+            .build();
+
+        if siblings.is_empty() {
+            menu_box.append(&segment_label("No folders"));
+        }
+        for sibling in siblings {
+            let name = sibling
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let label = Label::builder()
+                .label(&name)
+                .xalign(0.0)
+                .max_width_chars(40)
+                .ellipsize(gtk4::pango::EllipsizeMode::End)
+                .build();
+            let item = Button::builder()
+                .child(&label)
+                .has_frame(false)
+                .css_classes(vec!["context-menu-item".to_string()])
+                .build();
+            if sibling == target {
+                item.add_css_class("path-segment-current");
+            }
+
+            let bar = self.clone();
+            let popover_c = popover.clone();
+            item.connect_clicked(move |_| {
+                popover_c.popdown();
+                bar.navigate(sibling.clone());
+            });
+            menu_box.append(&item);
+        }
+
+        let scrolled = ScrolledWindow::builder()
+            .child(&menu_box)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(400)
+            .build();
+        popover.set_child(Some(&scrolled));
+        popover.popup();
+    }
+}
+
+// ─── Helpers ───
+
+fn segment_label(text: &str) -> Label {
+    Label::builder()
+        .label(text)
+        .css_classes(vec!["breadcrumb-label".to_string()])
+        .halign(Align::Start)
+        .margin_start(6)
+        .margin_end(6)
+        .build()
+}
+
+/// Splits `path` into (label, target) segments. Paths inside the home
+/// directory start at `~`.
+fn split_segments(path: &Path) -> Vec<(String, PathBuf)> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let (mut target, rest, first) = match path.strip_prefix(&home) {
+        Ok(rest) if !home.as_os_str().is_empty() => (home.clone(), rest, "~"),
+        _ => (
+            PathBuf::from("/"),
+            path.strip_prefix("/").unwrap_or(path),
+            "/",
+        ),
+    };
+
+    let mut segments = vec![(first.to_string(), target.clone())];
+    for component in rest.components() {
+        target.push(component);
+        segments.push((
+            component.as_os_str().to_string_lossy().to_string(),
+            target.clone(),
+        ));
+    }
+    segments
+}
+
+/// Turns user input into an absolute path: `file://` URIs, `~`,
+/// absolute paths and paths relative to `base`. `.` and `..` are
+/// resolved lexically.
+fn resolve_location(text: &str, base: &Path) -> Option<PathBuf> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let raw = if text.starts_with("file://") {
+        gio::File::for_uri(text).path()?
+    } else if text == "~" {
+        dirs::home_dir()?
+    } else if let Some(rest) = text.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else {
+        base.join(text)
+    };
+
+    let mut resolved = PathBuf::new();
+    for component in raw.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    Some(resolved)
+}
+
+/// Directory test that only stats symlinks.
+fn is_dir_entry(entry: &std::fs::DirEntry) -> bool {
+    match entry.file_type() {
+        Ok(t) if t.is_symlink() => entry.path().is_dir(),
+        Ok(t) => t.is_dir(),
+        Err(_) => false,
+    }
+}
+
+fn common_prefix(names: &[String]) -> String {
+    let Some(first) = names.first() else {
+        return String::new();
+    };
+    let mut end = first.len();
+    for name in &names[1..] {
+        end = first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, c), _)| i + c.len_utf8())
+            .unwrap_or(0)
+            .min(end);
+    }
+    first[..end].to_string()
+}
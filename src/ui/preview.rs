@@ -1,3 +1,4 @@
+use crate::filesystem;
 use crate::thumbnail;
 use gtk4::gdk_pixbuf::Pixbuf;
 use gtk4::prelude::*;
@@ -11,25 +12,18 @@ use std::path::Path;
 // Generates thumbnails for images and video keyframes.
 // Loading happens asynchronously so the main UI thread never freezes.
 
-/// File types that support preview thumbnails.
-fn is_image(ext: &str) -> bool {
-    matches!(
-        ext,
-        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico"
-    )
+/// MIME types that support preview thumbnails.
+fn is_image(mime: &str) -> bool {
+    thumbnail::is_thumbable_image(mime) || mime == "image/svg+xml"
 }
 
-fn is_video(ext: &str) -> bool {
-    matches!(ext, "mp4" | "mkv" | "avi" | "mov" | "webm")
+fn is_video(mime: &str) -> bool {
+    thumbnail::is_thumbable_video(mime)
 }
 
-/// Returns true if the file at `path` supports a media preview.
-pub fn supports_preview(path: &Path) -> bool {
-    let ext = path
-        .extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-    is_image(&ext) || is_video(&ext)
+/// Returns true if a file of type `mime` supports a media preview.
+pub fn supports_preview(mime: &str) -> bool {
+    is_image(mime) || is_video(mime)
 }
 
 // ═══════════════════════════════════════════════
@@ -49,14 +43,11 @@ pub fn build_preview_widget(file_path: &Path, max_width: i32, max_height: i32) -
         .css_classes(vec!["preview-container".to_string()])
         .build();
 
-    let ext = file_path
-        .extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
+    let mime = filesystem::detect_mime(file_path, false);
 
-    if is_image(&ext) {
+    if is_image(&mime) {
         build_image_preview(&container, file_path, max_width, max_height);
-    } else if is_video(&ext) {
+    } else if is_video(&mime) {
         build_video_placeholder(&container, file_path);
     }
 
@@ -229,13 +220,8 @@ fn build_video_placeholder(container: &Box, file_path: &Path) {
 
 /// Builds a small thumbnail suitable for tooltip / hover preview (96×96).
 /// Uses the disk cache so repeated hovers are instant.
-pub fn build_tooltip_preview(file_path: &Path) -> Option<Image> {
-    let ext = file_path
-        .extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-
-    if !is_image(&ext) && !is_video(&ext) {
+pub fn build_tooltip_preview(file_path: &Path, mime: &str) -> Option<Image> {
+    if !supports_preview(mime) {
         return None;
     }
 
@@ -250,7 +236,7 @@ pub fn build_tooltip_preview(file_path: &Path) -> Option<Image> {
     }
 
     // For images we can generate synchronously (fast enough for tooltip)
-    if is_image(&ext) {
+    if is_image(mime) {
         load_scaled_pixbuf(file_path, 96, 96).map(|pb| {
             let img = Image::from_pixbuf(Some(&pb));
             img.add_css_class("preview-tooltip-image");
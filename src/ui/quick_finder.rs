@@ -0,0 +1,522 @@
+use crate::config::AppConfig;
+use crate::core::worker::WorkerHandle;
+use crate::filesystem;
+use crate::filesystem::fuzzy::FuzzyMatcher;
+use crate::filesystem::index::{self, IndexEntry, IndexEvent};
+use crate::ui::content::{activate_path, refresh_content};
+use crate::ui::pane::{display_path, Pane};
+use gtk4::gdk::{Key, ModifierType};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, EventControllerKey, Image, Label, ListBox, ListBoxRow, Orientation, Popover,
+    PositionType, PropagationPhase, ScrolledWindow, SearchEntry,
+};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+// ═══════════════════════════════════════════════
+//  Quick Finder ("Go to File")
+// ═══════════════════════════════════════════════
+//
+// Ctrl+P drops a search field over the active pane that fuzzy-matches
+// every file and folder below the pane's folder, like an editor's file
+// finder. The tree is indexed in the background (see
+// `filesystem::index`) and results fill in while it is walked; a
+// finished index is reused for a minute, so reopening is instant.
+//
+//   ↑ / ↓        move through the results
+//   Enter        go to the item's folder and select it
+//   Ctrl+Enter   open the item (enter a folder)
+//   Escape       close
+//
+// Results rank by match score (see `filesystem::fuzzy`), then by
+// recency: items picked here recently, and files changed in the last
+// day or week, move up. An empty query lists the recent picks.
+
+/// Results shown at most.
+const MAX_RESULTS: usize = 50;
+
+/// How long a finished index is reused before the tree is walked again.
+const INDEX_TTL: Duration = Duration::from_secs(60);
+
+/// Recent picks remembered (this session only).
+const RECENT_LIMIT: usize = 50;
+
+/// Boost of the most recent pick; older picks get less.
+const RECENT_BONUS: i64 = 30;
+const CHANGED_TODAY_BONUS: i64 = 6;
+const CHANGED_THIS_WEEK_BONUS: i64 = 3;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A tree being (or done being) indexed.
+struct Index {
+    root: PathBuf,
+    include_hidden: bool,
+    respect_gitignore: bool,
+    entries: Vec<IndexEntry>,
+    /// Running walk; `None` once finished.
+    handle: Option<WorkerHandle>,
+    truncated: bool,
+    finished_at: Option<Instant>,
+    /// Called after every batch, while a finder shows this index.
+    on_update: Option<Rc<dyn Fn(usize)>>,
+}
+
+type SharedIndex = Rc<RefCell<Index>>;
+
+thread_local! {
+    static CACHE: RefCell<Option<SharedIndex>> = const { RefCell::new(None) };
+    /// Paths picked in the finder, most recent first.
+    static RECENT: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+// ─── Index Cache ───
+
+/// The index of `root` for these options: the cached one if it is still
+/// running or fresh, else a new walk.
+fn index_for(root: &Path, include_hidden: bool, respect_gitignore: bool) -> SharedIndex {
+    let cached = CACHE.with(|c| c.borrow().clone()).filter(|index| {
+        let index = index.borrow();
+        index.root == root
+            && index.include_hidden == include_hidden
+            && index.respect_gitignore == respect_gitignore
+            && index.finished_at.is_none_or(|t| t.elapsed() < INDEX_TTL)
+    });
+    if let Some(index) = cached {
+        return index;
+    }
+    if let Some(old) = CACHE.with(|c| c.borrow_mut().take()) {
+        if let Some(handle) = old.borrow_mut().handle.take() {
+            handle.cancel();
+        }
+    }
+
+    let index: SharedIndex = Rc::new(RefCell::new(Index {
+        root: root.to_path_buf(),
+        include_hidden,
+        respect_gitignore,
+        entries: Vec::new(),
+        handle: None,
+        truncated: false,
+        finished_at: None,
+        on_update: None,
+    }));
+    let weak = Rc::downgrade(&index);
+    let handle = index::start_index(
+        root.to_path_buf(),
+        include_hidden,
+        respect_gitignore,
+        move |event| {
+            let Some(index) = weak.upgrade() else {
+                return;
+            };
+            let first_new = index.borrow().entries.len();
+            match event {
+                IndexEvent::Batch(batch) => index.borrow_mut().entries.extend(batch),
+                IndexEvent::Finished { truncated } => {
+                    let mut index = index.borrow_mut();
+                    index.handle = None;
+                    index.truncated = truncated;
+                    index.finished_at = Some(Instant::now());
+                }
+            }
+            let on_update = index.borrow().on_update.clone();
+            if let Some(f) = on_update {
+                f(first_new);
+            }
+        },
+    );
+    index.borrow_mut().handle = Some(handle);
+    CACHE.with(|c| *c.borrow_mut() = Some(index.clone()));
+    index
+}
+
+/// Stops an unfinished walk once nobody waits for it.
+fn release_index(index: &SharedIndex) {
+    let mut index_mut = index.borrow_mut();
+    index_mut.on_update = None;
+    if let Some(handle) = index_mut.handle.take() {
+        handle.cancel();
+        drop(index_mut);
+        CACHE.with(|c| {
+            let mut cache = c.borrow_mut();
+            if cache.as_ref().is_some_and(|c| Rc::ptr_eq(c, index)) {
+                *cache = None;
+            }
+        });
+    }
+}
+
+// ─── Ranking ───
+
+fn record_recent(path: &Path) {
+    RECENT.with(|r| {
+        let mut recent = r.borrow_mut();
+        recent.retain(|p| p != path);
+        recent.insert(0, path.to_path_buf());
+        recent.truncate(RECENT_LIMIT);
+    });
+}
+
+fn recency_bonus(entry: &IndexEntry, recent: &[PathBuf], now: SystemTime) -> i64 {
+    let picked = recent
+        .iter()
+        .position(|p| *p == entry.path)
+        .map_or(0, |i| RECENT_BONUS - i as i64 / 2);
+    let age = entry
+        .modified
+        .and_then(|m| now.duration_since(m).ok())
+        .unwrap_or(Duration::MAX);
+    let changed = if age < DAY {
+        CHANGED_TODAY_BONUS
+    } else if age < DAY * 7 {
+        CHANGED_THIS_WEEK_BONUS
+    } else {
+        0
+    };
+    picked + changed
+}
+
+/// Merges the matches among `entries[from..]` into `results` (score,
+/// entry index), keeping the best `MAX_RESULTS`.
+fn rank(
+    matcher: &mut FuzzyMatcher,
+    entries: &[IndexEntry],
+    from: usize,
+    results: &mut Vec<(i64, usize)>,
+) {
+    let recent = RECENT.with(|r| r.borrow().clone());
+    let now = SystemTime::now();
+    for (i, entry) in entries.iter().enumerate().skip(from) {
+        if let Some(score) = matcher.score(&entry.rel) {
+            results.push((score + recency_bonus(entry, &recent, now), i));
+        }
+    }
+    // Best first; equal scores keep the walk's order
+    results.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    results.truncate(MAX_RESULTS);
+}
+
+// ═══════════════════════════════════════════════
+//  Finder Popover
+// ═══════════════════════════════════════════════
+
+/// Opens the quick finder over `pane`, searching below its folder.
+pub fn show_quick_finder(pane: &Pane, config: Rc<RefCell<AppConfig>>) {
+    let root = pane.current_path.borrow().clone();
+    if filesystem::is_trash_location(&root) {
+        return;
+    }
+    let (include_hidden, respect_gitignore) = {
+        let config = config.borrow();
+        (config.show_hidden, config.respect_gitignore)
+    };
+    let index = index_for(&root, include_hidden, respect_gitignore);
+
+    let anchor = pane.content_box.clone();
+    let popover = Popover::builder()
+        .has_arrow(false)
+        .position(PositionType::Bottom)
+        .css_classes(vec!["quick-finder".to_string()])
+        .build();
+    popover.set_parent(&anchor);
+    popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(
+        anchor.width() / 2,
+        8,
+        1,
+        1,
+    )));
+    {
+        let popover_c = popover.clone();
+        anchor.connect_destroy(move |_| popover_c.unparent());
+    }
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(6)
+        .margin_end(6)
+        .width_request(560)
+        .build();
+
+    let search = SearchEntry::builder()
+        .placeholder_text(format!("Go to file in {}…", display_path(&root)))
+        .hexpand(true)
+        .build();
+    vbox.append(&search);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::Browse)
+        .css_classes(vec!["quick-finder-list".to_string()])
+        .build();
+    let scrolled = ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(420)
+        .build();
+    vbox.append(&scrolled);
+
+    let status = Label::builder()
+        .css_classes(vec!["quick-finder-status".to_string()])
+        .halign(Align::Start)
+        .margin_start(4)
+        .build();
+    vbox.append(&status);
+    popover.set_child(Some(&vbox));
+
+    // Paths of the rows shown, in row order
+    let shown: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
+    let results: Rc<RefCell<Vec<(i64, usize)>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // ── Rendering ──
+    let render: Rc<dyn Fn()> = {
+        let index = index.clone();
+        let list = list.clone();
+        let scrolled = scrolled.clone();
+        let search = search.clone();
+        let status = status.clone();
+        let shown = shown.clone();
+        let results = results.clone();
+        Rc::new(move || {
+            let index = index.borrow();
+            let paths: Vec<(PathBuf, String, bool)> = if search.text().trim().is_empty() {
+                // Nothing typed yet: recent picks below this folder
+                RECENT.with(|r| {
+                    r.borrow()
+                        .iter()
+                        .filter(|p| p.starts_with(&index.root) && p.exists())
+                        .map(|p| {
+                            let rel = p.strip_prefix(&index.root).unwrap_or(p);
+                            (p.clone(), rel.to_string_lossy().to_string(), p.is_dir())
+                        })
+                        .take(MAX_RESULTS)
+                        .collect()
+                })
+            } else {
+                results
+                    .borrow()
+                    .iter()
+                    .map(|&(_, i)| {
+                        let e = &index.entries[i];
+                        (e.path.clone(), e.rel.clone(), e.is_dir)
+                    })
+                    .collect()
+            };
+
+            let selected = list
+                .selected_row()
+                .and_then(|row| shown.borrow().get(row.index() as usize).cloned());
+            while let Some(child) = list.first_child() {
+                list.remove(&child);
+            }
+            for (_, rel, is_dir) in &paths {
+                list.append(&result_row(rel, *is_dir));
+            }
+            // Keep the highlighted item if it is still listed
+            let keep = selected
+                .and_then(|s| paths.iter().position(|(p, _, _)| *p == s))
+                .unwrap_or(0);
+            if let Some(row) = list.row_at_index(keep as i32) {
+                list.select_row(Some(&row));
+            }
+            if keep == 0 {
+                scrolled.vadjustment().set_value(0.0);
+            }
+            *shown.borrow_mut() = paths.into_iter().map(|(p, _, _)| p).collect();
+
+            let count = index.entries.len();
+            status.set_label(&if index.handle.is_some() {
+                format!("Indexing… {} items", count)
+            } else if index.truncated {
+                format!("{}+ items (indexing stopped at the limit)", count)
+            } else {
+                format!("{} items", count)
+            });
+        })
+    };
+
+    // ── Matching: everything on a new query, new entries per batch ──
+    {
+        let index_c = index.clone();
+        let results = results.clone();
+        let render = render.clone();
+        let search_c = search.clone();
+        search.connect_search_changed(move |_| {
+            let mut matcher = FuzzyMatcher::new(&search_c.text());
+            results.borrow_mut().clear();
+            if !matcher.is_empty() {
+                rank(
+                    &mut matcher,
+                    &index_c.borrow().entries,
+                    0,
+                    &mut results.borrow_mut(),
+                );
+            }
+            render();
+        });
+    }
+    {
+        let results = results.clone();
+        let render = render.clone();
+        let search_c = search.clone();
+        let index_weak = Rc::downgrade(&index);
+        index.borrow_mut().on_update = Some(Rc::new(move |first_new| {
+            let Some(index) = index_weak.upgrade() else {
+                return;
+            };
+            let mut matcher = FuzzyMatcher::new(&search_c.text());
+            if !matcher.is_empty() {
+                rank(
+                    &mut matcher,
+                    &index.borrow().entries,
+                    first_new,
+                    &mut results.borrow_mut(),
+                );
+            }
+            render();
+        }));
+    }
+
+    // ── Picking ──
+    let pick: Rc<dyn Fn(bool)> = {
+        let pane = pane.clone();
+        let popover = popover.clone();
+        let list = list.clone();
+        let shown = shown.clone();
+        Rc::new(move |open: bool| {
+            let Some(path) = list
+                .selected_row()
+                .and_then(|row| shown.borrow().get(row.index() as usize).cloned())
+            else {
+                return;
+            };
+            popover.popdown();
+            record_recent(&path);
+            if open {
+                activate_path(&pane, &path, config.clone());
+            } else {
+                reveal(&pane, &path, config.clone());
+            }
+        })
+    };
+    {
+        let pick = pick.clone();
+        list.connect_row_activated(move |_, _| pick(false));
+    }
+
+    // Arrows move through the results while typing; Enter picks
+    {
+        let list = list.clone();
+        let scrolled = scrolled.clone();
+        let key = EventControllerKey::new();
+        key.set_propagation_phase(PropagationPhase::Capture);
+        key.connect_key_pressed(move |_, key, _code, state| {
+            let ctrl = state.contains(ModifierType::CONTROL_MASK);
+            match key {
+                Key::Down | Key::Up => {
+                    let current = list.selected_row().map_or(-1, |r| r.index());
+                    let next = if key == Key::Down {
+                        current + 1
+                    } else {
+                        current - 1
+                    };
+                    if let Some(row) = list.row_at_index(next.max(0)) {
+                        list.select_row(Some(&row));
+                        scroll_into_view(&scrolled, &list, &row);
+                    }
+                    glib::Propagation::Stop
+                }
+                Key::Return | Key::KP_Enter => {
+                    pick(ctrl);
+                    glib::Propagation::Stop
+                }
+                _ => glib::Propagation::Proceed,
+            }
+        });
+        search.add_controller(key);
+    }
+
+    // A new popover is built every time; drop it (and an unfinished
+    // walk) once closed
+    popover.connect_closed(move |p| {
+        release_index(&index);
+        let p = p.clone();
+        glib::idle_add_local_once(move || p.unparent());
+    });
+
+    render();
+    popover.popup();
+    search.grab_focus();
+}
+
+/// One result: icon, name, and the folder it is in.
+fn result_row(rel: &str, is_dir: bool) -> ListBoxRow {
+    let (folder, name) = match rel.rsplit_once('/') {
+        Some((folder, name)) => (folder, name),
+        None => ("", rel),
+    };
+    let row_box = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    row_box.append(&Image::from_icon_name(if is_dir {
+        "folder-symbolic"
+    } else {
+        "text-x-generic-symbolic"
+    }));
+    row_box.append(
+        &Label::builder()
+            .label(name)
+            .css_classes(vec!["quick-finder-name".to_string()])
+            .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+            .xalign(0.0)
+            .build(),
+    );
+    row_box.append(
+        &Label::builder()
+            .label(folder)
+            .css_classes(vec!["quick-finder-folder".to_string()])
+            .ellipsize(gtk4::pango::EllipsizeMode::Start)
+            .hexpand(true)
+            .xalign(0.0)
+            .build(),
+    );
+    ListBoxRow::builder().child(&row_box).build()
+}
+
+/// Scrolls just far enough for `row` to be fully visible.
+pub fn scroll_into_view(scrolled: &ScrolledWindow, list: &ListBox, row: &ListBoxRow) {
+    let Some(bounds) = row.compute_bounds(list) else {
+        return;
+    };
+    let adj = scrolled.vadjustment();
+    let top = bounds.y() as f64;
+    let bottom = (bounds.y() + bounds.height()) as f64;
+    if top < adj.value() {
+        adj.set_value(top);
+    } else if bottom > adj.value() + adj.page_size() {
+        adj.set_value(bottom - adj.page_size());
+    }
+}
+
+/// Shows `path` selected in its folder.
+fn reveal(pane: &Pane, path: &Path, config: Rc<RefCell<AppConfig>>) {
+    let Some(folder) = path.parent() else {
+        return;
+    };
+    if *pane.current_path.borrow() != folder {
+        *pane.current_path.borrow_mut() = folder.to_path_buf();
+        refresh_content(pane, config);
+    }
+    pane.selection.select_only(path);
+}
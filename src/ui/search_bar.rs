@@ -0,0 +1,485 @@
+use crate::config::{DateFilter, NameMatch, SearchQuery, TypeFilter};
+use crate::filesystem::{self, search};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, Button, DropDown, Entry, Label, Orientation, Popover, Revealer, SearchEntry,
+    ToggleButton,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Search Bar
+// ═══════════════════════════════════════════════
+//
+// Slides in above a pane's content (Ctrl+F or the header's search
+// button). The name field searches as you type; its mode picks substring,
+// glob or regex matching. The filter row narrows by type, size range,
+// modified date and file contents. Each change hands the whole query to
+// `connect_search`; a bad size, date or regex is flagged in the bar
+// instead. Escape or the close button closes the bar. The save button
+// hands the query and a name to `connect_save` (smart folders).
+
+type SearchCallback = Rc<dyn Fn(Option<SearchQuery>)>;
+type SaveCallback = Rc<dyn Fn(String, SearchQuery)>;
+
+/// Entries of the modified-date drop-down, in order.
+const DATE_CHOICES: [&str; 6] = [
+    "Any Time",
+    "Today",
+    "Past Week",
+    "Past Month",
+    "Past Year",
+    "Between…",
+];
+const BETWEEN: u32 = 5;
+
+/// CSS class of a field holding an invalid value.
+const ERROR_CLASS: &str = "search-field-error";
+
+/// The search bar widgets. Cheap to clone — every field is a handle.
+#[derive(Clone)]
+pub struct SearchBar {
+    pub root: Revealer,
+    entry: SearchEntry,
+    name_match: DropDown,
+    filters_btn: ToggleButton,
+    filters: Revealer,
+    kind: DropDown,
+    min_size: Entry,
+    max_size: Entry,
+    modified: DropDown,
+    dates: Box,
+    from: Entry,
+    to: Entry,
+    contents: Entry,
+    message: Label,
+    /// Set while `show_query` fills the fields, so they emit nothing.
+    loading: Rc<Cell<bool>>,
+    /// Set when `show_query` changed the name: the entry's delayed
+    /// search-changed for it is not a new search.
+    name_echo: Rc<Cell<bool>>,
+    on_search: Rc<RefCell<Option<SearchCallback>>>,
+    on_save: Rc<RefCell<Option<SaveCallback>>>,
+}
+
+impl SearchBar {
+    pub fn new() -> Self {
+        let entry = SearchEntry::builder()
+            .placeholder_text("Search in this folder and below…")
+            .hexpand(true)
+            .build();
+        let titles = |names: Vec<&str>| DropDown::from_strings(&names);
+        let name_match = titles(NameMatch::all().iter().map(|m| m.title()).collect());
+        name_match.set_tooltip_text(Some("How the name is matched"));
+
+        let filters_btn = ToggleButton::builder()
+            .icon_name("view-more-horizontal-symbolic")
+            .tooltip_text("Filters")
+            .css_classes(vec!["toolbar-btn".to_string()])
+            .build();
+        let save_btn = Button::builder()
+            .icon_name("folder-saved-search-symbolic")
+            .tooltip_text("Save as Smart Folder")
+            .css_classes(vec!["toolbar-btn".to_string()])
+            .build();
+        let close_btn = Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Close Search (Escape)")
+            .css_classes(vec!["toolbar-btn".to_string()])
+            .build();
+
+        let main_row = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        main_row.append(&entry);
+        main_row.append(&name_match);
+        main_row.append(&filters_btn);
+        main_row.append(&save_btn);
+        main_row.append(&close_btn);
+
+        // ── Filter row ──
+        let kind = titles(TypeFilter::all().iter().map(|t| t.title()).collect());
+        let size_entry = |placeholder: &str| {
+            Entry::builder()
+                .placeholder_text(placeholder)
+                .width_chars(8)
+                .build()
+        };
+        let min_size = size_entry("Min size");
+        let max_size = size_entry("Max size");
+        min_size.set_tooltip_text(Some("e.g. 500 KB, 10M, 2 GB"));
+        max_size.set_tooltip_text(Some("e.g. 500 KB, 10M, 2 GB"));
+        let modified = titles(DATE_CHOICES.to_vec());
+        let date_entry = |placeholder: &str| {
+            Entry::builder()
+                .placeholder_text(placeholder)
+                .width_chars(10)
+                .build()
+        };
+        let from = date_entry("YYYY-MM-DD");
+        let to = date_entry("YYYY-MM-DD");
+        let dates = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(4)
+            .visible(false)
+            .build();
+        dates.append(&from);
+        dates.append(&Label::new(Some("–")));
+        dates.append(&to);
+        let contents = Entry::builder()
+            .placeholder_text("Containing text…")
+            .hexpand(true)
+            .build();
+
+        let filter_row = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .margin_top(6)
+            .build();
+        filter_row.append(&kind);
+        filter_row.append(&min_size);
+        filter_row.append(&Label::new(Some("–")));
+        filter_row.append(&max_size);
+        filter_row.append(&modified);
+        filter_row.append(&dates);
+        filter_row.append(&contents);
+        let filters = Revealer::builder().child(&filter_row).build();
+
+        let message = Label::builder()
+            .css_classes(vec!["search-message".to_string()])
+            .halign(Align::Start)
+            .visible(false)
+            .build();
+
+        let vbox = Box::builder()
+            .orientation(Orientation::Vertical)
+            .css_classes(vec!["search-bar".to_string()])
+            .build();
+        vbox.append(&main_row);
+        vbox.append(&filters);
+        vbox.append(&message);
+        let root = Revealer::builder().child(&vbox).build();
+
+        let bar = Self {
+            root,
+            entry,
+            name_match,
+            filters_btn,
+            filters,
+            kind,
+            min_size,
+            max_size,
+            modified,
+            dates,
+            from,
+            to,
+            contents,
+            message,
+            loading: Rc::new(Cell::new(false)),
+            name_echo: Rc::new(Cell::new(false)),
+            on_search: Rc::new(RefCell::new(None)),
+            on_save: Rc::new(RefCell::new(None)),
+        };
+
+        {
+            let filters = bar.filters.clone();
+            bar.filters_btn
+                .connect_toggled(move |b| filters.set_reveal_child(b.is_active()));
+        }
+        bar.wire_save(&save_btn);
+        {
+            let bar_c = bar.clone();
+            close_btn.connect_clicked(move |_| bar_c.close());
+        }
+        bar.wire_changes();
+        bar
+    }
+
+    /// Registers the callback run with the query after every change, or
+    /// with `None` once the bar is cleared or closed.
+    pub fn connect_search(&self, f: impl Fn(Option<SearchQuery>) + 'static) {
+        *self.on_search.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Registers the callback run with the chosen name and the query
+    /// when the search is saved.
+    pub fn connect_save(&self, f: impl Fn(String, SearchQuery) + 'static) {
+        *self.on_save.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.root.reveals_child()
+    }
+
+    /// Shows the bar and focuses the name field.
+    pub fn open(&self) {
+        self.root.set_reveal_child(true);
+        self.entry.grab_focus();
+    }
+
+    /// Hides the bar; the pane goes back to its folder.
+    pub fn close(&self) {
+        self.root.set_reveal_child(false);
+        self.emit(None);
+    }
+
+    /// Fills the fields from `query`, opens the bar and runs the search.
+    pub fn show_query(&self, query: &SearchQuery) {
+        self.loading.set(true);
+        if !query.name.is_empty() && self.entry.text() != query.name {
+            self.name_echo.set(true);
+        }
+        self.entry.set_text(&query.name);
+        let position = |found: Option<usize>| found.unwrap_or(0) as u32;
+        self.name_match.set_selected(position(
+            NameMatch::all().iter().position(|m| *m == query.name_match),
+        ));
+        self.kind.set_selected(position(
+            TypeFilter::all().iter().position(|t| *t == query.kind),
+        ));
+        self.min_size
+            .set_text(&query.min_size.map(size_text).unwrap_or_default());
+        self.max_size
+            .set_text(&query.max_size.map(size_text).unwrap_or_default());
+        let (from, to) = match &query.modified {
+            DateFilter::Between { from, to } => (from.clone(), to.clone()),
+            _ => (None, None),
+        };
+        self.from.set_text(&from.unwrap_or_default());
+        self.to.set_text(&to.unwrap_or_default());
+        self.modified.set_selected(match query.modified {
+            DateFilter::Any => 0,
+            DateFilter::Today => 1,
+            DateFilter::PastWeek => 2,
+            DateFilter::PastMonth => 3,
+            DateFilter::PastYear => 4,
+            DateFilter::Between { .. } => BETWEEN,
+        });
+        self.contents.set_text(&query.contents);
+        self.loading.set(false);
+
+        let filtered = SearchQuery {
+            name: String::new(),
+            name_match: NameMatch::default(),
+            ..query.clone()
+        };
+        if !filtered.is_empty() {
+            self.filters_btn.set_active(true);
+        }
+        self.root.set_reveal_child(true);
+        self.changed();
+    }
+
+    /// Runs the current query again, e.g. for another view mode.
+    pub fn search_again(&self) {
+        self.changed();
+    }
+
+    fn emit(&self, query: Option<SearchQuery>) {
+        let callback = self.on_search.borrow().clone();
+        if let Some(cb) = callback {
+            cb(query);
+        }
+    }
+
+    /// Re-runs the search after any field changed.
+    fn wire_changes(&self) {
+        {
+            let bar = self.clone();
+            self.entry.connect_search_changed(move |_| {
+                if !bar.name_echo.replace(false) {
+                    bar.changed();
+                }
+            });
+        }
+        {
+            let bar = self.clone();
+            self.entry.connect_stop_search(move |_| bar.close());
+        }
+        for dropdown in [&self.name_match, &self.kind] {
+            let bar = self.clone();
+            dropdown.connect_selected_notify(move |_| bar.changed());
+        }
+        {
+            let bar = self.clone();
+            self.modified.connect_selected_notify(move |d| {
+                bar.dates.set_visible(d.selected() == BETWEEN);
+                bar.changed();
+            });
+        }
+        // Typed filters apply on Enter, not on every keystroke
+        for field in [
+            &self.min_size,
+            &self.max_size,
+            &self.from,
+            &self.to,
+            &self.contents,
+        ] {
+            let bar = self.clone();
+            field.connect_activate(move |_| bar.changed());
+        }
+    }
+
+    fn changed(&self) {
+        if self.loading.get() {
+            return;
+        }
+        match self.query() {
+            Ok(query) if query.is_empty() => {
+                self.set_message(None);
+                self.emit(None);
+            }
+            Ok(query) => match search::validate(&query) {
+                Ok(()) => {
+                    self.set_message(None);
+                    self.emit(Some(query));
+                }
+                Err(e) => self.set_message(Some(&e)),
+            },
+            Err(e) => self.set_message(Some(&e)),
+        }
+    }
+
+    /// Asks for a name, then hands it and the query to `connect_save`.
+    fn wire_save(&self, save_btn: &Button) {
+        let popover = Popover::builder()
+            .css_classes(vec!["context-menu".to_string()])
+            .build();
+        popover.set_parent(save_btn);
+
+        let vbox = Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(8)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+        let title = Label::builder()
+            .label("Save as Smart Folder")
+            .css_classes(vec!["context-menu-title".to_string()])
+            .halign(Align::Start)
+            .build();
+        let name = Entry::builder().placeholder_text("Name…").build();
+        let confirm_btn = Button::builder()
+            .label("Save")
+            .css_classes(vec!["btn-primary".to_string()])
+            .build();
+        vbox.append(&title);
+        vbox.append(&name);
+        vbox.append(&confirm_btn);
+        popover.set_child(Some(&vbox));
+
+        {
+            let bar = self.clone();
+            let popover = popover.clone();
+            let name = name.clone();
+            save_btn.connect_clicked(move |_| match bar.valid_query() {
+                Some(query) => {
+                    name.set_text(&query.name);
+                    popover.popup();
+                    name.grab_focus();
+                }
+                None => bar.set_message(Some("Enter a search to save it")),
+            });
+        }
+        let save: Rc<dyn Fn()> = {
+            let bar = self.clone();
+            let popover = popover.clone();
+            let name = name.clone();
+            Rc::new(move || {
+                let title = name.text().trim().to_string();
+                if title.is_empty() {
+                    return;
+                }
+                let callback = bar.on_save.borrow().clone();
+                if let (Some(cb), Some(query)) = (callback, bar.valid_query()) {
+                    cb(title, query);
+                }
+                popover.popdown();
+            })
+        };
+        {
+            let save = save.clone();
+            confirm_btn.connect_clicked(move |_| save());
+        }
+        name.connect_activate(move |_| save());
+    }
+
+    /// The query, if it is non-empty and runnable.
+    fn valid_query(&self) -> Option<SearchQuery> {
+        self.query()
+            .ok()
+            .filter(|q| !q.is_empty() && search::validate(q).is_ok())
+    }
+
+    fn set_message(&self, text: Option<&str>) {
+        self.message.set_label(text.unwrap_or(""));
+        self.message.set_visible(text.is_some());
+    }
+
+    // ─── Query ───
+
+    /// The query the fields describe, or a message naming a bad field.
+    pub fn query(&self) -> Result<SearchQuery, String> {
+        let size = |field: &Entry, what: &str| {
+            let text = field.text();
+            field.remove_css_class(ERROR_CLASS);
+            if text.trim().is_empty() {
+                return Ok(None);
+            }
+            filesystem::parse_size(&text).map(Some).ok_or_else(|| {
+                field.add_css_class(ERROR_CLASS);
+                format!("Invalid {} size “{}”", what, text)
+            })
+        };
+        let optional = |field: &Entry| {
+            let text = field.text().trim().to_string();
+            (!text.is_empty()).then_some(text)
+        };
+        let modified = match self.modified.selected() {
+            1 => DateFilter::Today,
+            2 => DateFilter::PastWeek,
+            3 => DateFilter::PastMonth,
+            4 => DateFilter::PastYear,
+            BETWEEN => DateFilter::Between {
+                from: optional(&self.from),
+                to: optional(&self.to),
+            },
+            _ => DateFilter::Any,
+        };
+
+        Ok(SearchQuery {
+            name: self.entry.text().trim().to_string(),
+            name_match: NameMatch::all()
+                .get(self.name_match.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            kind: TypeFilter::all()
+                .get(self.kind.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            min_size: size(&self.min_size, "minimum")?,
+            max_size: size(&self.max_size, "maximum")?,
+            modified,
+            contents: self.contents.text().trim().to_string(),
+        })
+    }
+}
+
+/// A size as typed in the size fields: whole units when exact.
+fn size_text(bytes: u64) -> String {
+    let units = [
+        (1 << 40, "TB"),
+        (1 << 30, "GB"),
+        (1 << 20, "MB"),
+        (1 << 10, "KB"),
+    ];
+    units
+        .iter()
+        .find(|(factor, _)| bytes >= *factor && bytes.is_multiple_of(*factor))
+        .map(|(factor, unit)| format!("{} {}", bytes / factor, unit))
+        .unwrap_or_else(|| bytes.to_string())
+}
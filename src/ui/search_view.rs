@@ -0,0 +1,192 @@
+use crate::config::{AppConfig, SearchQuery, ViewMode};
+use crate::filesystem::search::{self, SearchEvent, SearchHit};
+use crate::ui::content::wire_content_click;
+use crate::ui::pane::{display_path, Pane};
+use crate::ui::widgets;
+use glib::BoxedAnyObject;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Box, Button, GridView, Label, ListItem, ListView, NoSelection, Orientation,
+    ScrolledWindow, SignalListItemFactory, Spinner, Widget,
+};
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Search Results
+// ═══════════════════════════════════════════════
+//
+// Replaces a pane's content with the hits of a recursive search below
+// its folder, streamed in as they are found. Grid mode shows cards, the
+// other modes rows; both are the folder view's widgets plus the hit's
+// folder relative to the searched one, and act like them (click,
+// double-click, drag, context menu). Navigating anywhere refreshes the
+// content, which cancels the search.
+
+/// Runs `query` below the pane's folder and shows the results.
+pub fn show_search_results(pane: &Pane, query: SearchQuery, config: Rc<RefCell<AppConfig>>) {
+    let container = &pane.content_box;
+    let root = pane.current_path.borrow().clone();
+
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+    pane.selection.begin_view(&root);
+    pane.watches.clear();
+    pane.cancel_streams();
+    *pane.refilter.borrow_mut() = None;
+    pane.filter_bar.set_count(None);
+
+    let cfg = config.borrow().clone();
+    let store = gio::ListStore::new::<BoxedAnyObject>();
+    let grid = *pane.view_mode.borrow() == ViewMode::Grid;
+
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(|_, obj| {
+        if let Some(list_item) = obj.downcast_ref::<ListItem>() {
+            // Clicks are handled by the item widgets themselves
+            list_item.set_activatable(false);
+            list_item.set_selectable(false);
+        }
+    });
+    {
+        let pane = pane.clone();
+        let config = config.clone();
+        factory.connect_bind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            let Some(boxed) = list_item.item().and_downcast::<BoxedAnyObject>() else {
+                return;
+            };
+            let hit = boxed.borrow::<SearchHit>();
+            let location = location_of(&hit.rel);
+            let cfg = config.borrow().clone();
+            let btn: Button = if grid {
+                widgets::create_search_card(&hit.entry, &location, &cfg)
+            } else {
+                widgets::create_search_row(&hit.entry, &location, &cfg)
+            };
+            wire_content_click(&btn, &hit.entry, &pane, config.clone());
+            pane.selection.bind(&hit.entry.path, &btn);
+            list_item.set_child(Some(&btn));
+        });
+    }
+    {
+        let selection = pane.selection.clone();
+        factory.connect_unbind(move |_, obj| {
+            let Some(list_item) = obj.downcast_ref::<ListItem>() else {
+                return;
+            };
+            if let (Some(boxed), Some(child)) = (
+                list_item.item().and_downcast::<BoxedAnyObject>(),
+                list_item.child(),
+            ) {
+                selection.unbind(&boxed.borrow::<SearchHit>().entry.path, &child);
+            }
+            list_item.set_child(Widget::NONE);
+        });
+    }
+
+    let selection_model = NoSelection::new(Some(store.clone()));
+    let view: Widget = if grid {
+        GridView::builder()
+            .model(&selection_model)
+            .factory(&factory)
+            .min_columns(2)
+            .max_columns(20)
+            .css_classes(vec!["content-grid".to_string()])
+            .build()
+            .upcast()
+    } else {
+        ListView::builder()
+            .model(&selection_model)
+            .factory(&factory)
+            .css_classes(vec!["content-list".to_string()])
+            .build()
+            .upcast()
+    };
+    let scrolled = ScrolledWindow::builder()
+        .child(&view)
+        .vexpand(true)
+        .hexpand(true)
+        .visible(false)
+        .build();
+
+    // Status line: progress while searching, then the hit count
+    let spinner = Spinner::builder().spinning(true).build();
+    let status = Label::builder()
+        .label(format!("Searching in {}…", display_path(&root)))
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .build();
+    let status_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .halign(Align::Center)
+        .margin_top(4)
+        .margin_bottom(4)
+        .build();
+    status_row.append(&spinner);
+    status_row.append(&status);
+
+    let empty = Label::builder()
+        .label("No matches")
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .halign(Align::Center)
+        .valign(Align::Center)
+        .vexpand(true)
+        .visible(false)
+        .build();
+
+    container.append(&status_row);
+    container.append(&scrolled);
+    container.append(&empty);
+
+    let order: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
+    let count = Rc::new(Cell::new(0usize));
+    let pane_c = pane.clone();
+    let handle = search::start_search(
+        root.clone(),
+        query,
+        cfg.show_hidden,
+        move |event| match event {
+            SearchEvent::Batch(hits) => {
+                count.set(count.get() + hits.len());
+                order
+                    .borrow_mut()
+                    .extend(hits.iter().map(|h| h.entry.path.clone()));
+                let items: Vec<BoxedAnyObject> =
+                    hits.into_iter().map(BoxedAnyObject::new).collect();
+                store.splice(store.n_items(), 0, &items);
+                pane_c.selection.set_order(order.borrow().clone());
+                scrolled.set_visible(true);
+                status.set_label(&format!(
+                    "Searching in {}… {} found",
+                    display_path(&root),
+                    count.get()
+                ));
+            }
+            SearchEvent::Finished { truncated } => {
+                spinner.set_spinning(false);
+                spinner.set_visible(false);
+                let n = count.get();
+                status.set_label(&match (n, truncated) {
+                    (_, true) => format!("First {} results in {}", n, display_path(&root)),
+                    (1, _) => format!("1 result in {}", display_path(&root)),
+                    _ => format!("{} results in {}", n, display_path(&root)),
+                });
+                empty.set_visible(n == 0);
+            }
+        },
+    );
+    *pane.search.borrow_mut() = Some(handle);
+}
+
+/// The folder part of a hit's relative path, shown under its name.
+fn location_of(rel: &str) -> String {
+    match rel.rsplit_once('/') {
+        Some((folder, _)) => format!("{}/", folder),
+        None => "./".to_string(),
+    }
+}
@@ -0,0 +1,529 @@
+use crate::ui::clipboard;
+use gtk4::gdk::ModifierType;
+use gtk4::prelude::*;
+use gtk4::{GestureClick, PropagationPhase, Widget};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Multi-Selection Model
+// ═══════════════════════════════════════════════
+//
+// One `Selection` per pane. Views call `begin_view` before rendering
+// and `register` for every item widget in display order; the model
+// then keeps the `item-selected` CSS class of those widgets in sync.
+//
+//   Click         → select only this item
+//   Ctrl+Click    → toggle
+//   Shift+Click   → range from the anchor (display order)
+//   Double-click  → activate (open / navigate)
+//   Arrow keys    → move the cursor (see `keyboard`), Shift extends
+//
+// Navigating to another directory clears the selection.
+// Items cut to the clipboard carry `item-cut` and render dimmed.
+
+/// CSS class applied to selected item widgets.
+const SELECTED_CLASS: &str = "item-selected";
+
+/// CSS class applied to items cut to the clipboard.
+const CUT_CLASS: &str = "item-cut";
+
+type ChangeCallback = Rc<dyn Fn(&[PathBuf])>;
+type OrderSource = Rc<dyn Fn() -> Vec<PathBuf>>;
+
+#[derive(Default)]
+pub struct Selection {
+    /// Selected paths, in the order they were selected.
+    selected: RefCell<Vec<PathBuf>>,
+    /// Start of Shift-click ranges.
+    anchor: RefCell<Option<PathBuf>>,
+    /// Item keyboard movement starts from: the last one clicked or moved to.
+    cursor: RefCell<Option<PathBuf>>,
+    /// Directory the current view shows.
+    dir: RefCell<PathBuf>,
+    /// Visible items in display order.
+    order: RefCell<Vec<PathBuf>>,
+    /// Computes the display order instead of `order` (large virtualized
+    /// views, whose order changes with every listing batch).
+    order_source: RefCell<Option<OrderSource>>,
+    widgets: RefCell<HashMap<PathBuf, Widget>>,
+    on_change: RefCell<Option<ChangeCallback>>,
+}
+
+impl Selection {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self::default())
+    }
+
+    // ─── View Registration ───
+
+    /// Starts a fresh render of `dir`. Switching directories clears the selection;
+    /// re-rendering the same one keeps whatever still exists.
+    pub fn begin_view(&self, dir: &Path) {
+        self.order.borrow_mut().clear();
+        *self.order_source.borrow_mut() = None;
+        self.widgets.borrow_mut().clear();
+
+        if *self.dir.borrow() != dir {
+            *self.dir.borrow_mut() = dir.to_path_buf();
+            self.selected.borrow_mut().clear();
+            *self.anchor.borrow_mut() = None;
+            *self.cursor.borrow_mut() = None;
+            self.notify();
+        } else {
+            let before = self.selected.borrow().len();
+            self.selected
+                .borrow_mut()
+                .retain(|p| std::fs::symlink_metadata(p).is_ok());
+            if self.selected.borrow().len() != before {
+                self.notify();
+            }
+        }
+    }
+
+    /// Adds an item widget in display order.
+    pub fn register(&self, path: &Path, widget: &impl IsA<Widget>) {
+        let widget = widget.as_ref().clone();
+        if self.contains(path) {
+            widget.add_css_class(SELECTED_CLASS);
+        }
+        mark_cut(path, &widget);
+        self.order.borrow_mut().push(path.to_path_buf());
+        self.widgets.borrow_mut().insert(path.to_path_buf(), widget);
+    }
+
+    /// Attaches the widget currently showing `path` in a virtualized view,
+    /// where only on-screen items have widgets. The display order of such
+    /// views is set with `set_order` instead.
+    pub fn bind(&self, path: &Path, widget: &impl IsA<Widget>) {
+        let widget = widget.as_ref().clone();
+        if self.contains(path) {
+            widget.add_css_class(SELECTED_CLASS);
+        }
+        mark_cut(path, &widget);
+        self.widgets.borrow_mut().insert(path.to_path_buf(), widget);
+    }
+
+    /// Detaches `widget` from `path` when a virtualized view recycles it,
+    /// clearing the marks it carried for `path`.
+    pub fn unbind(&self, path: &Path, widget: &impl IsA<Widget>) {
+        let widget = widget.as_ref();
+        widget.remove_css_class(SELECTED_CLASS);
+        widget.remove_css_class(CUT_CLASS);
+        let mut widgets = self.widgets.borrow_mut();
+        if widgets.get(path) == Some(widget) {
+            widgets.remove(path);
+        }
+    }
+
+    /// Drops the widget of `path` (live updates replace or remove items).
+    /// A path that no longer exists is also deselected.
+    pub fn unregister(&self, path: &Path) {
+        self.order.borrow_mut().retain(|p| p != path);
+        self.widgets.borrow_mut().remove(path);
+
+        let gone = std::fs::symlink_metadata(path).is_err();
+        if gone && self.contains(path) {
+            self.selected.borrow_mut().retain(|p| p != path);
+            self.notify();
+        }
+    }
+
+    /// Replaces the display order after items were inserted out of order.
+    pub fn set_order(&self, order: Vec<PathBuf>) {
+        *self.order.borrow_mut() = order;
+        *self.order_source.borrow_mut() = None;
+    }
+
+    /// Makes `source` compute the display order whenever it is needed
+    /// (keyboard movement, ranges), so a growing view need not keep it.
+    pub fn set_order_source(&self, source: impl Fn() -> Vec<PathBuf> + 'static) {
+        self.order.borrow_mut().clear();
+        *self.order_source.borrow_mut() = Some(Rc::new(source));
+    }
+
+    /// Re-applies the cut marks after the clipboard changed.
+    pub fn refresh_cut_marks(&self) {
+        for (path, widget) in self.widgets.borrow().iter() {
+            mark_cut(path, widget);
+        }
+    }
+
+    /// Registered widgets with their paths (used by rubber-band selection).
+    pub fn widgets(&self) -> Vec<(PathBuf, Widget)> {
+        let order = self.order();
+        let widgets = self.widgets.borrow();
+        order
+            .iter()
+            .filter_map(|p| widgets.get(p).map(|w| (p.clone(), w.clone())))
+            .collect()
+    }
+
+    /// Items of the current view in display order.
+    pub fn order(&self) -> Vec<PathBuf> {
+        let source = self.order_source.borrow().clone();
+        match source {
+            Some(source) => source(),
+            None => self.order.borrow().clone(),
+        }
+    }
+
+    /// The widget showing `path`, if it has one (virtualized views only
+    /// have widgets for items near the visible area).
+    pub fn widget(&self, path: &Path) -> Option<Widget> {
+        self.widgets.borrow().get(path).cloned()
+    }
+
+    /// Registers the callback fired after every selection change.
+    pub fn connect_changed(&self, f: impl Fn(&[PathBuf]) + 'static) {
+        *self.on_change.borrow_mut() = Some(Rc::new(f));
+    }
+
+    // ─── Queries ───
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.selected.borrow().clone()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.selected.borrow().iter().any(|p| p == path)
+    }
+
+    /// The item keyboard movement starts from, if it is still shown:
+    /// the last one clicked or moved to, else the last selected one.
+    pub fn cursor(&self) -> Option<PathBuf> {
+        let order = self.order();
+        let cursor = self.cursor.borrow().clone();
+        cursor
+            .into_iter()
+            .chain(self.selected.borrow().iter().rev().cloned())
+            .find(|p| order.contains(p))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.borrow().is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.borrow().len()
+    }
+
+    // ─── Mutation ───
+
+    pub fn select_only(&self, path: &Path) {
+        *self.selected.borrow_mut() = vec![path.to_path_buf()];
+        *self.anchor.borrow_mut() = Some(path.to_path_buf());
+        *self.cursor.borrow_mut() = Some(path.to_path_buf());
+        self.changed();
+    }
+
+    pub fn toggle(&self, path: &Path) {
+        {
+            let mut selected = self.selected.borrow_mut();
+            if let Some(i) = selected.iter().position(|p| p == path) {
+                selected.remove(i);
+            } else {
+                selected.push(path.to_path_buf());
+            }
+        }
+        *self.anchor.borrow_mut() = Some(path.to_path_buf());
+        *self.cursor.borrow_mut() = Some(path.to_path_buf());
+        self.changed();
+    }
+
+    /// Selects the display-order range between the anchor and `path`.
+    /// With `add`, the range is added to the current selection.
+    pub fn extend_to(&self, path: &Path, add: bool) {
+        let anchor = self.anchor.borrow().clone();
+        let range = {
+            let order = self.order();
+            let end = order.iter().position(|p| p == path);
+            let start = anchor
+                .as_ref()
+                .and_then(|a| order.iter().position(|p| p == a))
+                .or(end);
+            match (start, end) {
+                (Some(s), Some(e)) => order[s.min(e)..=s.max(e)].to_vec(),
+                _ => vec![path.to_path_buf()],
+            }
+        };
+
+        {
+            let mut selected = self.selected.borrow_mut();
+            if !add {
+                selected.clear();
+            }
+            let mut seen: HashSet<PathBuf> = selected.iter().cloned().collect();
+            for p in range {
+                if seen.insert(p.clone()) {
+                    selected.push(p);
+                }
+            }
+        }
+        if anchor.is_none() {
+            *self.anchor.borrow_mut() = Some(path.to_path_buf());
+        }
+        *self.cursor.borrow_mut() = Some(path.to_path_buf());
+        self.changed();
+    }
+
+    pub fn select_all(&self) {
+        *self.selected.borrow_mut() = self.order();
+        self.changed();
+    }
+
+    /// Replaces the selection (rubber-band).
+    pub fn set(&self, paths: Vec<PathBuf>) {
+        *self.selected.borrow_mut() = paths;
+        self.changed();
+    }
+
+    pub fn clear(&self) {
+        if self.is_empty() {
+            return;
+        }
+        self.selected.borrow_mut().clear();
+        *self.anchor.borrow_mut() = None;
+        *self.cursor.borrow_mut() = None;
+        self.changed();
+    }
+
+    /// Applies a primary click with the given modifier state.
+    pub fn click(&self, path: &Path, state: ModifierType) {
+        let ctrl = state.contains(ModifierType::CONTROL_MASK);
+        if state.contains(ModifierType::SHIFT_MASK) {
+            self.extend_to(path, ctrl);
+        } else if ctrl {
+            self.toggle(path);
+        } else {
+            self.select_only(path);
+        }
+    }
+
+    // ─── Internals ───
+
+    fn changed(&self) {
+        {
+            let selected = self.selected.borrow();
+            let selected: HashSet<&PathBuf> = selected.iter().collect();
+            for (path, widget) in self.widgets.borrow().iter() {
+                if selected.contains(path) {
+                    widget.add_css_class(SELECTED_CLASS);
+                } else {
+                    widget.remove_css_class(SELECTED_CLASS);
+                }
+            }
+        }
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let callback = self.on_change.borrow().clone();
+        if let Some(cb) = callback {
+            let selected = self.paths();
+            cb(&selected);
+        }
+    }
+}
+
+fn mark_cut(path: &Path, widget: &Widget) {
+    if clipboard::is_cut(path) {
+        widget.add_css_class(CUT_CLASS);
+    } else {
+        widget.remove_css_class(CUT_CLASS);
+    }
+}
+
+/// Wires selection clicks on an item widget.
+/// Single clicks update `selection`, double clicks call `on_activate`.
+/// A right-click on an unselected item selects just that item, so
+/// context-menu actions always apply to what is highlighted.
+pub fn attach_item_clicks(
+    widget: &impl IsA<Widget>,
+    path: &Path,
+    selection: Rc<Selection>,
+    on_activate: impl Fn() + 'static,
+) {
+    let path = path.to_path_buf();
+    attach_current_item_clicks(
+        widget,
+        move || Some(path.clone()),
+        selection,
+        move |_| on_activate(),
+    );
+}
+
+/// Like `attach_item_clicks`, for a widget a virtualized view rebinds:
+/// `current` returns the path it shows at the time of the click.
+pub fn attach_current_item_clicks(
+    widget: &impl IsA<Widget>,
+    current: impl Fn() -> Option<PathBuf> + 'static,
+    selection: Rc<Selection>,
+    on_activate: impl Fn(&Path) + 'static,
+) {
+    let current = Rc::new(current);
+    // Capture phase: runs before the button's own click handling
+    let primary = GestureClick::builder()
+        .button(1)
+        .propagation_phase(PropagationPhase::Capture)
+        .build();
+    // Pressing an already selected item keeps the selection until release,
+    // so the whole selection can be dragged.
+    let deferred = Rc::new(Cell::new(false));
+    {
+        let current = current.clone();
+        let selection = selection.clone();
+        let deferred = deferred.clone();
+        primary.connect_pressed(move |g, n_press, _x, _y| {
+            deferred.set(false);
+            let Some(path) = current() else {
+                return;
+            };
+            let state = g.current_event_state();
+            if n_press == 2 {
+                on_activate(&path);
+            } else if n_press == 1 {
+                let plain =
+                    !state.intersects(ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK);
+                if plain && selection.contains(&path) && selection.len() > 1 {
+                    deferred.set(true);
+                } else {
+                    selection.click(&path, state);
+                }
+            }
+        });
+    }
+    {
+        let current = current.clone();
+        let selection = selection.clone();
+        primary.connect_released(move |_, _, _, _| {
+            if deferred.replace(false) {
+                if let Some(path) = current() {
+                    selection.select_only(&path);
+                }
+            }
+        });
+    }
+    widget.add_controller(primary);
+
+    let secondary = GestureClick::builder()
+        .button(3)
+        .propagation_phase(PropagationPhase::Capture)
+        .build();
+    secondary.connect_pressed(move |_, _, _, _| {
+        if let Some(path) = current().filter(|p| !selection.contains(p)) {
+            selection.select_only(&path);
+        }
+    });
+    widget.add_controller(secondary);
+}
+
+// ═══════════════════════════════════════════════
+//  Rubber-Band Selection
+// ═══════════════════════════════════════════════
+
+/// Lets the user drag a selection rectangle over empty space in `overlay`.
+/// Items whose widgets intersect the rectangle are selected; Ctrl adds to
+/// the existing selection. A plain click on empty space clears it.
+/// `enabled` is checked when a drag starts (e.g. only in Grid / List mode).
+pub fn attach_rubber_band(
+    overlay: &gtk4::Overlay,
+    selection: Rc<Selection>,
+    enabled: impl Fn() -> bool + 'static,
+) {
+    let band = gtk4::Box::builder()
+        .halign(gtk4::Align::Start)
+        .valign(gtk4::Align::Start)
+        .can_target(false)
+        .visible(false)
+        .css_classes(vec!["rubber-band".to_string()])
+        .build();
+    overlay.add_overlay(&band);
+
+    let drag = gtk4::GestureDrag::builder().button(1).build();
+    // Selection the drag started from (Ctrl+drag extends it)
+    let base: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
+
+    {
+        let overlay_c = overlay.clone();
+        let selection = selection.clone();
+        let base = base.clone();
+        drag.connect_drag_begin(move |g, x, y| {
+            // Presses on an item belong to the item (click / drag-and-drop)
+            if !enabled() || picks_item(&overlay_c, x, y) {
+                g.set_state(gtk4::EventSequenceState::Denied);
+                return;
+            }
+            let ctrl = g.current_event_state().contains(ModifierType::CONTROL_MASK);
+            *base.borrow_mut() = if ctrl { selection.paths() } else { Vec::new() };
+        });
+    }
+
+    {
+        let overlay_c = overlay.clone();
+        let band_c = band.clone();
+        let selection = selection.clone();
+        let base = base.clone();
+        drag.connect_drag_update(move |g, dx, dy| {
+            let Some((sx, sy)) = g.start_point() else {
+                return;
+            };
+            let (x, y) = (sx.min(sx + dx), sy.min(sy + dy));
+            let (w, h) = (dx.abs(), dy.abs());
+            if w < 4.0 && h < 4.0 {
+                return;
+            }
+
+            band_c.set_margin_start(x as i32);
+            band_c.set_margin_top(y as i32);
+            band_c.set_size_request(w as i32, h as i32);
+            band_c.set_visible(true);
+
+            let rect = gtk4::graphene::Rect::new(x as f32, y as f32, w as f32, h as f32);
+            let mut paths = base.borrow().clone();
+            for (path, widget) in selection.widgets() {
+                let hit = widget
+                    .compute_bounds(&overlay_c)
+                    .is_some_and(|b| b.intersection(&rect).is_some());
+                if hit && !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+            selection.set(paths);
+        });
+    }
+
+    drag.connect_drag_end(move |g, dx, dy| {
+        let was_band = band.is_visible();
+        band.set_visible(false);
+        if !was_band && dx.abs() < 4.0 && dy.abs() < 4.0 {
+            let ctrl = g.current_event_state().contains(ModifierType::CONTROL_MASK);
+            if !ctrl {
+                selection.clear();
+            }
+        }
+    });
+
+    overlay.add_controller(drag);
+}
+
+/// True if the widget under (x, y) is part of a file card / row.
+fn picks_item(overlay: &gtk4::Overlay, x: f64, y: f64) -> bool {
+    let mut widget = overlay.pick(x, y, gtk4::PickFlags::DEFAULT);
+    while let Some(w) = widget {
+        if w.has_css_class("file-card")
+            || w.has_css_class("file-row")
+            || w.has_css_class("details-cell")
+            || w.has_css_class("tree-row-btn")
+        {
+            return true;
+        }
+        if w == *overlay.upcast_ref::<Widget>() {
+            return false;
+        }
+        widget = w.parent();
+    }
+    false
+}
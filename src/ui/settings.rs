@@ -1,19 +1,17 @@
-use crate::config::{AppConfig, GroupBy, IconTheme, ViewMode};
+use crate::config::{Action, AppConfig, GroupBy, IconTheme, SortKey, ViewMode};
 use crate::core::Theme;
+use crate::filesystem::journal;
+use crate::ui::actions;
 use gtk4::prelude::*;
-use gtk4::{
-    Align, Box, CssProvider, DropDown, Label, Orientation, Scale, Separator, StringList, Switch,
-};
+use gtk4::{Align, Box, DropDown, Label, Orientation, Scale, Separator, StringList, Switch};
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// Builds the full settings panel as a Box widget.
 /// Takes shared config + a "refresh" callback to apply changes live.
-pub fn build_settings_panel(
-    config: Rc<RefCell<AppConfig>>,
-    css_provider: CssProvider,
-    on_change: Rc<dyn Fn()>,
-) -> Box {
+/// Theme, sorting, grouping and hidden files go through the registered
+/// actions (see `actions`), like the command palette.
+pub fn build_settings_panel(config: Rc<RefCell<AppConfig>>, on_change: Rc<dyn Fn()>) -> Box {
     let panel = Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(16)
@@ -60,18 +58,9 @@ pub fn build_settings_panel(
             }
         }
 
-        let config_c = config.clone();
-        let css_c = css_provider.clone();
-        let on_change_c = on_change.clone();
         dropdown.connect_selected_notify(move |dd| {
-            let idx = dd.selected() as usize;
-            let names = Theme::all_names();
-            if let Some(name) = names.get(idx) {
-                let theme = Theme::from_name(name);
-                css_c.load_from_data(&theme.to_css());
-                config_c.borrow_mut().theme = name.to_string();
-                config_c.borrow().save();
-                on_change_c();
+            if let Some(name) = Theme::all_names().get(dd.selected() as usize) {
+                actions::activate(dd, "theme", Some(&name.to_variant()));
             }
         });
         row.append(&dropdown);
@@ -112,9 +101,9 @@ pub fn build_settings_panel(
         panel.append(&row);
     }
 
-    // View mode toggle
+    // Default view mode for new panes (each pane toggles its own from the header)
     {
-        let row = setting_row("View Mode");
+        let row = setting_row("Default View Mode");
         let modes = StringList::new(&["Grid", "List", "Graph", "Tree"]);
         let dropdown = DropDown::builder().model(&modes).build();
         dropdown.set_selected(match config.borrow().view_mode {
@@ -155,15 +144,9 @@ pub fn build_settings_panel(
             }
         }
 
-        let config_c = config.clone();
-        let on_change_c = on_change.clone();
         dropdown.connect_selected_notify(move |dd| {
-            let idx = dd.selected() as usize;
-            let names = IconTheme::all_names();
-            if let Some(name) = names.get(idx) {
-                config_c.borrow_mut().icon_theme = IconTheme::from_name(name);
-                config_c.borrow().save();
-                on_change_c();
+            if let Some(name) = IconTheme::all_names().get(dd.selected() as usize) {
+                actions::activate(dd, "icon-theme", Some(&name.to_variant()));
             }
         });
         row.append(&dropdown);
@@ -178,32 +161,85 @@ pub fn build_settings_panel(
             .build(),
     );
 
+    // ═══════════════════════════════════
+    //  SORTING
+    // ═══════════════════════════════════
+    panel.append(&section_title("SORTING"));
+    {
+        let row = setting_row("Sort By");
+        let titles: Vec<&str> = SortKey::all().iter().map(|k| k.title()).collect();
+        let dropdown = DropDown::builder().model(&StringList::new(&titles)).build();
+        let current = config.borrow().sort_key;
+        let index = SortKey::all().iter().position(|k| *k == current);
+        dropdown.set_selected(index.unwrap_or(0) as u32);
+
+        dropdown.connect_selected_notify(move |dd| {
+            if let Some(key) = SortKey::all().get(dd.selected() as usize) {
+                actions::activate(dd, "sort-by", Some(&key.name().to_variant()));
+            }
+        });
+        row.append(&dropdown);
+        panel.append(&row);
+    }
+
+    // Direction
+    {
+        let row = setting_row("Descending");
+        let switch = Switch::builder()
+            .active(config.borrow().sort_descending)
+            .valign(Align::Center)
+            .build();
+        let config_c = config.clone();
+        switch.connect_active_notify(move |s| {
+            if config_c.borrow().sort_descending != s.is_active() {
+                actions::activate_bound(s, Action::ToggleSortDescending, false);
+            }
+        });
+        row.append(&switch);
+        panel.append(&row);
+    }
+
+    // Folders first
+    {
+        let row = setting_row("Folders First");
+        let switch = Switch::builder()
+            .active(config.borrow().folders_first)
+            .valign(Align::Center)
+            .build();
+        let config_c = config.clone();
+        switch.connect_active_notify(move |s| {
+            if config_c.borrow().folders_first != s.is_active() {
+                actions::activate_bound(s, Action::ToggleFoldersFirst, false);
+            }
+        });
+        row.append(&switch);
+        panel.append(&row);
+    }
+
+    panel.append(
+        &Separator::builder()
+            .orientation(Orientation::Horizontal)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build(),
+    );
+
     // ═══════════════════════════════════
     //  GROUPING
     // ═══════════════════════════════════
     panel.append(&section_title("GROUPING"));
     {
         let row = setting_row("Group By");
-        let groups = StringList::new(&["None", "Type", "Date", "Name"]);
-        let dropdown = DropDown::builder().model(&groups).build();
-        dropdown.set_selected(match config.borrow().grouping {
-            GroupBy::None => 0,
-            GroupBy::Type => 1,
-            GroupBy::Date => 2,
-            GroupBy::Name => 3,
-        });
+        let titles: Vec<&str> = GroupBy::all().iter().map(|g| g.title()).collect();
+        let dropdown = DropDown::builder().model(&StringList::new(&titles)).build();
+        let current = config.borrow().grouping.clone();
+        let index = GroupBy::all().iter().position(|g| *g == current);
+        dropdown.set_selected(index.unwrap_or(0) as u32);
 
-        let config_c = config.clone();
-        let on_change_c = on_change.clone();
         dropdown.connect_selected_notify(move |dd| {
-            config_c.borrow_mut().grouping = match dd.selected() {
-                1 => GroupBy::Type,
-                2 => GroupBy::Date,
-                3 => GroupBy::Name,
-                _ => GroupBy::None,
-            };
-            config_c.borrow().save();
-            on_change_c();
+            if let Some(group) = GroupBy::all().get(dd.selected() as usize) {
+                actions::activate(dd, "group-by", Some(&group.name().to_variant()));
+            }
         });
         row.append(&dropdown);
         panel.append(&row);
@@ -266,11 +302,69 @@ pub fn build_settings_panel(
             .valign(Align::Center)
             .build();
         let config_c = config.clone();
-        let on_change_c = on_change.clone();
         switch.connect_active_notify(move |s| {
-            config_c.borrow_mut().show_hidden = s.is_active();
+            if config_c.borrow().show_hidden != s.is_active() {
+                actions::activate_bound(s, Action::ToggleHidden, false);
+            }
+        });
+        row.append(&switch);
+        panel.append(&row);
+    }
+
+    panel.append(
+        &Separator::builder()
+            .orientation(Orientation::Horizontal)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build(),
+    );
+
+    // ═══════════════════════════════════
+    //  HISTORY
+    // ═══════════════════════════════════
+    panel.append(&section_title("HISTORY"));
+
+    // Keep the undo journal across restarts
+    {
+        let row = setting_row("Remember Undo History");
+        let switch = Switch::builder()
+            .active(config.borrow().persist_undo_history)
+            .valign(Align::Center)
+            .build();
+        let config_c = config.clone();
+        switch.connect_active_notify(move |s| {
+            config_c.borrow_mut().persist_undo_history = s.is_active();
+            config_c.borrow().save();
+            journal::set_persistent(s.is_active());
+        });
+        row.append(&switch);
+        panel.append(&row);
+    }
+
+    panel.append(
+        &Separator::builder()
+            .orientation(Orientation::Horizontal)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build(),
+    );
+
+    // ═══════════════════════════════════
+    //  SEARCH
+    // ═══════════════════════════════════
+    panel.append(&section_title("SEARCH"));
+
+    // Skip files a repository's .gitignore excludes when indexing
+    {
+        let row = setting_row("Respect .gitignore");
+        let switch = Switch::builder()
+            .active(config.borrow().respect_gitignore)
+            .valign(Align::Center)
+            .build();
+        let config_c = config.clone();
+        switch.connect_active_notify(move |s| {
+            config_c.borrow_mut().respect_gitignore = s.is_active();
             config_c.borrow().save();
-            on_change_c();
         });
         row.append(&switch);
         panel.append(&row);
@@ -1,33 +1,41 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, SavedSearch};
 use crate::filesystem;
-use crate::ui::widgets;
+use crate::filesystem::journal::{self, FileOp};
+use crate::filesystem::watch::WatchSet;
+use crate::ui::context_menu::context_menu_button;
+use crate::ui::pane::{display_path, Pane, Panes};
+use crate::ui::{drag_source, drop_target, widgets};
 use gtk4::prelude::*;
 use gtk4::{
-    Align, ApplicationWindow, Box, Button, Label, Orientation, Popover, ScrolledWindow, Separator,
-    ToggleButton,
+    Align, ApplicationWindow, Box, Button, GestureClick, Label, Orientation, Popover,
+    ScrolledWindow, Separator, Stack, ToggleButton,
 };
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::path::Path;
 use std::rc::Rc;
 
 use crate::ui::content::refresh_content;
 
+thread_local! {
+    /// Watches the directory shown in the sidebar browser.
+    static SIDEBAR_WATCH: WatchSet = WatchSet::default();
+}
+
 // ═══════════════════════════════════════════════
 //  Sidebar Construction
 // ═══════════════════════════════════════════════
 
 /// Builds the complete sidebar widget (toolbar + places + file browser).
+/// The sidebar always drives the active pane of `panes`.
+/// `main_stack` switches between the "panes" page and the "settings" page,
+/// whose scrolled window is `settings_scroll`.
 /// Returns (sidebar_widget, nav_box, settings_toggle).
 pub fn build_sidebar(
-    current_path: Rc<RefCell<PathBuf>>,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
+    panes: Rc<Panes>,
     config: Rc<RefCell<AppConfig>>,
-    content_box: Box,
-    content_scroll: ScrolledWindow,
-    breadcrumb_label: Label,
-    inspector_info: Label,
+    main_stack: Stack,
+    settings_scroll: ScrolledWindow,
     window: ApplicationWindow,
-    css_provider: gtk4::CssProvider,
 ) -> (Box, Box, ToggleButton) {
     let sidebar = Box::builder()
         .orientation(Orientation::Vertical)
@@ -82,6 +90,13 @@ pub fn build_sidebar(
         .build();
     sidebar.append(&places_box);
 
+    // ── Smart folders (saved searches); hidden while there are none ──
+    let smart_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(1)
+        .build();
+    sidebar.append(&smart_box);
+
     sidebar.append(
         &Separator::builder()
             .orientation(Orientation::Horizontal)
@@ -117,87 +132,62 @@ pub fn build_sidebar(
     // ── Wire places shortcuts ──
     bind_places_logic(
         &places_box,
-        current_path.clone(),
+        panes.clone(),
+        nav_box.clone(),
+        window.clone(),
+        config.clone(),
+    );
+
+    // ── Wire smart folders ──
+    bind_smart_folders(
+        &smart_box,
+        panes.clone(),
         nav_box.clone(),
-        content_box.clone(),
         window.clone(),
-        breadcrumb_label.clone(),
-        inspector_info.clone(),
-        selected_file_path.clone(),
         config.clone(),
     );
 
     // ── Wire creation popover ──
     setup_creation_popover(
         &new_item_btn,
-        current_path.clone(),
+        panes.clone(),
         nav_box.clone(),
-        content_box.clone(),
         window.clone(),
-        breadcrumb_label.clone(),
-        inspector_info.clone(),
-        selected_file_path.clone(),
         config.clone(),
     );
 
     // ── Wire settings toggle ──
     {
-        let content_scroll_c = content_scroll.clone();
         let config_c = config.clone();
-        let css_c = css_provider;
         let nav_box_c = nav_box.clone();
-        let content_box_c = content_box.clone();
         let window_c = window.clone();
-        let breadcrumb_c = breadcrumb_label.clone();
-        let inspector_info_c = inspector_info.clone();
-        let selected_c = selected_file_path.clone();
-        let current_path_c = current_path.clone();
+        let panes_c = panes.clone();
 
         settings_toggle.connect_toggled(move |btn| {
-            content_scroll_c.set_child(gtk4::Widget::NONE);
+            settings_scroll.set_child(gtk4::Widget::NONE);
 
             if btn.is_active() {
                 let on_change: Rc<dyn Fn()> = {
                     let nav_box_cc = nav_box_c.clone();
                     let window_cc = window_c.clone();
-                    let breadcrumb_cc = breadcrumb_c.clone();
-                    let inspector_info_cc = inspector_info_c.clone();
-                    let selected_cc = selected_c.clone();
-                    let current_path_cc = current_path_c.clone();
+                    let panes_cc = panes_c.clone();
                     let config_cc = config_c.clone();
                     Rc::new(move || {
                         refresh_sidebar(
                             &nav_box_cc,
-                            current_path_cc.clone(),
+                            &panes_cc.active(),
                             &window_cc,
-                            &breadcrumb_cc,
-                            &inspector_info_cc,
-                            selected_cc.clone(),
                             config_cc.clone(),
                         );
                     })
                 };
-                let settings_panel = crate::ui::settings::build_settings_panel(
-                    config_c.clone(),
-                    css_c.clone(),
-                    on_change,
-                );
-                let settings_scroll = ScrolledWindow::builder()
-                    .hscrollbar_policy(gtk4::PolicyType::Never)
-                    .vexpand(true)
-                    .hexpand(true)
-                    .child(&settings_panel)
-                    .build();
-                content_scroll_c.set_child(Some(&settings_scroll));
+                let settings_panel =
+                    crate::ui::settings::build_settings_panel(config_c.clone(), on_change);
+                settings_scroll.set_child(Some(&settings_panel));
+                main_stack.set_visible_child_name("settings");
             } else {
-                content_scroll_c.set_child(Some(&content_box_c));
-                refresh_content(
-                    &content_box_c,
-                    current_path_c.clone(),
-                    &inspector_info_c,
-                    selected_c.clone(),
-                    config_c.clone(),
-                );
+                main_stack.set_visible_child_name("panes");
+                panes_c.refresh_both(&config_c);
             }
         });
     }
@@ -211,13 +201,9 @@ pub fn build_sidebar(
 
 fn bind_places_logic(
     container: &Box,
-    current_path: Rc<RefCell<PathBuf>>,
+    panes: Rc<Panes>,
     nav_box: Box,
-    content_box: Box,
     window: ApplicationWindow,
-    breadcrumb: Label,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
     config: Rc<RefCell<AppConfig>>,
 ) {
     let places = vec![
@@ -236,6 +222,7 @@ fn bind_places_logic(
         ("Pictures", "folder-pictures-symbolic", dirs::picture_dir()),
         ("Music", "folder-music-symbolic", dirs::audio_dir()),
         ("Videos", "folder-videos-symbolic", dirs::video_dir()),
+        ("Trash", "user-trash-symbolic", filesystem::trash_location()),
     ];
 
     for (name, icon, path_opt) in places {
@@ -243,46 +230,252 @@ fn bind_places_logic(
             let btn = widgets::create_place_row(name, icon);
             let path_clone = path.clone();
 
-            let current_path = current_path.clone();
+            let panes_c = panes.clone();
+            let config_c = config.clone();
+            let panes = panes.clone();
             let nav_box = nav_box.clone();
-            let content_box = content_box.clone();
             let window = window.clone();
-            let breadcrumb = breadcrumb.clone();
-            let inspector_info = inspector_info.clone();
-            let selected_file_path = selected_file_path.clone();
             let config = config.clone();
 
-            btn.connect_clicked(move |_| {
-                *current_path.borrow_mut() = path_clone.clone();
-                refresh_all(
-                    &nav_box,
-                    &content_box,
-                    current_path.clone(),
-                    &window,
-                    &breadcrumb,
-                    &inspector_info,
-                    selected_file_path.clone(),
-                    config.clone(),
-                );
+            // Dropping onto a place copies / moves there (or trashes);
+            // lingering opens it in the active pane
+            let open_place: Rc<dyn Fn(&Path)> = Rc::new(move |path: &Path| {
+                let pane = panes.active();
+                *pane.current_path.borrow_mut() = path.to_path_buf();
+                refresh_all(&nav_box, &pane, &window, config.clone());
             });
+            drop_target::attach_folder_drop_target(
+                &btn,
+                &path,
+                &panes_c.active(),
+                config_c.clone(),
+                Some(open_place.clone()),
+            );
+
+            btn.connect_clicked(move |_| open_place(&path_clone));
             container.append(&btn);
         }
     }
 }
 
+// ═══════════════════════════════════════════════
+//  Smart Folders
+// ═══════════════════════════════════════════════
+//
+// Saved searches, listed below the places. Opening one goes to its folder
+// in the active pane and re-runs the search there; right-click renames
+// or removes it. Saving from a search bar under an existing name
+// replaces that smart folder.
+
+fn bind_smart_folders(
+    container: &Box,
+    panes: Rc<Panes>,
+    nav_box: Box,
+    window: ApplicationWindow,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    for pane in [&panes.left, &panes.right] {
+        let pane_c = pane.clone();
+        let container = container.clone();
+        let panes = panes.clone();
+        let nav_box = nav_box.clone();
+        let window = window.clone();
+        let config = config.clone();
+        pane.search_bar.connect_save(move |name, query| {
+            let saved = SavedSearch {
+                name,
+                root: pane_c.current_path.borrow().clone(),
+                query,
+            };
+            {
+                let mut cfg = config.borrow_mut();
+                match cfg.saved_searches.iter_mut().find(|s| s.name == saved.name) {
+                    Some(existing) => *existing = saved,
+                    None => cfg.saved_searches.push(saved),
+                }
+                cfg.save();
+            }
+            refresh_smart_folders(&container, &panes, &nav_box, &window, &config);
+        });
+    }
+    refresh_smart_folders(container, &panes, &nav_box, &window, &config);
+}
+
+/// Rebuilds the smart folder rows from the config.
+fn refresh_smart_folders(
+    container: &Box,
+    panes: &Rc<Panes>,
+    nav_box: &Box,
+    window: &ApplicationWindow,
+    config: &Rc<RefCell<AppConfig>>,
+) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+    let saved = config.borrow().saved_searches.clone();
+    if saved.is_empty() {
+        return;
+    }
+
+    container.append(
+        &Label::builder()
+            .label("SMART FOLDERS")
+            .css_classes(vec!["sidebar-title".to_string()])
+            .halign(Align::Start)
+            .margin_top(8)
+            .build(),
+    );
+    let rows = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(1)
+        .margin_start(4)
+        .margin_end(4)
+        .build();
+    container.append(&rows);
+
+    for (index, search) in saved.into_iter().enumerate() {
+        let btn = widgets::create_place_row(&search.name, "folder-saved-search-symbolic");
+        btn.set_tooltip_text(Some(&format!("Search in {}", display_path(&search.root))));
+        {
+            let panes = panes.clone();
+            let nav_box = nav_box.clone();
+            let window = window.clone();
+            let config = config.clone();
+            btn.connect_clicked(move |_| {
+                let pane = panes.active();
+                *pane.current_path.borrow_mut() = search.root.clone();
+                refresh_all(&nav_box, &pane, &window, config.clone());
+                pane.search_bar.show_query(&search.query);
+            });
+        }
+        attach_smart_folder_menu(&btn, index, container, panes, nav_box, window, config);
+        rows.append(&btn);
+    }
+}
+
+/// Right-click menu of a smart folder row: Rename, Remove.
+fn attach_smart_folder_menu(
+    btn: &Button,
+    index: usize,
+    container: &Box,
+    panes: &Rc<Panes>,
+    nav_box: &Box,
+    window: &ApplicationWindow,
+    config: &Rc<RefCell<AppConfig>>,
+) {
+    let popover = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    popover.set_parent(btn);
+    let popover_destroy = popover.clone();
+    btn.connect_destroy(move |_| popover_destroy.unparent());
+
+    let menu_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(6)
+        .margin_bottom(6)
+        .margin_start(4)
+        .margin_end(4)
+        .build();
+    let name_entry = gtk4::Entry::builder().visible(false).build();
+    let rename_btn = context_menu_button("document-edit-symbolic", "Rename");
+    let remove_btn = context_menu_button("list-remove-symbolic", "Remove");
+    menu_box.append(&name_entry);
+    menu_box.append(&rename_btn);
+    menu_box.append(&remove_btn);
+    popover.set_child(Some(&menu_box));
+
+    let gesture = GestureClick::builder().button(3).build();
+    {
+        let popover = popover.clone();
+        let name_entry = name_entry.clone();
+        let rename_btn = rename_btn.clone();
+        let remove_btn = remove_btn.clone();
+        gesture.connect_pressed(move |_, _, _, _| {
+            name_entry.set_visible(false);
+            rename_btn.set_visible(true);
+            remove_btn.set_visible(true);
+            popover.popup();
+        });
+    }
+    btn.add_controller(gesture);
+
+    // Rows are rebuilt from the config, outside the click being handled
+    let rebuild: Rc<dyn Fn()> = {
+        let container = container.clone();
+        let panes = panes.clone();
+        let nav_box = nav_box.clone();
+        let window = window.clone();
+        let config = config.clone();
+        let popover = popover.clone();
+        Rc::new(move || {
+            popover.popdown();
+            let container = container.clone();
+            let panes = panes.clone();
+            let nav_box = nav_box.clone();
+            let window = window.clone();
+            let config = config.clone();
+            glib::idle_add_local_once(move || {
+                refresh_smart_folders(&container, &panes, &nav_box, &window, &config);
+            });
+        })
+    };
+
+    {
+        let name_entry = name_entry.clone();
+        let remove_btn = remove_btn.clone();
+        let config = config.clone();
+        rename_btn.connect_clicked(move |btn| {
+            if let Some(search) = config.borrow().saved_searches.get(index) {
+                name_entry.set_text(&search.name);
+            }
+            name_entry.set_visible(true);
+            btn.set_visible(false);
+            remove_btn.set_visible(false);
+            name_entry.grab_focus();
+        });
+    }
+    {
+        let config = config.clone();
+        let rebuild = rebuild.clone();
+        name_entry.connect_activate(move |entry| {
+            let name = entry.text().trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let mut cfg = config.borrow_mut();
+            if let Some(search) = cfg.saved_searches.get_mut(index) {
+                search.name = name;
+            }
+            cfg.save();
+            drop(cfg);
+            rebuild();
+        });
+    }
+    {
+        let config = config.clone();
+        remove_btn.connect_clicked(move |_| {
+            let mut cfg = config.borrow_mut();
+            if index < cfg.saved_searches.len() {
+                cfg.saved_searches.remove(index);
+            }
+            cfg.save();
+            drop(cfg);
+            rebuild();
+        });
+    }
+}
+
 // ═══════════════════════════════════════════════
 //  Creation Popover
 // ═══════════════════════════════════════════════
 
 fn setup_creation_popover(
     parent_btn: &Button,
-    current_path: Rc<RefCell<PathBuf>>,
+    panes: Rc<Panes>,
     nav_box: Box,
-    content_box: Box,
     window: ApplicationWindow,
-    breadcrumb: Label,
-    inspector_info: Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
     config: Rc<RefCell<AppConfig>>,
 ) {
     let popover = Popover::builder()
@@ -343,38 +536,27 @@ fn setup_creation_popover(
     let wire_creation = |is_dir: bool| {
         let entry = entry.clone();
         let popover = popover.clone();
-        let current_path = current_path.clone();
+        let panes = panes.clone();
         let nav_box = nav_box.clone();
-        let content_box = content_box.clone();
         let window = window.clone();
-        let breadcrumb = breadcrumb.clone();
-        let inspector_info = inspector_info.clone();
-        let selected_file_path = selected_file_path.clone();
         let config = config.clone();
 
         move |_: &Button| {
             let name = entry.text();
             if !name.is_empty() {
-                let parent = current_path.borrow();
+                let pane = panes.active();
+                let parent = pane.current_path.borrow().clone();
                 let result = if is_dir {
                     filesystem::create_directory(&parent, &name)
                 } else {
                     filesystem::create_file(&parent, &name)
                 };
                 match result {
-                    Ok(_) => {
+                    Ok(path) => {
+                        journal::record(FileOp::Create { path, is_dir });
                         entry.set_text("");
                         popover.popdown();
-                        refresh_all(
-                            &nav_box,
-                            &content_box,
-                            current_path.clone(),
-                            &window,
-                            &breadcrumb,
-                            &inspector_info,
-                            selected_file_path.clone(),
-                            config.clone(),
-                        );
+                        refresh_all(&nav_box, &pane, &window, config.clone());
                     }
                     Err(e) => eprintln!("Creation failed: {}", e),
                 }
@@ -390,43 +572,22 @@ fn setup_creation_popover(
 //  Sidebar Refresh
 // ═══════════════════════════════════════════════
 
-/// Refreshes both sidebar and content.
+/// Refreshes both sidebar and the content of `pane`.
 pub fn refresh_all(
     nav_box: &Box,
-    content_box: &Box,
-    current_path: Rc<RefCell<PathBuf>>,
+    pane: &Pane,
     window: &ApplicationWindow,
-    breadcrumb: &Label,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
     config: Rc<RefCell<AppConfig>>,
 ) {
-    refresh_sidebar(
-        nav_box,
-        current_path.clone(),
-        window,
-        breadcrumb,
-        inspector_info,
-        selected_file_path.clone(),
-        config.clone(),
-    );
-    refresh_content(
-        content_box,
-        current_path,
-        inspector_info,
-        selected_file_path,
-        config,
-    );
+    refresh_sidebar(nav_box, pane, window, config.clone());
+    refresh_content(pane, config);
 }
 
-/// Refreshes the sidebar file browser.
+/// Refreshes the sidebar file browser for `pane`.
 pub fn refresh_sidebar(
     container: &Box,
-    current_path: Rc<RefCell<PathBuf>>,
+    pane: &Pane,
     window: &ApplicationWindow,
-    breadcrumb: &Label,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
     config: Rc<RefCell<AppConfig>>,
 ) {
     // Clear
@@ -434,97 +595,117 @@ pub fn refresh_sidebar(
         container.remove(&child);
     }
 
-    let path = current_path.borrow().clone();
+    let path = pane.current_path.borrow().clone();
     let cfg = config.borrow().clone();
 
     window.set_title(Some(&format!("Diptych — {}", path.to_string_lossy())));
 
     // Simplified breadcrumb
-    let home = dirs::home_dir().unwrap_or_default();
-    let display_path = if path.starts_with(&home) {
-        format!("~/{}", path.strip_prefix(&home).unwrap_or(&path).display())
-    } else {
-        path.to_string_lossy().to_string()
-    };
-    breadcrumb.set_label(&display_path);
-
-    // Reset inspector
-    *selected_file_path.borrow_mut() = None;
-    inspector_info.set_label("Select a file to inspect");
+    pane.update_breadcrumb();
 
     // Go up button
+    let mut up_row = None;
     if let Some(parent) = path.parent() {
         let parent_path = parent.to_path_buf();
         let up_btn = widgets::create_go_up_row();
 
-        let cp = current_path.clone();
+        let pane_c = pane.clone();
         let cont = container.clone();
         let win = window.clone();
-        let bc = breadcrumb.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
         let cfg_c = config.clone();
 
         up_btn.connect_clicked(move |_| {
-            *cp.borrow_mut() = parent_path.clone();
-            refresh_sidebar(
-                &cont,
-                cp.clone(),
-                &win,
-                &bc,
-                &info,
-                sel.clone(),
-                cfg_c.clone(),
-            );
+            *pane_c.current_path.borrow_mut() = parent_path.clone();
+            refresh_sidebar(&cont, &pane_c, &win, cfg_c.clone());
         });
         container.append(&up_btn);
+        up_row = Some(up_btn);
     }
 
     // List entries
-    let files = filesystem::list_directory(&path, cfg.show_hidden);
-    let dummy_config = AppConfig {
+    let files = filesystem::list_directory(&path, cfg.show_hidden, &cfg.sort_order());
+    let mut rows = Vec::with_capacity(files.len());
+    for entry in files {
+        let btn = sidebar_row(&entry, container, pane, window, &config);
+        container.append(&btn);
+        rows.push((entry, btn));
+    }
+
+    // Live updates: rows are inserted / removed in place
+    let rows = Rc::new(RefCell::new(rows));
+    let container_c = container.clone();
+    let pane_c = pane.clone();
+    let window_c = window.clone();
+    let dir = path.clone();
+    SIDEBAR_WATCH.with(|w| {
+        w.clear();
+        w.watch(&path, move |changed| {
+            let show_hidden = config.borrow().show_hidden;
+            let mut rows = rows.borrow_mut();
+            for changed_path in changed {
+                if changed_path.parent() != Some(dir.as_path()) {
+                    continue;
+                }
+                let entry = filesystem::Entry::from_path(changed_path)
+                    .filter(|e| show_hidden || !e.name.starts_with('.'));
+                let found = rows.iter().position(|(e, _)| e.path == *changed_path);
+                match (found, entry) {
+                    (Some(i), None) => {
+                        let (_, btn) = rows.remove(i);
+                        container_c.remove(&btn);
+                    }
+                    (None, Some(entry)) => {
+                        let order = config.borrow().sort_order();
+                        let key = filesystem::sort_key(&entry, &order);
+                        let pos =
+                            rows.partition_point(|(e, _)| filesystem::sort_key(e, &order) < key);
+                        let btn = sidebar_row(&entry, &container_c, &pane_c, &window_c, &config);
+                        let prev: Option<gtk4::Widget> = match pos {
+                            0 => up_row.as_ref().map(|b| b.clone().upcast()),
+                            _ => Some(rows[pos - 1].1.clone().upcast()),
+                        };
+                        container_c.insert_child_after(&btn, prev.as_ref());
+                        rows.insert(pos, (entry, btn));
+                    }
+                    _ => {}
+                }
+            }
+        });
+    });
+}
+
+/// One entry row of the sidebar browser: folders are entered,
+/// files are selected in `pane`.
+fn sidebar_row(
+    entry: &filesystem::Entry,
+    container: &Box,
+    pane: &Pane,
+    window: &ApplicationWindow,
+    config: &Rc<RefCell<AppConfig>>,
+) -> Button {
+    let row_config = AppConfig {
         icon_size: 48,
         show_file_size: false,
         show_modified_date: false,
-        ..cfg.clone()
+        ..config.borrow().clone()
     };
-
-    for entry in &files {
-        let btn = widgets::create_file_row(entry, &dummy_config);
-        let entry_path = entry.path.clone();
-
-        let cp = current_path.clone();
-        let cont = container.clone();
-        let win = window.clone();
-        let bc = breadcrumb.clone();
-        let info = inspector_info.clone();
-        let sel = selected_file_path.clone();
-        let cfg_c = config.clone();
-        let is_dir = entry.is_dir;
-        let name = entry.name.clone();
-        let size_display = entry.size_display();
-        let mod_display = entry.modified_display();
-
-        btn.connect_clicked(move |_| {
-            if is_dir {
-                *cp.borrow_mut() = entry_path.clone();
-                refresh_sidebar(
-                    &cont,
-                    cp.clone(),
-                    &win,
-                    &bc,
-                    &info,
-                    sel.clone(),
-                    cfg_c.clone(),
-                );
-            } else {
-                info.set_label(&format!(
-                    "{}  •  {}  •  {}",
-                    name, size_display, mod_display
-                ));
-                *sel.borrow_mut() = Some(entry_path.clone());
-            }
-        });
-        container.append(&btn);
-    }
+    let btn = widgets::create_file_row(entry, &row_config);
+    let entry_path = entry.path.clone();
+
+    let pane_c = pane.clone();
+    let cont = container.clone();
+    let win = window.clone();
+    let cfg_c = config.clone();
+    let is_dir = entry.is_dir;
+
+    btn.connect_clicked(move |_| {
+        if is_dir {
+            *pane_c.current_path.borrow_mut() = entry_path.clone();
+            refresh_sidebar(&cont, &pane_c, &win, cfg_c.clone());
+        } else {
+            pane_c.selection.select_only(&entry_path);
+        }
+    });
+    drag_source::attach_file_drag_source(&btn, &entry.path, None);
+    btn
 }
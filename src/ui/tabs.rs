@@ -0,0 +1,383 @@
+use crate::config::{SavedTab, ViewMode};
+use crate::filesystem;
+use crate::ui::history::{self, HistoryEntry, HistoryState};
+use crate::ui::pane::Pane;
+use gtk4::prelude::*;
+use gtk4::{Box, Button, GestureClick, Label, Orientation, PolicyType, ScrolledWindow};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Pane Tabs
+// ═══════════════════════════════════════════════
+//
+// Each pane has a row of tabs above its content. The active tab's state
+// lives in the pane itself (`current_path`, `view_mode`, `history`);
+// switching tabs parks it in the tab being left and loads the other one.
+// The open tabs are saved in the config's `[session]` on close.
+
+/// State of a tab in the background.
+struct TabState {
+    path: PathBuf,
+    view_mode: ViewMode,
+    history: HistoryState,
+    /// Scroll offset and selection when the tab was left.
+    view: Option<HistoryEntry>,
+}
+
+struct Tab {
+    widget: Box,
+    label: Label,
+    /// `None` while the tab is active.
+    state: RefCell<Option<TabState>>,
+}
+
+type SwitchCallback = Rc<dyn Fn(&Pane)>;
+
+/// The tab row of one pane.
+pub struct Tabs {
+    pub bar: Box,
+    strip: Box,
+    tabs: RefCell<Vec<Rc<Tab>>>,
+    active: Cell<usize>,
+    on_switch: RefCell<Option<SwitchCallback>>,
+}
+
+impl Tabs {
+    pub fn new() -> Rc<Self> {
+        let strip = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(2)
+            .build();
+        let scroller = ScrolledWindow::builder()
+            .child(&strip)
+            .hscrollbar_policy(PolicyType::External)
+            .vscrollbar_policy(PolicyType::Never)
+            .hexpand(true)
+            .build();
+
+        let bar = Box::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(4)
+            .css_classes(vec!["pane-tabs".to_string()])
+            .build();
+        bar.append(&scroller);
+
+        Rc::new(Self {
+            bar,
+            strip,
+            tabs: RefCell::new(Vec::new()),
+            active: Cell::new(0),
+            on_switch: RefCell::new(None),
+        })
+    }
+
+    /// Registers the callback that renders the pane after a tab switch.
+    pub fn connect_switch(&self, f: impl Fn(&Pane) + 'static) {
+        *self.on_switch.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.borrow().len()
+    }
+
+    fn index_of(&self, tab: &Rc<Tab>) -> Option<usize> {
+        self.tabs.borrow().iter().position(|t| Rc::ptr_eq(t, tab))
+    }
+
+    fn mark_active(&self) {
+        for (i, tab) in self.tabs.borrow().iter().enumerate() {
+            if i == self.active.get() {
+                tab.widget.add_css_class("pane-tab-active");
+            } else {
+                tab.widget.remove_css_class("pane-tab-active");
+            }
+        }
+    }
+}
+
+// ─── Setup ───
+
+/// Adds the tab for the pane's starting location plus the "new tab" button.
+pub fn init(pane: &Pane) {
+    let new_btn = Button::builder()
+        .icon_name("tab-new-symbolic")
+        .tooltip_text("New Tab (Ctrl+T)")
+        .has_frame(false)
+        .css_classes(vec!["pane-tab-new".to_string()])
+        .build();
+    {
+        let pane_c = pane.clone();
+        new_btn.connect_clicked(move |_| {
+            let path = pane_c.current_path.borrow().clone();
+            open_tab(&pane_c, path);
+        });
+    }
+    pane.tabs.bar.append(&new_btn);
+
+    let tab = build_tab(pane, None);
+    pane.tabs.strip.append(&tab.widget);
+    pane.tabs.tabs.borrow_mut().push(tab);
+    pane.tabs.mark_active();
+    update_label(pane);
+}
+
+fn build_tab(pane: &Pane, state: Option<TabState>) -> Rc<Tab> {
+    let label = Label::builder()
+        .max_width_chars(18)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .css_classes(vec!["pane-tab-label".to_string()])
+        .build();
+    let close_btn = Button::builder()
+        .icon_name("window-close-symbolic")
+        .tooltip_text("Close Tab (Ctrl+W)")
+        .has_frame(false)
+        .css_classes(vec!["pane-tab-close".to_string()])
+        .build();
+    let widget = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(4)
+        .css_classes(vec!["pane-tab".to_string()])
+        .build();
+    widget.append(&label);
+    widget.append(&close_btn);
+
+    if let Some(state) = &state {
+        set_label(&label, &state.path);
+    }
+    let tab = Rc::new(Tab {
+        widget,
+        label,
+        state: RefCell::new(state),
+    });
+
+    // Click selects, middle-click closes
+    let click = GestureClick::builder().button(0).build();
+    {
+        let pane_c = pane.clone();
+        let weak = Rc::downgrade(&tab);
+        click.connect_released(move |g, _, _, _| {
+            let Some(index) = weak.upgrade().and_then(|t| pane_c.tabs.index_of(&t)) else {
+                return;
+            };
+            match g.current_button() {
+                1 => select_tab(&pane_c, index),
+                2 => close_tab(&pane_c, index),
+                _ => {}
+            }
+        });
+    }
+    tab.widget.add_controller(click);
+
+    {
+        let pane_c = pane.clone();
+        let weak = Rc::downgrade(&tab);
+        close_btn.connect_clicked(move |_| {
+            if let Some(index) = weak.upgrade().and_then(|t| pane_c.tabs.index_of(&t)) {
+                close_tab(&pane_c, index);
+            }
+        });
+    }
+    tab
+}
+
+fn set_label(label: &Label, path: &Path) {
+    let name = if filesystem::is_trash_location(path) {
+        "Trash".to_string()
+    } else {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string())
+    };
+    label.set_label(&name);
+    label.set_tooltip_text(Some(&path.to_string_lossy()));
+}
+
+/// Shows the pane's current folder on the active tab.
+pub fn update_label(pane: &Pane) {
+    let tabs = pane.tabs.tabs.borrow();
+    if let Some(tab) = tabs.get(pane.tabs.active.get()) {
+        set_label(&tab.label, &pane.current_path.borrow());
+    }
+}
+
+// ─── Switching ───
+
+/// Parks the pane's state in the active tab.
+fn park_active(pane: &Pane) {
+    let view = history::capture(pane);
+    let state = TabState {
+        path: pane.current_path.borrow().clone(),
+        view_mode: pane.view_mode.borrow().clone(),
+        history: pane.history.take_state(),
+        view,
+    };
+    let tabs = pane.tabs.tabs.borrow();
+    if let Some(tab) = tabs.get(pane.tabs.active.get()) {
+        *tab.state.borrow_mut() = Some(state);
+    }
+}
+
+/// Loads the parked state of tab `index` into the pane and renders it.
+fn activate(pane: &Pane, index: usize) {
+    let tab = pane.tabs.tabs.borrow()[index].clone();
+    let Some(state) = tab.state.borrow_mut().take() else {
+        return;
+    };
+    pane.tabs.active.set(index);
+    pane.tabs.mark_active();
+
+    *pane.current_path.borrow_mut() = state.path;
+    *pane.view_mode.borrow_mut() = state.view_mode;
+    pane.history.set_state(state.history);
+
+    let callback = pane.tabs.on_switch.borrow().clone();
+    if let Some(cb) = callback {
+        cb(pane);
+    }
+    if let Some(view) = state.view {
+        history::restore(pane, &view);
+    }
+}
+
+pub fn select_tab(pane: &Pane, index: usize) {
+    if index == pane.tabs.active.get() || index >= pane.tabs.len() {
+        return;
+    }
+    park_active(pane);
+    activate(pane, index);
+}
+
+/// Moves to the next (or previous, for negative `delta`) tab, wrapping.
+pub fn cycle_tab(pane: &Pane, delta: isize) {
+    let len = pane.tabs.len() as isize;
+    if len > 1 {
+        let next = (pane.tabs.active.get() as isize + delta).rem_euclid(len);
+        select_tab(pane, next as usize);
+    }
+}
+
+/// Opens `path` in a new tab right after the active one.
+/// The new tab starts with the pane's current view mode.
+pub fn open_tab(pane: &Pane, path: PathBuf) {
+    let state = TabState {
+        path,
+        view_mode: pane.view_mode.borrow().clone(),
+        history: HistoryState::default(),
+        view: None,
+    };
+    let index = pane.tabs.active.get() + 1;
+    insert_tab(pane, index, state);
+    select_tab(pane, index);
+}
+
+fn insert_tab(pane: &Pane, index: usize, state: TabState) {
+    let tab = build_tab(pane, Some(state));
+    let prev = index
+        .checked_sub(1)
+        .and_then(|i| pane.tabs.tabs.borrow().get(i).map(|t| t.widget.clone()));
+    pane.tabs
+        .strip
+        .insert_child_after(&tab.widget, prev.as_ref());
+    pane.tabs.tabs.borrow_mut().insert(index, tab);
+    pane.tabs.mark_active();
+}
+
+/// Closes tab `index`. The last tab of a pane stays open.
+pub fn close_tab(pane: &Pane, index: usize) {
+    let len = pane.tabs.len();
+    if len <= 1 || index >= len {
+        return;
+    }
+
+    let active = pane.tabs.active.get();
+    if index == active {
+        // Show the neighbour first; the closed tab's state is dropped
+        let next = if index + 1 < len {
+            index + 1
+        } else {
+            index - 1
+        };
+        park_active(pane);
+        activate(pane, next);
+    }
+
+    let tab = pane.tabs.tabs.borrow_mut().remove(index);
+    pane.tabs.strip.remove(&tab.widget);
+    if pane.tabs.active.get() > index {
+        pane.tabs.active.set(pane.tabs.active.get() - 1);
+    }
+    pane.tabs.mark_active();
+}
+
+pub fn close_active_tab(pane: &Pane) {
+    close_tab(pane, pane.tabs.active.get());
+}
+
+// ═══════════════════════════════════════════════
+//  Session
+// ═══════════════════════════════════════════════
+
+/// The pane's tabs in order, and the index of the active one.
+pub fn saved_tabs(pane: &Pane) -> (Vec<SavedTab>, usize) {
+    let tabs = pane.tabs.tabs.borrow();
+    let saved = tabs
+        .iter()
+        .map(|tab| match &*tab.state.borrow() {
+            Some(state) => SavedTab {
+                path: state.path.clone(),
+                view_mode: state.view_mode.clone(),
+            },
+            None => SavedTab {
+                path: pane.current_path.borrow().clone(),
+                view_mode: pane.view_mode.borrow().clone(),
+            },
+        })
+        .collect();
+    (saved, pane.tabs.active.get())
+}
+
+/// Re-opens saved tabs before the first render. Folders that no longer
+/// exist are skipped; with none left, the pane keeps its single tab.
+pub fn restore_session(pane: &Pane, saved: &[SavedTab], active: usize) {
+    let (valid, active) = {
+        let mut valid = Vec::new();
+        let mut new_active = 0;
+        for (i, tab) in saved.iter().enumerate() {
+            if tab.path.is_dir() || filesystem::is_trash_location(&tab.path) {
+                if i <= active {
+                    new_active = valid.len();
+                }
+                valid.push(tab.clone());
+            }
+        }
+        (valid, new_active)
+    };
+    if valid.is_empty() {
+        return;
+    }
+
+    // The initial tab becomes the active saved one; the others are parked
+    *pane.current_path.borrow_mut() = valid[active].path.clone();
+    *pane.view_mode.borrow_mut() = valid[active].view_mode.clone();
+
+    for (i, tab) in valid.into_iter().enumerate() {
+        if i == active {
+            continue;
+        }
+        let state = TabState {
+            path: tab.path,
+            view_mode: tab.view_mode,
+            history: HistoryState::default(),
+            view: None,
+        };
+        // Tabs before the active one push it to the right
+        insert_tab(pane, i, state);
+        if i < active {
+            pane.tabs.active.set(pane.tabs.active.get() + 1);
+        }
+    }
+    pane.tabs.mark_active();
+    update_label(pane);
+}
@@ -0,0 +1,282 @@
+use crate::filesystem::format_size;
+use crate::transfer::{
+    self, ConflictChoice, Resolution, TransferEvent, TransferKind, TransferReport,
+};
+use crate::ui::pane::display_path;
+use gtk4::prelude::*;
+use gtk4::{Align, Box, Button, CheckButton, Label, Orientation, ProgressBar, Window};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+// ═══════════════════════════════════════════════
+//  Transfer Progress Dialog
+// ═══════════════════════════════════════════════
+//
+// One small window per copy / move job:
+//   • progress bar + "3 of 12 files • 4.1 MB of 20 MB"
+//   • Pause / Resume and Cancel
+//   • inline conflict prompt (Skip / Rename / Overwrite + "Apply to all")
+//
+// Short jobs finish before the window is ever shown.
+
+/// Delay before the progress window appears for a running job.
+const SHOW_DELAY: Duration = Duration::from_millis(300);
+
+/// Starts a copy / move job and shows its progress.
+/// `on_done` runs on the main thread once the job has finished or was cancelled.
+pub fn run_transfer(
+    parent: Option<&Window>,
+    kind: TransferKind,
+    sources: Vec<PathBuf>,
+    dest_dir: PathBuf,
+    on_done: Rc<dyn Fn(&TransferReport)>,
+) {
+    if sources.is_empty() {
+        return;
+    }
+
+    let window = Window::builder()
+        .title(kind.verb())
+        .default_width(440)
+        .resizable(false)
+        .css_classes(vec!["transfer-dialog".to_string()])
+        .build();
+    if let Some(p) = parent {
+        window.set_transient_for(Some(p));
+    }
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(10)
+        .margin_top(16)
+        .margin_bottom(16)
+        .margin_start(16)
+        .margin_end(16)
+        .build();
+
+    let item_word = if sources.len() == 1 { "item" } else { "items" };
+    let title = Label::builder()
+        .label(format!(
+            "{} {} {} to {}",
+            kind.verb(),
+            sources.len(),
+            item_word,
+            display_path(&dest_dir)
+        ))
+        .css_classes(vec!["context-menu-title".to_string()])
+        .halign(Align::Start)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .build();
+
+    let current_label = Label::builder()
+        .label("Preparing…")
+        .css_classes(vec!["inspector-subtitle".to_string()])
+        .halign(Align::Start)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .build();
+
+    let progress_bar = ProgressBar::builder().show_text(true).build();
+
+    let stats_label = Label::builder()
+        .label("")
+        .css_classes(vec!["file-row-meta".to_string()])
+        .halign(Align::Start)
+        .wrap(true)
+        .build();
+
+    // ── Conflict prompt (hidden until needed) ──
+    let conflict_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .visible(false)
+        .css_classes(vec!["transfer-conflict".to_string()])
+        .build();
+    let conflict_label = Label::builder()
+        .halign(Align::Start)
+        .wrap(true)
+        .xalign(0.0)
+        .build();
+    let apply_all_check = CheckButton::builder().label("Apply to all").build();
+    let conflict_buttons = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(Align::End)
+        .build();
+    let skip_btn = Button::builder()
+        .label("Skip")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let rename_btn = Button::builder()
+        .label("Keep Both")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let overwrite_btn = Button::builder()
+        .label("Overwrite")
+        .css_classes(vec!["btn-primary".to_string()])
+        .build();
+    conflict_buttons.append(&skip_btn);
+    conflict_buttons.append(&rename_btn);
+    conflict_buttons.append(&overwrite_btn);
+    conflict_box.append(&conflict_label);
+    conflict_box.append(&apply_all_check);
+    conflict_box.append(&conflict_buttons);
+
+    // ── Pause / Cancel ──
+    let control_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(Align::End)
+        .build();
+    let pause_btn = Button::builder()
+        .label("Pause")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let cancel_btn = Button::builder()
+        .label("Cancel")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    control_row.append(&pause_btn);
+    control_row.append(&cancel_btn);
+
+    vbox.append(&title);
+    vbox.append(&current_label);
+    vbox.append(&progress_bar);
+    vbox.append(&stats_label);
+    vbox.append(&conflict_box);
+    vbox.append(&control_row);
+    window.set_child(Some(&vbox));
+
+    let finished = Rc::new(Cell::new(false));
+
+    // ── Start the job ──
+    let handle = {
+        let window_c = window.clone();
+        let current_label_c = current_label.clone();
+        let progress_bar_c = progress_bar.clone();
+        let stats_label_c = stats_label.clone();
+        let conflict_box_c = conflict_box.clone();
+        let conflict_label_c = conflict_label.clone();
+        let apply_all_c = apply_all_check.clone();
+        let pause_btn_c = pause_btn.clone();
+        let cancel_btn_c = cancel_btn.clone();
+        let finished_c = finished.clone();
+
+        transfer::start_transfer(kind, sources, dest_dir, move |event| match event {
+            TransferEvent::Progress(p) => {
+                progress_bar_c.set_fraction(p.fraction());
+                progress_bar_c.set_text(Some(&format!("{:.0}%", p.fraction() * 100.0)));
+                if let Some(current) = &p.current {
+                    current_label_c.set_label(&file_name(current));
+                }
+                stats_label_c.set_label(&format!(
+                    "{} of {} files  •  {} of {}",
+                    p.files_done,
+                    p.files_total,
+                    format_size(p.bytes_done),
+                    format_size(p.bytes_total)
+                ));
+            }
+            TransferEvent::Conflict { source, dest } => {
+                conflict_label_c.set_label(&format!(
+                    "“{}” already exists in {}.",
+                    file_name(&dest),
+                    dest.parent().map(display_path).unwrap_or_default()
+                ));
+                conflict_label_c.set_tooltip_text(Some(&source.to_string_lossy()));
+                // Each conflict asks afresh unless the user ticks it again
+                apply_all_c.set_active(false);
+                conflict_box_c.set_visible(true);
+                window_c.present();
+            }
+            TransferEvent::Finished(report) => {
+                finished_c.set(true);
+                on_done(&report);
+
+                if report.errors.is_empty() {
+                    window_c.close();
+                    return;
+                }
+
+                // Keep the window open so the user can read what failed
+                conflict_box_c.set_visible(false);
+                pause_btn_c.set_visible(false);
+                cancel_btn_c.set_label("Close");
+                current_label_c.set_label(&format!("{} error(s)", report.errors.len()));
+                stats_label_c.set_label(&report.errors.join("\n"));
+                window_c.present();
+            }
+        })
+    };
+
+    // ── Wire: conflict answers ──
+    for (btn, choice) in [
+        (&skip_btn, ConflictChoice::Skip),
+        (&rename_btn, ConflictChoice::Rename),
+        (&overwrite_btn, ConflictChoice::Overwrite),
+    ] {
+        let handle_c = handle.clone();
+        let conflict_box_c = conflict_box.clone();
+        let apply_all_c = apply_all_check.clone();
+        btn.connect_clicked(move |_| {
+            conflict_box_c.set_visible(false);
+            handle_c.resolve(Resolution {
+                choice,
+                apply_to_all: apply_all_c.is_active(),
+            });
+        });
+    }
+
+    // ── Wire: pause / resume ──
+    {
+        let handle_c = handle.clone();
+        pause_btn.connect_clicked(move |btn| {
+            let paused = !handle_c.is_paused();
+            handle_c.set_paused(paused);
+            btn.set_label(if paused { "Resume" } else { "Pause" });
+        });
+    }
+
+    // ── Wire: cancel / close ──
+    {
+        let handle_c = handle.clone();
+        let finished_c = finished.clone();
+        let window_c = window.clone();
+        cancel_btn.connect_clicked(move |_| {
+            if finished_c.get() {
+                window_c.close();
+            } else {
+                handle_c.cancel();
+            }
+        });
+    }
+
+    // Closing the window mid-job cancels it
+    {
+        let handle_c = handle.clone();
+        let finished_c = finished.clone();
+        window.connect_close_request(move |_| {
+            if !finished_c.get() {
+                handle_c.cancel();
+            }
+            glib::Propagation::Proceed
+        });
+    }
+
+    // Only show the window for jobs that take a moment
+    {
+        let window_c = window.clone();
+        glib::timeout_add_local_once(SHOW_DELAY, move || {
+            if !finished.get() {
+                window_c.present();
+            }
+        });
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
@@ -0,0 +1,521 @@
+use crate::config::AppConfig;
+use crate::filesystem::{self, Entry, TrashItem};
+use crate::ui::content::refresh_content;
+use crate::ui::context_menu::{context_menu_button, show_confirm_dialog};
+use crate::ui::pane::{display_path, Pane};
+use crate::ui::selection;
+use crate::ui::widgets::icon::{icon_for_entry_themed, refine_icon};
+use gtk4::prelude::*;
+use gtk4::{Align, Box, Button, GestureClick, Image, Label, Orientation, Popover, Widget};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// ═══════════════════════════════════════════════
+//  Trash Browser
+// ═══════════════════════════════════════════════
+//
+// Shown instead of the regular views when a pane navigates to the
+// Trash place. Every row shows the original location and the deletion
+// date read from `.trashinfo`, plus:
+//   • Restore            — inline button and right-click menu
+//   • Delete Permanently — right-click menu, confirmed
+//   • Empty Trash        — header button, confirmed
+//
+// Restore and Delete Permanently apply to the whole selection when the
+// clicked row is part of it. Restoring onto an existing file asks:
+// Replace / Keep Both / Cancel.
+
+/// Builds the trash listing for `pane`.
+pub fn build_trash_view(pane: &Pane, config: Rc<RefCell<AppConfig>>) -> Box {
+    let view = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(4)
+        .build();
+
+    let items = filesystem::list_trash();
+
+    // ── Header: count + Empty Trash ──
+    let header = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .margin_bottom(6)
+        .build();
+    let count_label = Label::builder()
+        .label(match items.len() {
+            1 => "1 item in Trash".to_string(),
+            n => format!("{} items in Trash", n),
+        })
+        .css_classes(vec!["group-header".to_string()])
+        .halign(Align::Start)
+        .hexpand(true)
+        .build();
+    let empty_btn = Button::builder()
+        .label("Empty Trash")
+        .sensitive(!items.is_empty())
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    header.append(&count_label);
+    header.append(&empty_btn);
+    view.append(&header);
+
+    {
+        let pane_c = pane.clone();
+        let cfg = config.clone();
+        empty_btn.connect_clicked(move |btn| {
+            let pane_d = pane_c.clone();
+            let cfg_d = cfg.clone();
+            show_confirm_dialog(
+                btn,
+                "Permanently delete everything in the Trash?",
+                "Empty Trash",
+                move || {
+                    let failed = filesystem::empty_trash();
+                    if failed > 0 {
+                        eprintln!("[trash] {} item(s) could not be removed", failed);
+                    }
+                    refresh_content(&pane_d, cfg_d.clone());
+                },
+            );
+        });
+    }
+
+    // ── Items ──
+    let items = Rc::new(items);
+    for item in items.iter() {
+        let row = create_trash_row(item, &config.borrow());
+        wire_trash_row(&row, item, &items, pane, config.clone());
+        view.append(&row);
+    }
+
+    // Empty state
+    if items.is_empty() {
+        let empty = Label::builder()
+            .label("Trash is empty")
+            .css_classes(vec!["inspector-subtitle".to_string()])
+            .halign(Align::Center)
+            .valign(Align::Center)
+            .vexpand(true)
+            .build();
+        view.append(&empty);
+    }
+
+    view
+}
+
+// ═══════════════════════════════════════════════
+//  Rows
+// ═══════════════════════════════════════════════
+
+fn create_trash_row(item: &TrashItem, config: &AppConfig) -> Button {
+    let name = item.display_name();
+    // Borrow the regular icon lookup via a synthetic entry
+    let entry = Entry {
+        name: name.clone(),
+        path: item.trashed_path.clone(),
+        is_dir: item.is_dir,
+        size: item.size,
+        modified: None,
+        created: None,
+        accessed: None,
+        extension: item
+            .original_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        mime: filesystem::guess_mime(&item.trashed_path, item.is_dir),
+    };
+
+    let container = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .build();
+
+    let icon = Image::builder()
+        .icon_name(icon_for_entry_themed(&entry, &config.icon_theme))
+        .pixel_size(24)
+        .build();
+    refine_icon(&icon, &entry, &config.icon_theme);
+
+    let text_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    let name_label = Label::builder()
+        .label(&name)
+        .xalign(0.0)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .build();
+    let origin_label = Label::builder()
+        .label(
+            item.original_path
+                .parent()
+                .map(display_path)
+                .unwrap_or_default(),
+        )
+        .css_classes(vec!["file-row-meta".to_string()])
+        .xalign(0.0)
+        .ellipsize(gtk4::pango::EllipsizeMode::Start)
+        .build();
+    text_box.append(&name_label);
+    text_box.append(&origin_label);
+
+    let size_label = Label::builder()
+        .label(entry.size_display())
+        .css_classes(vec!["file-row-meta".to_string()])
+        .width_chars(8)
+        .xalign(1.0)
+        .build();
+    let date_label = Label::builder()
+        .label(item.deletion_display())
+        .css_classes(vec!["file-row-meta".to_string()])
+        .width_chars(16)
+        .xalign(1.0)
+        .build();
+
+    let restore_btn = Button::builder()
+        .icon_name("edit-undo-symbolic")
+        .tooltip_text("Restore")
+        .valign(Align::Center)
+        .css_classes(vec![
+            "toolbar-btn".to_string(),
+            "trash-restore-btn".to_string(),
+        ])
+        .build();
+
+    container.append(&icon);
+    container.append(&text_box);
+    container.append(&size_label);
+    container.append(&date_label);
+    container.append(&restore_btn);
+
+    Button::builder()
+        .child(&container)
+        .halign(Align::Fill)
+        .has_frame(false)
+        .css_classes(vec!["file-row".to_string()])
+        .build()
+}
+
+fn wire_trash_row(
+    row: &Button,
+    item: &TrashItem,
+    items: &Rc<Vec<TrashItem>>,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    // Click selects; a single selected item gets its details in the inspector
+    pane.selection.register(&item.trashed_path, row);
+    selection::attach_item_clicks(row, &item.trashed_path, pane.selection.clone(), || {});
+    {
+        let pane_c = pane.clone();
+        let summary = format!(
+            "{}  •  from {}  •  deleted {}",
+            item.display_name(),
+            item.original_path
+                .parent()
+                .map(display_path)
+                .unwrap_or_default(),
+            item.deletion_display()
+        );
+        row.connect_clicked(move |_| {
+            if pane_c.selection.len() == 1 {
+                pane_c.inspector_info.set_label(&summary);
+            }
+        });
+    }
+
+    // Inline restore button (last child of the row)
+    if let Some(restore_btn) = row
+        .child()
+        .and_then(|c| c.last_child())
+        .and_downcast::<Button>()
+    {
+        let item_c = item.clone();
+        let items_c = items.clone();
+        let pane_c = pane.clone();
+        let cfg = config.clone();
+        restore_btn.connect_clicked(move |btn| {
+            let targets = action_targets(&pane_c, &item_c, &items_c);
+            restore_items(btn.upcast_ref(), targets, &pane_c, cfg.clone());
+        });
+    }
+
+    // Right-click: Restore / Delete Permanently
+    let popover = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .has_arrow(true)
+        .build();
+    popover.set_parent(row);
+
+    // Ensure popover is unparented when the row is destroyed
+    let popover_destroy = popover.clone();
+    row.connect_destroy(move |_| {
+        popover_destroy.unparent();
+    });
+
+    let menu_box = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(4)
+        .margin_end(4)
+        .build();
+    let restore_item_btn = context_menu_button("edit-undo-symbolic", "Restore");
+    let delete_btn = context_menu_button("edit-delete-symbolic", "Delete Permanently");
+    delete_btn.add_css_class("context-menu-danger");
+    menu_box.append(&restore_item_btn);
+    menu_box.append(&delete_btn);
+    popover.set_child(Some(&menu_box));
+
+    let gesture = GestureClick::builder().button(3).build();
+    let popover_c = popover.clone();
+    gesture.connect_pressed(move |_, _, _, _| {
+        popover_c.popup();
+    });
+    row.add_controller(gesture);
+
+    {
+        let item_c = item.clone();
+        let items_c = items.clone();
+        let pane_c = pane.clone();
+        let cfg = config.clone();
+        let popover_c = popover.clone();
+        let row_c = row.clone();
+        restore_item_btn.connect_clicked(move |_| {
+            popover_c.popdown();
+            let targets = action_targets(&pane_c, &item_c, &items_c);
+            restore_items(row_c.upcast_ref(), targets, &pane_c, cfg.clone());
+        });
+    }
+
+    {
+        let item_c = item.clone();
+        let items_c = items.clone();
+        let pane_c = pane.clone();
+        let cfg = config.clone();
+        let popover_c = popover.clone();
+        let row_c = row.clone();
+        delete_btn.connect_clicked(move |_| {
+            popover_c.popdown();
+            let targets = action_targets(&pane_c, &item_c, &items_c);
+            purge_items(row_c.upcast_ref(), targets, &pane_c, cfg.clone());
+        });
+    }
+}
+
+// ═══════════════════════════════════════════════
+//  Restore
+// ═══════════════════════════════════════════════
+
+/// What to do with an item whose original path is taken.
+#[derive(Clone, Copy)]
+enum Conflict {
+    KeepBoth,
+    Replace,
+}
+
+/// Restores `items` to their original paths one after another, asking
+/// about each one whose path is taken, then refreshes the panes.
+fn restore_items(
+    anchor: &Widget,
+    items: Vec<TrashItem>,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    restore_next(
+        anchor.clone(),
+        items.into(),
+        Vec::new(),
+        pane.clone(),
+        config,
+    );
+}
+
+fn restore_next(
+    anchor: Widget,
+    mut items: VecDeque<TrashItem>,
+    mut restored: Vec<PathBuf>,
+    pane: Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    while let Some(item) = items.pop_front() {
+        let dest = item.original_path.clone();
+        if std::fs::symlink_metadata(&dest).is_err() {
+            restored.extend(restore_to(&item, &dest));
+            continue;
+        }
+
+        // Taken: ask, then carry on with the rest
+        let anchor_c = anchor.clone();
+        let prompt_item = item.clone();
+        ask_conflict(&anchor, &prompt_item, move |answer| {
+            match answer {
+                Some(Conflict::KeepBoth) => {
+                    restored.extend(restore_to(&item, &filesystem::unique_path(&dest)));
+                }
+                Some(Conflict::Replace) => match filesystem::delete_permanently(&dest) {
+                    Ok(_) => restored.extend(restore_to(&item, &dest)),
+                    Err(e) => eprintln!("[trash] Failed to replace {}: {}", dest.display(), e),
+                },
+                None => {}
+            }
+            restore_next(anchor_c, items, restored, pane, config);
+        });
+        return;
+    }
+
+    refresh_content(&pane, config.clone());
+    // The other pane may be showing a folder we restored into
+    if let Some(other) = pane.other() {
+        let shown = other.current_path.borrow().clone();
+        if restored
+            .iter()
+            .any(|dest| dest.parent() == Some(shown.as_path()))
+        {
+            refresh_content(&other, config);
+        }
+    }
+}
+
+/// Restores `item` to `dest`. Returns `dest` if that worked.
+fn restore_to(item: &TrashItem, dest: &Path) -> Option<PathBuf> {
+    match filesystem::restore(item, dest) {
+        Ok(_) => Some(dest.to_path_buf()),
+        Err(e) => {
+            eprintln!("[trash] Failed to restore {}: {}", dest.display(), e);
+            None
+        }
+    }
+}
+
+/// Asks what to do about `item`, whose original path is taken: Keep Both,
+/// Replace or Cancel (`None`, also when the prompt is dismissed).
+fn ask_conflict(
+    anchor: &Widget,
+    item: &TrashItem,
+    on_answer: impl FnOnce(Option<Conflict>) + 'static,
+) {
+    let dialog = Popover::builder()
+        .css_classes(vec!["context-menu".to_string()])
+        .build();
+    dialog.set_parent(anchor);
+
+    let dialog_destroy = dialog.clone();
+    anchor.connect_destroy(move |_| {
+        dialog_destroy.unparent();
+    });
+
+    let vbox = Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(8)
+        .margin_top(8)
+        .margin_bottom(8)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    let label = Label::builder()
+        .label(format!(
+            "“{}” already exists in {}.",
+            item.display_name(),
+            item.original_path
+                .parent()
+                .map(display_path)
+                .unwrap_or_default()
+        ))
+        .css_classes(vec!["context-menu-title".to_string()])
+        .halign(Align::Start)
+        .wrap(true)
+        .max_width_chars(32)
+        .build();
+
+    let button_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .halign(Align::End)
+        .build();
+    let cancel_btn = Button::builder()
+        .label("Cancel")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let keep_both_btn = Button::builder()
+        .label("Keep Both")
+        .css_classes(vec!["btn-secondary".to_string()])
+        .build();
+    let replace_btn = Button::builder()
+        .label("Replace")
+        .css_classes(vec!["btn-danger".to_string()])
+        .build();
+    button_row.append(&cancel_btn);
+    button_row.append(&keep_both_btn);
+    button_row.append(&replace_btn);
+
+    vbox.append(&label);
+    vbox.append(&button_row);
+    dialog.set_child(Some(&vbox));
+
+    let answer: Rc<Cell<Option<Conflict>>> = Rc::new(Cell::new(None));
+    for (btn, choice) in [
+        (&cancel_btn, None),
+        (&keep_both_btn, Some(Conflict::KeepBoth)),
+        (&replace_btn, Some(Conflict::Replace)),
+    ] {
+        let dialog_c = dialog.clone();
+        let answer = answer.clone();
+        btn.connect_clicked(move |_| {
+            answer.set(choice);
+            dialog_c.popdown();
+        });
+    }
+
+    // Answered once the prompt closes, however it was closed
+    let on_answer = RefCell::new(Some(on_answer));
+    dialog.connect_closed(move |d| {
+        if let Some(on_answer) = on_answer.borrow_mut().take() {
+            on_answer(answer.get());
+        }
+        let d = d.clone();
+        glib::idle_add_local_once(move || d.unparent());
+    });
+
+    dialog.popup();
+    cancel_btn.grab_focus();
+}
+
+/// Deletes `items` for good, after asking.
+fn purge_items(
+    anchor: &Widget,
+    items: Vec<TrashItem>,
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+) {
+    let message = match items.as_slice() {
+        [item] => format!("Permanently delete “{}”?", item.display_name()),
+        _ => format!("Permanently delete {} items?", items.len()),
+    };
+    let pane_c = pane.clone();
+    show_confirm_dialog(anchor, &message, "Delete", move || {
+        for item in &items {
+            if let Err(e) = filesystem::purge(item) {
+                eprintln!("[trash] Failed to delete {}: {}", item.display_name(), e);
+            }
+        }
+        refresh_content(&pane_c, config.clone());
+    });
+}
+
+/// Items a row action applies to: the whole selection when the clicked
+/// item is part of it, otherwise just the clicked item.
+fn action_targets(pane: &Pane, clicked: &TrashItem, items: &[TrashItem]) -> Vec<TrashItem> {
+    if !pane.selection.contains(&clicked.trashed_path) {
+        return vec![clicked.clone()];
+    }
+    let selected = pane.selection.paths();
+    items
+        .iter()
+        .filter(|i| selected.contains(&i.trashed_path))
+        .cloned()
+        .collect()
+}
@@ -1,12 +1,16 @@
 use crate::config::{AppConfig, IconTheme};
 use crate::filesystem;
+use crate::filesystem::filter::NameFilter;
+use crate::filesystem::watch::WatchSet;
 use crate::thumbnail;
-use crate::ui::drag_source;
-use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed};
+use crate::ui::pane::Pane;
+use crate::ui::selection::{self, Selection};
+use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed, refine_icon};
+use crate::ui::{drag_source, drop_target, open_with};
 use gtk4::prelude::*;
 use gtk4::{Align, Box, Button, Image, Label, Orientation};
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
@@ -20,7 +24,10 @@ use std::rc::Rc;
 //   • Rounded hover highlights
 //   • Directory item count badges
 //   • File type color coding
-//   • Selected item accent highlight
+//   • Multi-selection highlight (shared with Grid / List)
+//   • Folder rows take drops and expand when a drag lingers
+//   • The pane's filter hides non-matching rows of the expanded
+//     folders, keeping the ancestors of matches
 
 /// Indentation per nesting level (pixels).
 const INDENT_PX: i32 = 20;
@@ -28,14 +35,33 @@ const INDENT_PX: i32 = 20;
 /// Maximum recursive depth to prevent runaway expansion.
 const MAX_DEPTH: u32 = 12;
 
-/// Builds the full tree view starting from `root_path`.
-pub fn build_tree_view(
+/// Shared state of one tree view.
+struct TreeCtx {
+    container: Box,
+    expanded: RefCell<HashSet<PathBuf>>,
     root_path: Rc<RefCell<PathBuf>>,
     config: Rc<RefCell<AppConfig>>,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    on_navigate: Rc<dyn Fn(PathBuf)>,
+    selection: Rc<Selection>,
+    watches: Rc<WatchSet>,
+    pane: Pane,
+    // ── Rendered state, used by live updates ──
+    /// Row of every visible entry.
+    rows: RefCell<HashMap<PathBuf, Button>>,
+    /// Children of every rendered (expanded) directory, in display order.
+    children: RefCell<HashMap<PathBuf, Vec<filesystem::Entry>>>,
+    /// "Empty folder" hints of expanded empty directories.
+    hints: RefCell<HashMap<PathBuf, Box>>,
+}
+
+/// Builds the full tree view starting from the pane's current path.
+/// Every expanded directory is watched in the pane's watch set; changes
+/// on disk insert or remove just the affected rows.
+pub fn build_tree_view(
+    pane: &Pane,
+    config: Rc<RefCell<AppConfig>>,
+    _on_navigate: Rc<dyn Fn(PathBuf)>,
 ) -> Box {
+    let root_path = pane.current_path.clone();
     let container = Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(0)
@@ -44,412 +70,570 @@ pub fn build_tree_view(
         .css_classes(vec!["tree-view-container".to_string()])
         .build();
 
-    let expanded: Rc<RefCell<HashSet<PathBuf>>> = Rc::new(RefCell::new(HashSet::new()));
-
     // Expand the root itself by default
-    {
-        let root = root_path.borrow().clone();
-        expanded.borrow_mut().insert(root);
-    }
+    let mut expanded = HashSet::new();
+    expanded.insert(root_path.borrow().clone());
 
-    {
-        let root = root_path.borrow().clone();
-        render_tree(
-            &container,
-            &root,
-            0,
-            expanded.clone(),
-            root_path.clone(),
-            config.clone(),
-            inspector_info,
-            selected_file_path.clone(),
-            on_navigate.clone(),
-        );
-    }
+    let ctx = Rc::new(TreeCtx {
+        container: container.clone(),
+        expanded: RefCell::new(expanded),
+        root_path,
+        config,
+        selection: pane.selection.clone(),
+        watches: pane.watches.clone(),
+        pane: pane.clone(),
+        rows: RefCell::new(HashMap::new()),
+        children: RefCell::new(HashMap::new()),
+        hints: RefCell::new(HashMap::new()),
+    });
+    rebuild_tree(&ctx);
+
+    let weak = Rc::downgrade(&ctx);
+    *pane.refilter.borrow_mut() = Some(Rc::new(move || {
+        if let Some(ctx) = weak.upgrade() {
+            apply_filter(&ctx);
+        }
+    }));
 
     container
 }
 
 /// Recursively renders one level of the tree.
-fn render_tree(
-    container: &Box,
-    dir_path: &Path,
-    depth: u32,
-    expanded: Rc<RefCell<HashSet<PathBuf>>>,
-    root_path: Rc<RefCell<PathBuf>>,
-    config: Rc<RefCell<AppConfig>>,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    on_navigate: Rc<dyn Fn(PathBuf)>,
-) {
+fn render_tree(ctx: &Rc<TreeCtx>, dir_path: &Path, depth: u32) {
     if depth > MAX_DEPTH {
         return;
     }
 
-    let cfg = config.borrow().clone();
-    let entries = filesystem::list_directory(dir_path, cfg.show_hidden);
+    let cfg = ctx.config.borrow().clone();
+    let entries = filesystem::list_directory(dir_path, cfg.show_hidden, &cfg.sort_order());
+    ctx.children
+        .borrow_mut()
+        .insert(dir_path.to_path_buf(), entries.clone());
 
     if entries.is_empty() && depth > 0 {
-        // Polished "empty directory" hint
-        let indent = (depth as i32) * INDENT_PX + 8;
-        let empty_box = Box::builder()
-            .orientation(Orientation::Horizontal)
-            .spacing(10)
-            .halign(Align::Start)
-            .valign(Align::Center)
-            .margin_start(indent)
-            .margin_top(6)
-            .margin_bottom(6)
-            .css_classes(vec!["tree-empty-container".to_string()])
-            .build();
-
-        let empty_icon = Image::builder()
-            .icon_name("folder-open-symbolic")
-            .pixel_size(18)
-            .css_classes(vec!["tree-empty-icon".to_string()])
-            .build();
-        empty_box.append(&empty_icon);
-
-        let empty_label = Label::builder()
-            .label("Empty folder")
-            .css_classes(vec!["tree-empty-hint".to_string()])
-            .build();
-        empty_box.append(&empty_label);
-
-        container.append(&empty_box);
+        let hint = empty_hint(depth);
+        ctx.container.append(&hint);
+        ctx.hints.borrow_mut().insert(dir_path.to_path_buf(), hint);
         return;
     }
 
     for entry in &entries {
-        let indent = (depth as i32) * INDENT_PX;
+        let row_btn = build_row(ctx, entry, depth, &cfg);
+        ctx.container.append(&row_btn);
+        ctx.rows.borrow_mut().insert(entry.path.clone(), row_btn);
+
+        // ── Recurse into expanded directories ──
+        if entry.is_dir && ctx.expanded.borrow().contains(&entry.path) {
+            render_tree(ctx, &entry.path, depth + 1);
+        }
+    }
+}
+
+/// Polished "empty directory" hint.
+fn empty_hint(depth: u32) -> Box {
+    let indent = (depth as i32) * INDENT_PX + 8;
+    let empty_box = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .halign(Align::Start)
+        .valign(Align::Center)
+        .margin_start(indent)
+        .margin_top(6)
+        .margin_bottom(6)
+        .css_classes(vec!["tree-empty-container".to_string()])
+        .build();
+
+    let empty_icon = Image::builder()
+        .icon_name("folder-open-symbolic")
+        .pixel_size(18)
+        .css_classes(vec!["tree-empty-icon".to_string()])
+        .build();
+    empty_box.append(&empty_icon);
+
+    let empty_label = Label::builder()
+        .label("Empty folder")
+        .css_classes(vec!["tree-empty-hint".to_string()])
+        .build();
+    empty_box.append(&empty_label);
+
+    empty_box
+}
 
-        // ── Outer row wrapper with guide-line indentation ──
-        let outer = Box::builder()
+/// Builds the row of one entry at `depth`.
+fn build_row(ctx: &Rc<TreeCtx>, entry: &filesystem::Entry, depth: u32, cfg: &AppConfig) -> Button {
+    let indent = (depth as i32) * INDENT_PX;
+
+    // ── Outer row wrapper with guide-line indentation ──
+    let outer = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(0)
+        .build();
+
+    // Guide line area: one thin bar per depth level
+    if depth > 0 {
+        let guides = Box::builder()
             .orientation(Orientation::Horizontal)
             .spacing(0)
+            .width_request(indent)
             .build();
 
-        // Guide line area: one thin bar per depth level
-        if depth > 0 {
-            let guides = Box::builder()
-                .orientation(Orientation::Horizontal)
-                .spacing(0)
-                .width_request(indent)
+        for i in 0..depth {
+            let guide = Box::builder()
+                .width_request(INDENT_PX)
+                .vexpand(true)
                 .build();
-
-            for i in 0..depth {
-                let guide = Box::builder()
-                    .width_request(INDENT_PX)
-                    .vexpand(true)
-                    .build();
-                // Only the last guide before content gets the visible line
-                if i == depth - 1 {
-                    guide.add_css_class("tree-guide-line");
-                } else {
-                    guide.add_css_class("tree-guide-spacer");
-                }
-                guides.append(&guide);
+            // Only the last guide before content gets the visible line
+            if i == depth - 1 {
+                guide.add_css_class("tree-guide-line");
+            } else {
+                guide.add_css_class("tree-guide-spacer");
             }
-            outer.append(&guides);
+            guides.append(&guide);
         }
+        outer.append(&guides);
+    }
 
-        // ── Inner content row ──
-        let row = Box::builder()
-            .orientation(Orientation::Horizontal)
-            .spacing(10)
-            .hexpand(true)
-            .valign(Align::Center)
-            .build();
-
-        // ── Disclosure arrow (directories only) ──
-        if entry.is_dir {
-            let is_open = expanded.borrow().contains(&entry.path);
-            let arrow_label = if is_open { "▾" } else { "▸" };
+    // ── Inner content row ──
+    let row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(10)
+        .hexpand(true)
+        .valign(Align::Center)
+        .build();
 
-            let arrow_btn = Button::builder()
-                .label(arrow_label)
-                .has_frame(false)
-                .css_classes(vec!["tree-arrow".to_string()])
-                .build();
+    // ── Disclosure arrow (directories only) ──
+    if entry.is_dir {
+        let is_open = ctx.expanded.borrow().contains(&entry.path);
+        let arrow_label = if is_open { "▾" } else { "▸" };
 
-            if is_open {
-                arrow_btn.add_css_class("tree-arrow-open");
-            }
+        let arrow_btn = Button::builder()
+            .label(arrow_label)
+            .has_frame(false)
+            .css_classes(vec!["tree-arrow".to_string()])
+            .build();
 
-            // Toggle expand/collapse
-            let entry_path = entry.path.clone();
-            let expanded_c = expanded.clone();
-            let container_c = container.clone();
-            let root_c = root_path.clone();
-            let config_c = config.clone();
-            let info_c = inspector_info.clone();
-            let sel_c = selected_file_path.clone();
-            let nav_c = on_navigate.clone();
-
-            arrow_btn.connect_clicked(move |_| {
-                {
-                    let mut set = expanded_c.borrow_mut();
-                    if set.contains(&entry_path) {
-                        set.remove(&entry_path);
-                    } else {
-                        set.insert(entry_path.clone());
-                    }
-                }
-                rebuild_tree(
-                    &container_c,
-                    expanded_c.clone(),
-                    root_c.clone(),
-                    config_c.clone(),
-                    &info_c,
-                    sel_c.clone(),
-                    nav_c.clone(),
-                );
-            });
-
-            row.append(&arrow_btn);
-        } else {
-            // Dot spacer for files — aligns with arrows
-            let dot = Label::builder()
-                .label("·")
-                .width_request(22)
-                .halign(Align::Center)
-                .css_classes(vec!["tree-file-dot".to_string()])
-                .build();
-            row.append(&dot);
+        if is_open {
+            arrow_btn.add_css_class("tree-arrow-open");
         }
 
-        // ── Icon (bigger for scannability) ──
-        let ext = entry.extension.to_lowercase();
-        let has_thumb = !entry.is_dir && thumbnail::supports_thumbnail(&ext);
-        let icon_sz = 22;
-
-        let is_colorful = cfg.icon_theme == IconTheme::Colorful;
+        // Toggle expand/collapse
+        let entry_path = entry.path.clone();
+        let ctx_c = ctx.clone();
+        arrow_btn.connect_clicked(move |_| toggle_expanded(&ctx_c, &entry_path));
+
+        row.append(&arrow_btn);
+    } else {
+        // Dot spacer for files — aligns with arrows
+        let dot = Label::builder()
+            .label("·")
+            .width_request(22)
+            .halign(Align::Center)
+            .css_classes(vec!["tree-file-dot".to_string()])
+            .build();
+        row.append(&dot);
+    }
 
-        // Colorful → use real themed icons (same as Grid/List)
-        // Minimal/Outline → symbolic icons with CSS color tinting
-        let entry_icon_name = if is_colorful {
-            icon_for_entry_themed(entry, &cfg.icon_theme)
+    // ── Icon (bigger for scannability) ──
+    let has_thumb = !entry.is_dir && thumbnail::supports_thumbnail(&entry.mime);
+    let icon_sz = 22;
+
+    let is_colorful = cfg.icon_theme == IconTheme::Colorful;
+
+    // Colorful → use real themed icons (same as Grid/List)
+    // Minimal/Outline → symbolic icons with CSS color tinting
+    let entry_icon_name = if is_colorful {
+        icon_for_entry_themed(entry, &cfg.icon_theme)
+    } else {
+        tree_icon_name(entry)
+    };
+
+    let icon: Image = if has_thumb {
+        thumbnail::request_thumbnail(&entry.path, &entry.mime, icon_sz)
+    } else {
+        let mut classes = vec!["tree-icon".to_string()];
+        // Colorful icons get their CSS class for color tinting
+        // Symbolic icons also get it for CSS recoloring
+        classes.push(icon_css_class(entry).to_string());
+        if entry.is_dir {
+            classes.push("tree-icon-folder".to_string());
+        }
+        if is_colorful {
+            // Remove -gtk-icon-style: symbolic override for colorful
+            classes.push("tree-icon-colorful".to_string());
+        }
+        let image = Image::builder()
+            .icon_name(entry_icon_name)
+            .pixel_size(icon_sz)
+            .css_classes(classes)
+            .build();
+        let theme = if is_colorful {
+            cfg.icon_theme.clone()
         } else {
-            tree_icon_name(entry)
+            IconTheme::Outline
         };
+        refine_icon(&image, entry, &theme);
+        image
+    };
+    row.append(&icon);
+
+    // ── Name label (with per-extension color class) ──
+    let name_css = if entry.is_dir {
+        vec!["tree-name".to_string(), "tree-name-dir".to_string()]
+    } else {
+        vec![
+            "tree-name".to_string(),
+            "tree-name-file".to_string(),
+            format!(
+                "tree-ext-{}",
+                if entry.extension.is_empty() {
+                    "none"
+                } else {
+                    &entry.extension
+                }
+            ),
+        ]
+    };
+
+    let name_label = Label::builder()
+        .label(&entry.name)
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .css_classes(name_css)
+        .build();
+    row.append(&name_label);
 
-        let icon: Image = if has_thumb {
-            thumbnail::request_thumbnail(&entry.path, icon_sz)
-        } else {
-            let mut classes = vec!["tree-icon".to_string()];
-            // Colorful icons get their CSS class for color tinting
-            // Symbolic icons also get it for CSS recoloring
-            classes.push(icon_css_class(entry).to_string());
-            if entry.is_dir {
-                classes.push("tree-icon-folder".to_string());
-            }
-            if is_colorful {
-                // Remove -gtk-icon-style: symbolic override for colorful
-                classes.push("tree-icon-colorful".to_string());
+    // ── Badges / Metadata ──
+    if entry.is_dir {
+        // Show child count badge for directories
+        if let Ok(rd) = std::fs::read_dir(&entry.path) {
+            let count = rd
+                .filter_map(|e| e.ok())
+                .filter(|e| cfg.show_hidden || !e.file_name().to_string_lossy().starts_with('.'))
+                .count();
+            if count > 0 {
+                let badge = Label::builder()
+                    .label(count.to_string())
+                    .halign(Align::End)
+                    .css_classes(vec!["tree-badge".to_string()])
+                    .build();
+                row.append(&badge);
             }
-            Image::builder()
-                .icon_name(entry_icon_name)
-                .pixel_size(icon_sz)
-                .css_classes(classes)
-                .build()
-        };
-        row.append(&icon);
+        }
+    } else if cfg.show_file_size {
+        let size_label = Label::builder()
+            .label(&entry.size_display())
+            .halign(Align::End)
+            .css_classes(vec!["tree-meta".to_string()])
+            .build();
+        row.append(&size_label);
+    }
 
-        // ── Name label (with per-extension color class) ──
-        let name_css = if entry.is_dir {
-            vec!["tree-name".to_string(), "tree-name-dir".to_string()]
-        } else {
-            vec![
-                "tree-name".to_string(),
-                "tree-name-file".to_string(),
-                format!(
-                    "tree-ext-{}",
-                    if entry.extension.is_empty() {
-                        "none"
-                    } else {
-                        &entry.extension
-                    }
-                ),
-            ]
-        };
+    // ── Wrap in button for click handling ──
+    outer.append(&row);
 
-        let name_label = Label::builder()
-            .label(&entry.name)
-            .xalign(0.0)
-            .hexpand(true)
-            .ellipsize(gtk4::pango::EllipsizeMode::Middle)
-            .css_classes(name_css)
-            .build();
-        row.append(&name_label);
+    let row_btn = Button::builder()
+        .child(&outer)
+        .has_frame(false)
+        .css_classes(vec!["tree-row-btn".to_string()])
+        .build();
 
-        // ── Badges / Metadata ──
-        if entry.is_dir {
-            // Show child count badge for directories
-            if let Ok(rd) = std::fs::read_dir(&entry.path) {
-                let count = rd
-                    .filter_map(|e| e.ok())
-                    .filter(|e| {
-                        cfg.show_hidden || !e.file_name().to_string_lossy().starts_with('.')
-                    })
-                    .count();
-                if count > 0 {
-                    let badge = Label::builder()
-                        .label(&format!("{}", count))
-                        .halign(Align::End)
-                        .css_classes(vec!["tree-badge".to_string()])
-                        .build();
-                    row.append(&badge);
+    // ── Drag source (external drag & drop for files AND folders) ──
+    drag_source::attach_file_drag_source(&row_btn, &entry.path, Some(ctx.selection.clone()));
+
+    // ── Drop target: folders take drops and expand while hovered ──
+    if entry.is_dir {
+        let ctx_w = Rc::downgrade(ctx);
+        let spring: drop_target::Spring = Rc::new(move |path: &Path| {
+            if let Some(ctx) = ctx_w.upgrade() {
+                if !ctx.expanded.borrow().contains(path) {
+                    toggle_expanded(&ctx, path);
                 }
             }
-        } else if cfg.show_file_size {
-            let size_label = Label::builder()
-                .label(&entry.size_display())
-                .halign(Align::End)
-                .css_classes(vec!["tree-meta".to_string()])
-                .build();
-            row.append(&size_label);
+        });
+        drop_target::attach_folder_drop_target(
+            &row_btn,
+            &entry.path,
+            &ctx.pane,
+            ctx.config.clone(),
+            Some(spring),
+        );
+    }
+
+    // ── Click handling: select, double-click expands / opens ──
+    ctx.selection.register(&entry.path, &row_btn);
+    {
+        let entry_path = entry.path.clone();
+        let is_dir = entry.is_dir;
+        let ctx_c = ctx.clone();
+        selection::attach_item_clicks(&row_btn, &entry.path, ctx.selection.clone(), move || {
+            if is_dir {
+                // Toggle expansion in-place (don't navigate away)
+                toggle_expanded(&ctx_c, &entry_path);
+            } else {
+                open_with::open_default(&entry_path);
+            }
+        });
+    }
+
+    row_btn
+}
+
+/// Returns a symbolic icon name for the tree view.
+/// Always uses -symbolic suffix so CSS `color` property works.
+fn tree_icon_name(entry: &crate::filesystem::Entry) -> &'static str {
+    if entry.is_dir {
+        return "folder-symbolic";
+    }
+    icon_for_entry_themed(entry, &IconTheme::Outline)
+}
+
+/// Expands or collapses `path` and re-renders the tree.
+fn toggle_expanded(ctx: &Rc<TreeCtx>, path: &Path) {
+    {
+        let mut set = ctx.expanded.borrow_mut();
+        if !set.remove(path) {
+            set.insert(path.to_path_buf());
         }
+    }
+    rebuild_tree(ctx);
+}
 
-        // ── Wrap in button for click handling ──
-        outer.append(&row);
+/// Clears and re-renders the full tree (called after expand/collapse toggle).
+fn rebuild_tree(ctx: &Rc<TreeCtx>) {
+    while let Some(child) = ctx.container.first_child() {
+        ctx.container.remove(&child);
+    }
+    ctx.rows.borrow_mut().clear();
+    ctx.children.borrow_mut().clear();
+    ctx.hints.borrow_mut().clear();
+
+    let root = ctx.root_path.borrow().clone();
+    ctx.selection.begin_view(&root);
+    render_tree(ctx, &root, 0);
+    sync_watches(ctx);
+    apply_filter(ctx);
+}
 
-        let row_btn = Button::builder()
-            .child(&outer)
-            .has_frame(false)
-            .css_classes(vec!["tree-row-btn".to_string()])
-            .build();
+// ═══════════════════════════════════════════════
+//  Filtering
+// ═══════════════════════════════════════════════
 
-        // ── Drag source (external drag & drop for files AND folders) ──
-        drag_source::attach_file_drag_source(&row_btn, &entry.path, entry_icon_name, entry.is_dir);
+/// Shows the rows that match the pane's filter or lead to a match, and
+/// hides the rest (and the "Empty folder" hints while filtering).
+fn apply_filter(ctx: &Rc<TreeCtx>) {
+    let filter = ctx.pane.filter_bar.filter();
+    let root = ctx.root_path.borrow().clone();
+    let mut counts = (0, 0);
+    let mut order = Vec::new();
+    filter_level(ctx, &filter, &root, &mut counts, &mut order);
+    for hint in ctx.hints.borrow().values() {
+        hint.set_visible(!filter.is_active());
+    }
+    ctx.selection.set_order(order);
+    ctx.pane.filter_bar.set_count(Some(counts));
+}
 
-        // Highlight selected item
-        {
-            let sel_path = selected_file_path.borrow();
-            if sel_path.as_ref() == Some(&entry.path) {
-                row_btn.add_css_class("tree-row-selected");
-            }
+/// Filters the rows below `dir`, appending the visible ones to `order`.
+/// `counts` sums up (matching, total) entries. Returns true if any row
+/// below `dir` stays visible.
+fn filter_level(
+    ctx: &Rc<TreeCtx>,
+    filter: &NameFilter,
+    dir: &Path,
+    counts: &mut (usize, usize),
+    order: &mut Vec<PathBuf>,
+) -> bool {
+    let children = ctx.children.borrow().get(dir).cloned().unwrap_or_default();
+    let mut any_visible = false;
+    for child in children {
+        let matched = filter.matches(&child.name);
+        let mut below = Vec::new();
+        let leads_to_match = filter_level(ctx, filter, &child.path, counts, &mut below);
+        let visible = matched || leads_to_match;
+
+        counts.1 += 1;
+        if matched {
+            counts.0 += 1;
+        }
+        if let Some(row) = ctx.rows.borrow().get(&child.path) {
+            row.set_visible(visible);
         }
+        if visible {
+            order.push(child.path);
+            order.extend(below);
+        }
+        any_visible |= visible;
+    }
+    any_visible
+}
 
-        // ── Click handler ──
-        {
-            let entry_path = entry.path.clone();
-            let is_dir = entry.is_dir;
-            let name = entry.name.clone();
-            let size_disp = entry.size_display();
-            let mod_disp = entry.modified_display();
-            let info_c = inspector_info.clone();
-            let sel_c = selected_file_path.clone();
-            let nav_c = on_navigate.clone();
-            let expanded_c = expanded.clone();
-            let container_c = container.clone();
-            let root_c = root_path.clone();
-            let config_c = config.clone();
-
-            row_btn.connect_clicked(move |_| {
-                if is_dir {
-                    // Toggle expansion in-place (don't navigate away)
-                    {
-                        let mut set = expanded_c.borrow_mut();
-                        if set.contains(&entry_path) {
-                            set.remove(&entry_path);
-                        } else {
-                            set.insert(entry_path.clone());
-                        }
-                    }
-                    rebuild_tree(
-                        &container_c,
-                        expanded_c.clone(),
-                        root_c.clone(),
-                        config_c.clone(),
-                        &info_c,
-                        sel_c.clone(),
-                        nav_c.clone(),
-                    );
-                } else {
-                    info_c.set_label(&format!("{}  •  {}  •  {}", name, size_disp, mod_disp));
-                    *sel_c.borrow_mut() = Some(entry_path.clone());
-                    if let Err(e) = open::that(&entry_path) {
-                        eprintln!("Failed to open file: {}", e);
-                    }
+// ═══════════════════════════════════════════════
+//  Live Updates
+// ═══════════════════════════════════════════════
+
+/// Watches exactly the rendered (expanded) directories.
+fn sync_watches(ctx: &Rc<TreeCtx>) {
+    let dirs: Vec<PathBuf> = ctx.children.borrow().keys().cloned().collect();
+    ctx.watches.retain(|p| dirs.iter().any(|d| d == p));
+    for dir in dirs {
+        let weak = Rc::downgrade(ctx);
+        let dir_c = dir.clone();
+        ctx.watches.watch(&dir, move |changed| {
+            if let Some(ctx) = weak.upgrade() {
+                apply_changes(&ctx, &dir_c, changed);
+            }
+        });
+    }
+}
+
+/// Inserts, replaces or removes the rows of `dir` touched by `changed`.
+fn apply_changes(ctx: &Rc<TreeCtx>, dir: &Path, changed: &[PathBuf]) {
+    if !ctx.children.borrow().contains_key(dir) {
+        return;
+    }
+    let cfg = ctx.config.borrow().clone();
+    let depth = depth_of(ctx, dir);
+    let mut touched = false;
+
+    for path in changed {
+        if path.parent() != Some(dir) {
+            continue;
+        }
+        let entry = filesystem::Entry::from_path(path)
+            .filter(|e| cfg.show_hidden || !e.name.starts_with('.'));
+        let known = ctx.children.borrow()[dir]
+            .iter()
+            .position(|e| e.path == *path);
+
+        match (known, entry) {
+            (None, None) => {}
+            (Some(i), None) => {
+                remove_subtree(ctx, path);
+                let now_empty = {
+                    let mut children = ctx.children.borrow_mut();
+                    let siblings = children.get_mut(dir).expect("rendered dir");
+                    siblings.remove(i);
+                    siblings.is_empty()
+                };
+                if now_empty && depth > 0 {
+                    let hint = empty_hint(depth);
+                    let row = ctx.rows.borrow().get(dir).cloned();
+                    ctx.container.insert_child_after(&hint, row.as_ref());
+                    ctx.hints.borrow_mut().insert(dir.to_path_buf(), hint);
+                }
+                touched = true;
+            }
+            (Some(i), Some(entry)) => {
+                let same = {
+                    let children = ctx.children.borrow();
+                    let old = &children[dir][i];
+                    old.is_dir == entry.is_dir
+                        && old.size == entry.size
+                        && old.modified == entry.modified
+                };
+                if !same {
+                    replace_row(ctx, &entry, depth, &cfg);
+                    ctx.children
+                        .borrow_mut()
+                        .get_mut(dir)
+                        .expect("rendered dir")[i] = entry;
+                    touched = true;
+                }
+            }
+            (None, Some(entry)) => {
+                if let Some(hint) = ctx.hints.borrow_mut().remove(dir) {
+                    ctx.container.remove(&hint);
                 }
-            });
+                // A new entry always shows collapsed
+                ctx.expanded.borrow_mut().remove(&entry.path);
+
+                let order = ctx.config.borrow().sort_order();
+                let key = filesystem::sort_key(&entry, &order);
+                let pos = ctx.children.borrow()[dir]
+                    .partition_point(|e| filesystem::sort_key(e, &order) < key);
+                let prev: Option<gtk4::Widget> = match pos {
+                    0 => ctx.rows.borrow().get(dir).map(|r| r.clone().upcast()),
+                    _ => {
+                        let before = ctx.children.borrow()[dir][pos - 1].path.clone();
+                        last_widget(ctx, &before)
+                    }
+                };
+                let row_btn = build_row(ctx, &entry, depth, &cfg);
+                ctx.container.insert_child_after(&row_btn, prev.as_ref());
+                ctx.rows.borrow_mut().insert(entry.path.clone(), row_btn);
+                ctx.children
+                    .borrow_mut()
+                    .get_mut(dir)
+                    .expect("rendered dir")
+                    .insert(pos, entry);
+                touched = true;
+            }
         }
+    }
 
-        container.append(&row_btn);
-
-        // ── Recurse into expanded directories ──
-        if entry.is_dir && expanded.borrow().contains(&entry.path) {
-            render_tree(
-                container,
-                &entry.path,
-                depth + 1,
-                expanded.clone(),
-                root_path.clone(),
-                config.clone(),
-                inspector_info,
-                selected_file_path.clone(),
-                on_navigate.clone(),
-            );
+    if !touched {
+        return;
+    }
+    // The directory's own row shows a child count badge
+    if depth > 0 {
+        if let Some(entry) = filesystem::Entry::from_path(dir) {
+            replace_row(ctx, &entry, depth - 1, &cfg);
         }
     }
+    // New rows are filtered, and the display order follows
+    apply_filter(ctx);
 }
 
-/// Returns a symbolic icon name for the tree view.
-/// Always uses -symbolic suffix so CSS `color` property works.
-fn tree_icon_name(entry: &crate::filesystem::Entry) -> &'static str {
-    if entry.is_dir {
-        return "folder-symbolic";
+/// Swaps the row of `entry` for a freshly built one.
+fn replace_row(ctx: &Rc<TreeCtx>, entry: &filesystem::Entry, depth: u32, cfg: &AppConfig) {
+    let Some(old) = ctx.rows.borrow().get(&entry.path).cloned() else {
+        return;
+    };
+    ctx.selection.unregister(&entry.path);
+    let row_btn = build_row(ctx, entry, depth, cfg);
+    ctx.container.insert_child_after(&row_btn, Some(&old));
+    ctx.container.remove(&old);
+    ctx.rows.borrow_mut().insert(entry.path.clone(), row_btn);
+}
+
+/// Removes the row of `path` and, if it is expanded, everything below it.
+fn remove_subtree(ctx: &Rc<TreeCtx>, path: &Path) {
+    if let Some(row) = ctx.rows.borrow_mut().remove(path) {
+        ctx.container.remove(&row);
     }
-    match entry.extension.as_str() {
-        "rs" => "text-x-script-symbolic",
-        "py" => "text-x-script-symbolic",
-        "js" | "ts" | "jsx" | "tsx" => "text-x-script-symbolic",
-        "c" | "cpp" | "h" => "text-x-script-symbolic",
-        "java" | "kt" => "text-x-script-symbolic",
-        "go" => "text-x-script-symbolic",
-        "rb" | "swift" | "cs" | "lua" => "text-x-script-symbolic",
-        "sh" | "fish" | "zsh" | "bash" => "application-x-executable-symbolic",
-        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => {
-            "image-x-generic-symbolic"
-        }
-        "mp3" | "flac" | "ogg" | "wav" | "m4a" | "aac" => "audio-x-generic-symbolic",
-        "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic-symbolic",
-        "zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar" => "package-x-generic-symbolic",
-        "pdf" => "x-office-document-symbolic",
-        "html" | "htm" => "text-html-symbolic",
-        "css" | "scss" => "text-x-preview-symbolic",
-        "md" => "x-office-document-symbolic",
-        "json" | "toml" | "yaml" | "yml" | "xml" => "emblem-system-symbolic",
-        "txt" | "log" | "csv" => "accessories-text-editor-symbolic",
-        "nix" => "emblem-system-symbolic",
-        _ => "text-x-generic-symbolic",
+    ctx.selection.unregister(path);
+    if let Some(hint) = ctx.hints.borrow_mut().remove(path) {
+        ctx.container.remove(&hint);
+    }
+    let children = ctx.children.borrow_mut().remove(path);
+    for child in children.unwrap_or_default() {
+        remove_subtree(ctx, &child.path);
     }
+    ctx.watches.retain(|p| p != path);
 }
 
-/// Clears and re-renders the full tree (called after expand/collapse toggle).
-fn rebuild_tree(
-    container: &Box,
-    expanded: Rc<RefCell<HashSet<PathBuf>>>,
-    root_path: Rc<RefCell<PathBuf>>,
-    config: Rc<RefCell<AppConfig>>,
-    inspector_info: &Label,
-    selected_file_path: Rc<RefCell<Option<PathBuf>>>,
-    on_navigate: Rc<dyn Fn(PathBuf)>,
-) {
-    while let Some(child) = container.first_child() {
-        container.remove(&child);
+/// The last widget shown for `path`: its deepest last descendant,
+/// its "Empty folder" hint, or its own row.
+fn last_widget(ctx: &Rc<TreeCtx>, path: &Path) -> Option<gtk4::Widget> {
+    let last_child = ctx
+        .children
+        .borrow()
+        .get(path)
+        .and_then(|c| c.last().map(|e| e.path.clone()));
+    if let Some(child) = last_child {
+        return last_widget(ctx, &child);
+    }
+    if let Some(hint) = ctx.hints.borrow().get(path) {
+        return Some(hint.clone().upcast());
     }
+    ctx.rows.borrow().get(path).map(|r| r.clone().upcast())
+}
 
-    let root = root_path.borrow().clone();
-    render_tree(
-        container,
-        &root,
-        0,
-        expanded,
-        root_path,
-        config,
-        inspector_info,
-        selected_file_path,
-        on_navigate,
-    );
+/// Nesting level of the children of `dir` (0 for the root).
+fn depth_of(ctx: &Rc<TreeCtx>, dir: &Path) -> u32 {
+    let root = ctx.root_path.borrow().clone();
+    dir.strip_prefix(&root)
+        .map(|rel| rel.components().count() as u32)
+        .unwrap_or(0)
 }
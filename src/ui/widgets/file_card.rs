@@ -1,8 +1,7 @@
 use crate::config::{AppConfig, IconTheme};
 use crate::filesystem::Entry;
 use crate::thumbnail;
-use crate::ui::drag_source;
-use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed};
+use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed, refine_icon};
 use gtk4::prelude::*;
 use gtk4::{Align, Box, Button, Image, Label, Orientation};
 
@@ -10,84 +9,134 @@ use gtk4::{Align, Box, Button, Image, Label, Orientation};
 //  Grid Card Widget
 // ═══════════════════════════════════════════════
 
-/// Creates a card-style widget for grid view.
-pub fn create_file_card(entry: &Entry, config: &AppConfig) -> Button {
-    let icon_name = icon_for_entry_themed(entry, &config.icon_theme);
+/// Creates a card for a search hit, naming the folder it was found in
+/// (relative to the searched folder).
+pub fn create_search_card(entry: &Entry, location: &str, config: &AppConfig) -> Button {
+    let card = FileCard::new(Some(location), config);
+    card.bind(entry, config);
+    card.button
+}
 
-    let card_box = Box::builder()
-        .orientation(Orientation::Vertical)
-        .spacing(6)
-        .halign(Align::Center)
-        .valign(Align::Center)
-        .build();
+/// A grid card, built once and bound to an entry. Virtualized views
+/// rebind their cards as they scroll. The icon is the only part built
+/// anew on each bind: a thumbnail or a sniffed icon arrives later for
+/// the image it was requested for, which must not be reused.
+#[derive(Clone)]
+pub struct FileCard {
+    pub button: Button,
+    icon_slot: Box,
+    name_label: Label,
+    size_label: Option<Label>,
+    date_label: Option<Label>,
+}
 
-    // Check if this file supports a thumbnail preview
-    let ext = entry.extension.to_lowercase();
-    let has_thumb = !entry.is_dir && thumbnail::supports_thumbnail(&ext);
+impl FileCard {
+    /// An empty card; which metadata lines it has follows `config`.
+    pub fn new(location: Option<&str>, config: &AppConfig) -> Self {
+        let card_box = Box::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .halign(Align::Center)
+            .valign(Align::Center)
+            .build();
 
-    let icon: Image = if has_thumb {
-        // Async thumbnail — shows placeholder first, swaps in the real image
-        thumbnail::request_thumbnail(&entry.path, config.icon_size)
-    } else {
-        // Only apply color tinting for the Colorful icon theme
-        let icon_classes = if config.icon_theme == IconTheme::Colorful {
-            vec![icon_css_class(entry).to_string()]
-        } else {
-            vec![]
-        };
+        let icon_slot = Box::builder().halign(Align::Center).build();
 
-        Image::builder()
-            .icon_name(icon_name)
-            .pixel_size(config.icon_size)
+        let name_label = Label::builder()
+            .css_classes(vec!["file-card-name".to_string()])
             .halign(Align::Center)
-            .css_classes(icon_classes)
-            .build()
-    };
+            .wrap(true)
+            .max_width_chars(16)
+            .justify(gtk4::Justification::Center)
+            .build();
 
-    let name_label = Label::builder()
-        .label(&truncate_name(&entry.name, 18))
-        .css_classes(vec!["file-card-name".to_string()])
-        .halign(Align::Center)
-        .wrap(true)
-        .max_width_chars(16)
-        .justify(gtk4::Justification::Center)
-        .build();
-    name_label.set_tooltip_text(Some(&entry.name));
+        card_box.append(&icon_slot);
+        card_box.append(&name_label);
 
-    card_box.append(&icon);
-    card_box.append(&name_label);
+        if let Some(location) = location {
+            let location_label = Label::builder()
+                .label(location)
+                .css_classes(vec!["file-location".to_string()])
+                .halign(Align::Center)
+                .ellipsize(gtk4::pango::EllipsizeMode::Start)
+                .max_width_chars(16)
+                .build();
+            location_label.set_tooltip_text(Some(location));
+            card_box.append(&location_label);
+        }
 
-    // Metadata lines
-    if config.show_file_size && !entry.is_dir {
-        let size_label = Label::builder()
-            .label(&entry.size_display())
-            .css_classes(vec!["file-card-meta".to_string()])
-            .halign(Align::Center)
+        // Metadata lines
+        let meta_label = || {
+            Label::builder()
+                .css_classes(vec!["file-card-meta".to_string()])
+                .halign(Align::Center)
+                .build()
+        };
+        let size_label = config.show_file_size.then(meta_label);
+        let date_label = config.show_modified_date.then(meta_label);
+        for label in size_label.iter().chain(&date_label) {
+            card_box.append(label);
+        }
+
+        // Card size adapts to icon_size
+        let card_width = (config.icon_size as i32).max(48) + 40;
+        let button = Button::builder()
+            .child(&card_box)
+            .css_classes(vec!["file-card".to_string()])
+            .width_request(card_width)
+            .has_frame(false)
             .build();
-        card_box.append(&size_label);
+
+        Self {
+            button,
+            icon_slot,
+            name_label,
+            size_label,
+            date_label,
+        }
     }
-    if config.show_modified_date {
-        let date_label = Label::builder()
-            .label(&entry.modified_display())
-            .css_classes(vec!["file-card-meta".to_string()])
-            .halign(Align::Center)
-            .build();
-        card_box.append(&date_label);
+
+    /// Shows `entry` on the card.
+    pub fn bind(&self, entry: &Entry, config: &AppConfig) {
+        if let Some(old) = self.icon_slot.first_child() {
+            self.icon_slot.remove(&old);
+        }
+        self.icon_slot.append(&build_icon(entry, config));
+
+        self.name_label.set_label(&truncate_name(&entry.name, 18));
+        self.name_label.set_tooltip_text(Some(&entry.name));
+        if let Some(label) = &self.size_label {
+            label.set_label(&entry.size_display());
+            label.set_visible(!entry.is_dir);
+        }
+        if let Some(label) = &self.date_label {
+            label.set_label(&entry.modified_display());
+        }
     }
+}
 
-    // Card size adapts to icon_size
-    let card_width = (config.icon_size as i32).max(48) + 40;
-    let btn = Button::builder()
-        .child(&card_box)
-        .css_classes(vec!["file-card".to_string()])
-        .width_request(card_width)
-        .has_frame(false)
-        .build();
+fn build_icon(entry: &Entry, config: &AppConfig) -> Image {
+    // Check if this file supports a thumbnail preview
+    if !entry.is_dir && thumbnail::supports_thumbnail(&entry.mime) {
+        // Async thumbnail — shows placeholder first, swaps in the real image
+        return thumbnail::request_thumbnail(&entry.path, &entry.mime, config.icon_size);
+    }
 
-    // ── External drag & drop source (files AND folders) ──
-    drag_source::attach_file_drag_source(&btn, &entry.path, icon_name, entry.is_dir);
+    // Only apply color tinting for the Colorful icon theme
+    let icon_classes = if config.icon_theme == IconTheme::Colorful {
+        vec![icon_css_class(entry).to_string()]
+    } else {
+        vec![]
+    };
 
-    btn
+    let image = Image::builder()
+        .icon_name(icon_for_entry_themed(entry, &config.icon_theme))
+        .pixel_size(config.icon_size)
+        .halign(Align::Center)
+        .css_classes(icon_classes)
+        .build();
+    refine_icon(&image, entry, &config.icon_theme);
+    image
 }
 
 // ─── Helpers ───
@@ -1,8 +1,7 @@
 use crate::config::{AppConfig, IconTheme};
 use crate::filesystem::Entry;
 use crate::thumbnail;
-use crate::ui::drag_source;
-use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed};
+use crate::ui::widgets::icon::{icon_css_class, icon_for_entry_themed, refine_icon};
 use gtk4::prelude::*;
 use gtk4::{Align, Box, Button, Image, Label, Orientation};
 
@@ -12,6 +11,16 @@ use gtk4::{Align, Box, Button, Image, Label, Orientation};
 
 /// Creates a compact list-row widget.
 pub fn create_file_row(entry: &Entry, config: &AppConfig) -> Button {
+    build_row(entry, None, config)
+}
+
+/// Creates a row for a search hit, naming the folder it was found in
+/// (relative to the searched folder).
+pub fn create_search_row(entry: &Entry, location: &str, config: &AppConfig) -> Button {
+    build_row(entry, Some(location), config)
+}
+
+fn build_row(entry: &Entry, location: Option<&str>, config: &AppConfig) -> Button {
     let icon_name = icon_for_entry_themed(entry, &config.icon_theme);
     let icon_sz = (config.icon_size / 3).max(16).min(24);
 
@@ -21,11 +30,10 @@ pub fn create_file_row(entry: &Entry, config: &AppConfig) -> Button {
         .build();
 
     // Check if this file supports a thumbnail preview
-    let ext = entry.extension.to_lowercase();
-    let has_thumb = !entry.is_dir && thumbnail::supports_thumbnail(&ext);
+    let has_thumb = !entry.is_dir && thumbnail::supports_thumbnail(&entry.mime);
 
     let icon: Image = if has_thumb {
-        thumbnail::request_thumbnail(&entry.path, icon_sz)
+        thumbnail::request_thumbnail(&entry.path, &entry.mime, icon_sz)
     } else {
         let icon_classes = if config.icon_theme == IconTheme::Colorful {
             vec![icon_css_class(entry).to_string()]
@@ -33,11 +41,13 @@ pub fn create_file_row(entry: &Entry, config: &AppConfig) -> Button {
             vec![]
         };
 
-        Image::builder()
+        let image = Image::builder()
             .icon_name(icon_name)
             .pixel_size(icon_sz)
             .css_classes(icon_classes)
-            .build()
+            .build();
+        refine_icon(&image, entry, &config.icon_theme);
+        image
     };
 
     let name_label = Label::builder()
@@ -50,6 +60,18 @@ pub fn create_file_row(entry: &Entry, config: &AppConfig) -> Button {
     container.append(&icon);
     container.append(&name_label);
 
+    if let Some(location) = location {
+        let location_label = Label::builder()
+            .label(location)
+            .css_classes(vec!["file-location".to_string()])
+            .xalign(0.0)
+            .hexpand(true)
+            .ellipsize(gtk4::pango::EllipsizeMode::Start)
+            .build();
+        location_label.set_tooltip_text(Some(location));
+        container.append(&location_label);
+    }
+
     // Optional metadata columns
     if config.show_file_size {
         let size_label = Label::builder()
@@ -72,15 +94,10 @@ pub fn create_file_row(entry: &Entry, config: &AppConfig) -> Button {
         container.append(&date_label);
     }
 
-    let btn = Button::builder()
+    Button::builder()
         .child(&container)
         .halign(Align::Fill)
         .has_frame(false)
         .css_classes(vec!["file-row".to_string()])
-        .build();
-
-    // ── External drag & drop source (files AND folders) ──
-    drag_source::attach_file_drag_source(&btn, &entry.path, icon_name, entry.is_dir);
-
-    btn
+        .build()
 }
@@ -1,5 +1,7 @@
 use crate::config::IconTheme;
-use crate::filesystem::Entry;
+use crate::filesystem::{detect_mime_in_background, Entry, UNKNOWN_MIME};
+use gtk4::prelude::*;
+use gtk4::Image;
 
 // ═══════════════════════════════════════════════
 //  Dynamic Icon System
@@ -21,10 +23,118 @@ pub fn icon_for_entry_themed(entry: &Entry, theme: &IconTheme) -> &'static str {
     if entry.is_dir {
         return dir_icon(theme);
     }
+    let kind = file_kind(&entry.mime);
     match theme {
-        IconTheme::Minimal => minimal_icon(&entry.extension),
-        IconTheme::Colorful => colorful_icon(&entry.extension),
-        IconTheme::Outline => outline_icon(&entry.extension),
+        IconTheme::Minimal => minimal_icon(kind),
+        IconTheme::Colorful => colorful_icon(kind),
+        IconTheme::Outline => outline_icon(kind),
+    }
+}
+
+/// Listings only guess types from names. For a file whose name tells
+/// nothing, this sniffs its contents in the background and then gives
+/// `image` the icon (under `theme`) and tint class of what it is.
+pub fn refine_icon(image: &Image, entry: &Entry, theme: &IconTheme) {
+    if entry.is_dir || entry.mime != UNKNOWN_MIME {
+        return;
+    }
+    let send_weak: glib::SendWeakRef<Image> = image.downgrade().into();
+    let mut entry = entry.clone();
+    let theme = theme.clone();
+    let path = entry.path.clone();
+    detect_mime_in_background(&path, move |mime| {
+        if mime == UNKNOWN_MIME {
+            return;
+        }
+        glib::MainContext::default().invoke(move || {
+            let Some(image) = send_weak.upgrade() else {
+                return;
+            };
+            let old_class = icon_css_class(&entry);
+            entry.mime = mime;
+            image.set_icon_name(Some(icon_for_entry_themed(&entry, &theme)));
+            if image.has_css_class(old_class) {
+                image.remove_css_class(old_class);
+                image.add_css_class(icon_css_class(&entry));
+            }
+        });
+    });
+}
+
+// ─── File Kinds ───
+
+/// Icon category of a file, derived from its MIME type.
+#[derive(Clone, Copy, PartialEq)]
+enum FileKind {
+    Rust,
+    Python,
+    JavaScript,
+    C,
+    Java,
+    Go,
+    Shell,
+    /// Other source code.
+    Script,
+    Image,
+    Vector,
+    Audio,
+    Video,
+    Archive,
+    Pdf,
+    Html,
+    Css,
+    Markdown,
+    /// Data / config formats.
+    Config,
+    /// Plain text, logs, CSV.
+    Text,
+    Other,
+}
+
+fn file_kind(mime: &str) -> FileKind {
+    match mime {
+        "text/rust" | "text/x-rust" => FileKind::Rust,
+        "text/x-python" | "text/x-python3" => FileKind::Python,
+        "application/javascript"
+        | "text/javascript"
+        | "application/x-javascript"
+        | "text/x-typescript"
+        | "application/x-typescript"
+        | "text/jsx"
+        | "text/tsx" => FileKind::JavaScript,
+        "text/x-csrc" | "text/x-chdr" | "text/x-c++src" | "text/x-c++hdr" => FileKind::C,
+        "text/x-java" | "text/x-kotlin" => FileKind::Java,
+        "text/x-go" => FileKind::Go,
+        "application/x-shellscript" | "text/x-shellscript" | "application/x-fishscript" => {
+            FileKind::Shell
+        }
+        "application/x-ruby" | "text/x-ruby" | "text/x-swift" | "text/x-csharp" | "text/x-lua"
+        | "text/x-nix" => FileKind::Script,
+        "image/svg+xml" => FileKind::Vector,
+        "application/pdf" => FileKind::Pdf,
+        "text/html" | "application/xhtml+xml" => FileKind::Html,
+        "text/css" | "text/x-scss" => FileKind::Css,
+        "text/markdown" | "text/x-markdown" => FileKind::Markdown,
+        "application/json" | "application/toml" | "application/x-toml" | "application/yaml"
+        | "application/x-yaml" | "application/xml" | "text/xml" => FileKind::Config,
+        "text/plain" | "text/x-log" | "text/csv" => FileKind::Text,
+        "application/zip"
+        | "application/x-tar"
+        | "application/gzip"
+        | "application/x-bzip2"
+        | "application/x-xz"
+        | "application/x-7z-compressed"
+        | "application/vnd.rar"
+        | "application/x-rar" => FileKind::Archive,
+        m if m.starts_with("application/x-compressed-tar")
+            || m.starts_with("application/x-bzip") =>
+        {
+            FileKind::Archive
+        }
+        m if m.starts_with("image/") => FileKind::Image,
+        m if m.starts_with("audio/") => FileKind::Audio,
+        m if m.starts_with("video/") => FileKind::Video,
+        _ => FileKind::Other,
     }
 }
 
@@ -42,24 +152,13 @@ fn dir_icon(theme: &IconTheme) -> &'static str {
 // Clean, uniform symbolic icons — all files use the same base icon per category.
 // Designed for minimal visual clutter.
 
-fn minimal_icon(ext: &str) -> &'static str {
-    match ext {
-        // All source code → same single icon
-        "rs" | "py" | "js" | "ts" | "c" | "cpp" | "h" | "java" | "go" | "rb" | "swift" | "kt"
-        | "cs" | "lua" | "sh" | "fish" | "zsh" | "bash" | "html" | "htm" | "css" => {
-            "text-x-generic-symbolic"
-        }
-        // Images
-        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => {
-            "image-x-generic-symbolic"
-        }
-        // Audio
-        "mp3" | "flac" | "ogg" | "wav" | "m4a" | "aac" => "audio-x-generic-symbolic",
-        // Video
-        "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic-symbolic",
-        // Archives
-        "zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar" => "package-x-generic-symbolic",
-        // Everything else (docs, text, config)
+fn minimal_icon(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Image | FileKind::Vector => "image-x-generic-symbolic",
+        FileKind::Audio => "audio-x-generic-symbolic",
+        FileKind::Video => "video-x-generic-symbolic",
+        FileKind::Archive => "package-x-generic-symbolic",
+        // Source code, docs, text, config → same single icon
         _ => "text-x-generic-symbolic",
     }
 }
@@ -67,42 +166,21 @@ fn minimal_icon(ext: &str) -> &'static str {
 // ─── Colorful Theme ───
 // Vivid, category-specific icons — more visual distinction.
 
-fn colorful_icon(ext: &str) -> &'static str {
-    match ext {
-        // Rust
-        "rs" => "application-x-executable",
-        // Python
-        "py" => "text-x-python",
-        // JavaScript / TypeScript
-        "js" | "ts" => "text-x-script",
-        // C / C++
-        "c" | "cpp" | "h" => "text-x-csrc",
-        // Java
-        "java" => "text-x-java",
-        // Go
-        "go" => "text-x-generic",
-        // Shell scripts
-        "sh" | "fish" | "zsh" | "bash" => "application-x-shellscript",
-        // Other source code
-        "rb" | "swift" | "kt" | "cs" | "lua" => "text-x-script",
-        // Images
-        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => "image-x-generic",
-        // Audio
-        "mp3" | "flac" | "ogg" | "wav" | "m4a" | "aac" => "audio-x-generic",
-        // Video
-        "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic",
-        // Archives
-        "zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar" => "package-x-generic",
-        // PDF
-        "pdf" => "x-office-document",
-        // Web
-        "html" | "htm" | "css" => "text-html",
-        // Data / Config
-        "json" | "toml" | "yaml" | "yml" | "xml" => "text-x-generic",
-        // Markdown
-        "md" => "text-x-generic",
-        // Plain text
-        "txt" | "log" | "csv" => "text-x-generic",
+fn colorful_icon(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Rust => "application-x-executable",
+        FileKind::Python => "text-x-python",
+        FileKind::JavaScript | FileKind::Script => "text-x-script",
+        FileKind::C => "text-x-csrc",
+        FileKind::Java => "text-x-java",
+        FileKind::Shell => "application-x-shellscript",
+        FileKind::Image | FileKind::Vector => "image-x-generic",
+        FileKind::Audio => "audio-x-generic",
+        FileKind::Video => "video-x-generic",
+        FileKind::Archive => "package-x-generic",
+        FileKind::Pdf => "x-office-document",
+        FileKind::Html | FileKind::Css => "text-html",
+        // Go, data / config, markdown, plain text
         _ => "text-x-generic",
     }
 }
@@ -111,35 +189,28 @@ fn colorful_icon(ext: &str) -> &'static str {
 // Detailed symbolic icons — each major file type gets its own distinctive icon.
 // More visual variety than Minimal, but still monochrome symbolic style.
 
-fn outline_icon(ext: &str) -> &'static str {
-    match ext {
+fn outline_icon(kind: FileKind) -> &'static str {
+    match kind {
         // Source code — uses script icon to distinguish from plain text
-        "rs" | "py" | "js" | "ts" | "c" | "cpp" | "h" | "java" | "go" | "rb" | "swift" | "kt"
-        | "cs" | "lua" => "text-x-script-symbolic",
+        FileKind::Rust
+        | FileKind::Python
+        | FileKind::JavaScript
+        | FileKind::C
+        | FileKind::Java
+        | FileKind::Go
+        | FileKind::Script => "text-x-script-symbolic",
         // Shell scripts — distinct executable icon
-        "sh" | "fish" | "zsh" | "bash" => "application-x-executable-symbolic",
-        // Images
-        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "ico" | "webp" => "image-x-generic-symbolic",
-        // Vector images
-        "svg" => "image-x-generic-symbolic",
-        // Audio
-        "mp3" | "flac" | "ogg" | "wav" | "m4a" | "aac" => "audio-x-generic-symbolic",
-        // Video
-        "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic-symbolic",
-        // Archives
-        "zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar" => "package-x-generic-symbolic",
-        // PDF / Documents
-        "pdf" => "x-office-document-symbolic",
-        // Web
-        "html" | "htm" => "text-html-symbolic",
-        "css" => "text-x-preview-symbolic",
-        // Markdown
-        "md" => "x-office-document-symbolic",
-        // Config files
-        "json" | "toml" | "yaml" | "yml" | "xml" => "emblem-system-symbolic",
-        // Plain text / logs
-        "txt" | "log" | "csv" => "accessories-text-editor-symbolic",
-        _ => "text-x-generic-symbolic",
+        FileKind::Shell => "application-x-executable-symbolic",
+        FileKind::Image | FileKind::Vector => "image-x-generic-symbolic",
+        FileKind::Audio => "audio-x-generic-symbolic",
+        FileKind::Video => "video-x-generic-symbolic",
+        FileKind::Archive => "package-x-generic-symbolic",
+        FileKind::Pdf | FileKind::Markdown => "x-office-document-symbolic",
+        FileKind::Html => "text-html-symbolic",
+        FileKind::Css => "text-x-preview-symbolic",
+        FileKind::Config => "emblem-system-symbolic",
+        FileKind::Text => "accessories-text-editor-symbolic",
+        FileKind::Other => "text-x-generic-symbolic",
     }
 }
 
@@ -153,22 +224,22 @@ pub fn icon_css_class(entry: &Entry) -> &'static str {
     if entry.is_dir {
         return "icon-folder";
     }
-    match entry.extension.as_str() {
-        "rs" => "icon-rust",
-        "py" => "icon-python",
-        "js" | "ts" => "icon-js",
-        "c" | "cpp" | "h" => "icon-c",
-        "java" | "kt" => "icon-java",
-        "go" => "icon-go",
-        "sh" | "fish" | "zsh" | "bash" | "lua" | "rb" | "swift" | "cs" => "icon-script",
-        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => "icon-image",
-        "mp3" | "flac" | "ogg" | "wav" | "m4a" | "aac" => "icon-audio",
-        "mp4" | "mkv" | "avi" | "mov" | "webm" => "icon-video",
-        "zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar" => "icon-archive",
-        "pdf" => "icon-pdf",
-        "html" | "htm" | "css" => "icon-web",
-        "md" | "txt" | "log" | "csv" => "icon-text",
-        "json" | "toml" | "yaml" | "yml" | "xml" => "icon-config",
-        _ => "icon-default",
+    match file_kind(&entry.mime) {
+        FileKind::Rust => "icon-rust",
+        FileKind::Python => "icon-python",
+        FileKind::JavaScript => "icon-js",
+        FileKind::C => "icon-c",
+        FileKind::Java => "icon-java",
+        FileKind::Go => "icon-go",
+        FileKind::Shell | FileKind::Script => "icon-script",
+        FileKind::Image | FileKind::Vector => "icon-image",
+        FileKind::Audio => "icon-audio",
+        FileKind::Video => "icon-video",
+        FileKind::Archive => "icon-archive",
+        FileKind::Pdf => "icon-pdf",
+        FileKind::Html | FileKind::Css => "icon-web",
+        FileKind::Markdown | FileKind::Text => "icon-text",
+        FileKind::Config => "icon-config",
+        FileKind::Other => "icon-default",
     }
 }
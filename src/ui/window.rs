@@ -1,10 +1,20 @@
-use crate::config::{AppConfig, ViewMode};
-use crate::core::Theme;
-use crate::ui::{content, context_menu, graph_view, hamburger, inspector, sidebar};
+use crate::config::{
+    keybindings, Action, AppConfig, GroupBy, IconTheme, Keymap, Session, SortKey, ViewMode,
+};
+use crate::core::{apps, Theme};
+use crate::filesystem::journal;
+use crate::thumbnail::ThumbnailCache;
+use crate::transfer::TransferKind;
+use crate::ui::keyboard::{KeyPress, KeyReader};
+use crate::ui::pane::{self, Pane, Panes};
+use crate::ui::{
+    actions, clipboard, command_palette, content, context_menu, drop_target, hamburger, history,
+    inspector, keyboard, quick_finder, search_view, sidebar, tabs, transfer_dialog,
+};
 use gtk4::prelude::*;
 use gtk4::{
-    Align, Application, ApplicationWindow, Box, Button, CssProvider, Label, Orientation, Paned,
-    ScrolledWindow, StyleContext,
+    Application, ApplicationWindow, Box, Button, CssProvider, EventControllerKey, GestureClick,
+    Orientation, Paned, PropagationPhase, ScrolledWindow, Stack, StyleContext, ToggleButton,
 };
 use std::cell::RefCell;
 use std::path::PathBuf;
@@ -21,8 +31,6 @@ pub fn build(app: &Application) {
     let start_path = dirs::home_dir()
         .or_else(|| std::env::current_dir().ok())
         .unwrap_or_else(|| PathBuf::from("/"));
-    let current_path = Rc::new(RefCell::new(start_path));
-    let selected_file_path: Rc<RefCell<Option<PathBuf>>> = Rc::new(RefCell::new(None));
 
     // ── Theme setup ──
     let css_provider = CssProvider::new();
@@ -45,21 +53,8 @@ pub fn build(app: &Application) {
         .default_height(config.borrow().window_height)
         .build();
 
-    // Save window size on close
-    {
-        let config_c = config.clone();
-        window.connect_close_request(move |w| {
-            let (width, height) = (w.width(), w.height());
-            let mut cfg = config_c.borrow_mut();
-            cfg.window_width = width;
-            cfg.window_height = height;
-            cfg.save();
-            glib::Propagation::Proceed
-        });
-    }
-
     // ═══════════════════════════════════════════
-    //  Layout: Paned  [Sidebar | Content+Header]
+    //  Layout: Paned  [Sidebar | Header + Panes]
     // ═══════════════════════════════════════════
 
     let paned = Paned::builder()
@@ -67,7 +62,7 @@ pub fn build(app: &Application) {
         .position(220)
         .build();
 
-    // ── Right side: header + content + inspector ──
+    // ── Right side: header + panes + inspector ──
     let right_vbox = Box::builder()
         .orientation(Orientation::Vertical)
         .hexpand(true)
@@ -81,19 +76,39 @@ pub fn build(app: &Application) {
         .css_classes(vec!["header-bar".to_string()])
         .build();
 
+    let back_btn = Button::builder()
+        .icon_name("go-previous-symbolic")
+        .tooltip_text("Back (Alt+Left) — hold for recent locations")
+        .sensitive(false)
+        .css_classes(vec!["toolbar-btn".to_string()])
+        .build();
+
+    let forward_btn = Button::builder()
+        .icon_name("go-next-symbolic")
+        .tooltip_text("Forward (Alt+Right) — hold for recent locations")
+        .sensitive(false)
+        .css_classes(vec!["toolbar-btn".to_string()])
+        .build();
+
     let go_up_btn = Button::builder()
         .icon_name("go-up-symbolic")
-        .tooltip_text("Go Up")
+        .tooltip_text("Go Up (Backspace)")
         .css_classes(vec!["toolbar-btn".to_string()])
         .build();
 
-    let breadcrumb_label = Label::builder()
-        .label("~")
-        .css_classes(vec!["breadcrumb-label-active".to_string()])
-        .halign(Align::Start)
-        .hexpand(true)
-        .xalign(0.0)
-        .ellipsize(gtk4::pango::EllipsizeMode::Start)
+    let header_spacer = Box::builder().hexpand(true).build();
+
+    let dual_pane_btn = ToggleButton::builder()
+        .icon_name("view-dual-symbolic")
+        .tooltip_text("Dual Pane")
+        .active(config.borrow().dual_pane)
+        .css_classes(vec!["toolbar-btn".to_string()])
+        .build();
+
+    let search_btn = Button::builder()
+        .icon_name("system-search-symbolic")
+        .tooltip_text("Search (Ctrl+Shift+F)")
+        .css_classes(vec!["toolbar-btn".to_string()])
         .build();
 
     let view_toggle_btn = Button::builder()
@@ -102,51 +117,62 @@ pub fn build(app: &Application) {
         .css_classes(vec!["toolbar-btn".to_string()])
         .build();
 
+    header_bar.append(&back_btn);
+    header_bar.append(&forward_btn);
     header_bar.append(&go_up_btn);
-    header_bar.append(&breadcrumb_label);
+    header_bar.append(&header_spacer);
+    header_bar.append(&search_btn);
+    header_bar.append(&dual_pane_btn);
     header_bar.append(&view_toggle_btn);
 
     // ── Hamburger menu (right-most) ──
-    // Placeholder — will be fully wired after sidebar is built
     let hamburger_placeholder = Box::builder().build();
     header_bar.append(&hamburger_placeholder);
 
     right_vbox.append(&header_bar);
 
-    // Content area
-    let content_box = Box::builder()
-        .orientation(Orientation::Vertical)
-        .spacing(4)
-        .margin_top(8)
-        .margin_start(12)
-        .margin_end(12)
-        .margin_bottom(8)
+    // Inspector bar (shared by both panes)
+    let (inspector_bar, inspector_info) = inspector::build_inspector_bar();
+
+    // ── Panes ──
+    let panes = Panes::new(
+        start_path,
+        config.borrow().view_mode.clone(),
+        inspector_info.clone(),
+    );
+
+    let panes_paned = Paned::builder()
+        .orientation(Orientation::Horizontal)
+        .wide_handle(true)
+        .resize_start_child(true)
+        .resize_end_child(true)
+        .start_child(&panes.left.root)
+        .end_child(&panes.right.root)
         .build();
+    panes.set_dual(config.borrow().dual_pane);
 
-    let content_scroll = ScrolledWindow::builder()
-        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+    // Settings replace both panes while open
+    let settings_scroll = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
         .vexpand(true)
         .hexpand(true)
-        .child(&content_box)
         .build();
 
-    right_vbox.append(&content_scroll);
+    let main_stack = Stack::builder().vexpand(true).hexpand(true).build();
+    main_stack.add_named(&panes_paned, Some("panes"));
+    main_stack.add_named(&settings_scroll, Some("settings"));
+    main_stack.set_visible_child_name("panes");
 
-    // Inspector bar
-    let (inspector_bar, inspector_info) = inspector::build_inspector_bar();
+    right_vbox.append(&main_stack);
     right_vbox.append(&inspector_bar);
 
     // ── Left side: Sidebar ──
     let (sidebar_widget, nav_box, settings_toggle) = sidebar::build_sidebar(
-        current_path.clone(),
-        selected_file_path.clone(),
+        panes.clone(),
         config.clone(),
-        content_box.clone(),
-        content_scroll,
-        breadcrumb_label.clone(),
-        inspector_info.clone(),
+        main_stack,
+        settings_scroll,
         window.clone(),
-        css_provider,
     );
 
     // Assemble paned
@@ -154,147 +180,786 @@ pub fn build(app: &Application) {
     paned.set_end_child(Some(&right_vbox));
     window.set_child(Some(&paned));
 
+    // ═══════════════════════════════════════════
+    //  Pane Focus
+    // ═══════════════════════════════════════════
+    {
+        let nav_box_c = nav_box.clone();
+        let window_c = window.clone();
+        let config_c = config.clone();
+        let view_btn_c = view_toggle_btn.clone();
+        let back_btn_c = back_btn.clone();
+        let forward_btn_c = forward_btn.clone();
+
+        panes.connect_activate(move |pane| {
+            view_btn_c.set_icon_name(view_mode_icon(&pane.view_mode.borrow()));
+            back_btn_c.set_sensitive(pane.history.can_go_back());
+            forward_btn_c.set_sensitive(pane.history.can_go_forward());
+            sidebar::refresh_sidebar(&nav_box_c, pane, &window_c, config_c.clone());
+        });
+    }
+
     // ═══════════════════════════════════════════
     //  Go Up Button
     // ═══════════════════════════════════════════
     {
-        let current_path_c = current_path.clone();
+        let panes_c = panes.clone();
         let nav_box_c = nav_box.clone();
-        let content_box_c = content_box.clone();
         let window_c = window.clone();
-        let breadcrumb_c = breadcrumb_label.clone();
-        let inspector_info_c = inspector_info.clone();
-        let selected_c = selected_file_path.clone();
         let config_c = config.clone();
 
         go_up_btn.connect_clicked(move |_| {
-            let parent = {
-                let p = current_path_c.borrow();
-                p.parent().map(|pp| pp.to_path_buf())
+            go_parent(&panes_c.active(), &nav_box_c, &window_c, &config_c);
+        });
+    }
+
+    // ═══════════════════════════════════════════
+    //  Back / Forward
+    // ═══════════════════════════════════════════
+    let go_history: Rc<dyn Fn(bool, usize)> = {
+        let panes_c = panes.clone();
+        let nav_box_c = nav_box.clone();
+        let window_c = window.clone();
+        let config_c = config.clone();
+
+        Rc::new(move |forward, steps| {
+            let pane = panes_c.active();
+            if let Some(entry) = history::step(&pane, forward, steps) {
+                *pane.current_path.borrow_mut() = entry.path.clone();
+                sidebar::refresh_all(&nav_box_c, &pane, &window_c, config_c.clone());
+                history::restore(&pane, &entry);
+            }
+        })
+    };
+
+    for pane in [&panes.left, &panes.right] {
+        let panes_c = panes.clone();
+        let side = pane.side;
+        let back_btn_c = back_btn.clone();
+        let forward_btn_c = forward_btn.clone();
+        pane.history.connect_changed(move || {
+            if panes_c.active_side() == side {
+                let history = &panes_c.pane(side).history;
+                back_btn_c.set_sensitive(history.can_go_back());
+                forward_btn_c.set_sensitive(history.can_go_forward());
+            }
+        });
+    }
+
+    // ═══════════════════════════════════════════
+    //  Tabs
+    // ═══════════════════════════════════════════
+    {
+        let session = config.borrow().session.clone();
+        tabs::restore_session(&panes.left, &session.left, session.left_active);
+        tabs::restore_session(&panes.right, &session.right, session.right_active);
+    }
+    for pane in [&panes.left, &panes.right] {
+        let panes_c = panes.clone();
+        let nav_box_c = nav_box.clone();
+        let window_c = window.clone();
+        let config_c = config.clone();
+        let view_btn_c = view_toggle_btn.clone();
+        pane.tabs.connect_switch(move |pane| {
+            if panes_c.active_side() == pane.side {
+                view_btn_c.set_icon_name(view_mode_icon(&pane.view_mode.borrow()));
+            }
+            sidebar::refresh_all(&nav_box_c, pane, &window_c, config_c.clone());
+        });
+    }
+
+    // Save window size and open tabs on close
+    {
+        let config_c = config.clone();
+        let panes_c = panes.clone();
+        window.connect_close_request(move |w| {
+            let (width, height) = (w.width(), w.height());
+            let (left, left_active) = tabs::saved_tabs(&panes_c.left);
+            let (right, right_active) = tabs::saved_tabs(&panes_c.right);
+            let mut cfg = config_c.borrow_mut();
+            cfg.window_width = width;
+            cfg.window_height = height;
+            cfg.session = Session {
+                left_active,
+                right_active,
+                left,
+                right,
             };
-            if let Some(parent_path) = parent {
-                *current_path_c.borrow_mut() = parent_path;
-                sidebar::refresh_all(
-                    &nav_box_c,
-                    &content_box_c,
-                    current_path_c.clone(),
-                    &window_c,
-                    &breadcrumb_c,
-                    &inspector_info_c,
-                    selected_c.clone(),
-                    config_c.clone(),
-                );
+            cfg.save();
+            glib::Propagation::Proceed
+        });
+    }
+
+    // ═══════════════════════════════════════════
+    //  Path Bars
+    // ═══════════════════════════════════════════
+    for pane in [&panes.left, &panes.right] {
+        let pane_c = pane.clone();
+        let nav_box_c = nav_box.clone();
+        let window_c = window.clone();
+        let config_c = config.clone();
+        pane.path_bar.connect_navigate(move |path| {
+            *pane_c.current_path.borrow_mut() = path;
+            sidebar::refresh_all(&nav_box_c, &pane_c, &window_c, config_c.clone());
+        });
+    }
+
+    for (btn, forward) in [(&back_btn, false), (&forward_btn, true)] {
+        let go = go_history.clone();
+        btn.connect_clicked(move |_| go(forward, 1));
+
+        let panes_c = panes.clone();
+        let go = go_history.clone();
+        history::attach_history_menu(
+            btn,
+            forward,
+            move || panes_c.active(),
+            Rc::new(move |steps| go(forward, steps)),
+        );
+    }
+
+    // Mouse back / forward buttons (8 / 9). Bubble phase, so the pane
+    // under the pointer has already become the active one.
+    {
+        let go = go_history.clone();
+        let mouse = GestureClick::builder().button(0).build();
+        mouse.connect_pressed(move |g, _, _, _| match g.current_button() {
+            8 => go(false, 1),
+            9 => go(true, 1),
+            _ => {}
+        });
+        window.add_controller(mouse);
+    }
+
+    // ═══════════════════════════════════════════
+    //  Search
+    // ═══════════════════════════════════════════
+    for pane in [&panes.left, &panes.right] {
+        let pane_c = pane.clone();
+        let config_c = config.clone();
+        pane.search_bar.connect_search(move |query| match query {
+            Some(query) => search_view::show_search_results(&pane_c, query, config_c.clone()),
+            // Back to the folder, unless navigating already replaced the results
+            None => {
+                if pane_c.search.borrow().is_some() {
+                    content::refresh_content(&pane_c, config_c.clone());
+                }
             }
         });
     }
+    {
+        let panes_c = panes.clone();
+        search_btn.connect_clicked(move |_| toggle_search(&panes_c.active()));
+    }
 
     // ═══════════════════════════════════════════
-    //  View Mode Toggle
+    //  Dual Pane Toggle
     // ═══════════════════════════════════════════
     {
+        let panes_c = panes.clone();
+        let config_c = config.clone();
+
+        dual_pane_btn.connect_toggled(move |btn| {
+            let dual = btn.is_active();
+            panes_c.set_dual(dual);
+            let mut cfg = config_c.borrow_mut();
+            cfg.dual_pane = dual;
+            cfg.save();
+        });
+    }
+
+    // ═══════════════════════════════════════════
+    //  View Mode Toggle
+    // ═══════════════════════════════════════════
+    let set_view_mode: Rc<dyn Fn(ViewMode)> = {
         let config_c = config.clone();
-        let content_box_c = content_box.clone();
-        let current_path_c = current_path.clone();
-        let inspector_info_c = inspector_info.clone();
-        let selected_c = selected_file_path.clone();
+        let panes_c = panes.clone();
         let view_btn_c = view_toggle_btn.clone();
 
-        view_toggle_btn.connect_clicked(move |_| {
+        Rc::new(move |mode| {
+            let pane = panes_c.active();
+            view_btn_c.set_icon_name(view_mode_icon(&mode));
+            *pane.view_mode.borrow_mut() = mode.clone();
+
+            // Remember the last used mode for new panes
             {
                 let mut cfg = config_c.borrow_mut();
-                cfg.view_mode = match cfg.view_mode {
-                    ViewMode::Grid => ViewMode::List,
-                    ViewMode::List => ViewMode::Graph,
-                    ViewMode::Graph => ViewMode::Tree,
-                    ViewMode::Tree => ViewMode::Grid,
-                };
+                cfg.view_mode = mode;
                 cfg.save();
             }
-            let icon = match config_c.borrow().view_mode {
-                ViewMode::Grid => "view-grid-symbolic",
-                ViewMode::List => "view-list-symbolic",
-                ViewMode::Graph => "network-workgroup-symbolic",
-                ViewMode::Tree => "view-list-tree-symbolic",
-            };
-            view_btn_c.set_icon_name(icon);
 
-            // For Graph mode, replace content with graph view
-            if config_c.borrow().view_mode == ViewMode::Graph {
-                // Clear content box and show graph
-                while let Some(child) = content_box_c.first_child() {
-                    content_box_c.remove(&child);
-                }
-                let graph = graph_view::build_graph_view(current_path_c.clone(), config_c.clone());
-                content_box_c.append(&graph);
+            // Search results stay, shown the new way
+            if pane.search.borrow().is_some() {
+                pane.search_bar.search_again();
             } else {
-                content::refresh_content(
-                    &content_box_c,
-                    current_path_c.clone(),
-                    &inspector_info_c,
-                    selected_c.clone(),
-                    config_c.clone(),
-                );
+                content::refresh_content(&pane, config_c.clone());
             }
+        })
+    };
+    {
+        let panes_c = panes.clone();
+        let set_view_mode = set_view_mode.clone();
+        view_toggle_btn.connect_clicked(move |_| {
+            set_view_mode(next_view_mode(&panes_c.active().view_mode.borrow()));
         });
     }
 
     // ═══════════════════════════════════════════
     //  Initial Render
     // ═══════════════════════════════════════════
-    {
-        let icon = match config.borrow().view_mode {
-            ViewMode::Grid => "view-grid-symbolic",
-            ViewMode::List => "view-list-symbolic",
-            ViewMode::Graph => "network-workgroup-symbolic",
-            ViewMode::Tree => "view-list-tree-symbolic",
-        };
-        view_toggle_btn.set_icon_name(icon);
+    view_toggle_btn.set_icon_name(view_mode_icon(&panes.active().view_mode.borrow()));
+
+    // ── Undo journal: reload the saved one if enabled ──
+    journal::set_persistent(config.borrow().persist_undo_history);
+
+    // ── Hamburger menu ──
+    hamburger_placeholder.append(&hamburger::build_hamburger_menu(config.clone()));
+
+    // ── Attach background right-click context menu to each pane ──
+    for pane in [&panes.left, &panes.right] {
+        context_menu::attach_background_context_menu(
+            &pane.content_box,
+            pane.clone(),
+            config.clone(),
+        );
     }
 
-    // ── Hamburger menu (wired after sidebar) ──
-    {
+    // ── Files dropped on a pane's background land in its folder ──
+    for pane in [&panes.left, &panes.right] {
+        let current_path = pane.current_path.clone();
+        drop_target::attach_drop_target(
+            &pane.content_box,
+            move |_, _| Some(current_path.borrow().clone()),
+            pane,
+            config.clone(),
+            None,
+        );
+    }
+
+    // ═══════════════════════════════════════════
+    //  Actions
+    // ═══════════════════════════════════════════
+    //
+    // Every command is an action on the window (see `actions`): key
+    // bindings, menus, the settings panel and the command palette all run
+    // them the same way.
+    let (keymap, problems) = Keymap::new(&config.borrow().keybindings);
+
+    // Re-renders everything after a setting that affects listings changed
+    let refresh_everything: Rc<dyn Fn()> = {
+        let panes_c = panes.clone();
         let config_c = config.clone();
-        let cp_c = current_path.clone();
-        let cb_c = content_box.clone();
-        let info_c = inspector_info.clone();
-        let sel_c = selected_file_path.clone();
+        let window_c = window.clone();
+        let nav_box_c = nav_box.clone();
+        Rc::new(move || {
+            sidebar::refresh_sidebar(&nav_box_c, &panes_c.active(), &window_c, config_c.clone());
+            panes_c.refresh_both(&config_c);
+        })
+    };
+
+    let registry = actions::Registry::new();
 
-        // Wire settings toggle from sidebar into hamburger "Settings" button
+    let perform: Rc<dyn Fn(Action, bool)> = {
+        let registry_c = registry.clone();
+        let panes_c = panes.clone();
+        let config_c = config.clone();
+        let window_c = window.clone();
+        let nav_box_c = nav_box.clone();
+        let go = go_history.clone();
+        let refresh = refresh_everything.clone();
+        let dual_pane_btn_c = dual_pane_btn.clone();
         let settings_toggle_c = settings_toggle.clone();
-        let on_open_settings: Rc<dyn Fn()> = Rc::new(move || {
-            // Activate the sidebar settings toggle (if not already active)
-            if !settings_toggle_c.is_active() {
-                settings_toggle_c.set_active(true);
+        let set_view_mode = set_view_mode.clone();
+        let inspector_c = inspector_info.clone();
+
+        Rc::new(move |action, extend| {
+            let pane = panes_c.active();
+            match action {
+                Action::MoveLeft
+                | Action::MoveRight
+                | Action::MoveUp
+                | Action::MoveDown
+                | Action::PageUp
+                | Action::PageDown
+                | Action::First
+                | Action::Last => {
+                    if let Some(motion) = keyboard::motion(action) {
+                        keyboard::move_cursor(&pane, motion, extend);
+                    }
+                }
+                Action::Open => content::open_selection(&pane, config_c.clone()),
+                Action::GoParent => go_parent(&pane, &nav_box_c, &window_c, &config_c),
+                Action::GoBack => go(false, 1),
+                Action::GoForward => go(true, 1),
+                Action::Rename => {
+                    let paths = pane.selection.paths();
+                    let anchor = pane
+                        .selection
+                        .cursor()
+                        .and_then(|p| pane.selection.widget(&p))
+                        .unwrap_or_else(|| pane.content_box.clone().upcast());
+                    context_menu::rename_items(&anchor, paths, pane.clone(), config_c.clone());
+                }
+                Action::Trash => {
+                    let paths = pane.selection.paths();
+                    if !paths.is_empty() {
+                        context_menu::trash_items(paths, &pane, config_c.clone());
+                    }
+                }
+                Action::Copy => clipboard::copy(&pane.root, &pane.selection.paths()),
+                Action::Cut => clipboard::cut(&pane.root, &pane.selection.paths()),
+                Action::Paste => {
+                    let dest = pane.current_path.borrow().clone();
+                    clipboard::paste(&pane, dest, config_c.clone());
+                }
+                Action::CopyToOtherPane => transfer_selection(&pane, false, &config_c),
+                Action::MoveToOtherPane => transfer_selection(&pane, true, &config_c),
+                Action::SelectAll => pane.selection.select_all(),
+                Action::Cancel => {
+                    if pane.filter_bar.is_open() {
+                        pane.filter_bar.close();
+                    } else {
+                        pane.selection.clear();
+                    }
+                }
+                Action::SwitchPane => {
+                    if panes_c.right.root.is_visible() {
+                        panes_c.set_active(panes_c.active_side().opposite());
+                    }
+                }
+                Action::EditPath => pane.path_bar.start_editing(),
+                Action::NewTab => {
+                    let path = pane.current_path.borrow().clone();
+                    tabs::open_tab(&pane, path);
+                }
+                Action::CloseTab => tabs::close_active_tab(&pane),
+                Action::NextTab => tabs::cycle_tab(&pane, 1),
+                Action::PreviousTab => tabs::cycle_tab(&pane, -1),
+                Action::Undo => undo_redo(&panes_c, &nav_box_c, &window_c, &config_c, false),
+                Action::Redo => undo_redo(&panes_c, &nav_box_c, &window_c, &config_c, true),
+                Action::QuickFinder => quick_finder::show_quick_finder(&pane, config_c.clone()),
+                Action::CommandPalette => {
+                    command_palette::show_command_palette(&pane, registry_c.commands())
+                }
+                Action::Filter => toggle_filter(&pane),
+                Action::Search => toggle_search(&pane),
+                Action::ToggleHidden => {
+                    {
+                        let mut cfg = config_c.borrow_mut();
+                        cfg.show_hidden = !cfg.show_hidden;
+                        cfg.save();
+                    }
+                    refresh();
+                }
+                Action::ToggleDualPane => dual_pane_btn_c.set_active(!dual_pane_btn_c.is_active()),
+                Action::CycleViewMode => set_view_mode(next_view_mode(&pane.view_mode.borrow())),
+                Action::ToggleSortDescending => {
+                    {
+                        let mut cfg = config_c.borrow_mut();
+                        cfg.sort_descending = !cfg.sort_descending;
+                        cfg.save();
+                    }
+                    refresh();
+                }
+                Action::ToggleFoldersFirst => {
+                    {
+                        let mut cfg = config_c.borrow_mut();
+                        cfg.folders_first = !cfg.folders_first;
+                        cfg.save();
+                    }
+                    refresh();
+                }
+                Action::NewFolder => context_menu::create_item(&pane, true, config_c.clone()),
+                Action::NewFile => context_menu::create_item(&pane, false, config_c.clone()),
+                Action::Refresh => content::refresh_content(&pane, config_c.clone()),
+                Action::OpenTerminal => {
+                    let dir = pane.current_path.borrow().clone();
+                    if let Err(e) = apps::open_terminal(&dir) {
+                        eprintln!("[terminal] Cannot open a terminal: {}", e);
+                        inspector_c.set_label(&format!("Cannot open a terminal: {}", e));
+                    }
+                }
+                Action::ClearThumbnailCache => {
+                    let cache = ThumbnailCache::new();
+                    let count = cache.entry_count();
+                    cache.clear();
+                    inspector_c.set_label(&match count {
+                        1 => "Cleared 1 cached thumbnail".to_string(),
+                        n => format!("Cleared {} cached thumbnails", n),
+                    });
+                }
+                Action::Settings => {
+                    if !settings_toggle_c.is_active() {
+                        settings_toggle_c.set_active(true);
+                    }
+                }
+                Action::About => show_about(&window_c),
             }
-        });
+        })
+    };
+
+    for action in Action::all() {
+        let name = actions::action_name(action);
+        let keys = keymap.labels(action);
+        let perform = perform.clone();
+        if keyboard::motion(action).is_some() {
+            registry.add_motion(&window, &name, action.title(), keys, move |extend| {
+                perform(action, extend)
+            });
+        } else {
+            registry.add(&window, &name, action.title(), keys, move || {
+                perform(action, false)
+            });
+        }
+    }
+    actions::register_accels(&window, &keymap);
 
-        let hamburger_btn =
-            hamburger::build_hamburger_menu(config_c, cp_c, cb_c, info_c, sel_c, on_open_settings);
-        hamburger_placeholder.append(&hamburger_btn);
+    // ── Choices ──
+    {
+        let modes = [
+            (ViewMode::Grid, "grid", "Grid"),
+            (ViewMode::List, "list", "List"),
+            (ViewMode::Graph, "graph", "Graph"),
+            (ViewMode::Tree, "tree", "Tree"),
+        ];
+        let choices: Vec<(String, String)> = modes
+            .iter()
+            .map(|(_, name, title)| (name.to_string(), format!("View as {}", title)))
+            .collect();
+        registry.add_choice(&window, "view-mode", &choices, move |value| {
+            if let Some((mode, _, _)) = modes.iter().find(|(_, name, _)| *name == value) {
+                set_view_mode(mode.clone());
+            }
+        });
+    }
+    {
+        let choices: Vec<(String, String)> = Theme::all_names()
+            .into_iter()
+            .map(|name| (name.to_string(), format!("Theme: {}", name)))
+            .collect();
+        let config_c = config.clone();
+        registry.add_choice(&window, "theme", &choices, move |name| {
+            if !Theme::all_names().contains(&name) {
+                return;
+            }
+            css_provider.load_from_data(&Theme::from_name(name).to_css());
+            let mut cfg = config_c.borrow_mut();
+            cfg.theme = name.to_string();
+            cfg.save();
+        });
+    }
+    {
+        let choices: Vec<(String, String)> = IconTheme::all_names()
+            .into_iter()
+            .map(|name| (name.to_string(), format!("Icon Theme: {}", name)))
+            .collect();
+        let config_c = config.clone();
+        let refresh = refresh_everything.clone();
+        registry.add_choice(&window, "icon-theme", &choices, move |name| {
+            if !IconTheme::all_names().contains(&name) {
+                return;
+            }
+            {
+                let mut cfg = config_c.borrow_mut();
+                cfg.icon_theme = IconTheme::from_name(name);
+                cfg.save();
+            }
+            refresh();
+        });
+    }
+    {
+        let choices: Vec<(String, String)> = SortKey::all()
+            .iter()
+            .map(|key| (key.name().to_string(), format!("Sort by {}", key.title())))
+            .collect();
+        let config_c = config.clone();
+        let refresh = refresh_everything.clone();
+        registry.add_choice(&window, "sort-by", &choices, move |name| {
+            let Some(key) = SortKey::all().into_iter().find(|k| k.name() == name) else {
+                return;
+            };
+            {
+                let mut cfg = config_c.borrow_mut();
+                cfg.sort_key = key;
+                cfg.save();
+            }
+            refresh();
+        });
+    }
+    {
+        let choices: Vec<(String, String)> = GroupBy::all()
+            .iter()
+            .map(|group| {
+                (
+                    group.name().to_string(),
+                    format!("Group by {}", group.title()),
+                )
+            })
+            .collect();
+        let config_c = config.clone();
+        let refresh = refresh_everything.clone();
+        registry.add_choice(&window, "group-by", &choices, move |name| {
+            let Some(group) = GroupBy::all().into_iter().find(|g| g.name() == name) else {
+                return;
+            };
+            {
+                let mut cfg = config_c.borrow_mut();
+                cfg.grouping = group;
+                cfg.save();
+            }
+            refresh();
+        });
     }
 
-    // ── Attach background right-click context menu to content area ──
+    // ═══════════════════════════════════════════
+    //  Keyboard
+    // ═══════════════════════════════════════════
+    //
+    // Every shortcut is an `Action` bound in the `[keybindings]` config
+    // table (arrows move, Enter opens, F2 renames, Ctrl+Z undoes, …).
+    // Keys no binding claims type into the filter bar.
     {
-        context_menu::attach_background_context_menu(
-            &content_box,
-            current_path.clone(),
-            content_box.clone(),
-            inspector_info.clone(),
-            selected_file_path.clone(),
-            config.clone(),
-        );
+        let panes_c = panes.clone();
+        let window_c = window.clone();
+        let reader = KeyReader::new(keymap);
+
+        let key_ctrl = EventControllerKey::new();
+        key_ctrl.set_propagation_phase(PropagationPhase::Capture);
+        key_ctrl.connect_key_pressed(move |_, key, _code, state| {
+            // Entries, menus and dialogs get their keys (Enter, Escape, …)
+            if is_editing_text(&window_c) || in_popup(&window_c) {
+                reader.reset();
+                return glib::Propagation::Proceed;
+            }
+            // Plain keys (Enter, Tab, F2, letters) only act on the pane;
+            // elsewhere in the window they keep their usual meaning
+            let in_pane = focus_in(&window_c, &panes_c.active().root);
+            if !in_pane && is_plain(state) {
+                reader.reset();
+                return glib::Propagation::Proceed;
+            }
+
+            match reader.press(key, state) {
+                KeyPress::Action { action, extend } => {
+                    if claims_key(action, in_pane, &panes_c) {
+                        actions::activate_bound(&window_c, action, extend);
+                        return glib::Propagation::Stop;
+                    }
+                }
+                KeyPress::Pending => return glib::Propagation::Stop,
+                KeyPress::Modifier => return glib::Propagation::Proceed,
+                KeyPress::Unbound => {}
+            }
+
+            // Typing a name in the content area starts filtering the folder.
+            // Lock keys (Caps, Num) are not modifiers here either
+            let mods = state & keybindings::MODIFIERS;
+            let shift = gtk4::gdk::ModifierType::SHIFT_MASK;
+            if mods.is_empty() || mods == shift {
+                let pane = panes_c.active();
+                let typed = key.to_unicode().filter(|c| !c.is_control() && *c != ' ');
+                if let Some(c) = typed {
+                    let dir = pane.current_path.borrow().clone();
+                    pane.filter_bar.open(&dir, &c.to_string());
+                    return glib::Propagation::Stop;
+                }
+            }
+            glib::Propagation::Proceed
+        });
+        window.add_controller(key_ctrl);
     }
 
-    sidebar::refresh_all(
-        &nav_box,
-        &content_box,
-        current_path,
-        &window,
-        &breadcrumb_label,
-        &inspector_info,
-        selected_file_path,
-        config,
-    );
+    sidebar::refresh_all(&nav_box, &panes.left, &window, config.clone());
+    content::refresh_content(&panes.right, config);
+
+    // The config loader logged the details
+    if !problems.is_empty() {
+        let notice = match problems.len() {
+            1 => "1 key binding in the config was ignored".to_string(),
+            n => format!("{} key bindings in the config were ignored", n),
+        };
+        inspector_info.set_label(&notice);
+    }
 
     window.present();
 }
+
+// ─── Helpers ───
+
+fn next_view_mode(mode: &ViewMode) -> ViewMode {
+    match mode {
+        ViewMode::Grid => ViewMode::List,
+        ViewMode::List => ViewMode::Graph,
+        ViewMode::Graph => ViewMode::Tree,
+        ViewMode::Tree => ViewMode::Grid,
+    }
+}
+
+fn view_mode_icon(mode: &ViewMode) -> &'static str {
+    match mode {
+        ViewMode::Grid => "view-grid-symbolic",
+        ViewMode::List => "view-list-symbolic",
+        ViewMode::Graph => "network-workgroup-symbolic",
+        ViewMode::Tree => "view-list-tree-symbolic",
+    }
+}
+
+/// Shows the parent folder in `pane`.
+fn go_parent(
+    pane: &Pane,
+    nav_box: &Box,
+    window: &ApplicationWindow,
+    config: &Rc<RefCell<AppConfig>>,
+) {
+    let parent = pane.current_path.borrow().parent().map(|p| p.to_path_buf());
+    if let Some(parent) = parent {
+        *pane.current_path.borrow_mut() = parent;
+        sidebar::refresh_all(nav_box, pane, window, config.clone());
+    }
+}
+
+/// Opens the search bar of `pane`, or closes it if open.
+fn toggle_search(pane: &Pane) {
+    if pane.search_bar.is_open() {
+        pane.search_bar.close();
+    } else {
+        pane.search_bar.open();
+    }
+}
+
+/// Opens the filter bar of `pane` for its folder, or clears it if open.
+fn toggle_filter(pane: &Pane) {
+    if pane.filter_bar.is_open() {
+        pane.filter_bar.close();
+    } else {
+        let dir = pane.current_path.borrow().clone();
+        pane.filter_bar.open(&dir, "");
+    }
+}
+
+/// Whether a bound key should run `action` now rather than reach the
+/// focused widget: movement keys only act inside the pane (elsewhere, in
+/// the sidebar or tab strip, they move the focus), and the actions aimed
+/// at the other pane need it shown.
+fn claims_key(action: Action, in_pane: bool, panes: &Panes) -> bool {
+    if keyboard::motion(action).is_some() {
+        return in_pane;
+    }
+    match action {
+        Action::SwitchPane | Action::CopyToOtherPane | Action::MoveToOtherPane => {
+            panes.right.root.is_visible()
+        }
+        Action::Trash => !panes.active().selection.paths().is_empty(),
+        _ => true,
+    }
+}
+
+/// A key pressed without Ctrl, Alt or Super (Shift alone still counts).
+fn is_plain(state: gtk4::gdk::ModifierType) -> bool {
+    use gtk4::gdk::ModifierType;
+    !state
+        .intersects(ModifierType::CONTROL_MASK | ModifierType::ALT_MASK | ModifierType::SUPER_MASK)
+}
+
+/// Shows the About dialog over `window`.
+fn show_about(window: &ApplicationWindow) {
+    let about = gtk4::AboutDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .program_name("Diptych")
+        .version("0.1.0")
+        .comments("A modern GTK4 file manager built with Rust.")
+        .website("https://github.com/flear/diptych")
+        .license_type(gtk4::License::MitX11)
+        .build();
+    about.present();
+}
+
+/// True while a text entry has keyboard focus (shortcuts must not steal keys).
+fn is_editing_text(window: &ApplicationWindow) -> bool {
+    gtk4::prelude::GtkWindowExt::focus(window)
+        .map(|w| w.is::<gtk4::Text>() || w.is::<gtk4::Entry>())
+        .unwrap_or(false)
+}
+
+/// True while the focus is in a popover or dialog (menus, finders, prompts).
+fn in_popup(window: &ApplicationWindow) -> bool {
+    gtk4::prelude::GtkWindowExt::focus(window).is_some_and(|w| {
+        w.ancestor(gtk4::Popover::static_type()).is_some()
+            || w.ancestor(gtk4::Dialog::static_type()).is_some()
+    })
+}
+
+/// True if nothing or something inside `area` has keyboard focus.
+fn focus_in(window: &ApplicationWindow, area: &impl IsA<gtk4::Widget>) -> bool {
+    gtk4::prelude::GtkWindowExt::focus(window).is_none_or(|w| w.is_ancestor(area))
+}
+
+/// Copies or moves the active pane's selected items into the other pane.
+fn transfer_selection(pane: &Pane, move_items: bool, config: &Rc<RefCell<AppConfig>>) {
+    let selected = pane.selection.paths();
+    if !selected.is_empty() {
+        pane::transfer_to_other_pane(pane, &selected, move_items, config.clone());
+    }
+}
+
+/// Undoes (or redoes) the last file operation and refreshes both panes.
+/// The outcome is shown in the inspector bar.
+fn undo_redo(
+    panes: &Rc<Panes>,
+    nav_box: &Box,
+    window: &ApplicationWindow,
+    config: &Rc<RefCell<AppConfig>>,
+    redo: bool,
+) {
+    let verb = if redo { "Redo" } else { "Undo" };
+    let result = if redo {
+        journal::redo()
+    } else {
+        journal::undo()
+    };
+
+    let Some(outcome) = result else {
+        panes
+            .active()
+            .inspector_info
+            .set_label(&format!("Nothing to {}", verb.to_lowercase()));
+        return;
+    };
+
+    // Items on another filesystem go through the transfer engine
+    for item in outcome.deferred {
+        let Some(dest_dir) = item.to.parent().map(|p| p.to_path_buf()) else {
+            continue;
+        };
+        let panes_c = panes.clone();
+        let config_c = config.clone();
+        transfer_dialog::run_transfer(
+            Some(window.upcast_ref()),
+            TransferKind::Move,
+            vec![item.from],
+            dest_dir,
+            Rc::new(move |_| panes_c.refresh_both(&config_c)),
+        );
+    }
+
+    let status = match (&outcome.op, &outcome.error) {
+        (Some(op), None) => format!("{}: {}", verb, op.description()),
+        (Some(op), Some(e)) => {
+            eprintln!("[journal] {} stopped part way: {}", verb, e);
+            format!("{} incomplete: {} done, then {}", verb, op.description(), e)
+        }
+        (None, Some(e)) => {
+            eprintln!("[journal] {} failed: {}", verb, e);
+            format!("{} failed: {}", verb, e)
+        }
+        (None, None) => format!("Nothing to {}", verb.to_lowercase()),
+    };
+
+    let active = panes.active();
+    sidebar::refresh_all(nav_box, &active, window, config.clone());
+    if let Some(other) = active.other() {
+        content::refresh_content(&other, config.clone());
+    }
+    active.inspector_info.set_label(&status);
+}